@@ -0,0 +1,26 @@
+use std::path::{Path, PathBuf};
+
+use crate::features::{
+    archive::domain::{errors::ArchiveError, repository::ArchiveRepository},
+    space::domain::repository::SpaceRepository,
+};
+
+/// # Export Space use case
+/// Writes a space, with its folders, attachments and metadata, to a single
+/// archive.
+/// ## Params
+/// * `archive_repo`: Repository writing the archive.
+/// * `space_repo`: Repository of the spaces.
+/// * `space_name`: Name of the space to be exported.
+/// * `destination`: The archive file, or the directory it's written in.
+/// ## Result
+/// The path of the archive.
+pub async fn export_space_use_case<A: ArchiveRepository, S: SpaceRepository>(
+    archive_repo: &A,
+    space_repo: &S,
+    space_name: &str,
+    destination: &Path,
+) -> Result<PathBuf, ArchiveError> {
+    let space = space_repo.get_space(space_name).await?;
+    archive_repo.export_space(&space, destination).await
+}
@@ -0,0 +1,21 @@
+use std::path::Path;
+
+use crate::features::{
+    archive::domain::{errors::ArchiveError, repository::ArchiveRepository},
+    space::domain::space::Space,
+};
+
+/// # Import Space use case
+/// Unpacks an archive into a new space, after checking its format version
+/// and the checksum of every file.
+/// ## Params
+/// * `repo`: Repository reading the archive.
+/// * `archive`: The archive file.
+/// * `space_name`: Name of the new space, the archived one if `None`.
+pub async fn import_space_use_case<A: ArchiveRepository>(
+    repo: &A,
+    archive: &Path,
+    space_name: Option<&str>,
+) -> Result<Space, ArchiveError> {
+    repo.import_space(archive, space_name).await
+}
@@ -0,0 +1,145 @@
+//! # Archive Domain
+//! Defines the portable archive of a space: a zip holding the files of the
+//! space next to a manifest describing them.
+use std::path::{Component, Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::features::space::domain::space::SpaceManifest;
+
+/// Version of the archive format written by this version of the application.
+pub const ARCHIVE_FORMAT_VERSION: u32 = 1;
+
+/// Name of the manifest at the root of the archive.
+pub const ARCHIVE_MANIFEST: &str = "manifest.json";
+
+/// Directory of the archive holding the files of the space.
+pub const ARCHIVE_CONTENT_DIR: &str = "space";
+
+/// Extension of the archives.
+pub const ARCHIVE_EXTENSION: &str = "zip";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # ArchiveManifest
+/// Describes the content of an archive.
+/// ## Fields
+/// * `format_version`: Version of the archive format.
+/// * `space_name`: Name of the exported space.
+/// * `exported`: When the archive was written.
+/// * `space`: Metadata of the exported space.
+/// * `folders`: Every folder of the space, empty ones included.
+/// * `files`: Every file of the space with its checksum.
+pub struct ArchiveManifest {
+    pub format_version: u32,
+    pub space_name: String,
+    pub exported: DateTime<Utc>,
+    #[serde(default)]
+    pub space: SpaceManifest,
+    #[serde(default)]
+    pub folders: Vec<String>,
+    #[serde(default)]
+    pub files: Vec<ArchivedFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # ArchivedFile
+/// A file of the archived space.
+/// ## Fields
+/// * `path`: Path relative to the space, `/` separated.
+/// * `size`: Size in bytes.
+/// * `sha256`: SHA-256 of the content.
+pub struct ArchivedFile {
+    pub path: String,
+    pub size: u64,
+    pub sha256: String,
+}
+
+impl ArchivedFile {
+    pub fn of_content(path: String, content: &[u8]) -> Self {
+        Self {
+            path,
+            size: content.len() as u64,
+            sha256: checksum(content),
+        }
+    }
+}
+
+/// SHA-256 of a content, as written in the manifest.
+pub fn checksum(content: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(content))
+}
+
+/// # Archive path
+/// The `/` separated form of a path relative to the space.
+pub fn archive_path(relative: &Path) -> String {
+    relative
+        .components()
+        .map(|c| c.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// # Space path
+/// Turns a path of the manifest back into a path relative to the space,
+/// rejecting anything that could leave the space. Drive prefixes and `\`
+/// separators are rejected on every platform, an archive isn't always read
+/// where it was made.
+pub fn space_path(archive_path: &str) -> Option<PathBuf> {
+    let path = Path::new(archive_path);
+    let drive = archive_path.as_bytes();
+    let has_drive = drive.len() >= 2 && drive[0].is_ascii_alphabetic() && drive[1] == b':';
+    let safe = path.components().all(|c| matches!(c, Component::Normal(_)))
+        && !has_drive
+        && !archive_path.contains('\\');
+    (safe && !archive_path.is_empty()).then(|| path.to_path_buf())
+}
+
+/// # Unique space name
+/// The name itself, or the first free `name (n)`.
+pub fn unique_space_name(name: &str, taken: impl Fn(&str) -> bool) -> String {
+    if !taken(name) {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{} ({})", name, n))
+        .find(|candidate| !taken(candidate))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn space_paths_stay_inside_the_space() {
+        assert_eq!(
+            space_path("projects/plan.json"),
+            Some(PathBuf::from("projects").join("plan.json"))
+        );
+        assert!(space_path("Meeting: notes.json").is_some());
+        for path in [
+            "",
+            "../outside.json",
+            "projects/../../outside.json",
+            "/etc/passwd",
+            "C:/Windows/x.json",
+            "C:x.json",
+            "..\\outside.json",
+            "./plan.json",
+        ] {
+            assert_eq!(space_path(path), None, "{}", path);
+        }
+    }
+
+    #[test]
+    fn taken_space_names_get_a_number() {
+        let taken = ["Work", "Work (2)", "Work (3)"];
+        let is_taken = |name: &str| taken.contains(&name);
+
+        assert_eq!(unique_space_name("Personal", is_taken), "Personal");
+        assert_eq!(unique_space_name("Work", is_taken), "Work (4)");
+        assert_eq!(unique_space_name("Work (2)", is_taken), "Work (2) (2)");
+    }
+}
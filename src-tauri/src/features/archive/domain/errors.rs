@@ -0,0 +1,43 @@
+//! # Errors
+//! Defines all error that could ocurr while exporting or importing a space.
+use std::io;
+
+use thiserror::Error;
+use zip::result::ZipError;
+
+use crate::{features::space::domain::errors::SpaceError, shared::errors::app_errors::AppError};
+
+/// # ArchiveError Enum
+/// Defines the enum with all the possible error that could ocurr while
+/// writing or reading the archive of a space.
+/// ## Possible Errors
+/// * `InvalidArchive`: The archive or its manifest is malformed.
+/// * `UnsupportedVersion`: The archive was written by a newer format.
+/// * `ChecksumMismatch`: A file doesn't match its checksum.
+/// * `AlreadyExists`: The export destination already exists.
+#[derive(Debug, Error)]
+pub enum ArchiveError {
+    #[error("Invalid space archive: {0}")]
+    InvalidArchive(String),
+
+    #[error("Unsupported archive format version: {0}")]
+    UnsupportedVersion(u32),
+
+    #[error("Checksum mismatch for '{0}'.")]
+    ChecksumMismatch(String),
+
+    #[error("'{0}' already exists.")]
+    AlreadyExists(String),
+
+    #[error("Archive IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Archive zip error: {0}")]
+    Zip(#[from] ZipError),
+
+    #[error(transparent)]
+    Space(#[from] SpaceError),
+
+    #[error(transparent)]
+    AppError(#[from] AppError),
+}
@@ -0,0 +1,40 @@
+//! # Repository
+//! Definition of the storage of the space archives.
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+
+use super::errors::ArchiveError;
+use crate::features::space::domain::space::Space;
+
+/// # ArchiveRepository
+/// Writes and reads the portable archives of the spaces.
+#[async_trait]
+pub trait ArchiveRepository {
+    /// # [CREATE] Export Space (Method)
+    /// Writes the archive of a space.
+    /// ## Fields
+    /// * `space`: The space to be exported.
+    /// * `destination`: The archive file, or the directory it's written in.
+    /// ## Result
+    /// The path of the archive if successful, if not `ArchiveError`.
+    async fn export_space(
+        &self,
+        space: &Space,
+        destination: &Path,
+    ) -> Result<PathBuf, ArchiveError>;
+
+    /// # [CREATE] Import Space (Method)
+    /// Validates an archive and unpacks it into a new space.
+    /// ## Fields
+    /// * `archive`: The archive file.
+    /// * `space_name`: Name of the new space, the archived one if `None`. A
+    ///   taken name gets a ` (n)` suffix.
+    /// ## Result
+    /// The new `Space` if successful, if not `ArchiveError`.
+    async fn import_space(
+        &self,
+        archive: &Path,
+        space_name: Option<&str>,
+    ) -> Result<Space, ArchiveError>;
+}
@@ -0,0 +1,297 @@
+//! # FileSystem Repository
+//! External implementation of the space archives as zip files of the
+//! filesystem. The files of an encrypted space are archived decrypted and
+//! without its encryption manifest: the archive imports as a plain space.
+use std::{
+    collections::HashSet,
+    fs::{self, File},
+    io::{Read, Write},
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use chrono::Utc;
+use log::warn;
+use zip::{write::SimpleFileOptions, CompressionMethod, ZipArchive, ZipWriter};
+
+use crate::{
+    features::{
+        archive::domain::{
+            archive::{
+                archive_path, checksum, space_path, unique_space_name, ArchiveManifest,
+                ArchivedFile, ARCHIVE_CONTENT_DIR, ARCHIVE_EXTENSION, ARCHIVE_FORMAT_VERSION,
+                ARCHIVE_MANIFEST,
+            },
+            errors::ArchiveError,
+            repository::ArchiveRepository,
+        },
+        space::domain::space::Space,
+    },
+    shared::{
+        errors::app_errors::AppError,
+        repositories::{filesystem_repository::FileSystemRepository, keyring::ENCRYPTION_MANIFEST},
+        utils::atomic_write::{temp_path, TMP_SUFFIX},
+    },
+};
+
+/// # FileSystemArchiveRepository
+/// Implementation of the ArchiveRepository trait.
+#[derive(Clone)]
+pub struct FileSystemArchiveRepository {
+    filesystem_repo: FileSystemRepository,
+}
+
+impl FileSystemArchiveRepository {
+    pub fn new(filesystem_repo: FileSystemRepository) -> Self {
+        Self { filesystem_repo }
+    }
+}
+
+#[async_trait]
+impl ArchiveRepository for FileSystemArchiveRepository {
+    async fn export_space(
+        &self,
+        space: &Space,
+        destination: &Path,
+    ) -> Result<PathBuf, ArchiveError> {
+        let space_path = space
+            .route
+            .clone()
+            .ok_or_else(|| ArchiveError::InvalidArchive(space.name.clone()))?;
+        let archive = if destination.is_dir() {
+            destination.join(format!("{}.{}", space.name, ARCHIVE_EXTENSION))
+        } else {
+            destination.to_path_buf()
+        };
+        if archive.exists() {
+            return Err(ArchiveError::AlreadyExists(archive.display().to_string()));
+        }
+
+        let manifest = ArchiveManifest {
+            format_version: ARCHIVE_FORMAT_VERSION,
+            space_name: space.name.clone(),
+            exported: Utc::now(),
+            space: space.manifest.clone(),
+            folders: Vec::new(),
+            files: Vec::new(),
+        };
+        let tmp_archive = temp_path(&archive);
+        let written = {
+            let tmp_archive = tmp_archive.clone();
+            let filesystem_repo = self.filesystem_repo.clone();
+            let read_file =
+                move |path: &Path| tauri::async_runtime::block_on(filesystem_repo.read_file(path));
+            tokio::task::spawn_blocking(move || {
+                write_archive(&space_path, manifest, &tmp_archive, read_file)
+            })
+            .await
+            .map_err(|e| ArchiveError::Io(std::io::Error::other(e)))?
+        };
+        if let Err(e) = written {
+            let _ = tokio::fs::remove_file(&tmp_archive).await;
+            return Err(e);
+        }
+        tokio::fs::rename(&tmp_archive, &archive).await?;
+
+        Ok(archive)
+    }
+
+    async fn import_space(
+        &self,
+        archive: &Path,
+        space_name: Option<&str>,
+    ) -> Result<Space, ArchiveError> {
+        let archive = archive.to_path_buf();
+        let (manifest, archive) = tokio::task::spawn_blocking(move || {
+            read_manifest(&archive).map(|manifest| (manifest, archive))
+        })
+        .await
+        .map_err(|e| ArchiveError::Io(std::io::Error::other(e)))??;
+
+        let requested = space_name.map(str::trim).unwrap_or(&manifest.space_name);
+        if requested.is_empty() || requested.starts_with('.') || requested.contains(['/', '\\']) {
+            return Err(ArchiveError::InvalidArchive(format!(
+                "invalid space name '{}'",
+                requested
+            )));
+        }
+        let base_path = self.filesystem_repo.get_base_path()?;
+        self.filesystem_repo
+            .ensure_directory_exists(&base_path)
+            .await?;
+        let registered: HashSet<String> = self
+            .filesystem_repo
+            .get_registered_spaces()?
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        let name = unique_space_name(requested, |candidate| {
+            registered.contains(candidate) || base_path.join(candidate).exists()
+        });
+
+        // Unpacked in a hidden folder first, so a broken archive leaves no
+        // half imported space behind.
+        let staging = base_path.join(format!(".{}.import", name));
+        if staging.exists() {
+            tokio::fs::remove_dir_all(&staging).await?;
+        }
+        let unpacked = {
+            let staging = staging.clone();
+            let manifest = manifest.clone();
+            tokio::task::spawn_blocking(move || unpack_archive(&archive, &manifest, &staging))
+                .await
+                .map_err(|e| ArchiveError::Io(std::io::Error::other(e)))?
+        };
+        if let Err(e) = unpacked {
+            if let Err(e) = tokio::fs::remove_dir_all(&staging).await {
+                warn!("Failed to remove '{}': {}", staging.display(), e);
+            }
+            return Err(e);
+        }
+
+        let space_path = base_path.join(&name);
+        tokio::fs::rename(&staging, &space_path).await?;
+        self.filesystem_repo
+            .register_space(&name, space_path.clone())
+            .await?;
+        self.filesystem_repo.mark_space_changed(&name);
+
+        Ok(Space {
+            name,
+            route: Some(space_path),
+            manifest: manifest.space,
+        })
+    }
+}
+
+/// Writes the folders and files of a space, then the manifest, to a zip.
+/// ## Params
+/// * `read_file`: Reads a file of the space, decrypting it when it's sealed.
+fn write_archive(
+    space_path: &Path,
+    mut manifest: ArchiveManifest,
+    archive: &Path,
+    read_file: impl Fn(&Path) -> Result<Vec<u8>, AppError>,
+) -> Result<(), ArchiveError> {
+    let options = SimpleFileOptions::default().compression_method(CompressionMethod::Deflated);
+    let mut zip = ZipWriter::new(File::create(archive)?);
+
+    let mut pending = vec![space_path.to_path_buf()];
+    let mut folders = Vec::new();
+    let mut files = Vec::new();
+    while let Some(directory) = pending.pop() {
+        for entry in fs::read_dir(&directory)? {
+            let entry = entry?;
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            if entry.file_type()?.is_dir() {
+                folders.push(path.clone());
+                pending.push(path);
+            } else if !(name.starts_with('.') && name.ends_with(TMP_SUFFIX))
+                && name != ENCRYPTION_MANIFEST
+            {
+                files.push(path);
+            }
+        }
+    }
+    folders.sort();
+    files.sort();
+
+    for folder in folders {
+        let relative = archive_path(folder.strip_prefix(space_path).unwrap_or(&folder));
+        zip.add_directory(format!("{}/{}", ARCHIVE_CONTENT_DIR, relative), options)?;
+        manifest.folders.push(relative);
+    }
+    for file in files {
+        let relative = archive_path(file.strip_prefix(space_path).unwrap_or(&file));
+        let content = read_file(&file)?;
+        zip.start_file(format!("{}/{}", ARCHIVE_CONTENT_DIR, relative), options)?;
+        zip.write_all(&content)?;
+        manifest
+            .files
+            .push(ArchivedFile::of_content(relative, &content));
+    }
+
+    let content = serde_json::to_vec_pretty(&manifest)
+        .map_err(|e| ArchiveError::InvalidArchive(e.to_string()))?;
+    zip.start_file(ARCHIVE_MANIFEST, options)?;
+    zip.write_all(&content)?;
+    zip.finish()?.sync_all()?;
+    Ok(())
+}
+
+/// Reads the manifest of an archive and checks it describes every entry.
+fn read_manifest(archive: &Path) -> Result<ArchiveManifest, ArchiveError> {
+    let mut zip = ZipArchive::new(File::open(archive)?)?;
+
+    let manifest: ArchiveManifest = {
+        let entry = zip
+            .by_name(ARCHIVE_MANIFEST)
+            .map_err(|_| ArchiveError::InvalidArchive(format!("missing {}", ARCHIVE_MANIFEST)))?;
+        serde_json::from_reader(entry).map_err(|e| ArchiveError::InvalidArchive(e.to_string()))?
+    };
+    if manifest.format_version == 0 || manifest.format_version > ARCHIVE_FORMAT_VERSION {
+        return Err(ArchiveError::UnsupportedVersion(manifest.format_version));
+    }
+
+    let mut known: HashSet<String> = HashSet::from([ARCHIVE_MANIFEST.to_string()]);
+    for path in manifest
+        .folders
+        .iter()
+        .chain(manifest.files.iter().map(|f| &f.path))
+    {
+        if space_path(path).is_none() {
+            return Err(ArchiveError::InvalidArchive(format!(
+                "invalid path '{}'",
+                path
+            )));
+        }
+        known.insert(format!("{}/{}", ARCHIVE_CONTENT_DIR, path));
+    }
+    for name in zip.file_names() {
+        if !known.contains(name.trim_end_matches('/')) {
+            return Err(ArchiveError::InvalidArchive(format!(
+                "'{}' isn't listed in the manifest",
+                name
+            )));
+        }
+    }
+    Ok(manifest)
+}
+
+/// Extracts the folders and files listed in the manifest, checking them.
+fn unpack_archive(
+    archive: &Path,
+    manifest: &ArchiveManifest,
+    destination: &Path,
+) -> Result<(), ArchiveError> {
+    let mut zip = ZipArchive::new(File::open(archive)?)?;
+    fs::create_dir_all(destination)?;
+
+    for folder in &manifest.folders {
+        if let Some(relative) = space_path(folder) {
+            fs::create_dir_all(destination.join(relative))?;
+        }
+    }
+    for file in &manifest.files {
+        let relative = space_path(&file.path)
+            .ok_or_else(|| ArchiveError::InvalidArchive(format!("invalid path '{}'", file.path)))?;
+        let entry = zip
+            .by_name(&format!("{}/{}", ARCHIVE_CONTENT_DIR, file.path))
+            .map_err(|_| ArchiveError::InvalidArchive(format!("missing '{}'", file.path)))?;
+
+        // Never read more than announced, a lying entry fails the size check.
+        let mut content = Vec::new();
+        entry.take(file.size + 1).read_to_end(&mut content)?;
+        if content.len() as u64 != file.size || checksum(&content) != file.sha256 {
+            return Err(ArchiveError::ChecksumMismatch(file.path.clone()));
+        }
+
+        let path = destination.join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, content)?;
+    }
+    Ok(())
+}
@@ -0,0 +1,86 @@
+//! # Tauri Commands [Archive]
+//! Implementation of the commands exporting and importing spaces as
+//! portable archives to expose them on tauri for having them in the
+//! frontend application.
+use std::path::Path;
+
+use tauri::State;
+
+use crate::{
+    features::{
+        archive::{
+            application::{export, import},
+            infrastructure::filesystem_repository::FileSystemArchiveRepository,
+        },
+        search,
+        space::domain::space::Space,
+    },
+    shared::state::state::AppState,
+};
+
+/// # [CREATE] Export Space
+/// Writes a space to a zip archive holding its notes, folders, attachments
+/// and metadata, with a manifest of checksums.
+/// ## Params
+/// * `space_name`: The space to be exported.
+/// * `destination`: The archive file, or the directory it's written in as
+///   `<space_name>.zip`.
+/// ## Result
+/// The path of the archive.
+#[tauri::command]
+pub async fn export_space_cmd(
+    state: State<'_, AppState>,
+    archive_repo: State<'_, FileSystemArchiveRepository>,
+    space_name: &str,
+    destination: &str,
+) -> Result<String, String> {
+    // Holding the notes keeps the saves from changing the space meanwhile.
+    let _fs_repo_lock = state.filesystem_repo.lock().await;
+    let spaces_repo_lock = state.spaces_repo.lock().await;
+
+    export::export_space_use_case(
+        &*archive_repo,
+        &*spaces_repo_lock,
+        space_name,
+        Path::new(destination),
+    )
+    .await
+    .map(|path| path.display().to_string())
+    .map_err(|e| e.to_string())
+}
+
+/// # [CREATE] Import Space
+/// Validates an archive written by `export_space_cmd`, unpacks it into a new
+/// space and indexes its notes.
+/// ## Params
+/// * `archive_path`: The archive file.
+/// * `space_name`: Name of the new space, the archived one if not given. A
+///   taken name gets a ` (n)` suffix.
+#[tauri::command]
+pub async fn import_space_cmd(
+    state: State<'_, AppState>,
+    archive_repo: State<'_, FileSystemArchiveRepository>,
+    archive_path: &str,
+    space_name: Option<&str>,
+) -> Result<Space, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    let space = import::import_space_use_case(&*archive_repo, Path::new(archive_path), space_name)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    search::application::index::reindex_space_use_case(
+        &*fs_repo_lock,
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &space.name,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(space)
+}
@@ -0,0 +1,55 @@
+use std::path::Path;
+
+use crate::features::attachments::domain::{
+    errors::AttachmentError, repository::AttachmentRepository,
+};
+
+/// # Copy Referenced Attachments
+/// Copies the attachments referenced by a note content to another space and
+/// points the content to the copies.
+/// ## Fields
+/// * `repo` (&T): The repo that is implemented on the function
+/// * `source_space`: The space of the note content.
+/// * `target_space`: The space the content is copied to.
+/// * `content`: The note content.
+/// ## Returns
+/// The content referencing the attachments of `target_space`.
+#[doc(alias = "copy_attachments")]
+pub async fn copy_referenced_attachments_use_case<T: AttachmentRepository>(
+    repo: &T,
+    source_space: &str,
+    target_space: &str,
+    content: &str,
+) -> Result<String, AttachmentError> {
+    if source_space == target_space {
+        return Ok(content.to_string());
+    }
+
+    let mut content = content.to_string();
+    for attachment in repo.get_attachments(source_space).await? {
+        if !content.contains(&attachment.hash) {
+            continue;
+        }
+        let copy = repo
+            .copy_attachment(source_space, target_space, &attachment.id)
+            .await?;
+
+        // Files and thumbnails are referenced by absolute path, swap the
+        // store folder of the source space for the target one.
+        let source_dir = Path::new(&attachment.path).parent();
+        let target_dir = Path::new(&copy.path).parent();
+        if let (Some(source_dir), Some(target_dir)) = (source_dir, target_dir) {
+            content = replace_path(&content, source_dir, target_dir);
+        }
+    }
+    Ok(content)
+}
+
+/// Replaces a path inside a JSON content, as written and JSON-escaped.
+fn replace_path(content: &str, from: &Path, to: &Path) -> String {
+    let escape = |path: &Path| {
+        let quoted = serde_json::to_string(&path.to_string_lossy()).unwrap_or_default();
+        quoted.trim_matches('"').to_string()
+    };
+    content.replace(&escape(from), &escape(to))
+}
@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use crate::features::attachments::domain::{
+    errors::AttachmentError, repository::AttachmentRepository,
+};
+
+/// # Get Attachment Path
+/// Resolves the file of an attachment for the frontend to load it.
+/// ## Fields
+/// * `repo` (&T): The repo that is implemented on the function
+/// * `space_name`: The space of the attachment.
+/// * `attachment_id`: The stored file name.
+#[doc(alias = "get_attachment_path")]
+pub async fn get_attachment_path_use_case<T: AttachmentRepository>(
+    repo: &T,
+    space_name: &str,
+    attachment_id: &str,
+) -> Result<PathBuf, AttachmentError> {
+    repo.get_attachment_path(space_name, attachment_id).await
+}
+
+/// # Get Attachment Content
+/// Reads an attachment, for the frontend to show the ones of the encrypted
+/// spaces, which can't be loaded from their file.
+/// ## Fields
+/// * `repo` (&T): The repo that is implemented on the function
+/// * `space_name`: The space of the attachment.
+/// * `attachment_id`: The stored file name.
+#[doc(alias = "get_attachment_content")]
+pub async fn get_attachment_content_use_case<T: AttachmentRepository>(
+    repo: &T,
+    space_name: &str,
+    attachment_id: &str,
+) -> Result<Vec<u8>, AttachmentError> {
+    repo.get_attachment_content(space_name, attachment_id).await
+}
@@ -0,0 +1,63 @@
+use std::collections::BTreeMap;
+
+use crate::features::attachments::domain::{
+    errors::AttachmentError,
+    image::{is_image_extension, ImagePayload},
+    repository::{AttachmentRepository, ImageRepository},
+};
+
+/// # Upload Image
+/// Stores an image pasted into a note, without its location data, and
+/// generates its thumbnails.
+/// ## Fields
+/// * `repo` (&T): The repo that is implemented on the function
+/// * `space_name`: The space of the note.
+/// * `file_name`: The original file name.
+/// * `content`: The bytes of the image.
+/// ## Returns
+/// The payload of the Editor.js image block.
+#[doc(alias = "upload_image")]
+pub async fn upload_image_use_case<T: ImageRepository>(
+    repo: &T,
+    space_name: &str,
+    file_name: &str,
+    content: Vec<u8>,
+) -> Result<ImagePayload, AttachmentError> {
+    if content.is_empty() {
+        return Err(AttachmentError::EmptyFile);
+    }
+    let file = repo.save_image(space_name, file_name, content).await?;
+    Ok(ImagePayload { success: 1, file })
+}
+
+/// # Get Image Thumbnails
+/// Returns the thumbnails of an image, generating the missing ones.
+#[doc(alias = "get_image_thumbnails")]
+pub async fn get_image_thumbnails_use_case<T: ImageRepository>(
+    repo: &T,
+    space_name: &str,
+    attachment_id: &str,
+) -> Result<BTreeMap<u32, String>, AttachmentError> {
+    repo.get_thumbnails(space_name, attachment_id).await
+}
+
+/// # Rebuild Thumbnails
+/// Drops the thumbnail cache of a space and generates it again.
+/// ## Returns
+/// The number of images processed.
+#[doc(alias = "rebuild_thumbnails")]
+pub async fn rebuild_thumbnails_use_case<T: AttachmentRepository + ImageRepository>(
+    repo: &T,
+    space_name: &str,
+) -> Result<usize, AttachmentError> {
+    repo.clear_thumbnails(space_name).await?;
+
+    let mut processed = 0;
+    for attachment in repo.get_attachments(space_name).await? {
+        if is_image_extension(attachment.extension.as_deref()) {
+            repo.get_thumbnails(space_name, &attachment.id).await?;
+            processed += 1;
+        }
+    }
+    Ok(processed)
+}
@@ -0,0 +1,5 @@
+pub mod copy;
+pub mod get;
+pub mod image;
+pub mod upload;
+pub mod usage;
@@ -0,0 +1,26 @@
+use crate::features::attachments::domain::{
+    attachment::AttachesPayload, errors::AttachmentError, repository::AttachmentRepository,
+};
+
+/// # Upload Attachment
+/// Stores a file in the attachment store of a space.
+/// ## Fields
+/// * `repo` (&T): The repo that is implemented on the function
+/// * `space_name`: The space of the note the file is attached to.
+/// * `file_name`: The original file name, shown in the note.
+/// * `content`: The bytes of the file.
+/// ## Returns
+/// The payload of the Editor.js attaches block.
+#[doc(alias = "upload_attachment")]
+pub async fn upload_attachment_use_case<T: AttachmentRepository>(
+    repo: &T,
+    space_name: &str,
+    file_name: &str,
+    content: &[u8],
+) -> Result<AttachesPayload, AttachmentError> {
+    if content.is_empty() {
+        return Err(AttachmentError::EmptyFile);
+    }
+    let attachment = repo.save_attachment(space_name, file_name, content).await?;
+    Ok(AttachesPayload::new(&attachment, file_name))
+}
@@ -0,0 +1,112 @@
+use chrono::{TimeDelta, Utc};
+
+use crate::features::{
+    attachments::domain::{
+        attachment::{Attachment, AttachmentUsage, ORPHAN_GRACE_HOURS},
+        errors::AttachmentError,
+        repository::AttachmentRepository,
+    },
+    notes::domain::repository::NoteRepository,
+    templates::domain::repository::TemplateRepository,
+};
+
+/// # Get Attachments Usage
+/// Counts the references to every attachment of a space. A note or a
+/// template references an attachment when its blocks contain the attachment
+/// hash. The templates of the space and the global ones are both read.
+/// ## Fields
+/// * `repo` (&T): The attachments repository.
+/// * `notes_repo` (&N): The notes repository, to read the note blocks.
+/// * `templates_repo` (&M): The templates repository, to read the templates.
+/// * `space_name`: The space to inspect.
+#[doc(alias = "get_attachments_usage")]
+pub async fn get_attachments_usage_use_case<T, N, M>(
+    repo: &T,
+    notes_repo: &N,
+    templates_repo: &M,
+    space_name: &str,
+) -> Result<Vec<AttachmentUsage>, AttachmentError>
+where
+    T: AttachmentRepository,
+    N: NoteRepository,
+    M: TemplateRepository,
+{
+    let mut usages: Vec<AttachmentUsage> = repo
+        .get_attachments(space_name)
+        .await?
+        .into_iter()
+        .map(|attachment| AttachmentUsage {
+            attachment,
+            notes: Vec::new(),
+            templates: Vec::new(),
+        })
+        .collect();
+    if usages.is_empty() {
+        return Ok(usages);
+    }
+
+    for note in notes_repo.get_notes(space_name).await? {
+        let note = notes_repo
+            .get_note_content(space_name, &note.name, note.folder.as_deref())
+            .await?;
+        let content = note.content.as_deref().unwrap_or_default();
+        let note_path = match &note.folder {
+            Some(folder) => format!("{}/{}", folder, note.name),
+            None => note.name.clone(),
+        };
+        for usage in usages.iter_mut() {
+            if content.contains(&usage.attachment.hash) {
+                usage.notes.push(note_path.clone());
+            }
+        }
+    }
+
+    for template in templates_repo.get_templates(Some(space_name)).await? {
+        let template = templates_repo
+            .get_template(Some(space_name), &template.name, template.scope)
+            .await?;
+        let content = template.content.as_deref().unwrap_or_default();
+        for usage in usages.iter_mut() {
+            if content.contains(&usage.attachment.hash) {
+                usage.templates.push(template.name.clone());
+            }
+        }
+    }
+    Ok(usages)
+}
+
+/// # Collect Attachments
+/// Garbage-collects the attachments no note or template references anymore.
+/// Recent files are kept, their note may not have been saved yet.
+/// ## Returns
+/// The removed attachments.
+#[doc(alias = "collect_attachments")]
+pub async fn collect_attachments_use_case<T, N, M>(
+    repo: &T,
+    notes_repo: &N,
+    templates_repo: &M,
+    space_name: &str,
+) -> Result<Vec<Attachment>, AttachmentError>
+where
+    T: AttachmentRepository,
+    N: NoteRepository,
+    M: TemplateRepository,
+{
+    let threshold = Utc::now() - TimeDelta::hours(ORPHAN_GRACE_HOURS);
+
+    let mut removed = Vec::new();
+    for usage in
+        get_attachments_usage_use_case(repo, notes_repo, templates_repo, space_name).await?
+    {
+        let old_enough = usage
+            .attachment
+            .modified
+            .is_some_and(|modified| modified < threshold);
+        if usage.is_unused() && old_enough {
+            repo.delete_attachment(space_name, &usage.attachment.id)
+                .await?;
+            removed.push(usage.attachment);
+        }
+    }
+    Ok(removed)
+}
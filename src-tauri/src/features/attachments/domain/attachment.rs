@@ -0,0 +1,119 @@
+//! # Attachments Domain
+//! Defines the files attached to the notes of a space. Attachments are
+//! content-addressed: a file is stored once per space, named after the
+//! SHA-256 of its bytes.
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+/// Reserved folder of a space holding its attachments. Living inside the
+/// space, attachments move with it when the space is copied or exported.
+pub const ATTACHMENTS_DIR: &str = ".attachments";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # Attachment
+/// A file of the attachment store of a space.
+/// ## Fields
+/// * `id`: The stored file name, `<hash>.<extension>`. Notes reference the
+///   attachment by it.
+/// * `hash`: The SHA-256 of the content.
+/// * `extension`: The extension of the original file, lowercased.
+/// * `size`: The size in bytes.
+/// * `path`: The absolute path of the stored file, for the frontend to load it.
+/// * `modified`: When the file was stored.
+pub struct Attachment {
+    pub id: String,
+    pub hash: String,
+    pub extension: Option<String>,
+    pub size: u64,
+    pub path: String,
+    pub modified: Option<DateTime<Utc>>,
+}
+
+impl Attachment {
+    /// # Id of
+    /// The stored file name of a content with the extension of `file_name`.
+    /// ## Returns
+    /// The id, the hash and the extension.
+    pub fn id_of(content: &[u8], file_name: &str) -> (String, String, Option<String>) {
+        let hash = format!("{:x}", Sha256::digest(content));
+        let extension = extension_of(file_name);
+        let id = match &extension {
+            Some(extension) => format!("{}.{}", hash, extension),
+            None => hash.clone(),
+        };
+        (id, hash, extension)
+    }
+}
+
+/// Lowercased extension of a file name, only if it is a plain one.
+pub fn extension_of(file_name: &str) -> Option<String> {
+    let (stem, extension) = file_name.rsplit_once('.')?;
+    Some(extension.to_lowercase()).filter(|e| {
+        !stem.is_empty()
+            && !e.is_empty()
+            && e.len() <= 10
+            && e.chars().all(|c| c.is_ascii_alphanumeric())
+    })
+}
+
+/// Orphaned attachments younger than this are kept: they may belong to a
+/// note that hasn't been saved yet.
+pub const ORPHAN_GRACE_HOURS: i64 = 1;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # AttachesFile
+/// The `file` of an Editor.js attaches block.
+pub struct AttachesFile {
+    pub url: String,
+    pub name: String,
+    pub size: u64,
+    pub extension: Option<String>,
+    pub title: String,
+    pub id: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # AttachesPayload
+/// The response the Editor.js attaches tool expects from an upload.
+pub struct AttachesPayload {
+    pub success: u8,
+    pub file: AttachesFile,
+}
+
+impl AttachesPayload {
+    pub fn new(attachment: &Attachment, file_name: &str) -> Self {
+        Self {
+            success: 1,
+            file: AttachesFile {
+                url: attachment.path.clone(),
+                name: file_name.to_string(),
+                size: attachment.size,
+                extension: attachment.extension.clone(),
+                title: file_name.to_string(),
+                id: attachment.id.clone(),
+            },
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # AttachmentUsage
+/// An attachment with the notes and templates referencing it.
+/// ## Fields
+/// * `attachment`: The stored attachment.
+/// * `notes`: The notes referencing it, as `folder/name`.
+/// * `templates`: The space and global templates referencing it.
+pub struct AttachmentUsage {
+    pub attachment: Attachment,
+    pub notes: Vec<String>,
+    #[serde(default)]
+    pub templates: Vec<String>,
+}
+
+impl AttachmentUsage {
+    /// Whether nothing references the attachment.
+    pub fn is_unused(&self) -> bool {
+        self.notes.is_empty() && self.templates.is_empty()
+    }
+}
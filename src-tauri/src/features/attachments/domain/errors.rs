@@ -0,0 +1,46 @@
+//! # Errors
+//! Defines all error that could ocurr inside the attachments part of the
+//! application.
+use std::io;
+
+use thiserror::Error;
+
+use crate::{
+    features::{notes::domain::errors::NoteError, templates::domain::errors::TemplateError},
+    shared::errors::app_errors::AppError,
+};
+
+/// # AttachmentError Enum
+/// Defines the enum with all the possible error that could ocurr during an
+/// Attachment operation in the application.
+/// ## Possible Errors
+/// * `NotFound`: The attachment isn't in the store.
+/// * `EmptyFile`: The uploaded file has no content.
+/// * `InvalidId`: The attachment id isn't a stored file name.
+/// * `InvalidImage`: The file couldn't be read as an image.
+#[derive(Debug, Error)]
+pub enum AttachmentError {
+    #[error("Attachment '{0}' not found.")]
+    NotFound(String),
+
+    #[error("Cannot attach an empty file.")]
+    EmptyFile,
+
+    #[error("Invalid attachment id: {0}")]
+    InvalidId(String),
+
+    #[error("Invalid image: {0}")]
+    InvalidImage(String),
+
+    #[error(transparent)]
+    Note(#[from] NoteError),
+
+    #[error(transparent)]
+    Template(#[from] TemplateError),
+
+    #[error("Attachments IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    AppError(#[from] AppError),
+}
@@ -0,0 +1,53 @@
+//! # Images Domain
+//! Defines the images pasted into the notes: stored as attachments, with
+//! their dimensions and a few thumbnails.
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+/// Longest side of the generated thumbnails, in pixels.
+pub const THUMBNAIL_SIZES: [u32; 3] = [160, 320, 640];
+
+/// Folder of the attachment store caching the thumbnails. It can be removed
+/// at any time, thumbnails are generated again when requested.
+pub const THUMBNAILS_DIR: &str = ".thumbnails";
+
+/// Extensions handled by the image pipeline.
+const IMAGE_EXTENSIONS: [&str; 5] = ["png", "jpg", "jpeg", "gif", "webp"];
+
+/// Whether an attachment extension is an image one.
+pub fn is_image_extension(extension: Option<&str>) -> bool {
+    extension.is_some_and(|e| IMAGE_EXTENSIONS.contains(&e))
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # ImageFile
+/// The `file` of an Editor.js image block.
+/// ## Fields
+/// * `url`: The absolute path of the stored image.
+/// * `id`: The attachment id of the image.
+/// * `width`: The width as displayed, after the EXIF orientation.
+/// * `height`: The height as displayed, after the EXIF orientation.
+/// * `size`: The size in bytes.
+/// * `gps_removed`: Whether location data has been stripped from the image.
+/// * `thumbnails`: Path of the thumbnail of each size. Images smaller than a
+///   size use the original.
+pub struct ImageFile {
+    pub url: String,
+    pub id: String,
+    pub width: u32,
+    pub height: u32,
+    pub size: u64,
+    #[serde(default)]
+    pub gps_removed: bool,
+    #[serde(default)]
+    pub thumbnails: BTreeMap<u32, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # ImagePayload
+/// The response the Editor.js image tool expects from an upload.
+pub struct ImagePayload {
+    pub success: u8,
+    pub file: ImageFile,
+}
@@ -0,0 +1,4 @@
+pub mod attachment;
+pub mod errors;
+pub mod image;
+pub mod repository;
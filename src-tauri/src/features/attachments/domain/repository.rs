@@ -0,0 +1,108 @@
+//! # Repository
+//! Defines how the attachments of a space are stored.
+use std::{collections::BTreeMap, path::PathBuf};
+
+use async_trait::async_trait;
+
+use super::attachment::Attachment;
+use super::errors::AttachmentError;
+use super::image::ImageFile;
+
+/// # Attachment Repository
+/// It implements the methods to interact with the attachment store of a space.
+#[async_trait]
+pub trait AttachmentRepository {
+    /// # [GET] Attachments
+    /// Lists the attachments stored in a space.
+    async fn get_attachments(&self, space_name: &str) -> Result<Vec<Attachment>, AttachmentError>;
+
+    /// # [GET] Attachment path
+    /// Resolves the file of an attachment.
+    /// ## Fields
+    /// * `space_name`: The space of the attachment.
+    /// * `attachment_id`: The stored file name.
+    async fn get_attachment_path(
+        &self,
+        space_name: &str,
+        attachment_id: &str,
+    ) -> Result<PathBuf, AttachmentError>;
+
+    /// # [GET] Attachment content
+    /// Reads the bytes of an attachment, decrypted when its space is
+    /// encrypted.
+    /// ## Fields
+    /// * `space_name`: The space of the attachment.
+    /// * `attachment_id`: The stored file name.
+    async fn get_attachment_content(
+        &self,
+        space_name: &str,
+        attachment_id: &str,
+    ) -> Result<Vec<u8>, AttachmentError>;
+
+    /// # [CREATE] Save attachment
+    /// Stores a file, reusing the stored one when the content is the same.
+    /// ## Fields
+    /// * `space_name`: The space to attach the file to.
+    /// * `file_name`: The original file name, for its extension.
+    /// * `content`: The bytes of the file.
+    async fn save_attachment(
+        &self,
+        space_name: &str,
+        file_name: &str,
+        content: &[u8],
+    ) -> Result<Attachment, AttachmentError>;
+
+    /// # [CREATE] Copy attachment
+    /// Copies an attachment to the store of another space.
+    /// ## Fields
+    /// * `source_space`: The space holding the attachment.
+    /// * `target_space`: The space receiving the copy.
+    /// * `attachment_id`: The stored file name.
+    /// ## Result
+    /// The attachment in the target space.
+    async fn copy_attachment(
+        &self,
+        source_space: &str,
+        target_space: &str,
+        attachment_id: &str,
+    ) -> Result<Attachment, AttachmentError>;
+
+    /// # [DELETE] Attachment
+    /// Removes a file from the store.
+    async fn delete_attachment(
+        &self,
+        space_name: &str,
+        attachment_id: &str,
+    ) -> Result<(), AttachmentError>;
+}
+
+/// # Image Repository
+/// It implements the image pipeline on top of the attachment store.
+#[async_trait]
+pub trait ImageRepository {
+    /// # [CREATE] Save image
+    /// Stores an image without its location data and generates its
+    /// thumbnails.
+    /// ## Fields
+    /// * `space_name`: The space of the note the image is pasted into.
+    /// * `file_name`: The original file name, for its extension.
+    /// * `content`: The bytes of the image.
+    async fn save_image(
+        &self,
+        space_name: &str,
+        file_name: &str,
+        content: Vec<u8>,
+    ) -> Result<ImageFile, AttachmentError>;
+
+    /// # [GET] Thumbnails
+    /// Returns the thumbnails of a stored image, generating the missing ones.
+    async fn get_thumbnails(
+        &self,
+        space_name: &str,
+        attachment_id: &str,
+    ) -> Result<BTreeMap<u32, String>, AttachmentError>;
+
+    /// # [DELETE] Thumbnails
+    /// Empties the thumbnail cache of a space.
+    async fn clear_thumbnails(&self, space_name: &str) -> Result<(), AttachmentError>;
+}
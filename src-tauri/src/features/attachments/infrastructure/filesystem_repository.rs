@@ -0,0 +1,211 @@
+//! # FileSystem Repository
+//! Stores the attachments of a space inside its reserved `.attachments`
+//! folder, one file per distinct content.
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use tokio::fs::{self, read_dir};
+
+use crate::{
+    features::attachments::domain::{
+        attachment::{Attachment, ATTACHMENTS_DIR},
+        errors::AttachmentError,
+        image::THUMBNAILS_DIR,
+        repository::AttachmentRepository,
+    },
+    shared::repositories::filesystem_repository::FileSystemRepository,
+};
+
+/// # FileSystemAttachmentRepository
+/// Implementation of the AttachmentRepository trait.
+#[derive(Clone)]
+pub struct FileSystemAttachmentRepository {
+    pub(super) filesystem_repo: FileSystemRepository,
+}
+
+impl FileSystemAttachmentRepository {
+    pub fn new(filesystem_repo: FileSystemRepository) -> Self {
+        Self { filesystem_repo }
+    }
+
+    pub(super) fn get_attachments_path(
+        &self,
+        space_name: &str,
+    ) -> Result<PathBuf, AttachmentError> {
+        Ok(self
+            .filesystem_repo
+            .get_space_path(space_name)?
+            .join(ATTACHMENTS_DIR))
+    }
+
+    /// Resolves the path of an id, rejecting anything that isn't a stored
+    /// file name.
+    fn get_file_path(
+        &self,
+        space_name: &str,
+        attachment_id: &str,
+    ) -> Result<PathBuf, AttachmentError> {
+        let (hash, extension) = match attachment_id.split_once('.') {
+            Some((hash, extension)) => (hash, Some(extension)),
+            None => (attachment_id, None),
+        };
+        let valid = hash.len() == 64
+            && hash.chars().all(|c| c.is_ascii_hexdigit())
+            && extension
+                .is_none_or(|e| !e.is_empty() && e.chars().all(|c| c.is_ascii_alphanumeric()));
+        if !valid {
+            return Err(AttachmentError::InvalidId(attachment_id.to_string()));
+        }
+        Ok(self.get_attachments_path(space_name)?.join(attachment_id))
+    }
+
+    /// Removes the cached thumbnails of an attachment.
+    async fn remove_thumbnails(&self, space_name: &str, attachment_id: &str) {
+        let Ok(attachments_path) = self.get_attachments_path(space_name) else {
+            return;
+        };
+        let Ok(mut entries) = read_dir(attachments_path.join(THUMBNAILS_DIR)).await else {
+            return;
+        };
+        let prefix = format!("{}-", attachment_id.split('.').next().unwrap_or_default());
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            if entry.file_name().to_string_lossy().starts_with(&prefix) {
+                let _ = fs::remove_file(entry.path()).await;
+            }
+        }
+    }
+
+    /// Builds the attachment of a stored file.
+    async fn read_attachment(&self, path: &Path) -> Result<Attachment, AttachmentError> {
+        let metadata = fs::metadata(path).await?;
+        let id = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+            .to_string();
+        let (hash, extension) = match id.split_once('.') {
+            Some((hash, extension)) => (hash.to_string(), Some(extension.to_string())),
+            None => (id.clone(), None),
+        };
+        Ok(Attachment {
+            id,
+            hash,
+            extension,
+            size: metadata.len(),
+            path: path.to_string_lossy().to_string(),
+            modified: metadata.modified().ok().map(DateTime::<Utc>::from),
+        })
+    }
+}
+
+#[async_trait]
+impl AttachmentRepository for FileSystemAttachmentRepository {
+    async fn get_attachments(&self, space_name: &str) -> Result<Vec<Attachment>, AttachmentError> {
+        let attachments_path = self.get_attachments_path(space_name)?;
+        let mut entries = match read_dir(&attachments_path).await {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut attachments = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            let stored = path.is_file()
+                && path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| !n.starts_with('.'));
+            if stored {
+                attachments.push(self.read_attachment(&path).await?);
+            }
+        }
+        attachments.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(attachments)
+    }
+
+    async fn get_attachment_path(
+        &self,
+        space_name: &str,
+        attachment_id: &str,
+    ) -> Result<PathBuf, AttachmentError> {
+        let path = self.get_file_path(space_name, attachment_id)?;
+        if !path.is_file() {
+            return Err(AttachmentError::NotFound(attachment_id.to_string()));
+        }
+        Ok(path)
+    }
+
+    async fn get_attachment_content(
+        &self,
+        space_name: &str,
+        attachment_id: &str,
+    ) -> Result<Vec<u8>, AttachmentError> {
+        let path = self.get_attachment_path(space_name, attachment_id).await?;
+        Ok(self.filesystem_repo.read_file(&path).await?)
+    }
+
+    async fn save_attachment(
+        &self,
+        space_name: &str,
+        file_name: &str,
+        content: &[u8],
+    ) -> Result<Attachment, AttachmentError> {
+        let (id, _, _) = Attachment::id_of(content, file_name);
+        let path = self.get_file_path(space_name, &id)?;
+
+        // Same content, same file: nothing to write.
+        if !path.is_file() {
+            self.filesystem_repo
+                .ensure_directory_exists(&self.get_attachments_path(space_name)?)
+                .await?;
+            self.filesystem_repo.write_file(&path, content).await?;
+        }
+        self.read_attachment(&path).await
+    }
+
+    async fn copy_attachment(
+        &self,
+        source_space: &str,
+        target_space: &str,
+        attachment_id: &str,
+    ) -> Result<Attachment, AttachmentError> {
+        let source_path = self
+            .get_attachment_path(source_space, attachment_id)
+            .await?;
+        let target_path = self.get_file_path(target_space, attachment_id)?;
+
+        if !target_path.is_file() {
+            self.filesystem_repo
+                .ensure_directory_exists(&self.get_attachments_path(target_space)?)
+                .await?;
+            let content = self.filesystem_repo.read_file(&source_path).await?;
+            self.filesystem_repo
+                .write_file(&target_path, &content)
+                .await?;
+        }
+        self.read_attachment(&target_path).await
+    }
+
+    async fn delete_attachment(
+        &self,
+        space_name: &str,
+        attachment_id: &str,
+    ) -> Result<(), AttachmentError> {
+        let path = self.get_file_path(space_name, attachment_id)?;
+        match fs::remove_file(&path).await {
+            Ok(_) => {
+                self.remove_thumbnails(space_name, attachment_id).await;
+                Ok(())
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Err(AttachmentError::NotFound(attachment_id.to_string()))
+            }
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! # Image Processing
+//! Synchronous image operations of the image pipeline: location stripping,
+//! dimensions and thumbnails. Run them on a blocking thread.
+use std::io::Cursor;
+
+use exif::{Context, In, Reader, Tag};
+use image::{
+    codecs::jpeg::JpegEncoder, metadata::Orientation, DynamicImage, ImageDecoder, ImageFormat,
+    ImageReader,
+};
+
+use crate::features::attachments::domain::errors::AttachmentError;
+
+/// Quality of the re-encoded JPEG images and thumbnails.
+const JPEG_QUALITY: u8 = 85;
+
+/// Identifier opening the EXIF (APP1) segments of a JPEG.
+const JPEG_EXIF: &[u8] = b"Exif\0\0";
+
+/// Namespace opening the XMP (APP1) segments of a JPEG.
+const JPEG_XMP: &[u8] = b"http://ns.adobe.com/xap/1.0/\0";
+
+/// Keyword of the XMP (`iTXt`) chunks of a PNG.
+const PNG_XMP: &[u8] = b"XML:com.adobe.xmp\0";
+
+/// An image ready to be stored.
+pub struct ProcessedImage {
+    pub content: Vec<u8>,
+    pub extension: &'static str,
+    pub width: u32,
+    pub height: u32,
+    pub gps_removed: bool,
+}
+
+/// # Process image
+/// Checks that the content is an image and removes its location data. When
+/// the EXIF block or the XMP packet holds GPS tags both are dropped: JPEG
+/// and PNG lose the segments without re-encoding, unless the EXIF
+/// orientation has to be baked into the pixels first; other formats are
+/// re-encoded as PNG.
+pub fn process_image(content: Vec<u8>) -> Result<ProcessedImage, AttachmentError> {
+    let format = image::guess_format(&content).map_err(invalid_image)?;
+    let orientation = read_orientation(&content);
+    let gps = has_gps(&content) || has_xmp_gps(&content);
+
+    let (content, format) = match (gps, format) {
+        (false, _) => (content, format),
+        (true, ImageFormat::Jpeg) if orientation == Orientation::NoTransforms => {
+            (strip_jpeg_exif(&content)?, format)
+        }
+        (true, ImageFormat::Png) if orientation == Orientation::NoTransforms => {
+            (strip_png_exif(&content)?, format)
+        }
+        (true, _) => {
+            let image = decode(&content)?;
+            let format = match format {
+                ImageFormat::Jpeg => ImageFormat::Jpeg,
+                _ => ImageFormat::Png,
+            };
+            (encode(&image, format)?, format)
+        }
+    };
+
+    // Orientation is gone from re-encoded images, decoding applies it.
+    let image = decode(&content)?;
+    Ok(ProcessedImage {
+        content,
+        extension: extension_of(format),
+        width: image.width(),
+        height: image.height(),
+        gps_removed: gps,
+    })
+}
+
+/// # Thumbnail
+/// Scales an image down so its longest side is `size`.
+/// ## Returns
+/// The encoded thumbnail and its extension, `None` if the image is smaller.
+pub fn thumbnail(
+    content: &[u8],
+    size: u32,
+) -> Result<Option<(Vec<u8>, &'static str)>, AttachmentError> {
+    let image = decode(content)?;
+    if image.width().max(image.height()) <= size {
+        return Ok(None);
+    }
+    let thumbnail = image.thumbnail(size, size);
+    let format = if thumbnail.color().has_alpha() {
+        ImageFormat::Png
+    } else {
+        ImageFormat::Jpeg
+    };
+    Ok(Some((encode(&thumbnail, format)?, extension_of(format))))
+}
+
+/// Decodes an image applying its EXIF orientation.
+fn decode(content: &[u8]) -> Result<DynamicImage, AttachmentError> {
+    let mut decoder = ImageReader::new(Cursor::new(content))
+        .with_guessed_format()
+        .map_err(|e| AttachmentError::InvalidImage(e.to_string()))?
+        .into_decoder()
+        .map_err(invalid_image)?;
+    let orientation = decoder.orientation().unwrap_or(Orientation::NoTransforms);
+    let mut image = DynamicImage::from_decoder(decoder).map_err(invalid_image)?;
+    image.apply_orientation(orientation);
+    Ok(image)
+}
+
+fn encode(image: &DynamicImage, format: ImageFormat) -> Result<Vec<u8>, AttachmentError> {
+    let mut buffer = Cursor::new(Vec::new());
+    match format {
+        ImageFormat::Jpeg => {
+            let encoder = JpegEncoder::new_with_quality(&mut buffer, JPEG_QUALITY);
+            image
+                .to_rgb8()
+                .write_with_encoder(encoder)
+                .map_err(invalid_image)?;
+        }
+        _ => image.write_to(&mut buffer, format).map_err(invalid_image)?,
+    }
+    Ok(buffer.into_inner())
+}
+
+fn extension_of(format: ImageFormat) -> &'static str {
+    format.extensions_str().first().copied().unwrap_or("png")
+}
+
+fn invalid_image(e: image::ImageError) -> AttachmentError {
+    AttachmentError::InvalidImage(e.to_string())
+}
+
+fn read_orientation(content: &[u8]) -> Orientation {
+    Reader::new()
+        .read_from_container(&mut Cursor::new(content))
+        .ok()
+        .and_then(|exif| {
+            exif.get_field(Tag::Orientation, In::PRIMARY)
+                .and_then(|field| field.value.get_uint(0))
+        })
+        .and_then(|value| Orientation::from_exif(value as u8))
+        .unwrap_or(Orientation::NoTransforms)
+}
+
+fn has_gps(content: &[u8]) -> bool {
+    Reader::new()
+        .read_from_container(&mut Cursor::new(content))
+        .is_ok_and(|exif| {
+            exif.fields().any(|field| {
+                field.tag.context() == Context::Gps || field.tag == Tag::GPSInfoIFDPointer
+            })
+        })
+}
+
+/// Whether the XMP packet of an image holds a location.
+fn has_xmp_gps(content: &[u8]) -> bool {
+    find(content, b"<x:xmpmeta").is_some_and(|start| {
+        let packet = &content[start..];
+        let end = find(packet, b"</x:xmpmeta>").unwrap_or(packet.len());
+        [b"GPSLatitude".as_slice(), b"GPSLongitude"]
+            .iter()
+            .any(|tag| find(&packet[..end], tag).is_some())
+    })
+}
+
+fn find(content: &[u8], needle: &[u8]) -> Option<usize> {
+    content
+        .windows(needle.len())
+        .position(|window| window == needle)
+}
+
+fn malformed(format: &str) -> AttachmentError {
+    AttachmentError::InvalidImage(format!("Malformed {} metadata.", format))
+}
+
+/// Removes the EXIF and XMP (APP1) segments of a JPEG, keeping the rest
+/// untouched.
+fn strip_jpeg_exif(content: &[u8]) -> Result<Vec<u8>, AttachmentError> {
+    if content.len() < 2 {
+        return Err(malformed("JPEG"));
+    }
+    let mut result = Vec::with_capacity(content.len());
+    result.extend_from_slice(&content[..2]);
+    let mut pos = 2;
+
+    while pos + 4 <= content.len() && content[pos] == 0xFF {
+        let marker = content[pos + 1];
+        // Start of scan: the compressed data follows until the end.
+        if marker == 0xDA {
+            break;
+        }
+        // The length counts its own two bytes.
+        let length = u16::from_be_bytes([content[pos + 2], content[pos + 3]]) as usize;
+        let end = pos + 2 + length;
+        if length < 2 || end > content.len() {
+            return Err(malformed("JPEG"));
+        }
+        let data = &content[pos + 4..end];
+        let is_metadata =
+            marker == 0xE1 && (data.starts_with(JPEG_EXIF) || data.starts_with(JPEG_XMP));
+        if !is_metadata {
+            result.extend_from_slice(&content[pos..end]);
+        }
+        pos = end;
+    }
+    result.extend_from_slice(&content[pos..]);
+    Ok(result)
+}
+
+/// Removes the `eXIf` and XMP (`iTXt`) chunks of a PNG, keeping the rest
+/// untouched.
+fn strip_png_exif(content: &[u8]) -> Result<Vec<u8>, AttachmentError> {
+    if content.len() < 8 {
+        return Err(malformed("PNG"));
+    }
+    let mut result = Vec::with_capacity(content.len());
+    result.extend_from_slice(&content[..8]);
+    let mut pos = 8;
+
+    while pos + 12 <= content.len() {
+        let length = u32::from_be_bytes([
+            content[pos],
+            content[pos + 1],
+            content[pos + 2],
+            content[pos + 3],
+        ]) as usize;
+        // Length, type and CRC surround the data.
+        let end = match (pos + 12).checked_add(length) {
+            Some(end) if end <= content.len() => end,
+            _ => return Err(malformed("PNG")),
+        };
+        let kind = &content[pos + 4..pos + 8];
+        let is_metadata =
+            kind == b"eXIf" || (kind == b"iTXt" && content[pos + 8..end].starts_with(PNG_XMP));
+        if !is_metadata {
+            result.extend_from_slice(&content[pos..end]);
+        }
+        pos = end;
+    }
+    result.extend_from_slice(&content[pos..]);
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jpeg_segment(marker: u8, data: &[u8]) -> Vec<u8> {
+        let mut segment = vec![0xFF, marker];
+        segment.extend_from_slice(&(data.len() as u16 + 2).to_be_bytes());
+        segment.extend_from_slice(data);
+        segment
+    }
+
+    fn png_chunk(kind: &[u8], data: &[u8]) -> Vec<u8> {
+        let mut chunk = (data.len() as u32).to_be_bytes().to_vec();
+        chunk.extend_from_slice(kind);
+        chunk.extend_from_slice(data);
+        chunk.extend_from_slice(&[1, 2, 3, 4]);
+        chunk
+    }
+
+    const XMP: &[u8] = b"<x:xmpmeta><exif:GPSLatitude>48,51.4N</exif:GPSLatitude></x:xmpmeta>";
+
+    #[test]
+    fn jpeg_metadata_segments_are_removed() {
+        let jfif = jpeg_segment(0xE0, b"JFIF\0\x01\x02");
+        let exif = jpeg_segment(0xE1, &[JPEG_EXIF, b"GPS"].concat());
+        let xmp = jpeg_segment(0xE1, &[JPEG_XMP, XMP].concat());
+        let comment = jpeg_segment(0xFE, b"kept");
+        let scan = [0xFF, 0xDA, 0x00, 0x02, 0x12, 0x34, 0xFF, 0xD9];
+        let content = [&[0xFF, 0xD8][..], &jfif, &exif, &xmp, &comment, &scan].concat();
+
+        let stripped = strip_jpeg_exif(&content).unwrap();
+
+        assert_eq!(
+            stripped,
+            [&[0xFF, 0xD8][..], &jfif, &comment, &scan].concat()
+        );
+        assert!(has_xmp_gps(&content));
+        assert!(!has_xmp_gps(&stripped));
+    }
+
+    #[test]
+    fn png_metadata_chunks_are_removed() {
+        let signature = b"\x89PNG\r\n\x1a\n";
+        let header = png_chunk(b"IHDR", &[0; 13]);
+        let exif = png_chunk(b"eXIf", b"MM\0*GPS");
+        let xmp = png_chunk(b"iTXt", &[PNG_XMP, XMP].concat());
+        let text = png_chunk(b"iTXt", b"Comment\0\0\0\0\0kept");
+        let end = png_chunk(b"IEND", &[]);
+        let content = [&signature[..], &header, &exif, &xmp, &text, &end].concat();
+
+        let stripped = strip_png_exif(&content).unwrap();
+
+        assert_eq!(stripped, [&signature[..], &header, &text, &end].concat());
+        assert!(!has_xmp_gps(&stripped));
+    }
+
+    #[test]
+    fn malformed_metadata_is_rejected() {
+        for length in [0u8, 1, 200] {
+            let content = [0xFF, 0xD8, 0xFF, 0xE1, 0x00, length, b'E'];
+            assert!(
+                matches!(
+                    strip_jpeg_exif(&content),
+                    Err(AttachmentError::InvalidImage(_))
+                ),
+                "{}",
+                length
+            );
+        }
+        assert!(strip_jpeg_exif(&[0xFF]).is_err());
+
+        let mut content = b"\x89PNG\r\n\x1a\n".to_vec();
+        content.extend_from_slice(&png_chunk(b"IHDR", &[0; 13]));
+        content.extend_from_slice(&[0xFF, 0xFF, 0xFF, 0xFF]);
+        content.extend_from_slice(b"eXIfdatacrc");
+        assert!(matches!(
+            strip_png_exif(&content),
+            Err(AttachmentError::InvalidImage(_))
+        ));
+        assert!(strip_png_exif(b"\x89PNG").is_err());
+
+        // Bytes too short to hold another chunk are kept as they are.
+        let mut trailing = b"\x89PNG\r\n\x1a\n".to_vec();
+        trailing.extend_from_slice(&[0, 0]);
+        assert_eq!(strip_png_exif(&trailing).unwrap(), trailing);
+    }
+}
@@ -0,0 +1,143 @@
+//! # Image Repository
+//! Implementation of the image pipeline over the filesystem attachment
+//! store. Thumbnails are cached under `.attachments/.thumbnails`, named
+//! `<hash>-<size>.<extension>`.
+use std::{collections::BTreeMap, io::ErrorKind, path::Path};
+
+use async_trait::async_trait;
+use tokio::fs::{self, read_dir};
+
+use super::{filesystem_repository::FileSystemAttachmentRepository, image_processing};
+use crate::{
+    features::attachments::domain::{
+        errors::AttachmentError,
+        image::{is_image_extension, ImageFile, THUMBNAILS_DIR, THUMBNAIL_SIZES},
+        repository::{AttachmentRepository, ImageRepository},
+    },
+    shared::utils::atomic_write::write_synced,
+};
+
+/// Runs an image operation on a blocking thread.
+async fn blocking<T, F>(operation: F) -> Result<T, AttachmentError>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, AttachmentError> + Send + 'static,
+{
+    tauri::async_runtime::spawn_blocking(operation)
+        .await
+        .map_err(|e| AttachmentError::InvalidImage(e.to_string()))?
+}
+
+/// Path of a cached thumbnail, whatever its extension.
+async fn find_thumbnail(thumbnails_path: &Path, prefix: &str) -> Option<String> {
+    let mut entries = read_dir(thumbnails_path).await.ok()?;
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        let matches = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .is_some_and(|s| s == prefix);
+        if matches {
+            return Some(path.to_string_lossy().to_string());
+        }
+    }
+    None
+}
+
+#[async_trait]
+impl ImageRepository for FileSystemAttachmentRepository {
+    async fn save_image(
+        &self,
+        space_name: &str,
+        file_name: &str,
+        content: Vec<u8>,
+    ) -> Result<ImageFile, AttachmentError> {
+        let processed = blocking(move || image_processing::process_image(content)).await?;
+
+        // The extension follows the actual format, not the file name.
+        let stem = file_name
+            .rsplit_once('.')
+            .map_or(file_name, |(stem, _)| stem);
+        let attachment = self
+            .save_attachment(
+                space_name,
+                &format!("{}.{}", stem, processed.extension),
+                &processed.content,
+            )
+            .await?;
+        let thumbnails = self.get_thumbnails(space_name, &attachment.id).await?;
+
+        Ok(ImageFile {
+            url: attachment.path,
+            id: attachment.id,
+            width: processed.width,
+            height: processed.height,
+            size: attachment.size,
+            gps_removed: processed.gps_removed,
+            thumbnails,
+        })
+    }
+
+    async fn get_thumbnails(
+        &self,
+        space_name: &str,
+        attachment_id: &str,
+    ) -> Result<BTreeMap<u32, String>, AttachmentError> {
+        let image_path = self.get_attachment_path(space_name, attachment_id).await?;
+        let extension = image_path.extension().and_then(|e| e.to_str());
+        if !is_image_extension(extension) {
+            return Err(AttachmentError::InvalidImage(format!(
+                "'{}' is not an image.",
+                attachment_id
+            )));
+        }
+
+        // Thumbnails would be plaintext copies of the encrypted images.
+        if self.filesystem_repo.is_space_encrypted(space_name)? {
+            return Ok(BTreeMap::new());
+        }
+
+        let hash = attachment_id.split('.').next().unwrap_or(attachment_id);
+        let thumbnails_path = self.get_attachments_path(space_name)?.join(THUMBNAILS_DIR);
+        let original = image_path.to_string_lossy().to_string();
+
+        let mut thumbnails = BTreeMap::new();
+        let mut content = None;
+        for size in THUMBNAIL_SIZES {
+            let prefix = format!("{}-{}", hash, size);
+            if let Some(path) = find_thumbnail(&thumbnails_path, &prefix).await {
+                thumbnails.insert(size, path);
+                continue;
+            }
+
+            if content.is_none() {
+                content = Some(self.filesystem_repo.read_file(&image_path).await?);
+            }
+            let bytes = content.clone().unwrap_or_default();
+            match blocking(move || image_processing::thumbnail(&bytes, size)).await? {
+                Some((thumbnail, extension)) => {
+                    self.filesystem_repo
+                        .ensure_directory_exists(&thumbnails_path)
+                        .await?;
+                    let path = thumbnails_path.join(format!("{}.{}", prefix, extension));
+                    write_synced(&path, &thumbnail).await?;
+                    thumbnails.insert(size, path.to_string_lossy().to_string());
+                }
+                // Smaller than the thumbnail: the original is used.
+                None => {
+                    thumbnails.insert(size, original.clone());
+                }
+            }
+        }
+        Ok(thumbnails)
+    }
+
+    async fn clear_thumbnails(&self, space_name: &str) -> Result<(), AttachmentError> {
+        let thumbnails_path = self.get_attachments_path(space_name)?.join(THUMBNAILS_DIR);
+        match fs::remove_dir_all(&thumbnails_path).await {
+            Ok(_) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod filesystem_repository;
+pub mod image_processing;
+pub mod image_repository;
+pub mod tauri_commands;
@@ -0,0 +1,166 @@
+//! # Tauri Commands [Attachments]
+//! Implementation of the commands for the attachments to expose them on
+//! tauri for having them in the frontend application.
+use std::collections::BTreeMap;
+
+use tauri::State;
+
+use crate::{
+    features::{
+        attachments::{
+            application::{get, image, upload, usage},
+            domain::{
+                attachment::{AttachesPayload, Attachment, AttachmentUsage},
+                image::ImagePayload,
+            },
+            infrastructure::filesystem_repository::FileSystemAttachmentRepository,
+        },
+        templates::infrastructure::filesystem_repository::FileSystemTemplateRepository,
+    },
+    shared::state::state::AppState,
+};
+
+/// # [CREATE] Upload Attachment
+/// Stores a file in the attachment store of a space.
+/// ## Params
+/// * `repo`: A State of the FileSystemAttachmentRepository
+/// * `space_name`: The space of the note the file is attached to.
+/// * `file_name`: The original file name.
+/// * `content`: A `Vec` of `u8` (bytes) with the file content.
+/// ## Result
+/// The Editor.js attaches payload.
+#[tauri::command]
+pub async fn upload_attachment_cmd(
+    repo: State<'_, FileSystemAttachmentRepository>,
+    space_name: &str,
+    file_name: &str,
+    content: Vec<u8>,
+) -> Result<AttachesPayload, String> {
+    upload::upload_attachment_use_case(&*repo, space_name, file_name, &content)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// # [GET] Attachment Path
+/// Resolves the file of an attachment.
+/// ## Params
+/// * `space_name`: The space of the attachment.
+/// * `attachment_id`: The stored file name.
+#[tauri::command]
+pub async fn get_attachment_path_cmd(
+    repo: State<'_, FileSystemAttachmentRepository>,
+    space_name: &str,
+    attachment_id: &str,
+) -> Result<String, String> {
+    get::get_attachment_path_use_case(&*repo, space_name, attachment_id)
+        .await
+        .map(|path| path.to_string_lossy().to_string())
+        .map_err(|e| e.to_string())
+}
+
+/// # [GET] Attachment Content
+/// Reads the bytes of an attachment, decrypted when its space is encrypted.
+/// ## Params
+/// * `space_name`: The space of the attachment.
+/// * `attachment_id`: The stored file name.
+#[tauri::command]
+pub async fn get_attachment_content_cmd(
+    repo: State<'_, FileSystemAttachmentRepository>,
+    space_name: &str,
+    attachment_id: &str,
+) -> Result<Vec<u8>, String> {
+    get::get_attachment_content_use_case(&*repo, space_name, attachment_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// # [GET] Attachments Usage
+/// Lists the attachments of a space with the notes referencing them.
+/// ## Params
+/// * `space_name`: The space to inspect.
+#[tauri::command]
+pub async fn get_attachments_usage_cmd(
+    state: State<'_, AppState>,
+    repo: State<'_, FileSystemAttachmentRepository>,
+    templates_repo: State<'_, FileSystemTemplateRepository>,
+    space_name: &str,
+) -> Result<Vec<AttachmentUsage>, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    usage::get_attachments_usage_use_case(&*repo, &*fs_repo_lock, &*templates_repo, space_name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// # [DELETE] Collect Attachments
+/// Removes the attachments no note references anymore.
+/// ## Params
+/// * `space_name`: The space to clean.
+/// ## Result
+/// The removed attachments.
+#[tauri::command]
+pub async fn collect_attachments_cmd(
+    state: State<'_, AppState>,
+    repo: State<'_, FileSystemAttachmentRepository>,
+    templates_repo: State<'_, FileSystemTemplateRepository>,
+    space_name: &str,
+) -> Result<Vec<Attachment>, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    usage::collect_attachments_use_case(&*repo, &*fs_repo_lock, &*templates_repo, space_name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// # [CREATE] Upload Image
+/// Stores an image pasted into a note, stripping its location data and
+/// generating its thumbnails.
+/// ## Params
+/// * `space_name`: The space of the note.
+/// * `file_name`: The original file name.
+/// * `content`: A `Vec` of `u8` (bytes) with the image.
+/// ## Result
+/// The Editor.js image payload, with dimensions and thumbnails.
+#[tauri::command]
+pub async fn upload_image_cmd(
+    repo: State<'_, FileSystemAttachmentRepository>,
+    space_name: &str,
+    file_name: &str,
+    content: Vec<u8>,
+) -> Result<ImagePayload, String> {
+    image::upload_image_use_case(&*repo, space_name, file_name, content)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// # [GET] Image Thumbnails
+/// Returns the thumbnails of an image by size, generating the missing ones.
+/// ## Params
+/// * `space_name`: The space of the image.
+/// * `attachment_id`: The attachment id of the image.
+#[tauri::command]
+pub async fn get_image_thumbnails_cmd(
+    repo: State<'_, FileSystemAttachmentRepository>,
+    space_name: &str,
+    attachment_id: &str,
+) -> Result<BTreeMap<u32, String>, String> {
+    image::get_image_thumbnails_use_case(&*repo, space_name, attachment_id)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// # [UPDATE] Rebuild Thumbnails
+/// Regenerates the thumbnail cache of a space.
+/// ## Params
+/// * `space_name`: The space to process.
+/// ## Result
+/// The number of images processed.
+#[tauri::command]
+pub async fn rebuild_thumbnails_cmd(
+    repo: State<'_, FileSystemAttachmentRepository>,
+    space_name: &str,
+) -> Result<usize, String> {
+    image::rebuild_thumbnails_use_case(&*repo, space_name)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,316 @@
+//! # Apply **batch** module
+//! Applies an operation to a list of notes, undoing every change when one of
+//! them fails.
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+
+use crate::features::{
+    batch::domain::{
+        batch::{BatchItem, BatchItemReport, BatchOperation, BatchReport, BatchStatus},
+        errors::BatchError,
+        repository::ExportRepository,
+    },
+    folders::domain::{ordering::FolderOrdering, repository::FolderRepository},
+    notes::domain::{
+        note::Note,
+        properties::{normalize_tag, NoteProperties},
+        repository::NoteRepository,
+    },
+};
+
+/// A change made by the batch and what is needed to undo it.
+enum Applied {
+    Moved {
+        name: String,
+        from: Option<String>,
+        to: Option<String>,
+    },
+    Deleted {
+        name: String,
+        folder: Option<String>,
+        content: String,
+        ordering: FolderOrdering,
+        created: Option<DateTime<Utc>>,
+        modified: Option<DateTime<Utc>>,
+    },
+    Tagged {
+        name: String,
+        folder: Option<String>,
+        previous: NoteProperties,
+    },
+    Exported {
+        created: Vec<PathBuf>,
+    },
+}
+
+/// # Apply Batch
+/// Applies the operation to every note, in order. When a note fails, the
+/// notes already changed are restored and the remaining ones are skipped.
+/// ## Fields
+/// * `notes_repo` (&N): The notes repository.
+/// * `folders_repo` (&F): The folders repository, to restore the order and
+///   pins of the deleted notes.
+/// * `export_repo` (&E): Where the exported notes are written.
+/// * `space_name`: The space of the notes.
+/// * `operation`: The operation to apply.
+/// * `items`: The notes of the batch.
+/// * `on_progress`: Called with the position and the result of every note,
+///   as soon as it is processed.
+/// ## Returns
+/// The report of the batch, a `BatchError` only when the batch is invalid.
+#[doc(alias = "apply_batch")]
+pub async fn apply_batch_use_case<N, F, E>(
+    notes_repo: &N,
+    folders_repo: &F,
+    export_repo: &E,
+    space_name: &str,
+    operation: &BatchOperation,
+    items: &[BatchItem],
+    on_progress: impl Fn(usize, &BatchItemReport),
+) -> Result<BatchReport, BatchError>
+where
+    N: NoteRepository,
+    F: FolderRepository,
+    E: ExportRepository,
+{
+    validate(operation, items)?;
+
+    let mut journal: Vec<Applied> = Vec::new();
+    let mut reports: Vec<BatchItemReport> = Vec::new();
+
+    for (index, item) in items.iter().enumerate() {
+        match apply(
+            notes_repo,
+            folders_repo,
+            export_repo,
+            space_name,
+            operation,
+            item,
+        )
+        .await
+        {
+            Ok((applied, note)) => {
+                journal.push(applied);
+                let mut report = BatchItemReport::new(item, BatchStatus::Done);
+                report.note = note;
+                on_progress(index, &report);
+                reports.push(report);
+            }
+            Err(e) => {
+                let mut report = BatchItemReport::new(item, BatchStatus::Failed);
+                report.error = Some(e.to_string());
+                on_progress(index, &report);
+                reports.push(report);
+
+                rollback(
+                    notes_repo,
+                    folders_repo,
+                    export_repo,
+                    space_name,
+                    journal,
+                    &mut reports,
+                )
+                .await;
+                reports.extend(
+                    items[index + 1..]
+                        .iter()
+                        .map(|item| BatchItemReport::new(item, BatchStatus::Skipped)),
+                );
+                return Ok(BatchReport {
+                    committed: false,
+                    items: reports,
+                });
+            }
+        }
+    }
+
+    Ok(BatchReport {
+        committed: true,
+        items: reports,
+    })
+}
+
+/// Rejects the batches that can't do anything before touching a note.
+fn validate(operation: &BatchOperation, items: &[BatchItem]) -> Result<(), BatchError> {
+    if items.is_empty() {
+        return Err(BatchError::Empty);
+    }
+    match operation {
+        BatchOperation::Tag { add, remove } if add.is_empty() && remove.is_empty() => {
+            Err(BatchError::NothingToApply)
+        }
+        BatchOperation::Export { destination } if !Path::new(destination).is_dir() => Err(
+            BatchError::InvalidDestination(format!("'{}' is not a directory.", destination)),
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Applies the operation to a note.
+/// ## Returns
+/// How to undo the change and the note after it, when it still exists.
+async fn apply<N: NoteRepository, F: FolderRepository, E: ExportRepository>(
+    notes_repo: &N,
+    folders_repo: &F,
+    export_repo: &E,
+    space_name: &str,
+    operation: &BatchOperation,
+    item: &BatchItem,
+) -> Result<(Applied, Option<Note>), BatchError> {
+    let folder = item.folder.as_deref();
+
+    match operation {
+        BatchOperation::Move { target_folder } => {
+            let moved = notes_repo
+                .update_note_route(space_name, &item.name, folder, target_folder.as_deref())
+                .await?;
+            let applied = Applied::Moved {
+                name: item.name.clone(),
+                from: item.folder.clone(),
+                to: target_folder.clone(),
+            };
+            // The index needs the content of the moved note.
+            let note = notes_repo
+                .get_note_content(space_name, &moved.name, moved.folder.as_deref())
+                .await
+                .unwrap_or(moved);
+            Ok((applied, Some(note)))
+        }
+        BatchOperation::Delete => {
+            let note = notes_repo
+                .get_note_content(space_name, &item.name, folder)
+                .await?;
+            // Deleting forgets the note in the order and pins of its folder.
+            let ordering = folders_repo.get_folder_ordering(space_name, folder).await?;
+            notes_repo
+                .delete_note(space_name, &item.name, folder)
+                .await?;
+            let applied = Applied::Deleted {
+                name: item.name.clone(),
+                folder: item.folder.clone(),
+                content: note.content.unwrap_or_default(),
+                ordering,
+                created: note.created,
+                modified: note.modified,
+            };
+            Ok((applied, None))
+        }
+        BatchOperation::Tag { add, remove } => {
+            let note = notes_repo
+                .get_note_content(space_name, &item.name, folder)
+                .await?;
+            let previous = note.properties.unwrap_or_default();
+
+            let mut properties = previous.clone();
+            properties.tags.extend(add.iter().cloned());
+            let mut properties = properties.normalize()?;
+            let removed: Vec<String> = remove.iter().map(|t| normalize_tag(t)).collect();
+            properties.tags.retain(|tag| !removed.contains(tag));
+
+            let tagged = notes_repo
+                .update_note_properties(space_name, &item.name, properties, folder)
+                .await?;
+            let applied = Applied::Tagged {
+                name: item.name.clone(),
+                folder: item.folder.clone(),
+                previous,
+            };
+            Ok((applied, Some(tagged)))
+        }
+        BatchOperation::Export { destination } => {
+            let note = notes_repo
+                .get_note_content(space_name, &item.name, folder)
+                .await?;
+            let created = export_repo
+                .export_note(&note, Path::new(destination))
+                .await?;
+            Ok((Applied::Exported { created }, Some(note)))
+        }
+    }
+}
+
+/// Undoes the journal, last change first, updating the report of every
+/// changed note.
+async fn rollback<N: NoteRepository, F: FolderRepository, E: ExportRepository>(
+    notes_repo: &N,
+    folders_repo: &F,
+    export_repo: &E,
+    space_name: &str,
+    journal: Vec<Applied>,
+    reports: &mut [BatchItemReport],
+) {
+    let changed = &mut reports[..journal.len()];
+    for (applied, report) in journal.into_iter().zip(changed.iter_mut()).rev() {
+        match undo(notes_repo, folders_repo, export_repo, space_name, applied).await {
+            Ok(()) => {
+                report.status = BatchStatus::RolledBack;
+                report.note = None;
+            }
+            Err(e) => {
+                report.status = BatchStatus::RollbackFailed;
+                report.error = Some(e.to_string());
+            }
+        }
+    }
+}
+
+/// Undoes a single change. A deleted note gets back its content, its
+/// timestamps and its place in the order and pins of its folder.
+async fn undo<N: NoteRepository, F: FolderRepository, E: ExportRepository>(
+    notes_repo: &N,
+    folders_repo: &F,
+    export_repo: &E,
+    space_name: &str,
+    applied: Applied,
+) -> Result<(), BatchError> {
+    match applied {
+        Applied::Moved { name, from, to } => {
+            notes_repo
+                .update_note_route(space_name, &name, to.as_deref(), from.as_deref())
+                .await?;
+        }
+        Applied::Deleted {
+            name,
+            folder,
+            content,
+            ordering,
+            created,
+            modified,
+        } => {
+            let note = notes_repo
+                .create_note(space_name, &name, folder.as_deref())
+                .await?;
+            if !content.is_empty() {
+                notes_repo
+                    .update_note_content(
+                        space_name,
+                        &name,
+                        content.into_bytes(),
+                        folder.as_deref(),
+                        note.revision.as_deref().unwrap_or_default(),
+                    )
+                    .await?;
+            }
+            notes_repo
+                .set_note_times(space_name, &name, folder.as_deref(), created, modified)
+                .await?;
+            folders_repo
+                .set_folder_ordering(space_name, folder.as_deref(), ordering)
+                .await?;
+        }
+        Applied::Tagged {
+            name,
+            folder,
+            previous,
+        } => {
+            notes_repo
+                .update_note_properties(space_name, &name, previous, folder.as_deref())
+                .await?;
+        }
+        Applied::Exported { created } => {
+            export_repo.remove_export(&created).await?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,122 @@
+//! # Batch Domain
+//! Defines the operations applied to a list of notes at once and the report
+//! of every note.
+use serde::{Deserialize, Serialize};
+
+use crate::features::notes::domain::note::Note;
+
+/// Event emitted after every note of a batch.
+pub const BATCH_PROGRESS_EVENT: &str = "batch-progress";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "lowercase")]
+/// # BatchOperation
+/// The operation applied to every note of a batch.
+/// ## Variants
+/// * `Move`: Moves the notes to `target_folder`, the space root if `None`.
+/// * `Delete`: Deletes the notes.
+/// * `Tag`: Adds the `add` tags and removes the `remove` tags.
+/// * `Export`: Copies the note files to the `destination` directory, keeping
+///   their folders.
+pub enum BatchOperation {
+    Move {
+        #[serde(default)]
+        target_folder: Option<String>,
+    },
+    Delete,
+    Tag {
+        #[serde(default)]
+        add: Vec<String>,
+        #[serde(default)]
+        remove: Vec<String>,
+    },
+    Export {
+        destination: String,
+    },
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # BatchItem
+/// A note of a batch.
+/// ## Fields
+/// * `name`: The name of the note.
+/// * `folder`: The folder of the note relative to the space.
+pub struct BatchItem {
+    pub name: String,
+    #[serde(default)]
+    pub folder: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "kebab-case")]
+/// # BatchStatus
+/// What happened to a note of a batch.
+/// ## Variants
+/// * `Done`: The operation was applied and kept.
+/// * `Failed`: The operation failed on this note, the batch was rolled back.
+/// * `RolledBack`: The operation was applied, then undone.
+/// * `RollbackFailed`: The operation was applied and couldn't be undone.
+/// * `Skipped`: The batch stopped before reaching this note.
+pub enum BatchStatus {
+    Done,
+    Failed,
+    RolledBack,
+    RollbackFailed,
+    Skipped,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # BatchItemReport
+/// The result of a note of a batch.
+/// ## Fields
+/// * `name`: The name of the note.
+/// * `folder`: The folder of the note before the batch.
+/// * `status`: What happened to the note.
+/// * `note`: The note after the operation, with its content, when it still
+///   exists.
+/// * `error`: Why the operation, or its rollback, failed.
+pub struct BatchItemReport {
+    pub name: String,
+    pub folder: Option<String>,
+    pub status: BatchStatus,
+    pub note: Option<Note>,
+    pub error: Option<String>,
+}
+
+impl BatchItemReport {
+    /// A report without note nor error.
+    pub fn new(item: &BatchItem, status: BatchStatus) -> Self {
+        Self {
+            name: item.name.clone(),
+            folder: item.folder.clone(),
+            status,
+            note: None,
+            error: None,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # BatchProgress
+/// Payload of the `batch-progress` event, sent once per note.
+/// ## Fields
+/// * `index`: The position of the note in the batch.
+/// * `total`: The number of notes of the batch.
+/// * `item`: The result of the note.
+pub struct BatchProgress {
+    pub index: usize,
+    pub total: usize,
+    pub item: BatchItemReport,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # BatchReport
+/// The result of a batch.
+/// ## Fields
+/// * `committed`: Whether every note succeeded. Otherwise every change has
+///   been rolled back.
+/// * `items`: The result of every note, in the order of the batch.
+pub struct BatchReport {
+    pub committed: bool,
+    pub items: Vec<BatchItemReport>,
+}
@@ -0,0 +1,40 @@
+//! # Errors
+//! Defines all error that could ocurr while applying a batch of notes.
+use std::io;
+
+use thiserror::Error;
+
+use crate::features::{folders::domain::errors::FolderError, notes::domain::errors::NoteError};
+
+/// # BatchError Enum
+/// Defines the enum with all the possible error that could ocurr during a
+/// batch operation.
+/// ## Possible Errors
+/// * `Empty`: The batch has no notes.
+/// * `NothingToApply`: The operation wouldn't change anything.
+/// * `InvalidDestination`: The export destination isn't an existing directory.
+/// * `AlreadyExported`: A file with the name of an exported note already
+///   exists in the destination.
+#[derive(Debug, Error)]
+pub enum BatchError {
+    #[error("The batch has no notes.")]
+    Empty,
+
+    #[error("The batch operation has nothing to apply.")]
+    NothingToApply,
+
+    #[error("Invalid export destination: {0}")]
+    InvalidDestination(String),
+
+    #[error("'{0}' already exists in the export destination.")]
+    AlreadyExported(String),
+
+    #[error("Batch IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    Note(#[from] NoteError),
+
+    #[error(transparent)]
+    Folder(#[from] FolderError),
+}
@@ -0,0 +1,33 @@
+//! # Repository
+//! Defines where the notes of a batch are exported.
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+
+use super::errors::BatchError;
+use crate::features::notes::domain::note::Note;
+
+/// # Export Repository
+/// It implements the methods to copy notes out of their space.
+#[async_trait]
+pub trait ExportRepository {
+    /// # [CREATE] Export note
+    /// Writes the note file under `destination`, keeping its folder. Never
+    /// overwrites an existing file.
+    /// ## Fields
+    /// * `note`: The note, with its content.
+    /// * `destination`: The directory to export to.
+    /// ## Result
+    /// The files and directories created, in creation order.
+    async fn export_note(
+        &self,
+        note: &Note,
+        destination: &Path,
+    ) -> Result<Vec<PathBuf>, BatchError>;
+
+    /// # [DELETE] Remove export
+    /// Removes what `export_note` created, directories only when empty.
+    /// ## Fields
+    /// * `created`: The paths returned by `export_note`.
+    async fn remove_export(&self, created: &[PathBuf]) -> Result<(), BatchError>;
+}
@@ -0,0 +1,106 @@
+//! # Export Repository
+//! External implementation of the export of the notes of a batch to a
+//! directory of the filesystem.
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use tokio::{fs, io::AsyncWriteExt};
+
+use crate::features::{
+    batch::domain::{errors::BatchError, repository::ExportRepository},
+    notes::domain::note::Note,
+};
+
+/// # FileSystemExportRepository
+/// Implementation of the ExportRepository trait.
+#[derive(Clone, Default)]
+pub struct FileSystemExportRepository;
+
+impl FileSystemExportRepository {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl ExportRepository for FileSystemExportRepository {
+    async fn export_note(
+        &self,
+        note: &Note,
+        destination: &Path,
+    ) -> Result<Vec<PathBuf>, BatchError> {
+        let mut created = Vec::new();
+        let mut directory = destination.to_path_buf();
+
+        for segment in note.folder.iter().flat_map(|f| f.split(['/', '\\'])) {
+            if segment.is_empty() || segment == "." || segment == ".." {
+                return Err(BatchError::InvalidDestination(format!(
+                    "Invalid folder '{}'.",
+                    note.folder.as_deref().unwrap_or_default()
+                )));
+            }
+            directory.push(segment);
+            match fs::create_dir(&directory).await {
+                Ok(()) => created.push(directory.clone()),
+                Err(e) if e.kind() == ErrorKind::AlreadyExists => {}
+                Err(e) => {
+                    self.remove_export(&created).await?;
+                    return Err(BatchError::Io(e));
+                }
+            }
+        }
+
+        let file_path = directory.join(format!("{}.json", note.name));
+        let written = async {
+            let mut file = fs::File::create_new(&file_path).await?;
+            file.write_all(note.content.as_deref().unwrap_or_default().as_bytes())
+                .await?;
+            file.sync_all().await
+        }
+        .await;
+
+        match written {
+            Ok(()) => {
+                created.push(file_path);
+                Ok(created)
+            }
+            Err(e) => {
+                // Only a file this export created may be removed.
+                if e.kind() != ErrorKind::AlreadyExists {
+                    let _ = fs::remove_file(&file_path).await;
+                }
+                self.remove_export(&created).await?;
+                match e.kind() {
+                    ErrorKind::AlreadyExists => {
+                        Err(BatchError::AlreadyExported(file_path.display().to_string()))
+                    }
+                    _ => Err(BatchError::Io(e)),
+                }
+            }
+        }
+    }
+
+    async fn remove_export(&self, created: &[PathBuf]) -> Result<(), BatchError> {
+        for path in created.iter().rev() {
+            if path.is_dir() {
+                // A directory also holding other exports stays.
+                match fs::remove_dir(path).await {
+                    Ok(()) => {}
+                    Err(_) if path.read_dir().is_ok_and(|mut d| d.next().is_some()) => {}
+                    Err(e) if e.kind() == ErrorKind::NotFound => {}
+                    Err(e) => return Err(BatchError::Io(e)),
+                }
+            } else {
+                match fs::remove_file(path).await {
+                    Ok(()) => {}
+                    Err(e) if e.kind() == ErrorKind::NotFound => {}
+                    Err(e) => return Err(BatchError::Io(e)),
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+pub mod export_repository;
+pub mod tauri_commands;
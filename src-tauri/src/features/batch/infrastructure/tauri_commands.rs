@@ -0,0 +1,104 @@
+//! # Tauri Commands [Batch]
+//! Implementation of the commands applying an operation to many notes to
+//! expose them on tauri for having them in the frontend application.
+use tauri::{AppHandle, Emitter, State};
+
+use crate::{
+    features::{
+        batch::{
+            application::apply,
+            domain::batch::{
+                BatchItem, BatchOperation, BatchProgress, BatchReport, BatchStatus,
+                BATCH_PROGRESS_EVENT,
+            },
+            infrastructure::export_repository::FileSystemExportRepository,
+        },
+        notes::domain::note::Note,
+        search::{self, domain::search::Searchable},
+    },
+    shared::state::state::AppState,
+};
+
+/// # [UPDATE] Apply Batch
+/// Moves, deletes, tags or exports a list of notes. Either every note
+/// succeeds or every change is rolled back. A `batch-progress` event is
+/// emitted after each note and the index is committed once.
+/// ## Params
+/// * `space_name`: The space of the notes.
+/// * `operation`: The operation to apply.
+/// * `items`: The notes of the batch.
+/// ## Result
+/// The `BatchReport` with the result of every note.
+#[tauri::command]
+pub async fn apply_batch_cmd(
+    app_handle: AppHandle,
+    state: State<'_, AppState>,
+    export_repo: State<'_, FileSystemExportRepository>,
+    space_name: &str,
+    operation: BatchOperation,
+    items: Vec<BatchItem>,
+) -> Result<BatchReport, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+    let folders_repo_lock = state.folders_repo.lock().await;
+
+    let total = items.len();
+    let report = apply::apply_batch_use_case(
+        &*fs_repo_lock,
+        &*folders_repo_lock,
+        &*export_repo,
+        space_name,
+        &operation,
+        &items,
+        |index, item| {
+            let progress = BatchProgress {
+                index,
+                total,
+                item: item.clone(),
+            };
+            let _ = app_handle.emit(BATCH_PROGRESS_EVENT, progress);
+        },
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if !report.committed || matches!(operation, BatchOperation::Export { .. }) {
+        return Ok(report);
+    }
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    // Deletions only affect the documents added before them, so every note
+    // can be replaced within a single commit.
+    for item in report
+        .items
+        .iter()
+        .filter(|i| i.status == BatchStatus::Done)
+    {
+        let old_note = Note {
+            name: item.name.clone(),
+            folder: item.folder.clone(),
+            ..Default::default()
+        };
+        search::application::delete::delete_document_use_case(
+            &search_repo_lock,
+            &mut index_writer_lock,
+            &old_note.get_unique_id(space_name),
+        )
+        .map_err(|e| e.to_string())?;
+
+        if let Some(note) = &item.note {
+            search::application::index::index_document_use_case(
+                &search_repo_lock,
+                &mut index_writer_lock,
+                note,
+                space_name,
+            )
+            .map_err(|e| e.to_string())?;
+        }
+    }
+
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(report)
+}
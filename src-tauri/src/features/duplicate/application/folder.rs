@@ -0,0 +1,125 @@
+use crate::features::{
+    attachments::domain::repository::AttachmentRepository,
+    duplicate::{
+        application::note::write_copy,
+        domain::{duplicate::DuplicatedFolder, errors::DuplicateError},
+    },
+    folders::{
+        application::create::{create_named_folder_use_case, ensure_folder_use_case},
+        domain::{folder::FolderMetadata, repository::FolderRepository},
+    },
+    notes::domain::repository::NoteRepository,
+};
+
+/// # Duplicate Folder
+/// Copies a folder with its subfolders, notes and their attachments into a
+/// folder of any space. The copy keeps the folder name, or gets `name 2`,
+/// `name 3`... when it is taken. The folder metadata is copied, the locks
+/// aren't.
+/// ## Fields
+/// * `notes_repo` (&N): The notes repository.
+/// * `folders_repo` (&F): The folders repository.
+/// * `attachments_repo` (&A): The attachments repository.
+/// * `space_name`: The space of the folder.
+/// * `folder_path`: The path of the folder to copy.
+/// * `target_space`: The space of the copy.
+/// * `target_parent`: The folder receiving the copy, the space root if `None`.
+/// ## Returns
+/// The new folder and the copied notes.
+#[doc(alias = "duplicate_folder")]
+pub async fn duplicate_folder_use_case<
+    N: NoteRepository,
+    F: FolderRepository,
+    A: AttachmentRepository,
+>(
+    notes_repo: &N,
+    folders_repo: &F,
+    attachments_repo: &A,
+    space_name: &str,
+    folder_path: &str,
+    target_space: &str,
+    target_parent: Option<&str>,
+) -> Result<DuplicatedFolder, DuplicateError> {
+    let folder_path = folder_path.trim_matches('/');
+    let folder_name = folder_path
+        .rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| DuplicateError::NotFound(folder_path.to_string()))?;
+
+    // Everything is listed before copying, a folder copied into itself
+    // doesn't copy the copy.
+    let prefix = format!("{}/", folder_path);
+    let relative = |path: &str| -> Option<String> {
+        if path == folder_path {
+            Some(String::new())
+        } else {
+            path.strip_prefix(&prefix).map(str::to_string)
+        }
+    };
+    let relative_folders: Vec<(String, FolderMetadata)> = folders_repo
+        .get_folders(space_name)
+        .await?
+        .into_iter()
+        .filter_map(|folder| Some((relative(folder.path.as_deref()?)?, folder.metadata)))
+        .collect();
+    if relative_folders.is_empty() {
+        return Err(DuplicateError::NotFound(folder_path.to_string()));
+    }
+    let notes: Vec<_> = notes_repo
+        .get_notes(space_name)
+        .await?
+        .into_iter()
+        .filter_map(|note| {
+            let relative_folder = relative(note.folder.as_deref()?)?;
+            Some((note, relative_folder))
+        })
+        .collect();
+
+    let folder =
+        create_named_folder_use_case(folders_repo, target_space, folder_name, target_parent)
+            .await?;
+    let root = folder.path.clone().unwrap_or_default();
+    let join = |relative: &str| match relative {
+        "" => root.clone(),
+        relative => format!("{}/{}", root, relative),
+    };
+
+    for (subfolder, metadata) in relative_folders {
+        if !subfolder.is_empty() {
+            ensure_folder_use_case(folders_repo, target_space, &join(&subfolder)).await?;
+        }
+        if metadata != FolderMetadata::default() {
+            folders_repo
+                .update_folder_metadata(target_space, Some(&join(&subfolder)), metadata)
+                .await?;
+        }
+    }
+
+    let mut copies = Vec::new();
+    for (note, relative_folder) in notes {
+        let source = notes_repo
+            .get_note_content(space_name, &note.name, note.folder.as_deref())
+            .await?;
+        let target_folder = join(&relative_folder);
+        let copy = notes_repo
+            .create_note(target_space, &note.name, Some(&target_folder))
+            .await?;
+        copies.push(
+            write_copy(
+                notes_repo,
+                attachments_repo,
+                &source,
+                space_name,
+                target_space,
+                copy,
+            )
+            .await?,
+        );
+    }
+
+    Ok(DuplicatedFolder {
+        folder,
+        notes: copies,
+    })
+}
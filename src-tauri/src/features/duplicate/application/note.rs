@@ -0,0 +1,80 @@
+use crate::features::{
+    attachments::{
+        application::copy::copy_referenced_attachments_use_case,
+        domain::repository::AttachmentRepository,
+    },
+    duplicate::domain::{duplicate::unlocked_content, errors::DuplicateError},
+    notes::{
+        application::create::create_named_note_use_case,
+        domain::{note::Note, repository::NoteRepository},
+    },
+};
+
+/// # Duplicate Note
+/// Copies a note, and the attachments it references, to a folder of any
+/// space. The copy keeps the name of the note, or gets `name 2`, `name 3`...
+/// when it is taken.
+/// ## Fields
+/// * `notes_repo` (&N): The notes repository.
+/// * `attachments_repo` (&A): The attachments repository.
+/// * `space_name`: The space of the note.
+/// * `note_name`: The name of the note.
+/// * `folder_path`: The folder of the note.
+/// * `target_space`: The space of the copy.
+/// * `target_folder`: The folder of the copy.
+/// ## Returns
+/// The copy, with its content.
+#[doc(alias = "duplicate_note")]
+pub async fn duplicate_note_use_case<N: NoteRepository, A: AttachmentRepository>(
+    notes_repo: &N,
+    attachments_repo: &A,
+    space_name: &str,
+    note_name: &str,
+    folder_path: Option<&str>,
+    target_space: &str,
+    target_folder: Option<&str>,
+) -> Result<Note, DuplicateError> {
+    let source = notes_repo
+        .get_note_content(space_name, note_name, folder_path)
+        .await?;
+
+    let copy =
+        create_named_note_use_case(notes_repo, target_space, note_name, target_folder).await?;
+    write_copy(
+        notes_repo,
+        attachments_repo,
+        &source,
+        space_name,
+        target_space,
+        copy,
+    )
+    .await
+}
+
+/// Writes the content of `source` into the `copy` note.
+pub(super) async fn write_copy<N: NoteRepository, A: AttachmentRepository>(
+    notes_repo: &N,
+    attachments_repo: &A,
+    source: &Note,
+    space_name: &str,
+    target_space: &str,
+    copy: Note,
+) -> Result<Note, DuplicateError> {
+    let content = source.content.as_deref().unwrap_or_default();
+    if content.is_empty() {
+        return Ok(copy);
+    }
+
+    let content =
+        copy_referenced_attachments_use_case(attachments_repo, space_name, target_space, content)
+            .await?;
+    Ok(notes_repo
+        .update_note_content(
+            target_space,
+            &copy.name,
+            unlocked_content(&content).into_bytes(),
+            copy.folder.as_deref(),
+            copy.revision.as_deref().unwrap_or_default(),
+        )
+        .await?)
+}
@@ -0,0 +1,33 @@
+//! # Duplicate Domain
+//! Defines the result of duplicating a folder and how a copied note content
+//! is prepared.
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::features::{
+    folders::{domain::folder::Folder, infrastructure::manifest::NOTE_LOCKED_KEY},
+    notes::domain::note::Note,
+};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # DuplicatedFolder
+/// The copy of a folder.
+/// ## Fields
+/// * `folder`: The new folder, with its full path.
+/// * `notes`: The copied notes, with their content.
+pub struct DuplicatedFolder {
+    pub folder: Folder,
+    pub notes: Vec<Note>,
+}
+
+/// # Unlocked content
+/// The content of a copy: a copy of a locked note can be edited.
+pub fn unlocked_content(content: &str) -> String {
+    match serde_json::from_str::<Value>(content) {
+        Ok(Value::Object(mut document)) if document.contains_key(NOTE_LOCKED_KEY) => {
+            document.remove(NOTE_LOCKED_KEY);
+            Value::Object(document).to_string()
+        }
+        _ => content.to_string(),
+    }
+}
@@ -0,0 +1,28 @@
+//! # Errors
+//! Defines all error that could ocurr while duplicating notes and folders.
+use thiserror::Error;
+
+use crate::features::{
+    attachments::domain::errors::AttachmentError, folders::domain::errors::FolderError,
+    notes::domain::errors::NoteError,
+};
+
+/// # DuplicateError Enum
+/// Defines the enum with all the possible error that could ocurr during a
+/// duplication.
+/// ## Possible Errors
+/// * `NotFound`: The folder to duplicate doesn't exist.
+#[derive(Debug, Error)]
+pub enum DuplicateError {
+    #[error("Folder '{0}' not found.")]
+    NotFound(String),
+
+    #[error(transparent)]
+    Note(#[from] NoteError),
+
+    #[error(transparent)]
+    Folder(#[from] FolderError),
+
+    #[error(transparent)]
+    Attachment(#[from] AttachmentError),
+}
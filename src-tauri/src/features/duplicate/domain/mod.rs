@@ -0,0 +1,2 @@
+pub mod duplicate;
+pub mod errors;
@@ -0,0 +1 @@
+pub mod tauri_commands;
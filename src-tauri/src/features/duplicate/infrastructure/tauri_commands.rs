@@ -0,0 +1,119 @@
+//! # Tauri Commands [Duplicate]
+//! Implementation of the commands duplicating notes and folders to expose
+//! them on tauri for having them in the frontend application.
+use tauri::State;
+
+use crate::{
+    features::{
+        attachments::infrastructure::filesystem_repository::FileSystemAttachmentRepository,
+        duplicate::{
+            application::{folder, note},
+            domain::duplicate::DuplicatedFolder,
+        },
+        folders::infrastructure::filesystem_repository::FileSystemFolderRepository,
+        notes::domain::note::Note,
+        search,
+    },
+    shared::state::state::AppState,
+};
+
+/// # [CREATE] Duplicate Note
+/// Copies a note, with its attachments, and indexes the copy.
+/// ## Params
+/// * `space_name`: The space of the note.
+/// * `note_name`: The name of the note.
+/// * `folder_path`: The folder of the note.
+/// * `target_space`: The space of the copy, the same space if not given.
+/// * `target_folder`: The folder of the copy.
+#[tauri::command]
+pub async fn duplicate_note_cmd(
+    state: State<'_, AppState>,
+    attachments_repo: State<'_, FileSystemAttachmentRepository>,
+    space_name: &str,
+    note_name: &str,
+    folder_path: Option<&str>,
+    target_space: Option<&str>,
+    target_folder: Option<&str>,
+) -> Result<Note, String> {
+    let target_space = target_space.unwrap_or(space_name);
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    let copy = note::duplicate_note_use_case(
+        &*fs_repo_lock,
+        &*attachments_repo,
+        space_name,
+        note_name,
+        folder_path,
+        target_space,
+        target_folder,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    search::application::index::index_document_use_case(
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &copy,
+        target_space,
+    )
+    .map_err(|e| e.to_string())?;
+
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(copy)
+}
+
+/// # [CREATE] Duplicate Folder
+/// Copies a folder subtree, with the attachments of its notes, and indexes
+/// every copied note in a single commit.
+/// ## Params
+/// * `space_name`: The space of the folder.
+/// * `folder_path`: The path of the folder.
+/// * `target_space`: The space of the copy, the same space if not given.
+/// * `target_folder`: The folder receiving the copy, the space root if not
+///   given.
+#[tauri::command]
+pub async fn duplicate_folder_cmd(
+    state: State<'_, AppState>,
+    folders_repo: State<'_, FileSystemFolderRepository>,
+    attachments_repo: State<'_, FileSystemAttachmentRepository>,
+    space_name: &str,
+    folder_path: &str,
+    target_space: Option<&str>,
+    target_folder: Option<&str>,
+) -> Result<DuplicatedFolder, String> {
+    let target_space = target_space.unwrap_or(space_name);
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    let duplicated = folder::duplicate_folder_use_case(
+        &*fs_repo_lock,
+        &*folders_repo,
+        &*attachments_repo,
+        space_name,
+        folder_path,
+        target_space,
+        target_folder,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    for copy in &duplicated.notes {
+        search::application::index::index_document_use_case(
+            &search_repo_lock,
+            &mut index_writer_lock,
+            copy,
+            target_space,
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(duplicated)
+}
@@ -0,0 +1,60 @@
+use crate::features::{
+    encryption::domain::{
+        encryption::{auto_lock_duration, EncryptionChange, MIN_PASSPHRASE_LEN},
+        errors::EncryptionError,
+        repository::EncryptionRepository,
+    },
+    space::domain::repository::SpaceRepository,
+};
+
+/// # Enable Space Encryption use case
+/// Encrypts the notes, attachments and templates of a space with a key
+/// derived from a passphrase, and leaves the space unlocked.
+/// ## Params
+/// * `encryption_repo`: Repository encrypting the space.
+/// * `space_repo`: Repository of the spaces.
+/// * `space_name`: The space to be encrypted.
+/// * `passphrase`: The passphrase of the space.
+/// * `auto_lock_minutes`: Minutes it stays unlocked without being used,
+///   the default if `None`, forever if `0`.
+/// ## Result
+/// The files encrypted.
+pub async fn enable_space_encryption_use_case<E: EncryptionRepository, S: SpaceRepository>(
+    encryption_repo: &E,
+    space_repo: &S,
+    space_name: &str,
+    passphrase: &str,
+    auto_lock_minutes: Option<u32>,
+) -> Result<EncryptionChange, EncryptionError> {
+    if passphrase.chars().count() < MIN_PASSPHRASE_LEN {
+        return Err(EncryptionError::WeakPassphrase(MIN_PASSPHRASE_LEN));
+    }
+    space_repo.get_space(space_name).await?;
+
+    encryption_repo
+        .enable(
+            space_name,
+            passphrase,
+            auto_lock_duration(auto_lock_minutes),
+        )
+        .await
+}
+
+/// # Disable Space Encryption use case
+/// Decrypts every file of an encrypted space.
+/// ## Params
+/// * `encryption_repo`: Repository encrypting the space.
+/// * `space_repo`: Repository of the spaces.
+/// * `space_name`: The encrypted space.
+/// * `passphrase`: The passphrase of the space.
+/// ## Result
+/// The files decrypted.
+pub async fn disable_space_encryption_use_case<E: EncryptionRepository, S: SpaceRepository>(
+    encryption_repo: &E,
+    space_repo: &S,
+    space_name: &str,
+    passphrase: &str,
+) -> Result<EncryptionChange, EncryptionError> {
+    space_repo.get_space(space_name).await?;
+    encryption_repo.disable(space_name, passphrase).await
+}
@@ -0,0 +1,3 @@
+pub mod enable;
+pub mod status;
+pub mod unlock;
@@ -0,0 +1,15 @@
+use crate::features::encryption::domain::{
+    encryption::EncryptionStatus, errors::EncryptionError, repository::EncryptionRepository,
+};
+
+/// # Get Space Encryption use case
+/// Whether a space is encrypted and unlocked.
+/// ## Params
+/// * `encryption_repo`: Repository keeping the key of the space.
+/// * `space_name`: The space.
+pub async fn get_space_encryption_use_case<E: EncryptionRepository>(
+    encryption_repo: &E,
+    space_name: &str,
+) -> Result<EncryptionStatus, EncryptionError> {
+    encryption_repo.get_status(space_name).await
+}
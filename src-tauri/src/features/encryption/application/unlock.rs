@@ -0,0 +1,56 @@
+use crate::features::{
+    encryption::domain::{
+        encryption::{auto_lock_duration, UnlockedSpace},
+        errors::EncryptionError,
+        repository::EncryptionRepository,
+    },
+    notes::domain::repository::NoteRepository,
+};
+
+/// # Unlock Space use case
+/// Unlocks an encrypted space and runs the integrity check skipped while
+/// it was locked.
+/// ## Params
+/// * `encryption_repo`: Repository keeping the key of the space.
+/// * `notes_repo`: Repository of the notes, for the integrity check.
+/// * `space_name`: The encrypted space.
+/// * `passphrase`: The passphrase of the space.
+/// * `auto_lock_minutes`: Minutes it stays unlocked without being used,
+///   the default if `None`, forever if `0`.
+/// ## Result
+/// The status of the space and its integrity report.
+pub async fn unlock_space_use_case<E: EncryptionRepository, N: NoteRepository>(
+    encryption_repo: &E,
+    notes_repo: &N,
+    space_name: &str,
+    passphrase: &str,
+    auto_lock_minutes: Option<u32>,
+) -> Result<UnlockedSpace, EncryptionError> {
+    encryption_repo
+        .unlock(
+            space_name,
+            passphrase,
+            auto_lock_duration(auto_lock_minutes),
+        )
+        .await?;
+
+    let integrity = notes_repo.check_integrity(space_name).await?;
+    Ok(UnlockedSpace {
+        status: encryption_repo.get_status(space_name).await?,
+        integrity,
+    })
+}
+
+/// # Lock Space use case
+/// Forgets the key of an encrypted space.
+/// ## Params
+/// * `encryption_repo`: Repository keeping the key of the space.
+/// * `space_name`: The encrypted space.
+/// ## Result
+/// Whether the space was unlocked.
+pub async fn lock_space_use_case<E: EncryptionRepository>(
+    encryption_repo: &E,
+    space_name: &str,
+) -> Result<bool, EncryptionError> {
+    encryption_repo.lock(space_name).await
+}
@@ -0,0 +1,119 @@
+//! # Encryption Domain
+//! Defines how the key of an encrypted space is derived from its
+//! passphrase and what the frontend knows about the encryption of a space.
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::features::notes::domain::integrity::IntegrityReport;
+
+pub use crate::shared::repositories::keyring::ENCRYPTION_MANIFEST;
+
+/// Version of the encryption format written by this version of the
+/// application.
+pub const ENCRYPTION_FORMAT_VERSION: u32 = 1;
+
+/// Key derivation function of the encrypted spaces.
+pub const KDF_ARGON2ID: &str = "argon2id";
+
+/// Cipher of the sealed files.
+pub const CIPHER_XCHACHA20POLY1305: &str = "xchacha20poly1305";
+
+/// Argon2id memory cost of the new encrypted spaces, in KiB.
+pub const DEFAULT_MEMORY_KIB: u32 = 64 * 1024;
+
+/// Argon2id time cost of the new encrypted spaces.
+pub const DEFAULT_ITERATIONS: u32 = 3;
+
+/// Argon2id lanes of the new encrypted spaces.
+pub const DEFAULT_PARALLELISM: u32 = 1;
+
+/// Minutes an unlocked space stays unlocked without being used, when the
+/// unlock doesn't say.
+pub const DEFAULT_AUTO_LOCK_MINUTES: u32 = 15;
+
+/// Event sent to the frontend when a space locks by itself, with its name.
+pub const SPACE_LOCKED_EVENT: &str = "space-locked";
+
+/// Shortest passphrase accepted.
+pub const MIN_PASSPHRASE_LEN: usize = 8;
+
+/// Content sealed in the manifest to check a passphrase before opening
+/// any file with its key.
+pub const KEY_VERIFIER: &[u8] = b"merino-space-key";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # EncryptionManifest
+/// Hidden file of an encrypted space with what's needed to derive its key
+/// again. It holds no secret.
+/// ## Fields
+/// * `format_version`: Version of the encryption format.
+/// * `kdf`: The key derivation function, `argon2id`.
+/// * `memory_kib`: The Argon2id memory cost, in KiB.
+/// * `iterations`: The Argon2id time cost.
+/// * `parallelism`: The Argon2id lanes.
+/// * `salt`: The salt of the key derivation, in base64.
+/// * `cipher`: The cipher of the files, `xchacha20poly1305`.
+/// * `verifier`: `KEY_VERIFIER` sealed with the key, in base64.
+pub struct EncryptionManifest {
+    pub format_version: u32,
+    pub kdf: String,
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+    pub salt: String,
+    pub cipher: String,
+    pub verifier: String,
+}
+
+#[derive(Debug, Serialize, Clone)]
+/// # EncryptionStatus
+/// The encryption of a space, as seen by the frontend.
+/// ## Fields
+/// * `space_name`: The space.
+/// * `encrypted`: Whether its files are encrypted.
+/// * `unlocked`: Whether its key is known, always `true` when it isn't
+///   encrypted.
+/// * `auto_lock_minutes`: Minutes it stays unlocked without being used,
+///   `None` when it never locks by itself.
+pub struct EncryptionStatus {
+    pub space_name: String,
+    pub encrypted: bool,
+    pub unlocked: bool,
+    pub auto_lock_minutes: Option<u32>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+/// # UnlockedSpace
+/// The result of unlocking a space.
+/// ## Fields
+/// * `status`: The encryption of the space.
+/// * `integrity`: The integrity check of its notes, skipped while it was
+///   locked.
+pub struct UnlockedSpace {
+    pub status: EncryptionStatus,
+    pub integrity: IntegrityReport,
+}
+
+#[derive(Debug, Serialize, Clone, Default)]
+/// # EncryptionChange
+/// The result of encrypting or decrypting the files of a space.
+/// ## Fields
+/// * `notes`: The note files changed.
+/// * `attachments`: The attachments changed.
+/// * `templates`: The templates of the space changed.
+pub struct EncryptionChange {
+    pub notes: usize,
+    pub attachments: usize,
+    pub templates: usize,
+}
+
+/// # Auto-lock duration
+/// The auto-lock timeout for the minutes asked: the default one when not
+/// given, none (never locks by itself) for `0`.
+pub fn auto_lock_duration(minutes: Option<u32>) -> Option<Duration> {
+    match minutes.unwrap_or(DEFAULT_AUTO_LOCK_MINUTES) {
+        0 => None,
+        minutes => Some(Duration::from_secs(u64::from(minutes) * 60)),
+    }
+}
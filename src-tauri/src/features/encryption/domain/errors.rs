@@ -0,0 +1,54 @@
+//! # Errors
+//! Defines all error that could ocurr while encrypting, unlocking or
+//! locking a space.
+use std::io;
+
+use thiserror::Error;
+
+use crate::{
+    features::{notes::domain::errors::NoteError, space::domain::errors::SpaceError},
+    shared::errors::app_errors::AppError,
+};
+
+/// # EncryptionError Enum
+/// Defines the enum with all the possible error that could ocurr during
+/// an encryption operation in the application.
+/// ## Possible Errors
+/// * `WrongPassphrase`: The passphrase doesn't open the space.
+/// * `WeakPassphrase`: The passphrase is shorter than the minimum.
+/// * `AlreadyEncrypted`: The space is already encrypted.
+/// * `NotEncrypted`: The space isn't encrypted.
+/// * `InvalidManifest`: The encryption manifest is malformed or unsupported.
+/// * `Crypto`: The key derivation or a file encryption failed.
+#[derive(Debug, Error)]
+pub enum EncryptionError {
+    #[error("Wrong passphrase for space '{0}'.")]
+    WrongPassphrase(String),
+
+    #[error("The passphrase must be at least {0} characters long.")]
+    WeakPassphrase(usize),
+
+    #[error("Space '{0}' is already encrypted.")]
+    AlreadyEncrypted(String),
+
+    #[error("Space '{0}' isn't encrypted.")]
+    NotEncrypted(String),
+
+    #[error("Invalid encryption manifest: {0}")]
+    InvalidManifest(String),
+
+    #[error("Encryption error: {0}")]
+    Crypto(String),
+
+    #[error("Encryption IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    Space(#[from] SpaceError),
+
+    #[error(transparent)]
+    Note(#[from] NoteError),
+
+    #[error(transparent)]
+    AppError(#[from] AppError),
+}
@@ -0,0 +1,3 @@
+pub mod encryption;
+pub mod errors;
+pub mod repository;
@@ -0,0 +1,66 @@
+//! # Repository
+//! Definition of how the files of a space are encrypted and how its key is
+//! kept while it's unlocked.
+use std::time::Duration;
+
+use async_trait::async_trait;
+
+use super::{
+    encryption::{EncryptionChange, EncryptionStatus},
+    errors::EncryptionError,
+};
+
+/// # EncryptionRepository
+/// Encrypts and decrypts the spaces, and unlocks and locks them.
+#[async_trait]
+pub trait EncryptionRepository {
+    /// # [GET] Status
+    /// Whether a space is encrypted and unlocked.
+    async fn get_status(&self, space_name: &str) -> Result<EncryptionStatus, EncryptionError>;
+
+    /// # [CREATE] Enable encryption
+    /// Derives a key from the passphrase, encrypts the notes, attachments
+    /// and templates of the space and leaves it unlocked. The thumbnails
+    /// are removed, they would be plaintext copies of the images.
+    /// ## Fields
+    /// * `space_name`: The space to be encrypted.
+    /// * `passphrase`: The passphrase of the space.
+    /// * `auto_lock`: How long it stays unlocked without being used.
+    async fn enable(
+        &self,
+        space_name: &str,
+        passphrase: &str,
+        auto_lock: Option<Duration>,
+    ) -> Result<EncryptionChange, EncryptionError>;
+
+    /// # [DELETE] Disable encryption
+    /// Decrypts every file of the space and forgets its key.
+    /// ## Fields
+    /// * `space_name`: The encrypted space.
+    /// * `passphrase`: The passphrase of the space.
+    async fn disable(
+        &self,
+        space_name: &str,
+        passphrase: &str,
+    ) -> Result<EncryptionChange, EncryptionError>;
+
+    /// # [UPDATE] Unlock
+    /// Derives the key of the space and keeps it until the space is locked.
+    /// ## Fields
+    /// * `space_name`: The encrypted space.
+    /// * `passphrase`: The passphrase of the space.
+    /// * `auto_lock`: How long it stays unlocked without being used,
+    ///   forever if `None`.
+    async fn unlock(
+        &self,
+        space_name: &str,
+        passphrase: &str,
+        auto_lock: Option<Duration>,
+    ) -> Result<(), EncryptionError>;
+
+    /// # [UPDATE] Lock
+    /// Forgets the key of the space.
+    /// ## Result
+    /// Whether the space was unlocked.
+    async fn lock(&self, space_name: &str) -> Result<bool, EncryptionError>;
+}
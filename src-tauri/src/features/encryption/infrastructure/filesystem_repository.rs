@@ -0,0 +1,353 @@
+//! # FileSystem Repository
+//! Encrypts the files of a space in place. Only the content is sealed: the
+//! note names, folders, folder manifests and `space.json` stay readable, so
+//! a locked space can still be listed.
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use async_trait::async_trait;
+use base64::{engine::general_purpose::STANDARD, Engine};
+use tokio::fs::{self, read_dir};
+
+use crate::{
+    features::{
+        attachments::domain::{attachment::ATTACHMENTS_DIR, image::THUMBNAILS_DIR},
+        encryption::domain::{
+            encryption::{
+                EncryptionChange, EncryptionManifest, EncryptionStatus, CIPHER_XCHACHA20POLY1305,
+                DEFAULT_ITERATIONS, DEFAULT_MEMORY_KIB, DEFAULT_PARALLELISM,
+                ENCRYPTION_FORMAT_VERSION, ENCRYPTION_MANIFEST, KDF_ARGON2ID, KEY_VERIFIER,
+            },
+            errors::EncryptionError,
+            repository::EncryptionRepository,
+        },
+        stats::domain::writing::STATS_FILE,
+        templates::infrastructure::filesystem_repository::SPACE_TEMPLATES_DIR,
+    },
+    shared::{
+        repositories::filesystem_repository::FileSystemRepository,
+        utils::{
+            atomic_write::write_synced,
+            crypto::{self, SpaceKey, SALT_LEN},
+        },
+    },
+};
+
+/// The files of a space holding content, by kind. The writing log isn't
+/// reported.
+#[derive(Default)]
+struct ContentFiles {
+    notes: Vec<PathBuf>,
+    attachments: Vec<PathBuf>,
+    templates: Vec<PathBuf>,
+    logs: Vec<PathBuf>,
+}
+
+/// # FileSystemEncryptionRepository
+/// Implementation of the EncryptionRepository trait.
+#[derive(Clone)]
+pub struct FileSystemEncryptionRepository {
+    filesystem_repo: FileSystemRepository,
+}
+
+impl FileSystemEncryptionRepository {
+    pub fn new(filesystem_repo: FileSystemRepository) -> Self {
+        Self { filesystem_repo }
+    }
+
+    fn get_manifest_path(&self, space_name: &str) -> Result<PathBuf, EncryptionError> {
+        Ok(self
+            .filesystem_repo
+            .get_space_path(space_name)?
+            .join(ENCRYPTION_MANIFEST))
+    }
+
+    /// Reads the encryption manifest of a space.
+    async fn read_manifest(&self, space_name: &str) -> Result<EncryptionManifest, EncryptionError> {
+        let content = match fs::read_to_string(self.get_manifest_path(space_name)?).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Err(EncryptionError::NotEncrypted(space_name.to_string()))
+            }
+            Err(e) => return Err(e.into()),
+        };
+        let manifest: EncryptionManifest = serde_json::from_str(&content)
+            .map_err(|e| EncryptionError::InvalidManifest(e.to_string()))?;
+
+        if manifest.format_version > ENCRYPTION_FORMAT_VERSION
+            || manifest.kdf != KDF_ARGON2ID
+            || manifest.cipher != CIPHER_XCHACHA20POLY1305
+        {
+            return Err(EncryptionError::InvalidManifest(format!(
+                "unsupported format {} ({}, {})",
+                manifest.format_version, manifest.kdf, manifest.cipher
+            )));
+        }
+        Ok(manifest)
+    }
+
+    /// Derives the key of a space and checks it against the manifest.
+    async fn derive_key(
+        &self,
+        space_name: &str,
+        manifest: &EncryptionManifest,
+        passphrase: &str,
+    ) -> Result<SpaceKey, EncryptionError> {
+        let salt = STANDARD
+            .decode(&manifest.salt)
+            .map_err(|e| EncryptionError::InvalidManifest(e.to_string()))?;
+        let verifier = STANDARD
+            .decode(&manifest.verifier)
+            .map_err(|e| EncryptionError::InvalidManifest(e.to_string()))?;
+
+        let key = derive(passphrase, salt, manifest).await?;
+        match crypto::open(&key, ENCRYPTION_MANIFEST, &verifier) {
+            Ok(content) if content == KEY_VERIFIER => Ok(key),
+            _ => Err(EncryptionError::WrongPassphrase(space_name.to_string())),
+        }
+    }
+
+    /// Lists the files of a space holding content.
+    async fn content_files(&self, space_name: &str) -> Result<ContentFiles, EncryptionError> {
+        let space_path = self.filesystem_repo.get_space_path(space_name)?;
+        let mut files = ContentFiles::default();
+
+        let mut directories = vec![space_path.clone()];
+        while let Some(directory) = directories.pop() {
+            let mut entries = read_dir(&directory).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                if entry.file_name().to_string_lossy().starts_with('.') {
+                    continue;
+                }
+                if entry.file_type().await?.is_dir() {
+                    directories.push(path);
+                } else if path.extension().is_some_and(|e| e == "json") {
+                    files.notes.push(path);
+                }
+            }
+        }
+
+        files.attachments = list_files(&space_path.join(ATTACHMENTS_DIR)).await?;
+        files.templates = list_files(&space_path.join(SPACE_TEMPLATES_DIR))
+            .await?
+            .into_iter()
+            .filter(|p| p.extension().is_some_and(|e| e == "json"))
+            .collect();
+        let log = space_path.join(STATS_FILE);
+        if log.is_file() {
+            files.logs.push(log);
+        }
+        Ok(files)
+    }
+}
+
+#[async_trait]
+impl EncryptionRepository for FileSystemEncryptionRepository {
+    async fn get_status(&self, space_name: &str) -> Result<EncryptionStatus, EncryptionError> {
+        let encrypted = self.filesystem_repo.is_space_encrypted(space_name)?;
+        let keyring = self.filesystem_repo.keyring();
+        let unlocked = !encrypted || keyring.is_unlocked(space_name);
+
+        Ok(EncryptionStatus {
+            space_name: space_name.to_string(),
+            encrypted,
+            unlocked,
+            auto_lock_minutes: match encrypted && unlocked {
+                true => keyring
+                    .auto_lock(space_name)
+                    .map(|timeout| (timeout.as_secs() / 60) as u32),
+                false => None,
+            },
+        })
+    }
+
+    async fn enable(
+        &self,
+        space_name: &str,
+        passphrase: &str,
+        auto_lock: Option<Duration>,
+    ) -> Result<EncryptionChange, EncryptionError> {
+        if self.filesystem_repo.is_space_encrypted(space_name)? {
+            return Err(EncryptionError::AlreadyEncrypted(space_name.to_string()));
+        }
+        let files = self.content_files(space_name).await?;
+
+        let salt: [u8; SALT_LEN] = rand::random();
+        let mut manifest = EncryptionManifest {
+            format_version: ENCRYPTION_FORMAT_VERSION,
+            kdf: KDF_ARGON2ID.to_string(),
+            memory_kib: DEFAULT_MEMORY_KIB,
+            iterations: DEFAULT_ITERATIONS,
+            parallelism: DEFAULT_PARALLELISM,
+            salt: STANDARD.encode(salt),
+            cipher: CIPHER_XCHACHA20POLY1305.to_string(),
+            verifier: String::new(),
+        };
+        let key = derive(passphrase, salt.to_vec(), &manifest).await?;
+        manifest.verifier = STANDARD.encode(
+            crypto::seal(&key, ENCRYPTION_MANIFEST, KEY_VERIFIER)
+                .map_err(EncryptionError::Crypto)?,
+        );
+
+        // The manifest goes first: a space left half sealed by a crash reads
+        // both kinds of files, and the plaintext ones are sealed when saved.
+        let content = serde_json::to_string_pretty(&manifest)
+            .map_err(|e| EncryptionError::InvalidManifest(e.to_string()))?;
+        write_synced(&self.get_manifest_path(space_name)?, content.as_bytes()).await?;
+        self.filesystem_repo
+            .keyring()
+            .unlock(space_name, key.clone(), auto_lock);
+
+        let space_path = self.filesystem_repo.get_space_path(space_name)?;
+        let change = EncryptionChange {
+            notes: seal_files(&key, &space_path, &files.notes).await?,
+            attachments: seal_files(&key, &space_path, &files.attachments).await?,
+            templates: seal_files(&key, &space_path, &files.templates).await?,
+        };
+        seal_files(&key, &space_path, &files.logs).await?;
+
+        let thumbnails = space_path.join(ATTACHMENTS_DIR).join(THUMBNAILS_DIR);
+        match fs::remove_dir_all(&thumbnails).await {
+            Ok(_) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+
+        self.filesystem_repo.mark_space_changed(space_name);
+        Ok(change)
+    }
+
+    async fn disable(
+        &self,
+        space_name: &str,
+        passphrase: &str,
+    ) -> Result<EncryptionChange, EncryptionError> {
+        let manifest = self.read_manifest(space_name).await?;
+        let key = self.derive_key(space_name, &manifest, passphrase).await?;
+        let files = self.content_files(space_name).await?;
+        let space_path = self.filesystem_repo.get_space_path(space_name)?;
+
+        // The manifest goes last, until then the files opened so far and the
+        // sealed ones left both read fine.
+        let change = EncryptionChange {
+            notes: open_files(&key, &space_path, &files.notes).await?,
+            attachments: open_files(&key, &space_path, &files.attachments).await?,
+            templates: open_files(&key, &space_path, &files.templates).await?,
+        };
+        open_files(&key, &space_path, &files.logs).await?;
+        fs::remove_file(self.get_manifest_path(space_name)?).await?;
+        self.filesystem_repo.keyring().lock(space_name);
+
+        self.filesystem_repo.mark_space_changed(space_name);
+        Ok(change)
+    }
+
+    async fn unlock(
+        &self,
+        space_name: &str,
+        passphrase: &str,
+        auto_lock: Option<Duration>,
+    ) -> Result<(), EncryptionError> {
+        let manifest = self.read_manifest(space_name).await?;
+        let key = self.derive_key(space_name, &manifest, passphrase).await?;
+        self.filesystem_repo
+            .keyring()
+            .unlock(space_name, key, auto_lock);
+        Ok(())
+    }
+
+    async fn lock(&self, space_name: &str) -> Result<bool, EncryptionError> {
+        if !self.filesystem_repo.is_space_encrypted(space_name)? {
+            return Err(EncryptionError::NotEncrypted(space_name.to_string()));
+        }
+        Ok(self.filesystem_repo.keyring().lock(space_name))
+    }
+}
+
+/// Derives a key with the parameters of a manifest, on a blocking thread:
+/// Argon2id is slow on purpose.
+async fn derive(
+    passphrase: &str,
+    salt: Vec<u8>,
+    manifest: &EncryptionManifest,
+) -> Result<SpaceKey, EncryptionError> {
+    let passphrase = zeroize::Zeroizing::new(passphrase.to_string());
+    let (memory_kib, iterations, parallelism) = (
+        manifest.memory_kib,
+        manifest.iterations,
+        manifest.parallelism,
+    );
+
+    tauri::async_runtime::spawn_blocking(move || {
+        crypto::derive_key(&passphrase, &salt, memory_kib, iterations, parallelism)
+    })
+    .await
+    .map_err(|e| EncryptionError::Crypto(e.to_string()))?
+    .map_err(EncryptionError::Crypto)
+}
+
+/// The visible files of a directory, none if it doesn't exist.
+async fn list_files(directory: &Path) -> Result<Vec<PathBuf>, EncryptionError> {
+    let mut entries = match read_dir(directory).await {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+
+    let mut files = Vec::new();
+    while let Some(entry) = entries.next_entry().await? {
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if !hidden && entry.file_type().await?.is_file() {
+            files.push(entry.path());
+        }
+    }
+    Ok(files)
+}
+
+/// Seals the files not sealed yet.
+/// ## Result
+/// The number of files sealed.
+async fn seal_files(
+    key: &SpaceKey,
+    space_path: &Path,
+    files: &[PathBuf],
+) -> Result<usize, EncryptionError> {
+    let mut sealed = 0;
+    for path in files {
+        let content = fs::read(path).await?;
+        if crypto::is_sealed(&content) {
+            continue;
+        }
+        let content = crypto::seal(key, &crypto::sealed_name(space_path, path), &content)
+            .map_err(EncryptionError::Crypto)?;
+        write_synced(path, &content).await?;
+        sealed += 1;
+    }
+    Ok(sealed)
+}
+
+/// Opens the sealed files.
+/// ## Result
+/// The number of files opened.
+async fn open_files(
+    key: &SpaceKey,
+    space_path: &Path,
+    files: &[PathBuf],
+) -> Result<usize, EncryptionError> {
+    let mut opened = 0;
+    for path in files {
+        let content = fs::read(path).await?;
+        if !crypto::is_sealed(&content) {
+            continue;
+        }
+        let content = crypto::open(key, &crypto::sealed_name(space_path, path), &content)
+            .map_err(|e| EncryptionError::Crypto(format!("'{}': {}", path.display(), e)))?;
+        write_synced(path, &content).await?;
+        opened += 1;
+    }
+    Ok(opened)
+}
@@ -0,0 +1,214 @@
+//! # Tauri Commands [Encryption]
+//! Implementation of the commands encrypting, unlocking and locking the
+//! spaces to expose them on tauri for having them in the frontend
+//! application.
+use tauri::State;
+
+use crate::{
+    features::{
+        encryption::{
+            application::{enable, status, unlock},
+            domain::encryption::{EncryptionChange, EncryptionStatus, UnlockedSpace},
+            infrastructure::filesystem_repository::FileSystemEncryptionRepository,
+        },
+        notes::domain::repository::NoteRepository,
+        search::{self, domain::search::Searchable},
+    },
+    shared::state::state::AppState,
+};
+
+/// # [CREATE] Enable Space Encryption
+/// Encrypts a space with a passphrase. Its notes leave the search index on
+/// disk, from now on they're only indexed in memory while it's unlocked.
+/// ## Params
+/// * `space_name`: The space to be encrypted.
+/// * `passphrase`: The passphrase of the space.
+/// * `auto_lock_minutes`: Minutes it stays unlocked without being used,
+///   15 if not given, forever if `0`.
+/// ## Result
+/// The files encrypted.
+#[tauri::command]
+pub async fn enable_space_encryption_cmd(
+    state: State<'_, AppState>,
+    encryption_repo: State<'_, FileSystemEncryptionRepository>,
+    space_name: &str,
+    passphrase: &str,
+    auto_lock_minutes: Option<u32>,
+) -> Result<EncryptionChange, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+    let spaces_repo_lock = state.spaces_repo.lock().await;
+
+    let change = enable::enable_space_encryption_use_case(
+        &*encryption_repo,
+        &*spaces_repo_lock,
+        space_name,
+        passphrase,
+        auto_lock_minutes,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let notes = fs_repo_lock
+        .get_notes(space_name)
+        .await
+        .map_err(|e| e.to_string())?;
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    for note in &notes {
+        search::application::delete::delete_document_use_case(
+            &search_repo_lock,
+            &mut index_writer_lock,
+            &note.get_unique_id(space_name),
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    // Merged away, the deleted documents don't linger in the index files.
+    search_repo_lock
+        .compact(&mut index_writer_lock)
+        .map_err(|e| e.to_string())?;
+
+    search_repo_lock
+        .open_private_index(space_name)
+        .map_err(|e| e.to_string())?;
+    search::application::index::reindex_space_use_case(
+        &*fs_repo_lock,
+        &search_repo_lock,
+        &mut index_writer_lock,
+        space_name,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    Ok(change)
+}
+
+/// # [DELETE] Disable Space Encryption
+/// Decrypts a space, its notes go back to the search index on disk.
+/// ## Params
+/// * `space_name`: The encrypted space.
+/// * `passphrase`: The passphrase of the space.
+/// ## Result
+/// The files decrypted.
+#[tauri::command]
+pub async fn disable_space_encryption_cmd(
+    state: State<'_, AppState>,
+    encryption_repo: State<'_, FileSystemEncryptionRepository>,
+    space_name: &str,
+    passphrase: &str,
+) -> Result<EncryptionChange, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+    let spaces_repo_lock = state.spaces_repo.lock().await;
+
+    let change = enable::disable_space_encryption_use_case(
+        &*encryption_repo,
+        &*spaces_repo_lock,
+        space_name,
+        passphrase,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    search_repo_lock.close_private_index(space_name);
+    search::application::index::reindex_space_use_case(
+        &*fs_repo_lock,
+        &search_repo_lock,
+        &mut index_writer_lock,
+        space_name,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(change)
+}
+
+/// # [UPDATE] Unlock Space
+/// Unlocks an encrypted space, checks the integrity of its notes and
+/// indexes them in memory.
+/// ## Params
+/// * `space_name`: The encrypted space.
+/// * `passphrase`: The passphrase of the space.
+/// * `auto_lock_minutes`: Minutes it stays unlocked without being used,
+///   15 if not given, forever if `0`.
+/// ## Result
+/// The status of the space and its integrity report.
+#[tauri::command]
+pub async fn unlock_space_cmd(
+    state: State<'_, AppState>,
+    encryption_repo: State<'_, FileSystemEncryptionRepository>,
+    space_name: &str,
+    passphrase: &str,
+    auto_lock_minutes: Option<u32>,
+) -> Result<UnlockedSpace, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    let unlocked = unlock::unlock_space_use_case(
+        &*encryption_repo,
+        &*fs_repo_lock,
+        space_name,
+        passphrase,
+        auto_lock_minutes,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    search_repo_lock
+        .open_private_index(space_name)
+        .map_err(|e| e.to_string())?;
+    search::application::index::reindex_space_use_case(
+        &*fs_repo_lock,
+        &search_repo_lock,
+        &mut index_writer_lock,
+        space_name,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(unlocked)
+}
+
+/// # [UPDATE] Lock Space
+/// Forgets the key of an encrypted space and its in-memory search index.
+/// ## Params
+/// * `space_name`: The encrypted space.
+/// ## Result
+/// Whether the space was unlocked.
+#[tauri::command]
+pub async fn lock_space_cmd(
+    state: State<'_, AppState>,
+    encryption_repo: State<'_, FileSystemEncryptionRepository>,
+    space_name: &str,
+) -> Result<bool, String> {
+    let _fs_repo_lock = state.filesystem_repo.lock().await;
+
+    let locked = unlock::lock_space_use_case(&*encryption_repo, space_name)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let search_repo_lock = state.search_repo.lock().await;
+    search_repo_lock.close_private_index(space_name);
+
+    Ok(locked)
+}
+
+/// # [GET] Space Encryption
+/// Whether a space is encrypted and unlocked.
+/// ## Params
+/// * `space_name`: The space.
+#[tauri::command]
+pub async fn get_space_encryption_cmd(
+    encryption_repo: State<'_, FileSystemEncryptionRepository>,
+    space_name: &str,
+) -> Result<EncryptionStatus, String> {
+    status::get_space_encryption_use_case(&*encryption_repo, space_name)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -1,3 +1,5 @@
+use std::io::ErrorKind;
+
 use crate::features::folders::domain::{
     errors::FolderError, folder::Folder, repository::FolderRepository,
 };
@@ -15,15 +17,7 @@ pub async fn create_folder_use_case<T: FolderRepository>(
             .create_folder(space_name, &folder_name, folder_path)
             .await
         {
-            Ok(_) => {
-                let new_folder_path = match folder_path {
-                    Some(parent_path) => format!("{}/{}", parent_path, folder_name),
-                    None => folder_name,
-                };
-                return Ok(Folder {
-                    path: Some(new_folder_path),
-                });
-            }
+            Ok(folder) => return Ok(folder),
             Err(e) if matches!(e, FolderError::Io(_)) => {
                 folder_number += 1;
                 continue;
@@ -32,3 +26,65 @@ pub async fn create_folder_use_case<T: FolderRepository>(
         }
     }
 }
+
+/// # Create Named Folder Use Case
+/// Creates a folder named after `base_name`, when the name is already taken
+/// it tries `base_name 2`, `base_name 3`... like `create_folder_use_case`
+/// does with "Untitled n".
+pub async fn create_named_folder_use_case<T: FolderRepository>(
+    repo: &T,
+    space_name: &str,
+    base_name: &str,
+    folder_path: Option<&str>,
+) -> Result<Folder, FolderError> {
+    let base_name = base_name.trim();
+    if base_name.is_empty() {
+        return Err(FolderError::EmptyName);
+    }
+
+    let mut folder_number = 1;
+    loop {
+        let folder_name = if folder_number == 1 {
+            base_name.to_string()
+        } else {
+            format!("{} {}", base_name, folder_number)
+        };
+        match repo
+            .create_folder(space_name, &folder_name, folder_path)
+            .await
+        {
+            Ok(folder) => return Ok(folder),
+            Err(FolderError::Io(e)) if e.kind() == ErrorKind::AlreadyExists => {
+                folder_number += 1;
+                continue;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// # Ensure Folder Use Case
+/// Creates every missing folder of `folder_path` (`a/b/c`), leaving the
+/// existing ones untouched.
+pub async fn ensure_folder_use_case<T: FolderRepository>(
+    repo: &T,
+    space_name: &str,
+    folder_path: &str,
+) -> Result<(), FolderError> {
+    let mut parent: Option<String> = None;
+    for segment in folder_path.split(['/', '\\']).filter(|s| !s.is_empty()) {
+        match repo
+            .create_folder(space_name, segment, parent.as_deref())
+            .await
+        {
+            Ok(_) => {}
+            Err(FolderError::Io(e)) if e.kind() == ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e),
+        }
+        parent = Some(match parent {
+            Some(parent) => format!("{}/{}", parent, segment),
+            None => segment.to_string(),
+        });
+    }
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use crate::features::folders::domain::{
+    errors::FolderError,
+    ordering::{EntryKind, FolderOrdering, SortMode},
+    repository::FolderRepository,
+};
+
+pub async fn get_folder_ordering_use_case<T: FolderRepository>(
+    repo: &T,
+    space_name: &str,
+    folder_path: Option<&str>,
+) -> Result<FolderOrdering, FolderError> {
+    repo.get_folder_ordering(space_name, folder_path).await
+}
+
+/// # Set Folder Sort Use Case
+/// Changes the sort mode of a folder, keeping its manual order for when the
+/// manual sort is picked again.
+pub async fn set_folder_sort_use_case<T: FolderRepository>(
+    repo: &T,
+    space_name: &str,
+    folder_path: Option<&str>,
+    sort: SortMode,
+) -> Result<FolderOrdering, FolderError> {
+    let mut ordering = repo.get_folder_ordering(space_name, folder_path).await?;
+    ordering.sort = sort;
+    repo.set_folder_ordering(space_name, folder_path, ordering)
+        .await
+}
+
+/// # Reorder Entries Use Case
+/// Stores the manual order of the notes or subfolders of a folder and
+/// switches it to the manual sort.
+pub async fn reorder_entries_use_case<T: FolderRepository>(
+    repo: &T,
+    space_name: &str,
+    folder_path: Option<&str>,
+    kind: EntryKind,
+    order: Vec<String>,
+) -> Result<FolderOrdering, FolderError> {
+    let mut ordering = repo.get_folder_ordering(space_name, folder_path).await?;
+    ordering.set_order(kind, order);
+    repo.set_folder_ordering(space_name, folder_path, ordering)
+        .await
+}
+
+/// # Set Entry Pinned Use Case
+/// Pins or unpins a note or a subfolder of a folder.
+pub async fn set_entry_pinned_use_case<T: FolderRepository>(
+    repo: &T,
+    space_name: &str,
+    folder_path: Option<&str>,
+    kind: EntryKind,
+    name: &str,
+    pinned: bool,
+) -> Result<FolderOrdering, FolderError> {
+    if name.trim().is_empty() {
+        return Err(FolderError::EmptyName);
+    }
+    let mut ordering = repo.get_folder_ordering(space_name, folder_path).await?;
+    ordering.set_pinned(kind, name, pinned);
+    repo.set_folder_ordering(space_name, folder_path, ordering)
+        .await
+}
+
+/// Splits the path of a folder into its parent and its name.
+pub fn split_folder_path(folder_path: &str) -> (Option<&str>, &str) {
+    match folder_path.rsplit_once(['/', '\\']) {
+        Some((parent, name)) => (Some(parent).filter(|p| !p.is_empty()), name),
+        None => (None, folder_path),
+    }
+}
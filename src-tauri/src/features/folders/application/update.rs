@@ -1,25 +1,66 @@
 use crate::features::folders::domain::{
-    errors::FolderError, folder::Folder, repository::FolderRepository,
+    errors::FolderError,
+    folder::{CollisionPolicy, Folder, FolderMetadata},
+    repository::FolderRepository,
 };
 
+/// # Update Folder Route
+/// Moves a folder to another folder of the same space.
+/// ## Result
+/// The folder at its new path.
 pub async fn update_folder_route_use_case<T: FolderRepository>(
     repo: &T,
     space_name: &str,
     folder_name: &str,
     old_route: Option<&str>,
     new_route: Option<&str>,
-) -> Result<(), FolderError> {
-    repo.update_folder_route(space_name, folder_name, old_route, new_route)
+    on_collision: CollisionPolicy,
+) -> Result<Folder, FolderError> {
+    repo.update_folder_route(space_name, folder_name, old_route, new_route, on_collision)
         .await
 }
 
+/// # Update Folder Name
+/// Renames a folder inside its parent.
+/// ## Result
+/// The folder at its new path.
 pub async fn update_folder_name_use_case<T: FolderRepository>(
     repo: &T,
     space_name: &str,
     folder_name: &str,
     new_folder_name: &str,
     folder_path: Option<&str>,
+    on_collision: CollisionPolicy,
+) -> Result<Folder, FolderError> {
+    repo.update_folder_name(
+        space_name,
+        folder_name,
+        new_folder_name,
+        folder_path,
+        on_collision,
+    )
+    .await
+}
+
+pub async fn set_folder_locked_use_case<T: FolderRepository>(
+    repo: &T,
+    space_name: &str,
+    folder_path: &str,
+    locked: bool,
+) -> Result<Folder, FolderError> {
+    repo.set_folder_locked(space_name, folder_path, locked)
+        .await
+}
+
+/// # Update Folder Metadata
+/// Replaces the icon, color, description, default template and collapsed
+/// state of a folder.
+pub async fn update_folder_metadata_use_case<T: FolderRepository>(
+    repo: &T,
+    space_name: &str,
+    folder_path: Option<&str>,
+    metadata: FolderMetadata,
 ) -> Result<Folder, FolderError> {
-    repo.update_folder_name(space_name, folder_name, new_folder_name, folder_path)
+    repo.update_folder_metadata(space_name, folder_path, metadata.normalized())
         .await
 }
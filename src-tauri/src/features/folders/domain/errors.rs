@@ -14,6 +14,11 @@ use crate::shared::errors::app_errors::AppError;
 /// * `InavlidId`: A note has an invalid ID.
 /// * `FrontmatterParsing`: The frontmatter parser couldn't find the frontmatter.
 /// * `EmptyName`: The name of the note was empty.
+/// * `Locked`: The folder, or something inside it, is locked.
+/// * `InvalidName`: The folder name isn't a single visible path segment.
+/// * `OutsideSpace`: The path leaves the space.
+/// * `Cycle`: The folder would be moved inside itself.
+/// * `AlreadyExists`: The destination already has an entry with that name.
 #[derive(Debug, Error)]
 pub enum FolderError {
     #[error("Note not found in space: {0}")]
@@ -22,6 +27,21 @@ pub enum FolderError {
     #[error("Note name cannot be empty.")]
     EmptyName,
 
+    #[error("Folder '{0}' is locked.")]
+    Locked(String),
+
+    #[error("Invalid folder name: '{0}'.")]
+    InvalidName(String),
+
+    #[error("'{0}' is outside of the space.")]
+    OutsideSpace(String),
+
+    #[error("Folder '{0}' can't be moved inside itself.")]
+    Cycle(String),
+
+    #[error("'{0}' already exists.")]
+    AlreadyExists(String),
+
     #[error("Notes IO error: {0}")]
     Io(#[from] io::Error),
 
@@ -1,6 +1,110 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+use super::ordering::FolderOrdering;
+
+/// Hidden file of a folder holding its settings. Hidden files and folders
+/// are never listed as notes or folders.
+pub const FOLDER_MANIFEST: &str = ".folder.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// # CollisionPolicy
+/// What a move or rename does when the destination folder already has an
+/// entry with the same name, ignoring case.
+/// ## Variants
+/// * `Abort`: Nothing is moved.
+/// * `Rename`: The folder is moved as `name 2`, `name 3`...
+/// * `Merge`: The content is moved into the existing folder, which keeps
+///   its own settings. Nothing is moved when a note exists on both sides.
+pub enum CollisionPolicy {
+    #[default]
+    Abort,
+    Rename,
+    Merge,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+/// # Folder
+/// A folder of a space.
+/// ## Fields
+/// * `path`: The path of the folder relative to the space.
+/// * `created`: When the folder was created, if the platform knows it.
+/// * `modified`: When the folder content last changed.
+/// * `pinned`: Whether the folder is pinned in its parent.
+/// * `metadata`: How the folder is shown, from its manifest.
 pub struct Folder {
     pub path: Option<String>,
+    #[serde(default)]
+    pub created: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub modified: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub pinned: bool,
+    #[serde(flatten)]
+    pub metadata: FolderMetadata,
+}
+
+impl Folder {
+    /// The last segment of the path, empty for the space root.
+    pub fn name(&self) -> &str {
+        self.path
+            .as_deref()
+            .and_then(|p| p.rsplit(['/', '\\']).next())
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+/// # FolderManifest
+/// Settings of a folder, persisted in its `FOLDER_MANIFEST`.
+/// ## Fields
+/// * `locked`: Everything inside the folder (recursively) is read-only.
+/// * `ordering`: How the entries of the folder are sorted.
+/// * `metadata`: How the folder is shown.
+pub struct FolderManifest {
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default)]
+    pub ordering: FolderOrdering,
+    #[serde(flatten)]
+    pub metadata: FolderMetadata,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+/// # FolderMetadata
+/// How a folder is shown, persisted in its `FOLDER_MANIFEST` so it moves
+/// with the folder.
+/// ## Fields
+/// * `icon`: Icon of the folder.
+/// * `color`: Color of the folder.
+/// * `description`: What the folder is about.
+/// * `default_template`: Template of the notes created in the folder.
+/// * `collapsed`: Whether the folder is collapsed in the tree.
+pub struct FolderMetadata {
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub default_template: Option<String>,
+    #[serde(default)]
+    pub collapsed: bool,
+}
+
+impl FolderMetadata {
+    /// The metadata with its texts trimmed, the blank ones removed.
+    pub fn normalized(self) -> Self {
+        let clean =
+            |text: Option<String>| text.map(|t| t.trim().to_string()).filter(|t| !t.is_empty());
+        Self {
+            icon: clean(self.icon),
+            color: clean(self.color),
+            description: clean(self.description),
+            default_template: clean(self.default_template),
+            collapsed: self.collapsed,
+        }
+    }
 }
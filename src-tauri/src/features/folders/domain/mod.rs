@@ -1,3 +1,4 @@
 pub mod errors;
 pub mod folder;
+pub mod ordering;
 pub mod repository;
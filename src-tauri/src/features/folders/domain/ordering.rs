@@ -0,0 +1,343 @@
+//! # Ordering Domain
+//! Defines how the notes and subfolders of a folder are sorted: a sort mode,
+//! a manual order and the pinned entries listed first.
+use std::cmp::Ordering;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::folder::Folder;
+use crate::features::notes::domain::note::Note;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// # SortMode
+/// How the entries of a folder are sorted after the pinned ones.
+/// ## Variants
+/// * `Manual`: The order set by the user, new entries last by name.
+/// * `Name`: Alphabetically, ignoring case.
+/// * `Created`: Newest first.
+/// * `Modified`: Last modified first.
+pub enum SortMode {
+    Manual,
+    #[default]
+    Name,
+    Created,
+    Modified,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// # EntryKind
+/// The kind of entry of a folder.
+pub enum EntryKind {
+    Note,
+    Folder,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+/// # FolderOrdering
+/// Ordering of the entries of a folder, stored in its manifest. Entries are
+/// identified by their name inside the folder.
+/// ## Fields
+/// * `sort`: The sort mode.
+/// * `notes`: The manual order of the notes.
+/// * `folders`: The manual order of the subfolders.
+/// * `pinned_notes`: The pinned notes, in pin order.
+/// * `pinned_folders`: The pinned subfolders, in pin order.
+pub struct FolderOrdering {
+    #[serde(default)]
+    pub sort: SortMode,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub notes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub folders: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_notes: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub pinned_folders: Vec<String>,
+}
+
+impl FolderOrdering {
+    /// The manual order of a kind of entry.
+    pub fn order(&self, kind: EntryKind) -> &[String] {
+        match kind {
+            EntryKind::Note => &self.notes,
+            EntryKind::Folder => &self.folders,
+        }
+    }
+
+    /// The pinned entries of a kind.
+    pub fn pinned(&self, kind: EntryKind) -> &[String] {
+        match kind {
+            EntryKind::Note => &self.pinned_notes,
+            EntryKind::Folder => &self.pinned_folders,
+        }
+    }
+
+    /// # Set order
+    /// Replaces the manual order of a kind of entry and switches to the
+    /// manual sort.
+    pub fn set_order(&mut self, kind: EntryKind, order: Vec<String>) {
+        let mut seen = Vec::new();
+        for name in order {
+            if !name.is_empty() && !seen.contains(&name) {
+                seen.push(name);
+            }
+        }
+        match kind {
+            EntryKind::Note => self.notes = seen,
+            EntryKind::Folder => self.folders = seen,
+        }
+        self.sort = SortMode::Manual;
+    }
+
+    /// # Set pinned
+    /// Pins an entry after the already pinned ones, or unpins it.
+    pub fn set_pinned(&mut self, kind: EntryKind, name: &str, pinned: bool) {
+        let list = match kind {
+            EntryKind::Note => &mut self.pinned_notes,
+            EntryKind::Folder => &mut self.pinned_folders,
+        };
+        let present = list.iter().any(|n| n == name);
+        if pinned && !present {
+            list.push(name.to_string());
+        } else if !pinned {
+            list.retain(|n| n != name);
+        }
+    }
+
+    /// # Rename
+    /// Follows the rename of an entry.
+    /// ## Returns
+    /// Whether the ordering changed.
+    pub fn rename(&mut self, kind: EntryKind, old_name: &str, new_name: &str) -> bool {
+        let (order, pinned) = match kind {
+            EntryKind::Note => (&mut self.notes, &mut self.pinned_notes),
+            EntryKind::Folder => (&mut self.folders, &mut self.pinned_folders),
+        };
+        let mut changed = false;
+        for name in order.iter_mut().chain(pinned.iter_mut()) {
+            if name == old_name {
+                *name = new_name.to_string();
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    /// # Remove
+    /// Forgets an entry that left the folder.
+    /// ## Returns
+    /// Whether the ordering changed and whether the entry was pinned.
+    pub fn remove(&mut self, kind: EntryKind, name: &str) -> (bool, bool) {
+        let (order, pinned) = match kind {
+            EntryKind::Note => (&mut self.notes, &mut self.pinned_notes),
+            EntryKind::Folder => (&mut self.folders, &mut self.pinned_folders),
+        };
+        let (order_len, pinned_len) = (order.len(), pinned.len());
+        order.retain(|n| n != name);
+        pinned.retain(|n| n != name);
+        let was_pinned = pinned.len() != pinned_len;
+        (was_pinned || order.len() != order_len, was_pinned)
+    }
+
+    /// # Sort
+    /// Sorts the entries of the folder: the pinned ones first, in pin order,
+    /// then the others by the sort mode. Marks the pinned entries.
+    pub fn sort<T: Ordered>(&self, entries: &mut [T]) {
+        let Some(kind) = entries.first().map(Ordered::kind) else {
+            return;
+        };
+        let pinned = self.pinned(kind);
+        let order = self.order(kind);
+        let position = |list: &[String], name: &str| list.iter().position(|n| n == name);
+
+        for entry in entries.iter_mut() {
+            let is_pinned = position(pinned, entry.entry_name()).is_some();
+            entry.set_pinned(is_pinned);
+        }
+
+        entries.sort_by(|a, b| {
+            let by_pin = match (
+                position(pinned, a.entry_name()),
+                position(pinned, b.entry_name()),
+            ) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            };
+            let by_mode = match self.sort {
+                SortMode::Manual => {
+                    match (
+                        position(order, a.entry_name()),
+                        position(order, b.entry_name()),
+                    ) {
+                        (Some(a), Some(b)) => a.cmp(&b),
+                        (Some(_), None) => Ordering::Less,
+                        (None, Some(_)) => Ordering::Greater,
+                        (None, None) => Ordering::Equal,
+                    }
+                }
+                SortMode::Name => Ordering::Equal,
+                SortMode::Created => newest_first(a.created(), b.created()),
+                SortMode::Modified => newest_first(a.modified(), b.modified()),
+            };
+            by_pin.then(by_mode).then_with(|| by_name(a, b))
+        });
+    }
+}
+
+/// Compares two timestamps, the newest first and the unknown ones last.
+fn newest_first(a: Option<DateTime<Utc>>, b: Option<DateTime<Utc>>) -> Ordering {
+    match (a, b) {
+        (Some(a), Some(b)) => b.cmp(&a),
+        (Some(_), None) => Ordering::Less,
+        (None, Some(_)) => Ordering::Greater,
+        (None, None) => Ordering::Equal,
+    }
+}
+
+/// Compares the names ignoring case, the case only breaks ties.
+fn by_name<T: Ordered>(a: &T, b: &T) -> Ordering {
+    let (a, b) = (a.entry_name(), b.entry_name());
+    a.to_lowercase()
+        .cmp(&b.to_lowercase())
+        .then_with(|| a.cmp(b))
+}
+
+/// # Ordered
+/// An entry of a folder that can be sorted by its `FolderOrdering`.
+pub trait Ordered {
+    /// The kind of the entry.
+    fn kind(&self) -> EntryKind;
+    /// The name of the entry inside its folder.
+    fn entry_name(&self) -> &str;
+    fn created(&self) -> Option<DateTime<Utc>>;
+    fn modified(&self) -> Option<DateTime<Utc>>;
+    fn set_pinned(&mut self, pinned: bool);
+}
+
+impl Ordered for Note {
+    fn kind(&self) -> EntryKind {
+        EntryKind::Note
+    }
+
+    fn entry_name(&self) -> &str {
+        &self.name
+    }
+
+    fn created(&self) -> Option<DateTime<Utc>> {
+        self.created
+    }
+
+    fn modified(&self) -> Option<DateTime<Utc>> {
+        self.modified
+    }
+
+    fn set_pinned(&mut self, pinned: bool) {
+        self.pinned = pinned;
+    }
+}
+
+impl Ordered for Folder {
+    fn kind(&self) -> EntryKind {
+        EntryKind::Folder
+    }
+
+    fn entry_name(&self) -> &str {
+        self.name()
+    }
+
+    fn created(&self) -> Option<DateTime<Utc>> {
+        self.created
+    }
+
+    fn modified(&self) -> Option<DateTime<Utc>> {
+        self.modified
+    }
+
+    fn set_pinned(&mut self, pinned: bool) {
+        self.pinned = pinned;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    fn note(name: &str, day: u32) -> Note {
+        Note {
+            name: name.to_string(),
+            created: Some(Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap()),
+            modified: Some(Utc.with_ymd_and_hms(2024, 2, 10 - day, 0, 0, 0).unwrap()),
+            ..Default::default()
+        }
+    }
+
+    fn names(notes: &[Note]) -> Vec<&str> {
+        notes.iter().map(|n| n.name.as_str()).collect()
+    }
+
+    #[test]
+    fn pinned_entries_come_first_in_pin_order() {
+        let mut ordering = FolderOrdering::default();
+        ordering.set_pinned(EntryKind::Note, "c", true);
+        ordering.set_pinned(EntryKind::Note, "b", true);
+        let mut notes = vec![note("a", 1), note("b", 2), note("c", 3), note("D", 4)];
+
+        ordering.sort(&mut notes);
+        assert_eq!(names(&notes), vec!["c", "b", "a", "D"]);
+        assert!(notes[0].pinned && notes[1].pinned && !notes[2].pinned);
+    }
+
+    #[test]
+    fn sort_modes() {
+        let mut notes = vec![note("b", 2), note("a", 1), note("c", 3)];
+        let mut ordering = FolderOrdering {
+            sort: SortMode::Created,
+            ..Default::default()
+        };
+        ordering.sort(&mut notes);
+        assert_eq!(names(&notes), vec!["c", "b", "a"]);
+
+        ordering.sort = SortMode::Modified;
+        ordering.sort(&mut notes);
+        assert_eq!(names(&notes), vec!["a", "b", "c"]);
+
+        // Manual: the listed entries first, the new ones after, by name.
+        ordering.set_order(EntryKind::Note, vec!["c".into(), "a".into(), "c".into()]);
+        assert_eq!(ordering.sort, SortMode::Manual);
+        notes.push(note("B", 4));
+        ordering.sort(&mut notes);
+        assert_eq!(names(&notes), vec!["c", "a", "B", "b"]);
+    }
+
+    #[test]
+    fn rename_follows_the_order_and_the_pins() {
+        let mut ordering = FolderOrdering::default();
+        ordering.set_order(EntryKind::Note, vec!["a".into(), "b".into()]);
+        ordering.set_pinned(EntryKind::Note, "a", true);
+
+        assert!(ordering.rename(EntryKind::Note, "a", "z"));
+        assert_eq!(ordering.notes, vec!["z", "b"]);
+        assert_eq!(ordering.pinned_notes, vec!["z"]);
+        assert!(!ordering.rename(EntryKind::Note, "missing", "y"));
+        assert!(!ordering.rename(EntryKind::Folder, "b", "y"));
+    }
+
+    #[test]
+    fn remove_reports_the_pin() {
+        let mut ordering = FolderOrdering::default();
+        ordering.set_order(EntryKind::Folder, vec!["a".into(), "b".into()]);
+        ordering.set_pinned(EntryKind::Folder, "b", true);
+
+        assert_eq!(ordering.remove(EntryKind::Folder, "a"), (true, false));
+        assert_eq!(ordering.remove(EntryKind::Folder, "b"), (true, true));
+        assert_eq!(ordering.remove(EntryKind::Folder, "b"), (false, false));
+        assert!(ordering.folders.is_empty() && ordering.pinned_folders.is_empty());
+    }
+}
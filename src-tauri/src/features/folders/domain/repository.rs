@@ -1,18 +1,26 @@
 use async_trait::async_trait;
 
-use crate::features::folders::domain::{errors::FolderError, folder::Folder};
+use crate::features::folders::domain::{
+    errors::FolderError,
+    folder::{CollisionPolicy, Folder, FolderMetadata},
+    ordering::FolderOrdering,
+};
 
 #[async_trait]
 pub trait FolderRepository {
     async fn get_folders(&self, space_name: &str) -> Result<Vec<Folder>, FolderError>;
 
+    /// # [UPDATE] Folder route
+    /// Moves `folder_name` from the `old_route` folder to the `new_route`
+    /// one, both the space root when `None`.
     async fn update_folder_route(
         &self,
         space_name: &str,
         folder_name: &str,
         old_route: Option<&str>,
         new_route: Option<&str>,
-    ) -> Result<(), FolderError>;
+        on_collision: CollisionPolicy,
+    ) -> Result<Folder, FolderError>;
 
     async fn create_folder(
         &self,
@@ -21,6 +29,9 @@ pub trait FolderRepository {
         folder_path: Option<&str>,
     ) -> Result<Folder, FolderError>;
 
+    /// # [DELETE] Folder
+    /// Deletes the folder at `folder_path`, which must be named
+    /// `folder_name`, or `folder_name` at the space root when `None`.
     async fn delete_folder(
         &self,
         space_name: &str,
@@ -34,5 +45,36 @@ pub trait FolderRepository {
         folder_name: &str,
         new_folder_name: &str,
         folder_path: Option<&str>,
+        on_collision: CollisionPolicy,
+    ) -> Result<Folder, FolderError>;
+
+    async fn set_folder_locked(
+        &self,
+        space_name: &str,
+        folder_path: &str,
+        locked: bool,
+    ) -> Result<Folder, FolderError>;
+
+    /// # [UPDATE] Folder metadata
+    /// Replaces how a folder is shown, the space root when `folder_path` is
+    /// `None`.
+    async fn update_folder_metadata(
+        &self,
+        space_name: &str,
+        folder_path: Option<&str>,
+        metadata: FolderMetadata,
     ) -> Result<Folder, FolderError>;
+
+    async fn get_folder_ordering(
+        &self,
+        space_name: &str,
+        folder_path: Option<&str>,
+    ) -> Result<FolderOrdering, FolderError>;
+
+    async fn set_folder_ordering(
+        &self,
+        space_name: &str,
+        folder_path: Option<&str>,
+        ordering: FolderOrdering,
+    ) -> Result<FolderOrdering, FolderError>;
 }
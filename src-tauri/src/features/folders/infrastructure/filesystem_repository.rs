@@ -1,12 +1,25 @@
-use std::{collections::VecDeque, io::ErrorKind, path::PathBuf};
+use std::{
+    collections::VecDeque,
+    io::ErrorKind,
+    path::{Component, Path, PathBuf},
+};
 
 use async_trait::async_trait;
-use log::{error};
+use log::error;
 use tokio::fs::{self, read_dir};
 
 use crate::{
-    features::folders::domain::{
-        errors::FolderError, folder::Folder, repository::FolderRepository,
+    features::folders::{
+        domain::{
+            errors::FolderError,
+            folder::{CollisionPolicy, Folder, FolderMetadata, FOLDER_MANIFEST},
+            ordering::{EntryKind, FolderOrdering},
+            repository::FolderRepository,
+        },
+        infrastructure::manifest::{
+            entry_times, find_lock_in_subtree, find_locked_ancestor, move_ordered_entry,
+            move_ordered_entry_as, read_manifest, rename_ordered_entry, write_manifest,
+        },
     },
     shared::repositories::filesystem_repository::FileSystemRepository,
 };
@@ -20,6 +33,174 @@ impl FileSystemFolderRepository {
     pub fn new(filesystem_repo: FileSystemRepository) -> Self {
         Self { filesystem_repo }
     }
+
+    /// Fails with `FolderError::Locked` when the folder, one of its parents
+    /// or anything inside it is locked.
+    async fn ensure_unlocked(
+        &self,
+        space_path: &Path,
+        folder_path: &Path,
+    ) -> Result<(), FolderError> {
+        let locked = match find_locked_ancestor(space_path, folder_path).await? {
+            Some(locked) => Some(locked),
+            None if folder_path.is_dir() => {
+                find_lock_in_subtree(&self.filesystem_repo, folder_path).await?
+            }
+            None => None,
+        };
+        match locked {
+            Some(path) => Err(FolderError::Locked(
+                path.strip_prefix(space_path)
+                    .unwrap_or(&path)
+                    .display()
+                    .to_string(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Resolves an existing folder of a space, the space root when `None`.
+    fn resolve_folder(
+        &self,
+        space_name: &str,
+        folder_path: Option<&str>,
+    ) -> Result<PathBuf, FolderError> {
+        let space_path = self.filesystem_repo.get_space_path(space_name)?;
+        let path = match folder_path.filter(|p| !p.trim().is_empty()) {
+            Some(folder) => space_path.join(folder),
+            None => space_path,
+        };
+        if !path.is_dir() {
+            return Err(FolderError::NotFound(format!(
+                "Folder '{}' not found.",
+                folder_path.unwrap_or_default()
+            )));
+        }
+        Ok(path)
+    }
+
+    /// Fails with `FolderError::Locked` when nothing can be added to a folder.
+    async fn ensure_writable(
+        &self,
+        space_path: &Path,
+        folder_path: &Path,
+    ) -> Result<(), FolderError> {
+        match find_locked_ancestor(space_path, folder_path).await? {
+            Some(path) => Err(FolderError::Locked(
+                path.strip_prefix(space_path)
+                    .unwrap_or(&path)
+                    .display()
+                    .to_string(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Fails with `FolderError::OutsideSpace` when an existing path resolves
+    /// outside of the space, through a link for instance.
+    async fn ensure_inside_space(&self, space_path: &Path, path: &Path) -> Result<(), FolderError> {
+        let space = fs::canonicalize(space_path).await?;
+        if fs::canonicalize(path).await?.starts_with(&space) {
+            Ok(())
+        } else {
+            Err(FolderError::OutsideSpace(relative_path(space_path, path)))
+        }
+    }
+
+    /// # Move folder
+    /// Moves the folder at `old_path` into `new_parent` as `new_name`, both
+    /// in the same space, following `on_collision` when the name is taken.
+    /// A folder is never moved inside itself nor over another entry.
+    async fn move_folder(
+        &self,
+        space_name: &str,
+        old_path: &Path,
+        new_parent: &Path,
+        new_name: &str,
+        on_collision: CollisionPolicy,
+    ) -> Result<Folder, FolderError> {
+        let space_path = self.filesystem_repo.get_space_path(space_name)?;
+        let (Some(old_parent), Some(old_name)) = (old_path.parent(), old_path.file_name()) else {
+            return Err(FolderError::NotFound(relative_path(&space_path, old_path)));
+        };
+        let old_name = old_name.to_string_lossy().to_string();
+        for path in [old_path, new_parent] {
+            if !path.is_dir() {
+                return Err(FolderError::NotFound(format!(
+                    "Folder '{}' not found.",
+                    relative_path(&space_path, path)
+                )));
+            }
+            self.ensure_inside_space(&space_path, path).await?;
+        }
+
+        if is_inside(new_parent, old_path).await? {
+            return Err(FolderError::Cycle(relative_path(&space_path, old_path)));
+        }
+
+        self.ensure_unlocked(&space_path, old_path).await?;
+        self.ensure_writable(&space_path, new_parent).await?;
+
+        let mut final_name = new_name.to_string();
+        let mut merged = false;
+        if let Some(existing) = find_collision(new_parent, new_name, old_path).await? {
+            let existing_path = new_parent.join(&existing);
+            match on_collision {
+                CollisionPolicy::Abort => {
+                    return Err(FolderError::AlreadyExists(relative_path(
+                        &space_path,
+                        &existing_path,
+                    )))
+                }
+                CollisionPolicy::Rename => {
+                    final_name = available_name(new_parent, new_name, old_path).await?;
+                }
+                CollisionPolicy::Merge => {
+                    if !existing_path.is_dir() {
+                        return Err(FolderError::AlreadyExists(relative_path(
+                            &space_path,
+                            &existing_path,
+                        )));
+                    }
+                    self.ensure_unlocked(&space_path, &existing_path).await?;
+                    let moves = merge_folders(&space_path, old_path, &existing_path).await?;
+                    for (from, to) in moves {
+                        self.filesystem_repo.reseal_moved(&from, &to).await?;
+                    }
+                    final_name = existing;
+                    merged = true;
+                }
+            }
+        }
+
+        let new_path = new_parent.join(&final_name);
+        if !merged {
+            fs::rename(old_path, &new_path).await?;
+            self.filesystem_repo
+                .reseal_moved(old_path, &new_path)
+                .await?;
+        }
+        self.filesystem_repo.mark_path_changed(old_path);
+        self.filesystem_repo.mark_path_changed(&new_path);
+
+        // The merged folder keeps its place, the moved one is forgotten.
+        if merged {
+            move_ordered_entry(old_parent, None, EntryKind::Folder, &old_name).await;
+        } else if old_parent == new_parent {
+            rename_ordered_entry(new_parent, EntryKind::Folder, &old_name, &final_name).await;
+        } else {
+            move_ordered_entry_as(
+                old_parent,
+                Some(new_parent),
+                EntryKind::Folder,
+                &old_name,
+                &final_name,
+            )
+            .await;
+        }
+
+        folder_at(&space_path, &new_path).await
+    }
 }
 
 #[async_trait]
@@ -38,8 +219,16 @@ impl FolderRepository for FileSystemFolderRepository {
         directories_to_visit.push_back(space_path.clone());
 
         // Add the root folder explicitly, as its relative path is an empty string
+        let (created, modified) = match fs::metadata(&space_path).await {
+            Ok(metadata) => entry_times(&metadata),
+            Err(_) => (None, None),
+        };
         folders.push(Folder {
             path: Some("".to_string()),
+            created,
+            modified,
+            metadata: read_manifest(&space_path).await?.metadata,
+            ..Default::default()
         });
 
         while let Some(current_dir) = directories_to_visit.pop_front() {
@@ -58,6 +247,8 @@ impl FolderRepository for FileSystemFolderRepository {
                 }
             };
 
+            let mut subfolders: Vec<Folder> = Vec::new();
+
             while let Some(entry) = entries.next_entry().await.unwrap_or(None) {
                 let path = entry.path();
 
@@ -72,17 +263,36 @@ impl FolderRepository for FileSystemFolderRepository {
                             // Add the full relative path to the folders list
                             if let Some(path_str) = relative_path.to_str() {
                                 if !path_str.is_empty() {
-                                    folders.push(Folder {
+                                    let (created, modified) = match entry.metadata().await {
+                                        Ok(metadata) => entry_times(&metadata),
+                                        Err(_) => (None, None),
+                                    };
+                                    let folder = Folder {
                                         path: Some(path_str.to_string()),
-                                    });
+                                        created,
+                                        modified,
+                                        metadata: read_manifest(&path).await?.metadata,
+                                        ..Default::default()
+                                    };
+                                    subfolders.push(folder);
                                 }
                             }
-                            // Push the subdirectory to the queue for traversal
-                            directories_to_visit.push_back(path);
                         }
                     }
                 }
             }
+
+            // `read_dir` yields the entries in no particular order.
+            read_manifest(&current_dir)
+                .await?
+                .ordering
+                .sort(&mut subfolders);
+            for folder in subfolders {
+                // Push the subdirectory to the queue for traversal
+                let path = space_path.join(folder.path.as_deref().unwrap_or_default());
+                directories_to_visit.push_back(path);
+                folders.push(folder);
+            }
         }
 
         Ok(folders)
@@ -94,37 +304,21 @@ impl FolderRepository for FileSystemFolderRepository {
         folder_name: &str,
         old_route: Option<&str>,
         new_route: Option<&str>,
-    ) -> Result<(), FolderError> {
+        on_collision: CollisionPolicy,
+    ) -> Result<Folder, FolderError> {
+        validate_name(folder_name)?;
         let space_path = self.filesystem_repo.get_space_path(space_name)?;
-
-        let mut old_path = space_path.clone();
-        if let Some(route) = old_route {
-            old_path.push(route);
-        }
-        // Corrected: Append the folder name to the old path
-        old_path.push(folder_name);
-
-        let mut new_path = space_path.clone();
-        if let Some(route) = new_route {
-            new_path.push(route);
-        }
-        // Corrected: Append the folder name to the new path
-        new_path.push(folder_name);
-
-        // Check if the old path exists before attempting to move.
-        if !old_path.exists() {
-            return Err(FolderError::NotFound(format!(
-                "Old folder path not found: {}",
-                old_path.display()
-            )));
-        }
-
-        // Attempt the rename operation.
-        fs::rename(&old_path, &new_path)
-            .await
-            .map_err(|e| FolderError::Io(e))?;
-
-        Ok(())
+        let old_path = join_route(&space_path, old_route)?.join(folder_name);
+        let new_parent = join_route(&space_path, new_route)?;
+
+        self.move_folder(
+            space_name,
+            &old_path,
+            &new_parent,
+            folder_name,
+            on_collision,
+        )
+        .await
     }
 
     async fn create_folder(
@@ -133,20 +327,19 @@ impl FolderRepository for FileSystemFolderRepository {
         folder_name: &str,
         folder_path: Option<&str>,
     ) -> Result<Folder, FolderError> {
+        validate_name(folder_name)?;
         let space_path = self.filesystem_repo.get_space_path(space_name)?;
 
-        let mut new_folder_path = space_path;
-
-        if let Some(folder) = folder_path {
-            new_folder_path.push(folder);
-        }
+        let mut new_folder_path = join_route(&space_path, folder_path)?;
+        self.ensure_writable(&space_path, &new_folder_path).await?;
 
         new_folder_path.push(folder_name);
 
         match fs::create_dir(&new_folder_path).await {
-            Ok(_) => Ok(Folder {
-                path: Some(new_folder_path.to_str().unwrap().to_string()),
-            }),
+            Ok(_) => {
+                self.filesystem_repo.mark_path_changed(&new_folder_path);
+                folder_at(&space_path, &new_folder_path).await
+            }
             Err(e) if e.kind() == ErrorKind::AlreadyExists => Err(FolderError::Io(e)),
             Err(e) => Err(FolderError::Io(e)),
         }
@@ -158,17 +351,37 @@ impl FolderRepository for FileSystemFolderRepository {
         folder_name: &str,
         folder_path: Option<&str>,
     ) -> Result<String, FolderError> {
+        validate_name(folder_name)?;
         let space_path = self.filesystem_repo.get_space_path(space_name)?;
 
-        let mut final_path = space_path.clone();
-
-        // Append the parent path first, if it exists
-        if let Some(parent_path) = folder_path {
-            final_path.push(parent_path);
+        // The path names the folder itself, it must end with its name.
+        let final_path = join_route(&space_path, Some(folder_path.unwrap_or(folder_name)))?;
+        if final_path == space_path
+            || final_path.file_name().and_then(|n| n.to_str()) != Some(folder_name)
+        {
+            return Err(FolderError::NotFound(format!(
+                "Folder '{}' not found at '{}'.",
+                folder_name,
+                folder_path.unwrap_or_default()
+            )));
         }
+        if !final_path.is_dir() {
+            return Err(FolderError::NotFound(format!(
+                "Folder '{}' not found.",
+                relative_path(&space_path, &final_path)
+            )));
+        }
+        self.ensure_inside_space(&space_path, &final_path).await?;
+        self.ensure_unlocked(&space_path, &final_path).await?;
 
         match fs::remove_dir_all(&final_path).await {
-            Ok(_) => Ok(format!("Removed '{}' from '{}'.", folder_name, space_name)),
+            Ok(_) => {
+                self.filesystem_repo.mark_path_changed(&final_path);
+                if let Some(parent) = final_path.parent() {
+                    move_ordered_entry(parent, None, EntryKind::Folder, folder_name).await;
+                }
+                Ok(format!("Removed '{}' from '{}'.", folder_name, space_name))
+            }
             Err(e) if e.kind() == ErrorKind::NotFound => Err(FolderError::NotFound(e.to_string())),
             Err(e) => Err(FolderError::Io(e)),
         }
@@ -180,35 +393,420 @@ impl FolderRepository for FileSystemFolderRepository {
         folder_name: &str,
         new_folder_name: &str,
         folder_path: Option<&str>,
+        on_collision: CollisionPolicy,
     ) -> Result<Folder, FolderError> {
         if new_folder_name.trim().is_empty() {
             return Err(FolderError::EmptyName);
         }
+        validate_name(folder_name)?;
+        validate_name(new_folder_name)?;
 
         let space_path = self.filesystem_repo.get_space_path(space_name)?;
-        let mut old_path = space_path.clone();
-        let mut new_path = space_path.clone();
+        let parent = join_route(&space_path, folder_path)?;
+
+        // The manifest is renamed with the folder, so is its metadata.
+        self.move_folder(
+            space_name,
+            &parent.join(folder_name),
+            &parent,
+            new_folder_name,
+            on_collision,
+        )
+        .await
+    }
 
-        // Append the parent folder path if it exists
-        if let Some(folder) = folder_path {
-            old_path.push(folder);
-            new_path.push(folder);
+    async fn set_folder_locked(
+        &self,
+        space_name: &str,
+        folder_path: &str,
+        locked: bool,
+    ) -> Result<Folder, FolderError> {
+        let space_path = self.filesystem_repo.get_space_path(space_name)?;
+        let path = space_path.join(folder_path);
+
+        if folder_path.trim().is_empty() || !path.is_dir() {
+            return Err(FolderError::NotFound(format!(
+                "Folder '{}' not found.",
+                folder_path
+            )));
         }
 
-        // Append the old and new folder names to the respective paths
-        old_path.push(folder_name);
-        new_path.push(new_folder_name);
+        let mut manifest = read_manifest(&path).await?;
+        manifest.locked = locked;
+        write_manifest(&path, &manifest).await?;
+        self.filesystem_repo.mark_space_changed(space_name);
 
-        // Perform the rename operation
-        fs::rename(&old_path, &new_path)
-            .await
-            .map_err(|e| FolderError::Io(e))?;
+        Ok(Folder {
+            path: Some(folder_path.to_string()),
+            ..Default::default()
+        })
+    }
 
-        // Correctly handle the folder path for the returned Folder struct
-        let final_folder_path = folder_path.map(|s| s.to_string());
+    async fn update_folder_metadata(
+        &self,
+        space_name: &str,
+        folder_path: Option<&str>,
+        metadata: FolderMetadata,
+    ) -> Result<Folder, FolderError> {
+        let path = self.resolve_folder(space_name, folder_path)?;
+
+        let mut manifest = read_manifest(&path).await?;
+        manifest.metadata = metadata;
+        write_manifest(&path, &manifest).await?;
+        self.filesystem_repo.mark_space_changed(space_name);
 
         Ok(Folder {
-            path: final_folder_path,
+            path: Some(folder_path.unwrap_or_default().to_string()),
+            metadata: manifest.metadata,
+            ..Default::default()
         })
     }
+
+    async fn get_folder_ordering(
+        &self,
+        space_name: &str,
+        folder_path: Option<&str>,
+    ) -> Result<FolderOrdering, FolderError> {
+        let path = self.resolve_folder(space_name, folder_path)?;
+        Ok(read_manifest(&path).await?.ordering)
+    }
+
+    async fn set_folder_ordering(
+        &self,
+        space_name: &str,
+        folder_path: Option<&str>,
+        ordering: FolderOrdering,
+    ) -> Result<FolderOrdering, FolderError> {
+        let path = self.resolve_folder(space_name, folder_path)?;
+
+        let mut manifest = read_manifest(&path).await?;
+        manifest.ordering = ordering;
+        write_manifest(&path, &manifest).await?;
+        self.filesystem_repo.mark_space_changed(space_name);
+
+        Ok(manifest.ordering)
+    }
+}
+
+/// Fails with `FolderError::InvalidName` unless the name is a single visible
+/// path segment, hidden entries are never listed as folders.
+fn validate_name(name: &str) -> Result<(), FolderError> {
+    if name.trim().is_empty() {
+        return Err(FolderError::EmptyName);
+    }
+    if name.starts_with('.') || name.contains(['/', '\\']) {
+        return Err(FolderError::InvalidName(name.to_string()));
+    }
+    Ok(())
+}
+
+/// Joins a folder path relative to the space, the space root when `None`.
+/// Only plain visible segments are accepted, the path can't leave the space.
+fn join_route(space_path: &Path, route: Option<&str>) -> Result<PathBuf, FolderError> {
+    let mut path = space_path.to_path_buf();
+    for segment in route
+        .unwrap_or_default()
+        .split(['/', '\\'])
+        .filter(|s| !s.is_empty())
+    {
+        let plain = matches!(
+            Path::new(segment)
+                .components()
+                .collect::<Vec<_>>()
+                .as_slice(),
+            [Component::Normal(_)]
+        );
+        if !plain || segment.starts_with('.') {
+            return Err(FolderError::OutsideSpace(
+                route.unwrap_or_default().to_string(),
+            ));
+        }
+        path.push(segment);
+    }
+    Ok(path)
+}
+
+/// The path of an entry relative to its space, `/` separated.
+fn relative_path(space_path: &Path, path: &Path) -> String {
+    path.strip_prefix(space_path)
+        .unwrap_or(path)
+        .to_string_lossy()
+        .replace('\\', "/")
+}
+
+/// A folder of a space as listed by `get_folders`.
+async fn folder_at(space_path: &Path, path: &Path) -> Result<Folder, FolderError> {
+    let (created, modified) = match fs::metadata(path).await {
+        Ok(metadata) => entry_times(&metadata),
+        Err(_) => (None, None),
+    };
+    let pinned = match (path.parent(), path.file_name()) {
+        (Some(parent), Some(name)) => read_manifest(parent)
+            .await?
+            .ordering
+            .pinned(EntryKind::Folder)
+            .iter()
+            .any(|pinned| pinned.as_str() == name.to_string_lossy()),
+        _ => false,
+    };
+    Ok(Folder {
+        path: Some(relative_path(space_path, path)),
+        created,
+        modified,
+        pinned,
+        metadata: read_manifest(path).await?.metadata,
+    })
+}
+
+/// Whether `path` is `folder` or inside it. Checked on the resolved paths,
+/// `a/../a/b` is still inside `a`.
+async fn is_inside(path: &Path, folder: &Path) -> Result<bool, FolderError> {
+    Ok(fs::canonicalize(path)
+        .await?
+        .starts_with(fs::canonicalize(folder).await?))
+}
+
+/// The entry of `folder` named `name` ignoring case, other than `source`.
+/// Case-insensitive filesystems would take one for the other.
+async fn find_collision(
+    folder: &Path,
+    name: &str,
+    source: &Path,
+) -> Result<Option<String>, FolderError> {
+    let name = name.to_lowercase();
+    let mut entries = read_dir(folder).await?;
+    while let Some(entry) = entries.next_entry().await? {
+        let entry_name = entry.file_name().to_string_lossy().to_string();
+        if entry_name.to_lowercase() == name && entry.path() != source {
+            return Ok(Some(entry_name));
+        }
+    }
+    Ok(None)
+}
+
+/// The first of `name 2`, `name 3`... free in `folder`.
+async fn available_name(folder: &Path, name: &str, source: &Path) -> Result<String, FolderError> {
+    let mut number = 2;
+    loop {
+        let candidate = format!("{} {}", name, number);
+        if find_collision(folder, &candidate, source).await?.is_none() {
+            return Ok(candidate);
+        }
+        number += 1;
+    }
+}
+
+/// # Merge folders
+/// Moves the content of `from` into the existing `to`, merging the
+/// subfolders found on both sides, then removes `from`. Every entry is
+/// checked before anything moves: an entry other than a folder found on
+/// both sides aborts the merge. The manifests of `to` are kept.
+/// ## Result
+/// The entries moved, with their new path.
+async fn merge_folders(
+    space_path: &Path,
+    from: &Path,
+    to: &Path,
+) -> Result<Vec<(PathBuf, PathBuf)>, FolderError> {
+    let mut moves: Vec<(PathBuf, PathBuf)> = Vec::new();
+    let mut merged: Vec<PathBuf> = Vec::new();
+    let mut directories = vec![(from.to_path_buf(), to.to_path_buf())];
+
+    while let Some((source, target)) = directories.pop() {
+        let mut entries = read_dir(&source).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let name = entry.file_name().to_string_lossy().to_string();
+            if name == FOLDER_MANIFEST {
+                continue;
+            }
+            let path = entry.path();
+            match find_collision(&target, &name, &path).await? {
+                None => moves.push((path, target.join(&name))),
+                Some(existing) => {
+                    let existing_path = target.join(&existing);
+                    if !(entry.file_type().await?.is_dir() && existing_path.is_dir()) {
+                        return Err(FolderError::AlreadyExists(relative_path(
+                            space_path,
+                            &existing_path,
+                        )));
+                    }
+                    directories.push((path, existing_path));
+                }
+            }
+        }
+        merged.push(source);
+    }
+
+    for (source, target) in &moves {
+        fs::rename(source, target).await?;
+    }
+    // The subfolders are listed after their parents, they are emptied first.
+    for folder in merged.iter().rev() {
+        match fs::remove_file(folder.join(FOLDER_MANIFEST)).await {
+            Ok(_) => {}
+            Err(e) if e.kind() == ErrorKind::NotFound => {}
+            Err(e) => return Err(e.into()),
+        }
+        fs::remove_dir(folder).await?;
+    }
+    Ok(moves)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_folder(name: &str) -> PathBuf {
+        std::env::temp_dir().join(format!("merino-folders-{}-{}", name, std::process::id()))
+    }
+
+    #[test]
+    fn only_visible_single_segments_are_valid_names() {
+        assert!(validate_name("Projects").is_ok());
+        assert!(validate_name("My notes").is_ok());
+
+        assert!(matches!(validate_name(""), Err(FolderError::EmptyName)));
+        assert!(matches!(validate_name("  "), Err(FolderError::EmptyName)));
+        for name in [".hidden", "a/b", "a\\b", ".."] {
+            assert!(
+                matches!(validate_name(name), Err(FolderError::InvalidName(_))),
+                "{}",
+                name
+            );
+        }
+    }
+
+    #[test]
+    fn routes_are_joined_inside_the_space() {
+        let space = Path::new("/spaces/work");
+
+        assert_eq!(join_route(space, None).unwrap(), space);
+        assert_eq!(join_route(space, Some("")).unwrap(), space);
+        assert_eq!(
+            join_route(space, Some("a/b")).unwrap(),
+            space.join("a").join("b")
+        );
+        assert_eq!(
+            join_route(space, Some("a\\b/")).unwrap(),
+            space.join("a").join("b")
+        );
+    }
+
+    #[test]
+    fn routes_leaving_the_space_are_rejected() {
+        let space = Path::new("/spaces/work");
+
+        for route in ["..", "a/../../b", "a/.hidden", "./a"] {
+            assert!(
+                matches!(
+                    join_route(space, Some(route)),
+                    Err(FolderError::OutsideSpace(_))
+                ),
+                "{}",
+                route
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn collisions_ignore_case_and_the_source() {
+        let folder = temp_folder("collision");
+        fs::create_dir_all(folder.join("Projects")).await.unwrap();
+
+        assert_eq!(
+            find_collision(&folder, "projects", &folder.join("other"))
+                .await
+                .unwrap(),
+            Some("Projects".to_string())
+        );
+        assert_eq!(
+            find_collision(&folder, "projects", &folder.join("Projects"))
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            find_collision(&folder, "missing", &folder.join("other"))
+                .await
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            available_name(&folder, "PROJECTS", &folder.join("other"))
+                .await
+                .unwrap(),
+            "PROJECTS 2"
+        );
+
+        fs::remove_dir_all(&folder).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn folders_are_inside_themselves_and_their_parents() {
+        let folder = temp_folder("cycle");
+        fs::create_dir_all(folder.join("a").join("b"))
+            .await
+            .unwrap();
+        fs::create_dir_all(folder.join("c")).await.unwrap();
+        let a = folder.join("a");
+
+        assert!(is_inside(&a, &a).await.unwrap());
+        assert!(is_inside(&a.join("b"), &a).await.unwrap());
+        assert!(is_inside(&a.join("..").join("a").join("b"), &a)
+            .await
+            .unwrap());
+        assert!(!is_inside(&folder.join("c"), &a).await.unwrap());
+        assert!(!is_inside(&folder, &a).await.unwrap());
+
+        fs::remove_dir_all(&folder).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn merging_moves_the_entries_and_merges_the_subfolders() {
+        let space = temp_folder("merge");
+        let (from, to) = (space.join("from"), space.join("to"));
+        fs::create_dir_all(from.join("sub")).await.unwrap();
+        fs::create_dir_all(to.join("Sub")).await.unwrap();
+        fs::write(from.join("a.json"), "{}").await.unwrap();
+        fs::write(from.join("sub").join("b.json"), "{}")
+            .await
+            .unwrap();
+        fs::write(from.join(FOLDER_MANIFEST), r#"{"locked":false}"#)
+            .await
+            .unwrap();
+        fs::write(to.join("Sub").join("c.json"), "{}")
+            .await
+            .unwrap();
+
+        let moves = merge_folders(&space, &from, &to).await.unwrap();
+
+        assert_eq!(moves.len(), 2);
+        assert!(to.join("a.json").is_file());
+        assert!(to.join("Sub").join("b.json").is_file());
+        assert!(to.join("Sub").join("c.json").is_file());
+        assert!(!to.join(FOLDER_MANIFEST).exists());
+        assert!(!from.exists());
+
+        fs::remove_dir_all(&space).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn merging_over_a_taken_note_moves_nothing() {
+        let space = temp_folder("merge-conflict");
+        let (from, to) = (space.join("from"), space.join("to"));
+        fs::create_dir_all(&from).await.unwrap();
+        fs::create_dir_all(&to).await.unwrap();
+        fs::write(from.join("a.json"), "{}").await.unwrap();
+        fs::write(from.join("b.json"), "{}").await.unwrap();
+        fs::write(to.join("A.json"), "{}").await.unwrap();
+
+        let error = merge_folders(&space, &from, &to).await.unwrap_err();
+
+        assert!(matches!(error, FolderError::AlreadyExists(path) if path == "to/A.json"));
+        assert!(from.join("a.json").is_file());
+        assert!(from.join("b.json").is_file());
+        assert!(!to.join("b.json").exists());
+
+        fs::remove_dir_all(&space).await.unwrap();
+    }
 }
@@ -0,0 +1,234 @@
+//! # Folder manifest
+//! Reads and writes the hidden manifest of the folders and resolves the
+//! locks, which apply to everything inside a locked folder, and keeps the
+//! ordering of the folders in sync with the entries moving around.
+use std::{
+    collections::VecDeque,
+    fs::Metadata,
+    io,
+    path::{Path, PathBuf},
+};
+
+use chrono::{DateTime, Utc};
+use log::error;
+use tokio::fs::{self, read_dir};
+
+use crate::{
+    features::folders::domain::{
+        folder::{FolderManifest, FOLDER_MANIFEST},
+        ordering::EntryKind,
+    },
+    shared::{
+        errors::app_errors::AppError, repositories::filesystem_repository::FileSystemRepository,
+        utils::atomic_write::write_synced,
+    },
+};
+
+/// Key used inside a note file to mark it as locked.
+pub const NOTE_LOCKED_KEY: &str = "locked";
+
+/// Reads the manifest of a folder, a missing manifest is the default one.
+/// An unreadable manifest is an error: defaulting would drop its lock, and
+/// the next write would save the default over it.
+pub async fn read_manifest(folder_path: &Path) -> io::Result<FolderManifest> {
+    let path = folder_path.join(FOLDER_MANIFEST);
+    match fs::read_to_string(&path).await {
+        Ok(content) => serde_json::from_str(&content).map_err(|e| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("Invalid folder manifest '{}': {}", path.display(), e),
+            )
+        }),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(FolderManifest::default()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Writes the manifest of a folder, without risking a truncated manifest on
+/// a crash.
+pub async fn write_manifest(folder_path: &Path, manifest: &FolderManifest) -> io::Result<()> {
+    let content = serde_json::to_string_pretty(manifest)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    write_synced(&folder_path.join(FOLDER_MANIFEST), content.as_bytes()).await
+}
+
+/// # Locked folder
+/// Returns the first locked folder between `folder_path` and the space root
+/// (both included), if any.
+pub async fn find_locked_ancestor(
+    space_path: &Path,
+    folder_path: &Path,
+) -> io::Result<Option<PathBuf>> {
+    let mut current = Some(folder_path);
+    while let Some(folder) = current {
+        if !folder.starts_with(space_path) {
+            break;
+        }
+        if read_manifest(folder).await?.locked {
+            return Ok(Some(folder.to_path_buf()));
+        }
+        current = folder.parent();
+    }
+    Ok(None)
+}
+
+/// Whether a note file carries the lock flag. The notes of a locked
+/// encrypted space can't be told apart, they're all taken as locked.
+pub async fn is_note_file_locked(filesystem_repo: &FileSystemRepository, note_path: &Path) -> bool {
+    match filesystem_repo.read_file_to_string(note_path).await {
+        Ok(content) => is_content_locked(&content),
+        Err(AppError::SpaceLocked(_)) => true,
+        Err(_) => false,
+    }
+}
+
+/// Whether the content of a note carries the lock flag.
+pub fn is_content_locked(content: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(content)
+        .ok()
+        .and_then(|v| v.get(NOTE_LOCKED_KEY).and_then(|l| l.as_bool()))
+        .unwrap_or(false)
+}
+
+/// # Subtree locks
+/// Returns the first locked folder or note inside `folder_path` (included),
+/// if any.
+pub async fn find_lock_in_subtree(
+    filesystem_repo: &FileSystemRepository,
+    folder_path: &Path,
+) -> io::Result<Option<PathBuf>> {
+    let mut directories_to_visit: VecDeque<PathBuf> = VecDeque::new();
+    directories_to_visit.push_back(folder_path.to_path_buf());
+
+    while let Some(current_dir) = directories_to_visit.pop_front() {
+        if read_manifest(&current_dir).await?.locked {
+            return Ok(Some(current_dir));
+        }
+
+        let mut entries = read_dir(&current_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if entry.file_name().to_string_lossy().starts_with('.') {
+                continue;
+            }
+            let file_type = entry.file_type().await?;
+            if file_type.is_dir() {
+                directories_to_visit.push_back(path);
+            } else if path.extension().is_some_and(|e| e == "json")
+                && is_note_file_locked(filesystem_repo, &path).await
+            {
+                return Ok(Some(path));
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// The creation and modification times of an entry, when the platform
+/// provides them.
+pub fn entry_times(metadata: &Metadata) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+    (
+        metadata.created().ok().map(DateTime::<Utc>::from),
+        metadata.modified().ok().map(DateTime::<Utc>::from),
+    )
+}
+
+/// # Rename ordered entry
+/// Follows the rename of an entry in the ordering of its folder. The
+/// ordering is only presentation, failing to update it is logged.
+pub async fn rename_ordered_entry(folder_path: &Path, kind: EntryKind, old: &str, new: &str) {
+    let Some(mut manifest) = read_ordering_manifest(folder_path).await else {
+        return;
+    };
+    if manifest.ordering.rename(kind, old, new) {
+        if let Err(e) = write_manifest(folder_path, &manifest).await {
+            error!(
+                "Failed to update the order of '{}': {}",
+                folder_path.display(),
+                e
+            );
+        }
+    }
+}
+
+/// # Move ordered entry
+/// Forgets an entry in the ordering of the folder it left and keeps it
+/// pinned in the folder it went to. `to` is `None` when the entry is gone.
+pub async fn move_ordered_entry(from: &Path, to: Option<&Path>, kind: EntryKind, name: &str) {
+    move_ordered_entry_as(from, to, kind, name, name).await
+}
+
+/// # Move ordered entry as
+/// Like `move_ordered_entry`, for an entry renamed to `new_name` while
+/// moving.
+pub async fn move_ordered_entry_as(
+    from: &Path,
+    to: Option<&Path>,
+    kind: EntryKind,
+    name: &str,
+    new_name: &str,
+) {
+    let Some(mut manifest) = read_ordering_manifest(from).await else {
+        return;
+    };
+    let (changed, pinned) = manifest.ordering.remove(kind, name);
+    if !changed {
+        return;
+    }
+    if let Err(e) = write_manifest(from, &manifest).await {
+        error!("Failed to update the order of '{}': {}", from.display(), e);
+    }
+
+    if let (Some(to), true) = (to, pinned) {
+        let Some(mut manifest) = read_ordering_manifest(to).await else {
+            return;
+        };
+        manifest.ordering.set_pinned(kind, new_name, true);
+        if let Err(e) = write_manifest(to, &manifest).await {
+            error!("Failed to update the order of '{}': {}", to.display(), e);
+        }
+    }
+}
+
+/// Reads a manifest to update its ordering, `None` when it can't be read:
+/// the ordering is only presentation, the unreadable manifest is logged and
+/// left as it is.
+async fn read_ordering_manifest(folder_path: &Path) -> Option<FolderManifest> {
+    match read_manifest(folder_path).await {
+        Ok(manifest) => Some(manifest),
+        Err(e) => {
+            error!(
+                "Failed to update the order of '{}': {}",
+                folder_path.display(),
+                e
+            );
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn only_missing_manifests_default() {
+        let folder = std::env::temp_dir().join(format!("merino-manifest-{}", std::process::id()));
+        fs::create_dir_all(&folder).await.unwrap();
+
+        assert!(!read_manifest(&folder).await.unwrap().locked);
+
+        fs::write(folder.join(FOLDER_MANIFEST), r#"{"locked":true}"#)
+            .await
+            .unwrap();
+        assert!(read_manifest(&folder).await.unwrap().locked);
+
+        fs::write(folder.join(FOLDER_MANIFEST), r#"{"locked":tr"#)
+            .await
+            .unwrap();
+        let error = read_manifest(&folder).await.unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        fs::remove_dir_all(&folder).await.unwrap();
+    }
+}
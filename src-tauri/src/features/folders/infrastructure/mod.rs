@@ -1,2 +1,3 @@
 pub mod filesystem_repository;
+pub mod manifest;
 pub mod tauri_commands;
@@ -1,9 +1,19 @@
 use tauri::State;
 
-use crate::features::folders::{
-    application::{create, delete, get, update},
-    domain::folder::Folder,
-    infrastructure::filesystem_repository::FileSystemFolderRepository,
+use crate::{
+    features::{
+        folders::{
+            application::{create, delete, get, ordering, update},
+            domain::{
+                folder::{CollisionPolicy, Folder, FolderMetadata},
+                ordering::{EntryKind, FolderOrdering, SortMode},
+            },
+            infrastructure::filesystem_repository::FileSystemFolderRepository,
+        },
+        notes::infrastructure::filesystem_repository::FileSystemNoteRepository,
+        search,
+    },
+    shared::state::state::AppState,
 };
 
 #[tauri::command]
@@ -16,17 +26,45 @@ pub async fn get_folders_in_space_cmd(
         .map_err(|e| e.to_string())
 }
 
+/// # [UPDATE] Folder Route
+/// Moves a folder to another folder of the same space.
+/// ## Params
+/// * `old_route`: The parent of the folder, the space root if not given.
+/// * `new_route`: The new parent, the space root if not given.
+/// * `on_collision`: What to do when the name is taken, `abort` if not given.
+/// ## Result
+/// The folder at its new path, its notes indexed again there.
 #[tauri::command]
 pub async fn update_folder_route_cmd(
+    state: State<'_, AppState>,
     repo: State<'_, FileSystemFolderRepository>,
     space_name: &str,
     folder_name: &str,
     old_route: Option<&str>,
     new_route: Option<&str>,
-) -> Result<(), String> {
-    update::update_folder_route_use_case(&*repo, &space_name, &folder_name, old_route, new_route)
-        .await
-        .map_err(|e| e.to_string())
+    on_collision: Option<CollisionPolicy>,
+) -> Result<Folder, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    let folder = update::update_folder_route_use_case(
+        &*repo,
+        &space_name,
+        &folder_name,
+        old_route,
+        new_route,
+        on_collision.unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    reindex_folder(
+        &state,
+        &fs_repo_lock,
+        space_name,
+        &child_path(old_route, folder_name),
+    )
+    .await?;
+    Ok(folder)
 }
 
 #[tauri::command]
@@ -40,33 +78,268 @@ pub async fn create_folder_cmd(
         .map_err(|e| e.to_string())
 }
 
+/// # [DELETE] Folder
+/// Deletes a folder with everything inside it.
+/// ## Params
+/// * `folder_name`: The name of the folder.
+/// * `folder_path`: The path of the folder, ending with `folder_name`. The
+///   folder is `folder_name` at the space root if not given.
 #[tauri::command]
 pub async fn delete_folder_cmd(
+    state: State<'_, AppState>,
     repo: State<'_, FileSystemFolderRepository>,
     space_name: &str,
     folder_name: &str,
     folder_path: Option<&str>,
 ) -> Result<String, String> {
-    delete::delete_folder_use_case(&*repo, &space_name, &folder_name, folder_path)
+    let _fs_repo_lock = state.filesystem_repo.lock().await;
+
+    let message = delete::delete_folder_use_case(&*repo, &space_name, &folder_name, folder_path)
         .await
-        .map_err(|e| e.to_string())
+        .map_err(|e| e.to_string())?;
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+    search::application::delete::delete_folder_documents_use_case(
+        &search_repo_lock,
+        &mut index_writer_lock,
+        space_name,
+        folder_path.unwrap_or(folder_name),
+    )
+    .map_err(|e| e.to_string())?;
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(message)
 }
 
+/// # [UPDATE] Folder Name
+/// Renames a folder inside its parent.
+/// ## Params
+/// * `folder_path`: The parent of the folder, the space root if not given.
+/// * `on_collision`: What to do when the name is taken, `abort` if not given.
+/// ## Result
+/// The folder at its new path, its notes indexed again there.
 #[tauri::command]
 pub async fn update_folder_name_cmd(
+    state: State<'_, AppState>,
     repo: State<'_, FileSystemFolderRepository>,
     space_name: &str,
     folder_name: &str,
     new_folder_name: &str,
     folder_path: Option<&str>,
+    on_collision: Option<CollisionPolicy>,
 ) -> Result<Folder, String> {
-    update::update_folder_name_use_case(
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    let folder = update::update_folder_name_use_case(
         &*repo,
         &space_name,
         &folder_name,
         &new_folder_name,
         folder_path,
+        on_collision.unwrap_or_default(),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    reindex_folder(
+        &state,
+        &fs_repo_lock,
+        space_name,
+        &child_path(folder_path, folder_name),
+    )
+    .await?;
+    Ok(folder)
+}
+
+/// The path of `name` inside `parent`, the space root if not given.
+fn child_path(parent: Option<&str>, name: &str) -> String {
+    match parent
+        .map(|p| p.trim_matches('/'))
+        .filter(|p| !p.is_empty())
+    {
+        Some(parent) => format!("{}/{}", parent, name),
+        None => name.to_string(),
+    }
+}
+
+/// Removes the documents of the notes that were inside `old_folder` and
+/// indexes the space again, so the moved notes, merged ones included, are
+/// found at their new path.
+async fn reindex_folder(
+    state: &AppState,
+    notes_repo: &FileSystemNoteRepository,
+    space_name: &str,
+    old_folder: &str,
+) -> Result<(), String> {
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    search::application::delete::delete_folder_documents_use_case(
+        &search_repo_lock,
+        &mut index_writer_lock,
+        space_name,
+        old_folder,
+    )
+    .map_err(|e| e.to_string())?;
+    search::application::index::reindex_space_use_case(
+        notes_repo,
+        &search_repo_lock,
+        &mut index_writer_lock,
+        space_name,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+#[tauri::command]
+pub async fn lock_folder_cmd(
+    repo: State<'_, FileSystemFolderRepository>,
+    space_name: &str,
+    folder_path: &str,
+) -> Result<Folder, String> {
+    update::set_folder_locked_use_case(&*repo, space_name, folder_path, true)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn unlock_folder_cmd(
+    repo: State<'_, FileSystemFolderRepository>,
+    space_name: &str,
+    folder_path: &str,
+) -> Result<Folder, String> {
+    update::set_folder_locked_use_case(&*repo, space_name, folder_path, false)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// # [UPDATE] Folder Metadata
+/// Replaces the icon, color, description, default template and collapsed
+/// state of a folder.
+/// ## Params
+/// * `folder_path`: The folder, the space root if not given.
+/// * `metadata`: The new metadata.
+#[tauri::command]
+pub async fn update_folder_metadata_cmd(
+    repo: State<'_, FileSystemFolderRepository>,
+    space_name: &str,
+    folder_path: Option<&str>,
+    metadata: FolderMetadata,
+) -> Result<Folder, String> {
+    update::update_folder_metadata_use_case(&*repo, space_name, folder_path, metadata)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn get_folder_ordering_cmd(
+    repo: State<'_, FileSystemFolderRepository>,
+    space_name: &str,
+    folder_path: Option<&str>,
+) -> Result<FolderOrdering, String> {
+    ordering::get_folder_ordering_use_case(&*repo, space_name, folder_path)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn set_folder_sort_cmd(
+    repo: State<'_, FileSystemFolderRepository>,
+    space_name: &str,
+    folder_path: Option<&str>,
+    sort: SortMode,
+) -> Result<FolderOrdering, String> {
+    ordering::set_folder_sort_use_case(&*repo, space_name, folder_path, sort)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn reorder_notes_cmd(
+    repo: State<'_, FileSystemFolderRepository>,
+    space_name: &str,
+    folder_path: Option<&str>,
+    order: Vec<String>,
+) -> Result<FolderOrdering, String> {
+    ordering::reorder_entries_use_case(&*repo, space_name, folder_path, EntryKind::Note, order)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn reorder_folders_cmd(
+    repo: State<'_, FileSystemFolderRepository>,
+    space_name: &str,
+    folder_path: Option<&str>,
+    order: Vec<String>,
+) -> Result<FolderOrdering, String> {
+    ordering::reorder_entries_use_case(&*repo, space_name, folder_path, EntryKind::Folder, order)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn pin_note_cmd(
+    repo: State<'_, FileSystemFolderRepository>,
+    space_name: &str,
+    note_name: &str,
+    folder_path: Option<&str>,
+) -> Result<FolderOrdering, String> {
+    ordering::set_entry_pinned_use_case(
+        &*repo,
+        space_name,
+        folder_path,
+        EntryKind::Note,
+        note_name,
+        true,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn unpin_note_cmd(
+    repo: State<'_, FileSystemFolderRepository>,
+    space_name: &str,
+    note_name: &str,
+    folder_path: Option<&str>,
+) -> Result<FolderOrdering, String> {
+    ordering::set_entry_pinned_use_case(
+        &*repo,
+        space_name,
+        folder_path,
+        EntryKind::Note,
+        note_name,
+        false,
     )
     .await
     .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn pin_folder_cmd(
+    repo: State<'_, FileSystemFolderRepository>,
+    space_name: &str,
+    folder_path: &str,
+) -> Result<FolderOrdering, String> {
+    let (parent, name) = ordering::split_folder_path(folder_path);
+    ordering::set_entry_pinned_use_case(&*repo, space_name, parent, EntryKind::Folder, name, true)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn unpin_folder_cmd(
+    repo: State<'_, FileSystemFolderRepository>,
+    space_name: &str,
+    folder_path: &str,
+) -> Result<FolderOrdering, String> {
+    let (parent, name) = ordering::split_folder_path(folder_path);
+    ordering::set_entry_pinned_use_case(&*repo, space_name, parent, EntryKind::Folder, name, false)
+        .await
+        .map_err(|e| e.to_string())
+}
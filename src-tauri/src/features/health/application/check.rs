@@ -0,0 +1,237 @@
+use std::collections::HashSet;
+
+use chrono::{TimeDelta, Utc};
+use tantivy::IndexWriter;
+
+use crate::features::{
+    attachments::domain::{
+        attachment::{ATTACHMENTS_DIR, ORPHAN_GRACE_HOURS},
+        repository::AttachmentRepository,
+    },
+    health::domain::{
+        errors::HealthError,
+        health::{case_duplicates, content_issue, IssueKind, SpaceHealth, SpaceIssue},
+        repository::HealthRepository,
+    },
+    notes::domain::{link::find_links, repository::NoteRepository},
+    search::{
+        domain::search::Searchable, infrastructure::search_repository::TantivySearchRepository,
+    },
+};
+
+/// # Check Space use case
+/// Scans a space for broken or empty notes, names colliding on
+/// case-insensitive filesystems, broken links, orphaned attachments,
+/// ignored and temporary files, and search index entries without a note.
+/// The auto-fix mode only fixes what can't lose anything: the temporary
+/// files are recovered or removed, the old orphaned attachments removed
+/// and the stale index entries deleted.
+/// ## Params
+/// * `health_repo`: Repository scanning the files of the space.
+/// * `notes_repo`: Repository of the notes.
+/// * `attachments_repo`: Repository of the attachments.
+/// * `search_repo`: The search index.
+/// * `index_writer`: The writer of the search index.
+/// * `space_name`: The space to check.
+/// * `fix`: Whether the safe fixes are applied.
+/// ## Result
+/// The problems found, with the fixed ones marked.
+pub async fn check_space_use_case<
+    H: HealthRepository,
+    N: NoteRepository,
+    A: AttachmentRepository,
+>(
+    health_repo: &H,
+    notes_repo: &N,
+    attachments_repo: &A,
+    search_repo: &TantivySearchRepository,
+    index_writer: &mut IndexWriter,
+    space_name: &str,
+    fix: bool,
+) -> Result<SpaceHealth, HealthError> {
+    let mut health = SpaceHealth {
+        space: space_name.to_string(),
+        ..Default::default()
+    };
+
+    // The integrity check knows which temporary file holds a lost save.
+    if fix {
+        let report = notes_repo.check_integrity(space_name).await?;
+        for note in report.recovered {
+            let mut issue = SpaceIssue::new(
+                IssueKind::StrayTempFile,
+                note,
+                "The note was restored from the temporary file of an interrupted save.",
+            )
+            .fixable();
+            issue.fixed = true;
+            health.issues.push(issue);
+        }
+        for temp_file in report.discarded {
+            let mut issue = SpaceIssue::new(
+                IssueKind::StrayTempFile,
+                temp_file,
+                "Removed, the note was intact.",
+            )
+            .fixable();
+            issue.fixed = true;
+            health.issues.push(issue);
+        }
+    }
+
+    let files = health_repo.get_files(space_name).await?;
+    for temp_file in files.temp_files {
+        let mut issue = SpaceIssue::new(
+            IssueKind::StrayTempFile,
+            temp_file,
+            "Left behind by an interrupted save.",
+        )
+        .fixable();
+        if fix {
+            health_repo
+                .remove_temp_file(space_name, &issue.path)
+                .await?;
+            issue.fixed = true;
+        }
+        health.issues.push(issue);
+    }
+    for ignored in files.ignored {
+        health.issues.push(SpaceIssue::new(
+            IssueKind::IgnoredFile,
+            ignored,
+            "Not a note, the app doesn't show it.",
+        ));
+    }
+    for group in case_duplicates(&files.entries) {
+        health.issues.push(SpaceIssue::new(
+            IssueKind::CaseDuplicate,
+            group[0].clone(),
+            format!(
+                "Same name as {} on case-insensitive filesystems.",
+                group[1..]
+                    .iter()
+                    .map(|entry| format!("'{}'", entry))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ));
+    }
+
+    let notes = notes_repo.get_notes(space_name).await?;
+    let note_keys: HashSet<(Option<String>, String)> = notes
+        .iter()
+        .map(|note| (normalize_folder(note.folder.as_deref()), note.name.clone()))
+        .collect();
+
+    let mut contents = Vec::new();
+    for note in &notes {
+        let note_path = match normalize_folder(note.folder.as_deref()) {
+            Some(folder) => format!("{}/{}", folder, note.name),
+            None => note.name.clone(),
+        };
+        let content = notes_repo
+            .get_note_content(space_name, &note.name, note.folder.as_deref())
+            .await?
+            .content
+            .unwrap_or_default();
+
+        match content_issue(&content) {
+            Some(IssueKind::EmptyNote) => health.issues.push(SpaceIssue::new(
+                IssueKind::EmptyNote,
+                note_path.clone(),
+                "The note has no blocks.",
+            )),
+            Some(kind) => health.issues.push(SpaceIssue::new(
+                kind,
+                note_path.clone(),
+                "The note isn't a valid Editor.js document.",
+            )),
+            None => {}
+        }
+
+        let mut reported = HashSet::new();
+        for link in find_links(&content) {
+            let target = (normalize_folder(link.folder.as_deref()), link.name);
+            if note_keys.contains(&target) || !reported.insert(target.clone()) {
+                continue;
+            }
+            let target_path = match &target.0 {
+                Some(folder) => format!("{}/{}", folder, target.1),
+                None => target.1.clone(),
+            };
+            health.issues.push(SpaceIssue::new(
+                IssueKind::BrokenLink,
+                note_path.clone(),
+                format!("Links to '{}', which doesn't exist.", target_path),
+            ));
+        }
+        contents.push(content);
+    }
+
+    // Recent attachments may belong to a note not saved yet.
+    let threshold = Utc::now() - TimeDelta::hours(ORPHAN_GRACE_HOURS);
+    for attachment in attachments_repo.get_attachments(space_name).await? {
+        if contents.iter().any(|c| c.contains(&attachment.hash)) {
+            continue;
+        }
+        let old_enough = attachment.modified.is_some_and(|m| m < threshold);
+        let mut issue = SpaceIssue::new(
+            IssueKind::OrphanedAttachment,
+            format!("{}/{}", ATTACHMENTS_DIR, attachment.id),
+            "No note references it.",
+        );
+        if old_enough {
+            issue = issue.fixable();
+            if fix {
+                attachments_repo
+                    .delete_attachment(space_name, &attachment.id)
+                    .await?;
+                issue.fixed = true;
+            }
+        }
+        health.issues.push(issue);
+    }
+
+    let routes: HashSet<String> = notes
+        .iter()
+        .map(|note| note.get_unique_id(space_name))
+        .collect();
+    let prefix = format!("{}/", space_name);
+    let mut deleted = false;
+    for route in search_repo.get_space_routes(space_name)? {
+        if routes.contains(&route) {
+            continue;
+        }
+        let mut issue = SpaceIssue::new(
+            IssueKind::StaleIndexEntry,
+            route.strip_prefix(&prefix).unwrap_or(&route),
+            "The search index has an entry for a note that doesn't exist.",
+        )
+        .fixable();
+        if fix {
+            search_repo.delete_document(index_writer, &route)?;
+            issue.fixed = true;
+            deleted = true;
+        }
+        health.issues.push(issue);
+    }
+    if deleted {
+        index_writer
+            .commit()
+            .map_err(|e| HealthError::Search(e.to_string()))?;
+    }
+
+    Ok(health)
+}
+
+/// A folder as `/` separated, `None` for the space root.
+fn normalize_folder(folder: Option<&str>) -> Option<String> {
+    folder
+        .map(|f| {
+            f.split(['/', '\\'])
+                .filter(|s| !s.is_empty())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .filter(|f| !f.is_empty())
+}
@@ -0,0 +1,46 @@
+//! # Errors
+//! Defines all error that could ocurr while checking a space.
+use std::io;
+
+use thiserror::Error;
+
+use crate::{
+    features::{
+        attachments::domain::errors::AttachmentError, notes::domain::errors::NoteError,
+        search::domain::error::SearchError,
+    },
+    shared::errors::app_errors::AppError,
+};
+
+/// # HealthError Enum
+/// Defines the enum with all the possible error that could ocurr while
+/// checking or fixing a space.
+/// ## Possible Errors
+/// * `InvalidPath`: A path to fix isn't a file of the space it can fix.
+/// * `Search`: The search index couldn't be read or updated.
+#[derive(Debug, Error)]
+pub enum HealthError {
+    #[error("Invalid path: {0}")]
+    InvalidPath(String),
+
+    #[error("Search index error: {0}")]
+    Search(String),
+
+    #[error(transparent)]
+    Note(#[from] NoteError),
+
+    #[error(transparent)]
+    Attachment(#[from] AttachmentError),
+
+    #[error("Health check IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    AppError(#[from] AppError),
+}
+
+impl From<SearchError> for HealthError {
+    fn from(err: SearchError) -> Self {
+        HealthError::Search(err.to_string())
+    }
+}
@@ -0,0 +1,218 @@
+//! # Health Domain
+//! Defines the problems found when checking a space and how the notes and
+//! the entries of a space are judged.
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::shared::utils::atomic_write::temp_target;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+/// # IssueKind
+/// The kinds of problems of a space.
+/// ## Variants
+/// * `InvalidNote`: A note that isn't an Editor.js document.
+/// * `EmptyNote`: A note without blocks.
+/// * `CaseDuplicate`: Entries of a folder whose names differ only by case,
+///   the same file on case-insensitive filesystems.
+/// * `BrokenLink`: A link to a note that doesn't exist.
+/// * `OrphanedAttachment`: An attachment no note references.
+/// * `IgnoredFile`: A file that isn't a note, the app doesn't show it.
+/// * `StrayTempFile`: A temporary file left by an interrupted save.
+/// * `StaleIndexEntry`: A search index entry whose note doesn't exist.
+pub enum IssueKind {
+    InvalidNote,
+    EmptyNote,
+    CaseDuplicate,
+    BrokenLink,
+    OrphanedAttachment,
+    IgnoredFile,
+    StrayTempFile,
+    StaleIndexEntry,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # SpaceIssue
+/// A problem found in a space.
+/// ## Fields
+/// * `kind`: The kind of problem.
+/// * `path`: The file, note or index entry, relative to the space and `/`
+///   separated.
+/// * `detail`: What's wrong, for the user.
+/// * `fixable`: Whether the auto-fix mode can fix it safely.
+/// * `fixed`: Whether it was fixed.
+pub struct SpaceIssue {
+    pub kind: IssueKind,
+    pub path: String,
+    pub detail: String,
+    pub fixable: bool,
+    pub fixed: bool,
+}
+
+impl SpaceIssue {
+    pub fn new(kind: IssueKind, path: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            kind,
+            path: path.into(),
+            detail: detail.into(),
+            fixable: false,
+            fixed: false,
+        }
+    }
+
+    /// The issue, marked as fixable.
+    pub fn fixable(self) -> Self {
+        Self {
+            fixable: true,
+            ..self
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+/// # SpaceHealth
+/// The result of checking a space.
+/// ## Fields
+/// * `space`: The checked space.
+/// * `issues`: The problems found, the fixed ones included.
+pub struct SpaceHealth {
+    pub space: String,
+    pub issues: Vec<SpaceIssue>,
+}
+
+impl SpaceHealth {
+    /// Whether every problem found was fixed.
+    pub fn is_healthy(&self) -> bool {
+        self.issues.iter().all(|issue| issue.fixed)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// # SpaceFiles
+/// The files of a space, as found on disk. Paths are relative to the space
+/// and `/` separated.
+/// ## Fields
+/// * `entries`: The visible files and folders, notes included.
+/// * `ignored`: The visible files that aren't notes.
+/// * `temp_files`: The temporary files of interrupted saves.
+pub struct SpaceFiles {
+    pub entries: Vec<String>,
+    pub ignored: Vec<String>,
+    pub temp_files: Vec<String>,
+}
+
+/// # Content issue
+/// What's wrong with the content of a note, if anything: not an Editor.js
+/// document (an object with a `blocks` list of typed blocks), or no blocks.
+pub fn content_issue(content: &str) -> Option<IssueKind> {
+    if content.trim().is_empty() {
+        return Some(IssueKind::EmptyNote);
+    }
+    let Ok(document) = serde_json::from_str::<Value>(content) else {
+        return Some(IssueKind::InvalidNote);
+    };
+    let blocks = document.get("blocks").and_then(Value::as_array);
+    match blocks {
+        None => Some(IssueKind::InvalidNote),
+        Some(blocks)
+            if !blocks
+                .iter()
+                .all(|block| block.get("type").is_some_and(Value::is_string)) =>
+        {
+            Some(IssueKind::InvalidNote)
+        }
+        Some(blocks) if blocks.is_empty() => Some(IssueKind::EmptyNote),
+        Some(_) => None,
+    }
+}
+
+/// # Case duplicates
+/// The groups of entries of a same folder whose names differ only by case.
+pub fn case_duplicates(entries: &[String]) -> Vec<Vec<String>> {
+    let mut groups: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for entry in entries {
+        groups
+            .entry(entry.to_lowercase())
+            .or_default()
+            .push(entry.clone());
+    }
+    groups
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .collect()
+}
+
+/// # Removable temp file
+/// Whether a `/` separated path of a space is a temporary file of a save,
+/// the only files the auto-fix removes.
+pub fn is_removable_temp_file(path: &str) -> bool {
+    let inside = !path.is_empty()
+        && path
+            .split(['/', '\\'])
+            .all(|part| !part.is_empty() && part != "." && part != ".." && !part.contains(':'));
+    inside && path.rsplit('/').next().and_then(temp_target).is_some()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn contents_are_judged() {
+        assert_eq!(content_issue(r#"{"blocks":[{"type":"paragraph"}]}"#), None);
+        assert_eq!(content_issue("  "), Some(IssueKind::EmptyNote));
+        assert_eq!(
+            content_issue(r#"{"blocks":[]}"#),
+            Some(IssueKind::EmptyNote)
+        );
+        for content in [
+            "not json",
+            r#"{"time":1}"#,
+            r#"{"blocks":{}}"#,
+            r#"{"blocks":[{"data":{}}]}"#,
+            r#"{"blocks":[{"type":1}]}"#,
+        ] {
+            assert_eq!(
+                content_issue(content),
+                Some(IssueKind::InvalidNote),
+                "{}",
+                content
+            );
+        }
+    }
+
+    #[test]
+    fn names_differing_by_case_are_grouped() {
+        let entries = ["Plan", "notes/a", "plan", "PLAN", "notes/A", "Other"].map(String::from);
+
+        assert_eq!(
+            case_duplicates(&entries),
+            vec![
+                vec!["notes/a".to_string(), "notes/A".to_string()],
+                vec!["Plan".to_string(), "plan".to_string(), "PLAN".to_string()],
+            ]
+        );
+        assert!(case_duplicates(&["a".to_string(), "b".to_string()]).is_empty());
+    }
+
+    #[test]
+    fn only_temp_files_are_removable() {
+        assert!(is_removable_temp_file(".plan.json.tmp"));
+        assert!(is_removable_temp_file("projects/.plan.json.12-3.tmp"));
+        assert!(is_removable_temp_file(".attachments/.image.png.12-3.tmp"));
+        for path in [
+            "plan.json",
+            "projects/plan.json.tmp",
+            ".plan.json",
+            "../.plan.json.tmp",
+            "projects/../.plan.json.tmp",
+            "/etc/.passwd.tmp",
+            "C:/.plan.json.tmp",
+            "",
+        ] {
+            assert!(!is_removable_temp_file(path), "{}", path);
+        }
+    }
+}
@@ -0,0 +1,23 @@
+//! # Repository
+//! Definition of how the files of a space are scanned and cleaned.
+use async_trait::async_trait;
+
+use super::{errors::HealthError, health::SpaceFiles};
+
+/// # HealthRepository
+/// Scans the files of a space for the problems the other repositories
+/// don't see.
+#[async_trait]
+pub trait HealthRepository {
+    /// # [GET] Files
+    /// Lists the files and folders of a space, the ignored and the
+    /// temporary ones included.
+    async fn get_files(&self, space_name: &str) -> Result<SpaceFiles, HealthError>;
+
+    /// # [DELETE] Temp file
+    /// Removes a temporary file of an interrupted save.
+    /// ## Fields
+    /// * `space_name`: The space of the file.
+    /// * `path`: The file, relative to the space and `/` separated.
+    async fn remove_temp_file(&self, space_name: &str, path: &str) -> Result<(), HealthError>;
+}
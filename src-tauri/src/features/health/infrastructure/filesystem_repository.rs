@@ -0,0 +1,105 @@
+//! # FileSystem Repository
+//! Walks the folders of a space for its entries, the files the app ignores
+//! and the temporary files left by interrupted saves.
+use std::{io::ErrorKind, path::Path};
+
+use async_trait::async_trait;
+use tokio::fs::{self, read_dir};
+
+use crate::{
+    features::{
+        attachments::domain::attachment::ATTACHMENTS_DIR,
+        health::domain::{
+            errors::HealthError,
+            health::{is_removable_temp_file, SpaceFiles},
+            repository::HealthRepository,
+        },
+        templates::infrastructure::filesystem_repository::SPACE_TEMPLATES_DIR,
+    },
+    shared::{
+        repositories::filesystem_repository::FileSystemRepository, utils::atomic_write::TMP_SUFFIX,
+    },
+};
+
+/// # FileSystemHealthRepository
+/// Implementation of the HealthRepository trait.
+#[derive(Clone)]
+pub struct FileSystemHealthRepository {
+    filesystem_repo: FileSystemRepository,
+}
+
+impl FileSystemHealthRepository {
+    pub fn new(filesystem_repo: FileSystemRepository) -> Self {
+        Self { filesystem_repo }
+    }
+}
+
+#[async_trait]
+impl HealthRepository for FileSystemHealthRepository {
+    async fn get_files(&self, space_name: &str) -> Result<SpaceFiles, HealthError> {
+        let space_path = self.filesystem_repo.get_space_path(space_name)?;
+        let relative = |path: &Path| {
+            path.strip_prefix(&space_path)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/")
+        };
+
+        let mut files = SpaceFiles::default();
+        let mut directories = vec![space_path.clone()];
+        while let Some(directory) = directories.pop() {
+            let mut entries = read_dir(&directory).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                let is_dir = entry.file_type().await?.is_dir();
+
+                if file_name.starts_with('.') {
+                    if !is_dir && file_name.ends_with(TMP_SUFFIX) {
+                        files.temp_files.push(relative(&path));
+                    }
+                    continue;
+                }
+                files.entries.push(relative(&path));
+                if is_dir {
+                    directories.push(path);
+                } else if !file_name.ends_with(".json") {
+                    files.ignored.push(relative(&path));
+                }
+            }
+        }
+
+        // The reserved folders are written with temporary files too.
+        for reserved in [ATTACHMENTS_DIR, SPACE_TEMPLATES_DIR] {
+            let mut entries = match read_dir(space_path.join(reserved)).await {
+                Ok(entries) => entries,
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(e.into()),
+            };
+            while let Some(entry) = entries.next_entry().await? {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                if file_name.starts_with('.')
+                    && file_name.ends_with(TMP_SUFFIX)
+                    && entry.file_type().await?.is_file()
+                {
+                    files.temp_files.push(relative(&entry.path()));
+                }
+            }
+        }
+
+        files.entries.sort();
+        files.ignored.sort();
+        files.temp_files.sort();
+        Ok(files)
+    }
+
+    async fn remove_temp_file(&self, space_name: &str, path: &str) -> Result<(), HealthError> {
+        if !is_removable_temp_file(path) {
+            return Err(HealthError::InvalidPath(path.to_string()));
+        }
+
+        let space_path = self.filesystem_repo.get_space_path(space_name)?;
+        fs::remove_file(space_path.join(path)).await?;
+        Ok(())
+    }
+}
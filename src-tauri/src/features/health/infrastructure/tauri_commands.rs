@@ -0,0 +1,49 @@
+//! # Tauri Commands [Health]
+//! Implementation of the command checking the health of a space to expose
+//! it on tauri for having it in the frontend application.
+use tauri::State;
+
+use crate::{
+    features::{
+        attachments::infrastructure::filesystem_repository::FileSystemAttachmentRepository,
+        health::{
+            application::check, domain::health::SpaceHealth,
+            infrastructure::filesystem_repository::FileSystemHealthRepository,
+        },
+    },
+    shared::state::state::AppState,
+};
+
+/// # [CHECK] Space
+/// Scans a space and reports its problems: broken or empty notes, names
+/// differing only by case, broken links, orphaned attachments, ignored and
+/// temporary files, and search index entries without a note.
+/// ## Params
+/// * `space_name`: The space to check.
+/// * `fix`: Whether the safe problems are fixed, `false` if not given.
+/// ## Result
+/// The problems found, with the fixed ones marked.
+#[tauri::command]
+pub async fn check_space_cmd(
+    state: State<'_, AppState>,
+    health_repo: State<'_, FileSystemHealthRepository>,
+    attachments_repo: State<'_, FileSystemAttachmentRepository>,
+    space_name: &str,
+    fix: Option<bool>,
+) -> Result<SpaceHealth, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    check::check_space_use_case(
+        &*health_repo,
+        &*fs_repo_lock,
+        &*attachments_repo,
+        &search_repo_lock,
+        &mut index_writer_lock,
+        space_name,
+        fix.unwrap_or(false),
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
@@ -0,0 +1,180 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+};
+
+use crate::features::{
+    attachments::domain::repository::{AttachmentRepository, ImageRepository},
+    folders::domain::repository::FolderRepository,
+    import::{
+        application::obsidian::{create_folders, prepare_space, store_file, write_note},
+        domain::{
+            errors::ImportError,
+            evernote::{parse_enex, ResourceResolver, ENEX_EXTENSION},
+            html::html_to_blocks,
+            import::{Embed, ImportReport},
+            repository::SourceRepository,
+        },
+    },
+    notes::{
+        application::create::create_named_note_use_case,
+        domain::{link::NoteLink, properties::NoteProperties, repository::NoteRepository},
+    },
+    space::domain::repository::SpaceRepository,
+};
+
+/// # Import Evernote Export
+/// Imports Evernote `.enex` exports into a space created when it doesn't
+/// exist. Every export is a notebook and gets a folder, the notebooks of a
+/// directory keep its hierarchy, so stacks can be kept as parent folders.
+/// The ENML of the notes is converted to Editor.js, with their checklists,
+/// and their resources are copied to the attachment store. The tags, dates,
+/// author and source of the notes become properties.
+/// ## Fields
+/// * `export`: An `.enex` file, or a directory or zip holding them.
+/// * `space_name`: The space receiving the notes, named after the export if
+///   `None`.
+/// ## Returns
+/// The report of the import, listing what couldn't be converted.
+#[doc(alias = "import_evernote_export")]
+#[allow(clippy::too_many_arguments)]
+pub async fn import_evernote_export_use_case<S, N, F, A, R>(
+    space_repo: &S,
+    notes_repo: &N,
+    folders_repo: &F,
+    attachments_repo: &A,
+    source_repo: &R,
+    export: &Path,
+    space_name: Option<&str>,
+) -> Result<ImportReport, ImportError>
+where
+    S: SpaceRepository,
+    N: NoteRepository,
+    F: FolderRepository,
+    A: AttachmentRepository + ImageRepository,
+    R: SourceRepository,
+{
+    let invalid_source = || ImportError::InvalidSource(export.display().to_string());
+    let (root, notebooks) = if is_enex(&export.to_string_lossy()) {
+        let file_name = export.file_name().ok_or_else(invalid_source)?;
+        let root = export.parent().ok_or_else(invalid_source)?;
+        (root, vec![file_name.to_string_lossy().to_string()])
+    } else {
+        let tree = source_repo.get_source_tree(export).await?;
+        let notebooks: Vec<String> = tree.files.into_iter().filter(|f| is_enex(f)).collect();
+        (export, notebooks)
+    };
+    if notebooks.is_empty() {
+        return Err(invalid_source());
+    }
+
+    let space_name = match space_name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => name.to_string(),
+        None => export
+            .file_stem()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(invalid_source)?,
+    };
+    let mut report = ImportReport::new(&space_name);
+
+    prepare_space(space_repo, &space_name).await?;
+    let mut folders = BTreeSet::new();
+    for notebook in &notebooks {
+        let folder = notebook_folder(notebook);
+        let segments: Vec<&str> = folder.split('/').collect();
+        for end in 1..=segments.len() {
+            folders.insert(segments[..end].join("/"));
+        }
+    }
+    let folders: Vec<String> = folders.into_iter().collect();
+    report.folders = create_folders(folders_repo, &space_name, &folders).await?;
+
+    for notebook in &notebooks {
+        let folder = notebook_folder(notebook);
+        let notes = match source_repo.read_source_file(root, notebook).await {
+            Ok(content) => parse_enex(&String::from_utf8_lossy(&content)),
+            Err(e) => Err(e.to_string()),
+        };
+        let notes = match notes {
+            Ok(notes) => notes,
+            Err(e) => {
+                report.issue(notebook, format!("Can't be read: {}", e));
+                continue;
+            }
+        };
+
+        for note in notes {
+            let source = format!("{}/{}", notebook, note.title);
+            let created =
+                create_named_note_use_case(notes_repo, &space_name, &note.name(), Some(&folder))
+                    .await;
+            let link = match created {
+                Ok(created) => NoteLink {
+                    folder: Some(folder.clone()),
+                    name: created.name,
+                    heading: None,
+                },
+                Err(e) => {
+                    report.issue(&source, format!("Not imported: {}", e));
+                    continue;
+                }
+            };
+
+            let mut embeds: HashMap<String, Embed> = HashMap::new();
+            for resource in &note.resources {
+                let file_name = resource.stored_name();
+                match store_file(
+                    attachments_repo,
+                    &space_name,
+                    &file_name,
+                    resource.data.clone(),
+                )
+                .await
+                {
+                    Ok(embed) => {
+                        embeds.insert(resource.hash.clone(), embed);
+                        report.attachments += 1;
+                    }
+                    Err(e) => report.issue(&source, format!("'{}' not copied: {}", file_name, e)),
+                }
+            }
+
+            let resolver = ResourceResolver { embeds: &embeds };
+            let converted = html_to_blocks(&note.content, &resolver);
+            for issue in converted.issues {
+                report.issue(&source, issue);
+            }
+            let properties = note.properties().normalize().unwrap_or_else(|e| {
+                report.issue(&source, format!("Properties not imported: {}", e));
+                NoteProperties::default()
+            });
+            match write_note(
+                notes_repo,
+                &space_name,
+                &link,
+                converted.blocks,
+                &properties,
+            )
+            .await
+            {
+                Ok(_) => report.notes += 1,
+                Err(e) => report.issue(&source, format!("Content not imported: {}", e)),
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Whether a file is an Evernote export.
+fn is_enex(path: &str) -> bool {
+    path.rsplit_once('.')
+        .is_some_and(|(_, extension)| extension.eq_ignore_ascii_case(ENEX_EXTENSION))
+}
+
+/// The folder of a notebook: the path of its export, without extension.
+fn notebook_folder(notebook: &str) -> String {
+    notebook
+        .rsplit_once('.')
+        .map_or(notebook, |(folder, _)| folder)
+        .to_string()
+}
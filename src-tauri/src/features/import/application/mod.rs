@@ -0,0 +1,3 @@
+pub mod evernote;
+pub mod notion;
+pub mod obsidian;
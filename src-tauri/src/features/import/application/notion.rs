@@ -0,0 +1,223 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    path::Path,
+};
+
+use crate::features::{
+    attachments::domain::repository::{AttachmentRepository, ImageRepository},
+    folders::domain::repository::FolderRepository,
+    import::{
+        application::obsidian::{
+            copy_file, create_folders, is_markdown, prepare_space, split_source_path, write_note,
+        },
+        domain::{
+            errors::ImportError,
+            import::{Embed, ImportReport, SourceIndex, SourceResolver},
+            markdown::{markdown_to_blocks, referenced_files},
+            notion::{
+                database_blocks, notion_folder, parse_csv, row_properties, split_properties,
+                strip_notion_id, strip_title, ALL_ROWS_SUFFIX, CSV_EXTENSION,
+            },
+            repository::SourceRepository,
+        },
+    },
+    notes::{
+        application::create::create_named_note_use_case,
+        domain::{link::NoteLink, properties::NoteProperties, repository::NoteRepository},
+    },
+    space::domain::repository::SpaceRepository,
+};
+
+/// # Import Notion Export
+/// Imports a Notion "Markdown & CSV" export, the zip or its extracted
+/// directory, into a space created when it doesn't exist. The page ids are
+/// removed from the names, the pages with subpages get a folder holding
+/// them, the databases become notes listing their rows and the rows keep
+/// their columns as properties. The links point to the imported notes and
+/// the embedded files are copied to the attachment store.
+/// ## Fields
+/// * `export`: The zip file or directory of the export.
+/// * `space_name`: The space receiving the notes, named after the export if
+///   `None`.
+/// ## Returns
+/// The report of the import, listing what couldn't be converted.
+#[doc(alias = "import_notion_export")]
+#[allow(clippy::too_many_arguments)]
+pub async fn import_notion_export_use_case<S, N, F, A, R>(
+    space_repo: &S,
+    notes_repo: &N,
+    folders_repo: &F,
+    attachments_repo: &A,
+    source_repo: &R,
+    export: &Path,
+    space_name: Option<&str>,
+) -> Result<ImportReport, ImportError>
+where
+    S: SpaceRepository,
+    N: NoteRepository,
+    F: FolderRepository,
+    A: AttachmentRepository + ImageRepository,
+    R: SourceRepository,
+{
+    let tree = source_repo.get_source_tree(export).await?;
+    let space_name = match space_name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => name.to_string(),
+        None => export
+            .file_stem()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| ImportError::InvalidSource(export.display().to_string()))?,
+    };
+    let mut report = ImportReport::new(&space_name);
+
+    prepare_space(space_repo, &space_name).await?;
+    let folders: Vec<String> = tree
+        .folders
+        .iter()
+        .map(|folder| notion_folder(folder))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    report.folders = create_folders(folders_repo, &space_name, &folders).await?;
+
+    // A database is exported twice when its view hides rows, the export
+    // with every row is kept.
+    let databases: Vec<&String> = tree
+        .files
+        .iter()
+        .filter(|path| is_csv(path))
+        .filter(|path| {
+            let stem = &path[..path.len() - CSV_EXTENSION.len() - 1];
+            stem.ends_with(ALL_ROWS_SUFFIX) || !tree.files.contains(&all_rows_path(stem))
+        })
+        .collect();
+    let mut notes = Vec::new();
+    for path in tree
+        .files
+        .iter()
+        .filter(|path| is_markdown(path) || databases.contains(path))
+    {
+        let (folder, name) = split_source_path(path);
+        let folder = folder.map(notion_folder);
+        let name = strip_notion_id(name.strip_suffix(ALL_ROWS_SUFFIX).unwrap_or(name));
+        match create_named_note_use_case(notes_repo, &space_name, name, folder.as_deref()).await {
+            Ok(note) => {
+                let link = NoteLink {
+                    folder,
+                    name: note.name,
+                    heading: None,
+                };
+                notes.push((path.to_string(), link));
+            }
+            Err(e) => report.issue(path, format!("Not imported: {}", e)),
+        }
+    }
+
+    // The rows of a database are the pages of the folder named like it.
+    let mut columns: HashMap<String, Vec<String>> = HashMap::new();
+    for (path, link) in notes.iter().filter(|(path, _)| is_csv(path)) {
+        let rows = match source_repo.read_source_file(export, path).await {
+            Ok(content) => parse_csv(&String::from_utf8_lossy(&content)),
+            Err(e) => {
+                report.issue(path, format!("Can't be read: {}", e));
+                continue;
+            }
+        };
+        let stem = &path[..path.len() - CSV_EXTENSION.len() - 1];
+        let folder = stem.strip_suffix(ALL_ROWS_SUFFIX).unwrap_or(stem);
+        columns.insert(
+            folder.to_string(),
+            rows.first().cloned().unwrap_or_default(),
+        );
+
+        match write_note(
+            notes_repo,
+            &space_name,
+            link,
+            database_blocks(&rows),
+            &NoteProperties::default(),
+        )
+        .await
+        {
+            Ok(_) => report.notes += 1,
+            Err(e) => report.issue(path, format!("Content not imported: {}", e)),
+        }
+    }
+
+    let files: Vec<&String> = tree
+        .files
+        .iter()
+        .filter(|path| !is_markdown(path) && !is_csv(path))
+        .collect();
+    let index = SourceIndex::new(notes.iter().cloned(), files.iter().map(|f| f.to_string()));
+    let mut embeds: HashMap<String, Embed> = HashMap::new();
+    for (path, link) in notes.iter().filter(|(path, _)| is_markdown(path)) {
+        let content = match source_repo.read_source_file(export, path).await {
+            Ok(content) => String::from_utf8_lossy(&content).to_string(),
+            Err(e) => {
+                report.issue(path, format!("Can't be read: {}", e));
+                continue;
+            }
+        };
+        let (source_folder, _) = split_source_path(path);
+        let mut body = strip_title(&content);
+        let mut properties = NoteProperties::default();
+        if let Some(columns) = source_folder.and_then(|folder| columns.get(folder)) {
+            let (row, rest) = split_properties(body, columns);
+            properties = row_properties(&row);
+            body = rest;
+        }
+
+        for target in referenced_files(body) {
+            let Some(file) = index.resolve_file(&target, source_folder) else {
+                continue;
+            };
+            if embeds.contains_key(&file) {
+                continue;
+            }
+            match copy_file(attachments_repo, source_repo, export, &space_name, &file).await {
+                Ok(embed) => {
+                    embeds.insert(file, embed);
+                    report.attachments += 1;
+                }
+                Err(e) => report.issue(&file, format!("Not copied: {}", e)),
+            }
+        }
+
+        let resolver = SourceResolver {
+            index: &index,
+            folder: source_folder,
+            embeds: &embeds,
+        };
+        // Notion has no inline `#tags`, the converted ones are ignored.
+        let converted = markdown_to_blocks(body, &resolver);
+        for issue in converted.issues {
+            report.issue(path, issue);
+        }
+        let properties = properties.normalize().unwrap_or_else(|e| {
+            report.issue(path, format!("Properties not imported: {}", e));
+            NoteProperties::default()
+        });
+        match write_note(notes_repo, &space_name, link, converted.blocks, &properties).await {
+            Ok(_) => report.notes += 1,
+            Err(e) => report.issue(path, format!("Content not imported: {}", e)),
+        }
+    }
+
+    for file in files {
+        if !embeds.contains_key(file.as_str()) {
+            report.issue(file, "Not referenced by any page, not copied.");
+        }
+    }
+    Ok(report)
+}
+
+/// Whether an exported file is a database.
+fn is_csv(path: &str) -> bool {
+    path.rsplit_once('.')
+        .is_some_and(|(_, extension)| extension.eq_ignore_ascii_case(CSV_EXTENSION))
+}
+
+/// The path of the export of a database with every row.
+fn all_rows_path(stem: &str) -> String {
+    format!("{}{}.{}", stem, ALL_ROWS_SUFFIX, CSV_EXTENSION)
+}
@@ -0,0 +1,289 @@
+use std::{collections::HashMap, io::ErrorKind, path::Path};
+
+use serde_json::Value;
+
+use crate::features::{
+    attachments::domain::{
+        attachment::{extension_of, AttachesPayload},
+        image::is_image_extension,
+        repository::{AttachmentRepository, ImageRepository},
+    },
+    folders::domain::{errors::FolderError, repository::FolderRepository},
+    import::domain::{
+        errors::ImportError,
+        import::{Embed, ImportReport, SourceIndex, SourceResolver, MARKDOWN_EXTENSION},
+        markdown::{markdown_to_blocks, referenced_files, to_document},
+        obsidian::{frontmatter_properties, split_frontmatter},
+        repository::SourceRepository,
+    },
+    notes::{
+        application::create::create_named_note_use_case,
+        domain::{
+            conflict::content_revision, errors::NoteError, link::NoteLink,
+            properties::NoteProperties, repository::NoteRepository,
+        },
+    },
+    space::domain::{errors::SpaceError, repository::SpaceRepository},
+};
+
+/// # Import Obsidian Vault
+/// Imports the notes of an Obsidian vault into a space, created when it
+/// doesn't exist. The folders of the vault are recreated, the Markdown notes
+/// are converted to Editor.js with their frontmatter as properties, the
+/// `[[wikilinks]]` point to the imported notes and the embedded files are
+/// copied to the attachment store. Hidden folders, like `.obsidian`, are
+/// skipped.
+/// ## Fields
+/// * `vault`: The directory of the vault.
+/// * `space_name`: The space receiving the notes, named after the vault if
+///   `None`.
+/// ## Returns
+/// The report of the import, listing what couldn't be converted.
+#[doc(alias = "import_obsidian_vault")]
+#[allow(clippy::too_many_arguments)]
+pub async fn import_obsidian_vault_use_case<S, N, F, A, R>(
+    space_repo: &S,
+    notes_repo: &N,
+    folders_repo: &F,
+    attachments_repo: &A,
+    source_repo: &R,
+    vault: &Path,
+    space_name: Option<&str>,
+) -> Result<ImportReport, ImportError>
+where
+    S: SpaceRepository,
+    N: NoteRepository,
+    F: FolderRepository,
+    A: AttachmentRepository + ImageRepository,
+    R: SourceRepository,
+{
+    let tree = source_repo.get_source_tree(vault).await?;
+    let space_name = match space_name.map(str::trim).filter(|n| !n.is_empty()) {
+        Some(name) => name.to_string(),
+        None => vault
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .ok_or_else(|| ImportError::InvalidSource(vault.display().to_string()))?,
+    };
+    let mut report = ImportReport::new(&space_name);
+
+    prepare_space(space_repo, &space_name).await?;
+    report.folders = create_folders(folders_repo, &space_name, &tree.folders).await?;
+
+    // Every note is created first, the links need their final names.
+    let (markdown, files): (Vec<&String>, Vec<&String>) =
+        tree.files.iter().partition(|path| is_markdown(path));
+    let mut notes = Vec::new();
+    for path in markdown {
+        let (folder, name) = split_source_path(path);
+        match create_named_note_use_case(notes_repo, &space_name, name, folder).await {
+            Ok(note) => {
+                if note.name != name {
+                    report.issue(
+                        path,
+                        format!("Imported as '{}', the name was taken.", note.name),
+                    );
+                }
+                let link = NoteLink {
+                    folder: folder.map(str::to_string),
+                    name: note.name,
+                    heading: None,
+                };
+                notes.push((path.to_string(), link));
+            }
+            Err(e) => report.issue(path, format!("Not imported: {}", e)),
+        }
+    }
+
+    let index = SourceIndex::new(notes.iter().cloned(), files.iter().map(|f| f.to_string()));
+    let mut embeds: HashMap<String, Embed> = HashMap::new();
+    for (path, link) in &notes {
+        let content = match source_repo.read_source_file(vault, path).await {
+            Ok(content) => String::from_utf8_lossy(&content).to_string(),
+            Err(e) => {
+                report.issue(path, format!("Can't be read: {}", e));
+                continue;
+            }
+        };
+        let (frontmatter, body) = split_frontmatter(&content);
+        let mut properties = NoteProperties::default();
+        if let Some(yaml) = frontmatter {
+            match frontmatter_properties(yaml) {
+                Ok((read, skipped)) => {
+                    properties = read;
+                    for key in skipped {
+                        report.issue(path, format!("Property '{}' can't be converted.", key));
+                    }
+                }
+                Err(e) => report.issue(path, format!("The frontmatter can't be read: {}", e)),
+            }
+        }
+
+        let folder = link.folder.as_deref();
+        for target in referenced_files(body) {
+            let Some(file) = index.resolve_file(&target, folder) else {
+                continue;
+            };
+            if embeds.contains_key(&file) {
+                continue;
+            }
+            match copy_file(attachments_repo, source_repo, vault, &space_name, &file).await {
+                Ok(embed) => {
+                    embeds.insert(file, embed);
+                    report.attachments += 1;
+                }
+                Err(e) => report.issue(&file, format!("Not copied: {}", e)),
+            }
+        }
+
+        let resolver = SourceResolver {
+            index: &index,
+            folder,
+            embeds: &embeds,
+        };
+        let converted = markdown_to_blocks(body, &resolver);
+        for issue in converted.issues {
+            report.issue(path, issue);
+        }
+        properties.tags.extend(converted.tags);
+        let properties = properties.normalize().unwrap_or_else(|e| {
+            report.issue(path, format!("Properties not imported: {}", e));
+            NoteProperties::default()
+        });
+
+        match write_note(notes_repo, &space_name, link, converted.blocks, &properties).await {
+            Ok(_) => report.notes += 1,
+            Err(e) => report.issue(path, format!("Content not imported: {}", e)),
+        }
+    }
+
+    for file in files {
+        if !embeds.contains_key(file.as_str()) {
+            report.issue(file, "Not referenced by any note, not copied.");
+        }
+    }
+    Ok(report)
+}
+
+/// Creates the space receiving an import, unless it exists.
+pub(super) async fn prepare_space<S: SpaceRepository>(
+    space_repo: &S,
+    space_name: &str,
+) -> Result<(), ImportError> {
+    match space_repo.get_space(space_name).await {
+        Ok(_) => Ok(()),
+        Err(SpaceError::NotFound(_)) => {
+            space_repo.create_space(space_name).await?;
+            Ok(())
+        }
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Recreates the imported folders, parents first, keeping the existing ones.
+/// ## Returns
+/// The number of folders created.
+pub(super) async fn create_folders<F: FolderRepository>(
+    folders_repo: &F,
+    space_name: &str,
+    folders: &[String],
+) -> Result<usize, ImportError> {
+    let mut created = 0;
+    for folder in folders {
+        let (parent, name) = match folder.rsplit_once('/') {
+            Some((parent, name)) => (Some(parent), name),
+            None => (None, folder.as_str()),
+        };
+        match folders_repo.create_folder(space_name, name, parent).await {
+            Ok(_) => created += 1,
+            Err(FolderError::Io(e)) if e.kind() == ErrorKind::AlreadyExists => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+    Ok(created)
+}
+
+/// Copies an imported file to the attachment store.
+pub(super) async fn copy_file<A, R>(
+    attachments_repo: &A,
+    source_repo: &R,
+    root: &Path,
+    space_name: &str,
+    path: &str,
+) -> Result<Embed, ImportError>
+where
+    A: AttachmentRepository + ImageRepository,
+    R: SourceRepository,
+{
+    let content = source_repo.read_source_file(root, path).await?;
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    store_file(attachments_repo, space_name, file_name, content).await
+}
+
+/// Stores an imported file in the attachment store, images go through the
+/// image pipeline.
+pub(super) async fn store_file<A>(
+    attachments_repo: &A,
+    space_name: &str,
+    file_name: &str,
+    content: Vec<u8>,
+) -> Result<Embed, ImportError>
+where
+    A: AttachmentRepository + ImageRepository,
+{
+    if is_image_extension(extension_of(file_name).as_deref()) {
+        // An image the pipeline can't decode is still kept, as a file.
+        if let Ok(image) = attachments_repo
+            .save_image(space_name, file_name, content.clone())
+            .await
+        {
+            return Ok(Embed::Image(image));
+        }
+    }
+    let attachment = attachments_repo
+        .save_attachment(space_name, file_name, &content)
+        .await?;
+    Ok(Embed::File(
+        AttachesPayload::new(&attachment, file_name).file,
+    ))
+}
+
+/// Writes the blocks and properties of an imported note.
+pub(super) async fn write_note<N: NoteRepository>(
+    notes_repo: &N,
+    space_name: &str,
+    link: &NoteLink,
+    blocks: Vec<Value>,
+    properties: &NoteProperties,
+) -> Result<(), NoteError> {
+    let document = to_document(blocks, properties)?;
+    notes_repo
+        .update_note_content(
+            space_name,
+            &link.name,
+            document.into_bytes(),
+            link.folder.as_deref(),
+            // The notes are created empty before being written.
+            &content_revision(""),
+        )
+        .await?;
+    Ok(())
+}
+
+/// Whether an imported file is a Markdown note.
+pub(super) fn is_markdown(path: &str) -> bool {
+    path.rsplit_once('.')
+        .is_some_and(|(_, extension)| extension.eq_ignore_ascii_case(MARKDOWN_EXTENSION))
+}
+
+/// The folder and the name, without extension, of an imported file.
+pub(super) fn split_source_path(path: &str) -> (Option<&str>, &str) {
+    let (folder, file_name) = match path.rsplit_once('/') {
+        Some((folder, file_name)) => (Some(folder), file_name),
+        None => (None, path),
+    };
+    let name = file_name
+        .rsplit_once('.')
+        .map_or(file_name, |(name, _)| name);
+    (folder, name)
+}
@@ -0,0 +1,49 @@
+//! # Errors
+//! Defines all error that could ocurr while importing notes from another
+//! application.
+use std::io;
+
+use thiserror::Error;
+use zip::result::ZipError;
+
+use crate::{
+    features::{
+        attachments::domain::errors::AttachmentError, folders::domain::errors::FolderError,
+        notes::domain::errors::NoteError, space::domain::errors::SpaceError,
+    },
+    shared::errors::app_errors::AppError,
+};
+
+/// # ImportError Enum
+/// Defines the enum with all the possible error that could ocurr during an
+/// import. The problems of a single note don't fail the import, they are
+/// listed in its report.
+/// ## Possible Errors
+/// * `InvalidSource`: The imported directory or file can't be read.
+/// * `Zip`: The imported zip file is damaged.
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("Invalid import source: {0}")]
+    InvalidSource(String),
+
+    #[error("Import IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error("Import zip error: {0}")]
+    Zip(#[from] ZipError),
+
+    #[error(transparent)]
+    Note(#[from] NoteError),
+
+    #[error(transparent)]
+    Folder(#[from] FolderError),
+
+    #[error(transparent)]
+    Space(#[from] SpaceError),
+
+    #[error(transparent)]
+    Attachment(#[from] AttachmentError),
+
+    #[error(transparent)]
+    AppError(#[from] AppError),
+}
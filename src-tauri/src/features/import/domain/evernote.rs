@@ -0,0 +1,369 @@
+//! # Evernote Domain
+//! Defines how the `.enex` exports of Evernote are read: every file is a
+//! notebook holding notes written in ENML, an XHTML dialect, and the
+//! resources they embed.
+use std::collections::HashMap;
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use chrono::{NaiveDate, NaiveDateTime};
+use md5::{Digest, Md5};
+use quick_xml::{escape::resolve_predefined_entity, events::Event, Reader};
+
+use super::import::{Embed, LinkResolver};
+use crate::features::notes::domain::properties::{NoteProperties, PropertyValue};
+
+/// Extension of the Evernote exports.
+pub const ENEX_EXTENSION: &str = "enex";
+
+/// Name given to the notes without a title.
+const UNTITLED: &str = "Untitled";
+
+#[derive(Debug, Clone, Default)]
+/// # EnexNote
+/// A note of an Evernote export.
+/// ## Fields
+/// * `title`: The title of the note.
+/// * `content`: The ENML content of the note.
+/// * `created`: The day the note was created.
+/// * `updated`: The day the note was last changed.
+/// * `tags`: The tags of the note.
+/// * `author`: Who wrote the note.
+/// * `source_url`: The page a web clip comes from.
+/// * `resources`: The files embedded by the note.
+pub struct EnexNote {
+    pub title: String,
+    pub content: String,
+    pub created: Option<NaiveDate>,
+    pub updated: Option<NaiveDate>,
+    pub tags: Vec<String>,
+    pub author: Option<String>,
+    pub source_url: Option<String>,
+    pub resources: Vec<EnexResource>,
+}
+
+impl EnexNote {
+    /// # Name
+    /// A note name from the title, without the characters a file name
+    /// can't hold.
+    pub fn name(&self) -> String {
+        let name: String = self
+            .title
+            .chars()
+            .map(|c| {
+                if c.is_control()
+                    || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|')
+                {
+                    ' '
+                } else {
+                    c
+                }
+            })
+            .collect();
+        let name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+        match name.trim_start_matches('.').trim() {
+            "" => UNTITLED.to_string(),
+            name => name.to_string(),
+        }
+    }
+
+    /// # Properties
+    /// The note properties from the tags, dates and attributes of the note.
+    pub fn properties(&self) -> NoteProperties {
+        let mut properties = NoteProperties {
+            tags: self.tags.clone(),
+            ..Default::default()
+        };
+        if let Some(created) = self.created {
+            properties.dates.insert("created".to_string(), created);
+        }
+        if let Some(updated) = self.updated {
+            properties.dates.insert("updated".to_string(), updated);
+        }
+        if let Some(author) = &self.author {
+            properties
+                .custom
+                .insert("author".to_string(), PropertyValue::Text(author.clone()));
+        }
+        if let Some(source_url) = &self.source_url {
+            properties.custom.insert(
+                "source".to_string(),
+                PropertyValue::Text(source_url.clone()),
+            );
+        }
+        properties
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+/// # EnexResource
+/// A file embedded by an Evernote note.
+/// ## Fields
+/// * `data`: The content of the file.
+/// * `mime`: The type of the file.
+/// * `file_name`: The original name of the file, when known.
+/// * `hash`: The MD5 of the content, the ENML refers to the file by it.
+pub struct EnexResource {
+    pub data: Vec<u8>,
+    pub mime: String,
+    pub file_name: Option<String>,
+    pub hash: String,
+}
+
+impl EnexResource {
+    /// The name the file is stored with: its original one or, without it,
+    /// the hash with an extension matching its type.
+    pub fn stored_name(&self) -> String {
+        let original = self
+            .file_name
+            .as_deref()
+            .and_then(|name| name.rsplit(['/', '\\']).next())
+            .map(str::trim)
+            .filter(|name| !name.is_empty() && !name.starts_with('.'));
+        match original {
+            Some(name) => name.to_string(),
+            None => format!("{}.{}", self.hash, mime_extension(&self.mime)),
+        }
+    }
+}
+
+/// # ResourceResolver
+/// Resolves the resources embedded by one Evernote note, by hash. Links
+/// between notes use `evernote:` URLs that can't be resolved.
+/// ## Fields
+/// * `embeds`: The copied resources of the note, by hash.
+pub struct ResourceResolver<'a> {
+    pub embeds: &'a HashMap<String, Embed>,
+}
+
+impl LinkResolver for ResourceResolver<'_> {
+    fn note_link(&self, _target: &str) -> Option<String> {
+        None
+    }
+
+    fn embed(&self, target: &str) -> Option<Embed> {
+        self.embeds.get(&target.to_lowercase()).cloned()
+    }
+}
+
+/// # Parse ENEX
+/// Reads the notes of an Evernote export.
+/// ## Returns
+/// The notes, or why the export can't be read.
+pub fn parse_enex(xml: &str) -> Result<Vec<EnexNote>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut notes = Vec::new();
+    let mut note: Option<EnexNote> = None;
+    let mut resource: Option<EnexResource> = None;
+    let mut text = String::new();
+
+    loop {
+        match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(element) => {
+                match element.local_name().as_ref() {
+                    b"note" => note = Some(EnexNote::default()),
+                    b"resource" if note.is_some() => resource = Some(EnexResource::default()),
+                    _ => {}
+                }
+                text.clear();
+            }
+            Event::Text(content) => text.push_str(&content.decode().map_err(|e| e.to_string())?),
+            Event::CData(content) => text.push_str(&content.decode().map_err(|e| e.to_string())?),
+            Event::GeneralRef(reference) => {
+                let name = reference.decode().map_err(|e| e.to_string())?;
+                match reference.resolve_char_ref().map_err(|e| e.to_string())? {
+                    Some(c) => text.push(c),
+                    None => match resolve_predefined_entity(&name) {
+                        Some(entity) => text.push_str(entity),
+                        None => text.push_str(&format!("&{};", name)),
+                    },
+                }
+            }
+            Event::End(element) => {
+                let name = String::from_utf8_lossy(element.local_name().as_ref()).to_string();
+                let value = std::mem::take(&mut text);
+                if let Some(mut current) = resource.take_if(|_| name == "resource") {
+                    // Evernote identifies the resources by their MD5.
+                    current.hash = format!("{:x}", Md5::digest(&current.data));
+                    if let Some(note) = &mut note {
+                        note.resources.push(current);
+                    }
+                } else if let Some(current) = &mut resource {
+                    match name.as_str() {
+                        "data" => {
+                            let encoded: String =
+                                value.chars().filter(|c| !c.is_whitespace()).collect();
+                            current.data = STANDARD
+                                .decode(encoded)
+                                .map_err(|e| format!("a resource can't be decoded: {}", e))?;
+                        }
+                        "mime" => current.mime = value.trim().to_string(),
+                        "file-name" => current.file_name = Some(value.trim().to_string()),
+                        _ => {}
+                    }
+                } else if let Some(current) = note.take_if(|_| name == "note") {
+                    notes.push(current);
+                } else if let Some(current) = &mut note {
+                    let value = value.trim();
+                    match name.as_str() {
+                        "title" => current.title = value.to_string(),
+                        "content" => current.content = value.to_string(),
+                        "created" => current.created = parse_enex_date(value),
+                        "updated" => current.updated = parse_enex_date(value),
+                        "tag" if !value.is_empty() => current.tags.push(value.to_string()),
+                        "author" if !value.is_empty() => current.author = Some(value.to_string()),
+                        "source-url" if !value.is_empty() => {
+                            current.source_url = Some(value.to_string())
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(notes)
+}
+
+/// A `20240102T030405Z` timestamp.
+fn parse_enex_date(text: &str) -> Option<NaiveDate> {
+    NaiveDateTime::parse_from_str(text.trim(), "%Y%m%dT%H%M%SZ")
+        .ok()
+        .map(|timestamp| timestamp.date())
+}
+
+/// The usual extension of a file type.
+fn mime_extension(mime: &str) -> &str {
+    match mime.split_once('/').map(|(_, subtype)| subtype) {
+        Some("jpeg") => "jpg",
+        Some("svg+xml") => "svg",
+        Some("plain") => "txt",
+        Some("mpeg") => "mp3",
+        Some(subtype) if !subtype.is_empty() && subtype.chars().all(char::is_alphanumeric) => {
+            subtype
+        }
+        _ => "bin",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::{
+        attachments::domain::attachment::AttachesFile, import::domain::html::html_to_blocks,
+    };
+
+    const ENEX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE en-export SYSTEM "http://xml.evernote.com/pub/evernote-export4.dtd">
+<en-export>
+  <note>
+    <title>Trip: Paris/Rome</title>
+    <content><![CDATA[<en-note><div><en-todo checked="true"/>Book &amp; pay</div><en-media hash="5d41402abc4b2a76b9719d911017c592" type="text/plain"/></en-note>]]></content>
+    <created>20240102T030405Z</created>
+    <updated>20240305T000000Z</updated>
+    <tag>travel</tag>
+    <note-attributes>
+      <author>Ada</author>
+      <source-url>https://example.com</source-url>
+    </note-attributes>
+    <resource>
+      <data encoding="base64">aGVs
+bG8=</data>
+      <mime>text/plain</mime>
+    </resource>
+  </note>
+  <note>
+    <title> .. </title>
+    <content><![CDATA[<en-note/>]]></content>
+  </note>
+</en-export>"#;
+
+    #[test]
+    fn parses_notes_and_resources() {
+        let notes = parse_enex(ENEX).unwrap();
+        assert_eq!(notes.len(), 2);
+
+        let note = &notes[0];
+        assert_eq!(note.name(), "Trip Paris Rome");
+        assert_eq!(note.created, NaiveDate::from_ymd_opt(2024, 1, 2));
+        assert_eq!(note.updated, NaiveDate::from_ymd_opt(2024, 3, 5));
+        assert_eq!(note.tags, vec!["travel"]);
+        assert!(note.content.contains("en-todo"));
+
+        let resource = &note.resources[0];
+        assert_eq!(resource.data, b"hello");
+        assert_eq!(resource.hash, "5d41402abc4b2a76b9719d911017c592");
+        assert_eq!(resource.stored_name(), format!("{}.txt", resource.hash));
+
+        assert_eq!(notes[1].name(), UNTITLED);
+        assert!(parse_enex("<en-export><note><title>x</title></en-export>").is_err());
+    }
+
+    #[test]
+    fn attributes_become_properties() {
+        let notes = parse_enex(ENEX).unwrap();
+        let properties = notes[0].properties();
+        assert_eq!(properties.tags, vec!["travel"]);
+        assert_eq!(
+            properties.dates.get("created"),
+            NaiveDate::from_ymd_opt(2024, 1, 2).as_ref()
+        );
+        assert_eq!(
+            properties.custom.get("author"),
+            Some(&PropertyValue::Text("Ada".to_string()))
+        );
+        assert_eq!(
+            properties.custom.get("source"),
+            Some(&PropertyValue::Text("https://example.com".to_string()))
+        );
+    }
+
+    #[test]
+    fn resources_keep_their_original_name() {
+        let resource = EnexResource {
+            mime: "image/jpeg".to_string(),
+            file_name: Some("scans/receipt.jpg".to_string()),
+            hash: "abc".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(resource.stored_name(), "receipt.jpg");
+
+        let unnamed = EnexResource {
+            file_name: Some(".hidden".to_string()),
+            ..resource
+        };
+        assert_eq!(unnamed.stored_name(), "abc.jpg");
+    }
+
+    #[test]
+    fn enml_is_converted_with_its_resources() {
+        let notes = parse_enex(ENEX).unwrap();
+        let hash = notes[0].resources[0].hash.clone();
+        let file = AttachesFile {
+            url: "attachments/hello.txt".to_string(),
+            name: "hello.txt".to_string(),
+            size: 5,
+            extension: Some("txt".to_string()),
+            title: "hello.txt".to_string(),
+            id: "hello".to_string(),
+        };
+        let embeds = HashMap::from([(hash, Embed::File(file))]);
+
+        let converted = html_to_blocks(&notes[0].content, &ResourceResolver { embeds: &embeds });
+        let types: Vec<&str> = converted
+            .blocks
+            .iter()
+            .map(|b| b["type"].as_str().unwrap())
+            .collect();
+        assert_eq!(types, vec!["list", "attaches"]);
+
+        let checklist = &converted.blocks[0]["data"];
+        assert_eq!(checklist["style"], "checklist");
+        assert_eq!(checklist["items"][0]["content"], "Book &amp; pay");
+        assert_eq!(checklist["items"][0]["meta"]["checked"], true);
+        assert_eq!(
+            converted.blocks[1]["data"]["file"]["url"],
+            "attachments/hello.txt"
+        );
+    }
+}
@@ -0,0 +1,456 @@
+//! # HTML Domain
+//! Converts HTML notes, like the ENML of Evernote, to the Editor.js blocks
+//! of the note editor. Evernote checkboxes (`<en-todo>`) become checklists
+//! and its resources (`<en-media>`) images or attaches.
+use std::sync::LazyLock;
+
+use regex::Regex;
+use scraper::{ElementRef, Html, Node};
+use serde_json::{json, Value};
+
+use super::{
+    import::LinkResolver,
+    markdown::{block, embed_block, is_external, list_block, ConvertedNote, ListItem},
+};
+use crate::shared::utils::html::escape_html;
+
+/// Matches the self-closing Evernote elements, which an HTML parser would
+/// otherwise keep open around the rest of the note.
+static SELF_CLOSING: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"<(en-todo|en-media|en-crypt)\b([^>]*?)\s*/>").unwrap());
+
+/// Elements holding their own block of text.
+const TEXT_BLOCKS: [&str; 12] = [
+    "p", "div", "section", "article", "header", "footer", "center", "address", "figure", "dl",
+    "dt", "dd",
+];
+
+/// # HTML to blocks
+/// Converts an HTML note to Editor.js blocks, the resources it embeds are
+/// found by the resolver from their `src` or, in ENML, their hash.
+pub fn html_to_blocks<R: LinkResolver>(html: &str, resolver: &R) -> ConvertedNote {
+    let html = SELF_CLOSING.replace_all(html, "<$1$2></$1>");
+    let fragment = Html::parse_fragment(&html);
+    let mut converter = HtmlConverter {
+        resolver,
+        output: ConvertedNote::default(),
+        text: String::new(),
+        captures: Vec::new(),
+        todo: None,
+        checklist: Vec::new(),
+    };
+    converter.walk(fragment.root_element());
+    converter.flush();
+    converter.end_checklist();
+    converter.output
+}
+
+/// Walks the HTML elements, building the blocks.
+struct HtmlConverter<'r, R: LinkResolver> {
+    resolver: &'r R,
+    output: ConvertedNote,
+    text: String,
+    /// The lines of the list items, quotes and table cells being read.
+    captures: Vec<Vec<String>>,
+    /// The checkbox of the text being read.
+    todo: Option<bool>,
+    /// The checked items read since the last block.
+    checklist: Vec<Value>,
+}
+
+impl<R: LinkResolver> HtmlConverter<'_, R> {
+    fn walk(&mut self, element: ElementRef) {
+        for child in element.children() {
+            match child.value() {
+                Node::Text(text) => self.push_text(text),
+                Node::Element(_) => {
+                    if let Some(child) = ElementRef::wrap(child) {
+                        self.element(child);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn element(&mut self, element: ElementRef) {
+        let name = element.value().name();
+        match name {
+            "br" => self.text.push_str("<br>"),
+            "b" | "strong" => self.wrap(element, "<b>", "</b>"),
+            "i" | "em" => self.wrap(element, "<i>", "</i>"),
+            "s" | "strike" | "del" => self.wrap(element, "<s>", "</s>"),
+            "span" | "font" => {
+                let style = style_of(element);
+                let tags: Vec<(&str, &str)> = [
+                    (style.contains("font-weight:bold") || style.contains("font-weight:700"))
+                        .then_some(("<b>", "</b>")),
+                    style
+                        .contains("font-style:italic")
+                        .then_some(("<i>", "</i>")),
+                    style.contains("line-through").then_some(("<s>", "</s>")),
+                ]
+                .into_iter()
+                .flatten()
+                .collect();
+                for (open, _) in &tags {
+                    self.text.push_str(open);
+                }
+                self.walk(element);
+                for (_, close) in tags.iter().rev() {
+                    self.text.push_str(close);
+                }
+            }
+            "code" | "kbd" | "samp" => {
+                let code: String = element.text().collect();
+                self.text.push_str(&format!(
+                    "<code class=\"inline-code\">{}</code>",
+                    escape_html(&code)
+                ));
+            }
+            "a" => match element.value().attr("href") {
+                Some(href) if href.starts_with("evernote:") => {
+                    self.issue("Links between Evernote notes are kept as text.");
+                    self.walk(element);
+                }
+                Some(href) if is_external(href) => {
+                    self.text
+                        .push_str(&format!("<a href=\"{}\">", escape_html(href)));
+                    self.walk(element);
+                    self.text.push_str("</a>");
+                }
+                Some(href) => match self.resolver.note_link(href) {
+                    Some(href) => {
+                        self.text
+                            .push_str(&format!("<a href=\"{}\">", escape_html(&href)));
+                        self.walk(element);
+                        self.text.push_str("</a>");
+                    }
+                    None => {
+                        self.issue(&format!("Link to the missing note or file '{}'.", href));
+                        self.walk(element);
+                    }
+                },
+                None => self.walk(element),
+            },
+            "en-todo" => {
+                self.todo = Some(element.value().attr("checked") == Some("true"));
+            }
+            "en-media" => {
+                let target = element.value().attr("hash").unwrap_or_default();
+                self.embed(target);
+            }
+            "img" => {
+                let src = element.value().attr("src").unwrap_or_default();
+                if is_external(src) && !src.starts_with("data:") {
+                    self.issue("Remote images are kept as links.");
+                    let label = element.value().attr("alt").unwrap_or(src);
+                    self.push_anchor(src, label);
+                } else if src.starts_with("data:") {
+                    self.issue("Images inside the HTML aren't imported.");
+                } else {
+                    self.embed(src);
+                }
+            }
+            "en-crypt" => self.issue("Encrypted text isn't imported."),
+            "script" | "style" | "head" | "title" | "object" => {}
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.flush();
+                self.walk(element);
+                let text = trim_breaks(&std::mem::take(&mut self.text)).to_string();
+                if text.is_empty() {
+                    return;
+                }
+                match self.captures.last_mut() {
+                    Some(lines) => lines.push(text),
+                    None => {
+                        let level: usize = name[1..].parse().unwrap_or(1);
+                        self.push_block(block("header", json!({ "text": text, "level": level })));
+                    }
+                }
+            }
+            "ul" | "ol" => {
+                self.flush();
+                let (style, items) = self.list(element);
+                match self.captures.last_mut() {
+                    Some(lines) => {
+                        lines.extend(items.iter().map(|item| {
+                            format!("• {}", item["content"].as_str().unwrap_or_default())
+                        }))
+                    }
+                    None if !items.is_empty() => self.push_block(list_block(style, items)),
+                    None => {}
+                }
+            }
+            "blockquote" => {
+                self.flush();
+                let lines = self.capture_checked(|converter| converter.walk(element));
+                match self.captures.last_mut() {
+                    Some(parent) => parent.extend(lines),
+                    None if !lines.is_empty() => {
+                        let data = json!({ "text": lines.join("<br>"), "caption": "", "alignment": "left" });
+                        self.push_block(block("quote", data));
+                    }
+                    None => {}
+                }
+            }
+            "table" => {
+                self.flush();
+                self.issue("Tables are kept as paragraphs.");
+                let rows: Vec<ElementRef> = element
+                    .descendants()
+                    .filter_map(ElementRef::wrap)
+                    .filter(|e| e.value().name() == "tr")
+                    .collect();
+                for row in rows {
+                    let cells: Vec<String> = row
+                        .children()
+                        .filter_map(ElementRef::wrap)
+                        .filter(|e| matches!(e.value().name(), "td" | "th"))
+                        .map(|cell| {
+                            self.capture_checked(|converter| converter.walk(cell))
+                                .join(" ")
+                        })
+                        .collect();
+                    self.text = cells.join(" | ");
+                    self.flush();
+                }
+            }
+            "hr" => {
+                self.flush();
+                if self.captures.is_empty() {
+                    self.push_block(block("delimiter", json!({})));
+                }
+            }
+            "pre" => self.code_block(element),
+            "div" if style_of(element).contains("-en-codeblock:true") => self.code_block(element),
+            name if TEXT_BLOCKS.contains(&name) => {
+                self.flush();
+                self.walk(element);
+                self.flush();
+            }
+            _ => self.walk(element),
+        }
+    }
+
+    fn wrap(&mut self, element: ElementRef, open: &str, close: &str) {
+        self.text.push_str(open);
+        self.walk(element);
+        self.text.push_str(close);
+    }
+
+    /// Reads the items of a list, the nested lists become their children.
+    fn list(&mut self, list: ElementRef) -> (&'static str, Vec<Value>) {
+        let mut style = match list.value().name() {
+            "ol" => "ordered",
+            _ if style_of(list).contains("--en-todo:true") => "checklist",
+            _ => "unordered",
+        };
+        let mut items: Vec<Value> = Vec::new();
+        for child in list.children().filter_map(ElementRef::wrap) {
+            if matches!(child.value().name(), "ul" | "ol") {
+                // A list right inside another one belongs to its last item.
+                let (_, nested) = self.list(child);
+                match items
+                    .last_mut()
+                    .and_then(|item| item["items"].as_array_mut())
+                {
+                    Some(children) => children.extend(nested),
+                    None => items.extend(nested),
+                }
+                continue;
+            }
+
+            let mut item = ListItem::default();
+            let lines = self.capture(|converter| {
+                for node in child.children() {
+                    match ElementRef::wrap(node) {
+                        Some(e) if matches!(e.value().name(), "ul" | "ol") => {
+                            converter.flush();
+                            item.children.extend(converter.list(e).1);
+                        }
+                        Some(e) => converter.element(e),
+                        None => {
+                            if let Node::Text(text) = node.value() {
+                                converter.push_text(text);
+                            }
+                        }
+                    }
+                }
+            });
+            item.content = lines.join("<br>");
+            if style == "checklist" {
+                item.checked = Some(style_of(child).contains("--en-checked:true"));
+            } else if let Some(checked) = self.todo.take() {
+                style = "checklist";
+                item.checked = Some(checked);
+            }
+            items.push(item.into_value());
+        }
+        (style, items)
+    }
+
+    /// Reads the lines of a list item, quote or table cell, instead of
+    /// adding their text as blocks.
+    fn capture(&mut self, read: impl FnOnce(&mut Self)) -> Vec<String> {
+        self.captures.push(Vec::new());
+        read(self);
+        self.flush();
+        self.captures.pop().unwrap_or_default()
+    }
+
+    /// Reads the lines of a quote or table cell, which can't hold a
+    /// checklist: their checkbox is kept as a symbol.
+    fn capture_checked(&mut self, read: impl FnOnce(&mut Self)) -> Vec<String> {
+        let mut lines = self.capture(read);
+        if let (Some(checked), Some(first)) = (self.todo.take(), lines.first_mut()) {
+            let checkbox = if checked { "☑" } else { "☐" };
+            *first = format!("{} {}", checkbox, first);
+        }
+        lines
+    }
+
+    fn code_block(&mut self, element: ElementRef) {
+        self.flush();
+        self.issue("Code blocks are kept as paragraphs.");
+        let mut code = String::new();
+        code_text(element, &mut code);
+        self.text = format!(
+            "<code class=\"inline-code\">{}</code>",
+            escape_html(code.trim_matches('\n')).replace('\n', "<br>")
+        );
+        self.flush();
+    }
+
+    /// Adds an embedded resource: an image or attaches block, or a link
+    /// when it can't be a block of its own.
+    fn embed(&mut self, target: &str) {
+        let Some(embed) = self.resolver.embed(target) else {
+            self.issue(&format!("Missing embedded file '{}'.", target));
+            return;
+        };
+        if !self.captures.is_empty() {
+            let name = embed
+                .url()
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .to_string();
+            self.push_anchor(embed.url(), &name);
+            return;
+        }
+        self.flush();
+        self.push_block(embed_block(embed, String::new()));
+    }
+
+    fn push_text(&mut self, text: &str) {
+        // The layout whitespace of the HTML is collapsed, like a browser does.
+        let mut collapsed = String::with_capacity(text.len());
+        let mut space = self.text.is_empty() || self.text.ends_with(' ');
+        for c in text.chars() {
+            if c.is_whitespace() && c != '\u{a0}' {
+                if !space {
+                    collapsed.push(' ');
+                }
+                space = true;
+            } else {
+                collapsed.push(c);
+                space = false;
+            }
+        }
+        self.text.push_str(&escape_html(&collapsed));
+    }
+
+    fn push_anchor(&mut self, href: &str, label: &str) {
+        self.text.push_str(&format!(
+            "<a href=\"{}\">{}</a>",
+            escape_html(href),
+            escape_html(label)
+        ));
+    }
+
+    fn push_block(&mut self, block: Value) {
+        self.end_checklist();
+        self.output.blocks.push(block);
+    }
+
+    fn issue(&mut self, message: &str) {
+        if !self.output.issues.iter().any(|i| i == message) {
+            self.output.issues.push(message.to_string());
+        }
+    }
+
+    /// Ends the text being read: a paragraph, a checklist item or a line of
+    /// the open capture.
+    fn flush(&mut self) {
+        let text = std::mem::take(&mut self.text);
+        let text = trim_breaks(&text);
+        if text.is_empty() {
+            return;
+        }
+        if let Some(lines) = self.captures.last_mut() {
+            lines.push(text.to_string());
+        } else if let Some(checked) = self.todo.take() {
+            let item = ListItem {
+                content: text.to_string(),
+                checked: Some(checked),
+                ..Default::default()
+            };
+            self.checklist.push(item.into_value());
+        } else {
+            self.push_block(block("paragraph", json!({ "text": text })));
+        }
+    }
+
+    /// Adds the checked items read since the last block as a checklist.
+    fn end_checklist(&mut self) {
+        if !self.checklist.is_empty() {
+            let items = std::mem::take(&mut self.checklist);
+            self.output.blocks.push(list_block("checklist", items));
+        }
+    }
+}
+
+/// The inline style of an element, without spaces.
+fn style_of(element: ElementRef) -> String {
+    element
+        .value()
+        .attr("style")
+        .unwrap_or_default()
+        .replace(char::is_whitespace, "")
+        .to_lowercase()
+}
+
+/// The text of a code block, its lines kept.
+fn code_text(element: ElementRef, code: &mut String) {
+    for child in element.children() {
+        match child.value() {
+            Node::Text(text) => code.push_str(text),
+            Node::Element(e) if e.name() == "br" => code.push('\n'),
+            Node::Element(_) => {
+                if let Some(child) = ElementRef::wrap(child) {
+                    code_text(child, code);
+                    if TEXT_BLOCKS.contains(&child.value().name()) && !code.ends_with('\n') {
+                        code.push('\n');
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Trims the whitespace and the line breaks around a text.
+fn trim_breaks(text: &str) -> &str {
+    let mut text = text.trim();
+    loop {
+        let trimmed = text
+            .trim_start_matches("<br>")
+            .trim_end_matches("<br>")
+            .trim();
+        if trimmed.len() == text.len() {
+            return text;
+        }
+        text = trimmed;
+    }
+}
@@ -0,0 +1,213 @@
+//! # Import Domain
+//! Defines the result of importing notes from another application and what
+//! the converters need to know about the imported files.
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::features::{
+    attachments::domain::{attachment::AttachesFile, image::ImageFile},
+    notes::domain::link::NoteLink,
+};
+
+/// Extension of the Markdown notes.
+pub const MARKDOWN_EXTENSION: &str = "md";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+/// # ImportReport
+/// What an import did.
+/// ## Fields
+/// * `space`: The space the notes were imported into.
+/// * `notes`: Number of notes imported.
+/// * `folders`: Number of folders created.
+/// * `attachments`: Number of files copied to the attachment store.
+/// * `issues`: Everything that couldn't be converted as it was.
+pub struct ImportReport {
+    pub space: String,
+    pub notes: usize,
+    pub folders: usize,
+    pub attachments: usize,
+    pub issues: Vec<ImportIssue>,
+}
+
+impl ImportReport {
+    pub fn new(space: &str) -> Self {
+        Self {
+            space: space.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Records an issue of an imported file.
+    pub fn issue(&mut self, file: &str, message: impl Into<String>) {
+        self.issues.push(ImportIssue {
+            file: file.to_string(),
+            message: message.into(),
+        });
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # ImportIssue
+/// Something of an imported file that couldn't be converted as it was.
+/// ## Fields
+/// * `file`: The imported file, relative to the imported directory.
+/// * `message`: What happened.
+pub struct ImportIssue {
+    pub file: String,
+    pub message: String,
+}
+
+#[derive(Debug, Clone)]
+/// # Embed
+/// A file embedded in a note, once copied to the attachment store.
+/// ## Variants
+/// * `Image`: Shown with an image block.
+/// * `File`: Shown with an attaches block.
+pub enum Embed {
+    Image(ImageFile),
+    File(AttachesFile),
+}
+
+impl Embed {
+    /// The path of the stored file.
+    pub fn url(&self) -> &str {
+        match self {
+            Embed::Image(file) => &file.url,
+            Embed::File(file) => &file.url,
+        }
+    }
+}
+
+/// # Link Resolver
+/// Finds the targets of the links and embeds of the imported notes.
+pub trait LinkResolver {
+    /// The `href` of a link to another imported note, `None` when the target
+    /// isn't one.
+    fn note_link(&self, target: &str) -> Option<String>;
+
+    /// The copied file embedded by a note.
+    fn embed(&self, target: &str) -> Option<Embed>;
+}
+
+/// # SourceIndex
+/// The notes and files of an imported directory, to find the targets of
+/// the links by path or, like Obsidian does, by name alone. Lookups ignore
+/// the case and the Markdown extension.
+#[derive(Debug, Default)]
+pub struct SourceIndex {
+    notes: BTreeMap<String, NoteLink>,
+    files: BTreeMap<String, String>,
+}
+
+impl SourceIndex {
+    /// Indexes the imported notes, by their `/` separated source path, and
+    /// the other files.
+    pub fn new(
+        notes: impl IntoIterator<Item = (String, NoteLink)>,
+        files: impl IntoIterator<Item = String>,
+    ) -> Self {
+        Self {
+            notes: notes
+                .into_iter()
+                .map(|(path, link)| (note_key(&path), link))
+                .collect(),
+            files: files
+                .into_iter()
+                .map(|path| (path.to_lowercase(), path))
+                .collect(),
+        }
+    }
+
+    /// # Resolve note
+    /// The note a link points to from a note of `folder`, with the heading
+    /// after `#`.
+    pub fn resolve_note(&self, target: &str, folder: Option<&str>) -> Option<NoteLink> {
+        let (path, heading) = match target.split_once('#') {
+            Some((path, heading)) => (path, Some(heading)),
+            None => (target, None),
+        };
+        let key = note_key(&relative_to(path, folder)?);
+        let mut link = lookup(&self.notes, &key)?.clone();
+        // Block references (`#^id`) have no equivalent, they link the note.
+        link.heading = heading
+            .map(str::trim)
+            .filter(|h| !h.is_empty() && !h.starts_with('^'))
+            .map(str::to_string);
+        Some(link)
+    }
+
+    /// # Resolve file
+    /// The source path of the file a note of `folder` embeds or links.
+    pub fn resolve_file(&self, target: &str, folder: Option<&str>) -> Option<String> {
+        let key = relative_to(target, folder)?.to_lowercase();
+        lookup(&self.files, &key).cloned()
+    }
+}
+
+/// # SourceResolver
+/// Resolves the links of one imported note.
+/// ## Fields
+/// * `index`: The imported notes and files.
+/// * `folder`: The source folder of the note.
+/// * `embeds`: The copied files, by source path.
+pub struct SourceResolver<'a> {
+    pub index: &'a SourceIndex,
+    pub folder: Option<&'a str>,
+    pub embeds: &'a HashMap<String, Embed>,
+}
+
+impl LinkResolver for SourceResolver<'_> {
+    fn note_link(&self, target: &str) -> Option<String> {
+        self.index
+            .resolve_note(target, self.folder)
+            .map(|link| link.href())
+    }
+
+    fn embed(&self, target: &str) -> Option<Embed> {
+        let path = self.index.resolve_file(target, self.folder)?;
+        self.embeds.get(&path).cloned()
+    }
+}
+
+/// Lookup key of a note path: lowercased, without the Markdown extension.
+fn note_key(path: &str) -> String {
+    let path = path.to_lowercase();
+    let suffix = format!(".{}", MARKDOWN_EXTENSION);
+    path.strip_suffix(&suffix).unwrap_or(&path).to_string()
+}
+
+/// Finds a key, or else the shortest one ending with `/key`.
+fn lookup<'m, T>(map: &'m BTreeMap<String, T>, key: &str) -> Option<&'m T> {
+    if let Some(value) = map.get(key) {
+        return Some(value);
+    }
+    let suffix = format!("/{}", key);
+    map.iter()
+        .filter(|(path, _)| path.ends_with(&suffix))
+        .min_by_key(|(path, _)| path.len())
+        .map(|(_, value)| value)
+}
+
+/// Resolves `./` and `../` against the folder of the linking note, other
+/// targets are kept for a lookup by name.
+fn relative_to(target: &str, folder: Option<&str>) -> Option<String> {
+    let target = target.trim().replace('\\', "/");
+    if !target.starts_with("./") && !target.starts_with("../") {
+        let target = target.trim_start_matches('/').to_string();
+        return Some(target).filter(|t| !t.is_empty());
+    }
+    let mut segments: Vec<&str> = folder
+        .map(|f| f.split('/').filter(|s| !s.is_empty()).collect())
+        .unwrap_or_default();
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop()?;
+            }
+            segment => segments.push(segment),
+        }
+    }
+    Some(segments.join("/")).filter(|t| !t.is_empty())
+}
@@ -0,0 +1,721 @@
+//! # Markdown Domain
+//! Converts Markdown notes to the Editor.js blocks of the note editor:
+//! paragraphs, headers, lists, quotes, delimiters, images and attaches.
+//! What has no block of its own (code, tables, HTML) is kept as text.
+use std::{collections::BTreeSet, sync::LazyLock};
+
+use pulldown_cmark::{BlockQuoteKind, Event, LinkType, Options, Parser, Tag, TagEnd};
+use regex::Regex;
+use serde_json::{json, Value};
+
+use super::import::{Embed, LinkResolver};
+use crate::{
+    features::notes::domain::{
+        errors::NoteError,
+        link::percent_decode,
+        properties::{embed_properties, empty_document, normalize_tag, NoteProperties},
+    },
+    shared::utils::{html::escape_html, id_generator::generate_id},
+};
+
+/// Matches the `#tags` written inside the text.
+static HASHTAG: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?:^|\s)#([\p{L}\p{N}_/-]+)").unwrap());
+
+/// Matches the first line of an Obsidian callout: `[!type] Title`.
+static CALLOUT: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\[!(\w+)\][+-]?\s*(.*)$").unwrap());
+
+#[derive(Debug, Default)]
+/// # ConvertedNote
+/// The result of converting a note to Editor.js.
+/// ## Fields
+/// * `blocks`: The Editor.js blocks.
+/// * `tags`: The `#tags` found in the text, normalized.
+/// * `issues`: What couldn't be converted as it was, once per kind.
+pub struct ConvertedNote {
+    pub blocks: Vec<Value>,
+    pub tags: BTreeSet<String>,
+    pub issues: Vec<String>,
+}
+
+/// The Markdown extensions understood by the converter.
+fn options() -> Options {
+    Options::ENABLE_TABLES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_WIKILINKS
+        | Options::ENABLE_GFM
+}
+
+/// Whether a link points outside of the imported files.
+pub fn is_external(url: &str) -> bool {
+    url.contains("://") || url.starts_with("mailto:") || url.starts_with("data:")
+}
+
+/// # Referenced files
+/// The local targets of the links and embeds of a Markdown note, as
+/// written. Some are other notes, the rest are files to copy.
+pub fn referenced_files(markdown: &str) -> Vec<String> {
+    Parser::new_ext(markdown, options())
+        .filter_map(|event| match event {
+            Event::Start(Tag::Image { dest_url, .. })
+            | Event::Start(Tag::Link { dest_url, .. })
+                if !is_external(&dest_url) =>
+            {
+                Some(dest_url.to_string())
+            }
+            _ => None,
+        })
+        .flat_map(|target| {
+            let decoded = percent_decode(&target);
+            [Some(target.clone()), Some(decoded).filter(|d| *d != target)]
+        })
+        .flatten()
+        .collect()
+}
+
+/// # Markdown to blocks
+/// Converts a Markdown note, without its frontmatter, to Editor.js blocks.
+pub fn markdown_to_blocks<R: LinkResolver>(markdown: &str, resolver: &R) -> ConvertedNote {
+    Converter::new(resolver).run(markdown)
+}
+
+/// # To document
+/// The content of a note holding the blocks and the properties.
+pub fn to_document(blocks: Vec<Value>, properties: &NoteProperties) -> Result<String, NoteError> {
+    let mut document = empty_document();
+    document.insert("blocks".to_string(), Value::Array(blocks));
+    let content = serde_json::to_string(&Value::Object(document))
+        .map_err(|e| NoteError::InvalidProperty(e.to_string()))?;
+    embed_properties(&content, properties)
+}
+
+/// An Editor.js block with a new id.
+pub(super) fn block(kind: &str, data: Value) -> Value {
+    json!({ "id": generate_id(), "type": kind, "data": data })
+}
+
+/// A list being read.
+struct ListState {
+    style: &'static str,
+    items: Vec<Value>,
+    item: Option<ListItem>,
+}
+
+/// An item of a list being read.
+#[derive(Default)]
+pub(super) struct ListItem {
+    pub(super) content: String,
+    pub(super) checked: Option<bool>,
+    pub(super) children: Vec<Value>,
+}
+
+impl ListItem {
+    pub(super) fn into_value(self) -> Value {
+        let meta = match self.checked {
+            Some(checked) => json!({ "checked": checked }),
+            None => json!({}),
+        };
+        json!({ "content": self.content, "meta": meta, "items": self.children })
+    }
+}
+
+/// A quote being read, nested quotes are flattened into it.
+struct QuoteState {
+    depth: usize,
+    lines: Vec<String>,
+    caption: String,
+}
+
+/// Walks the Markdown events, building the blocks.
+struct Converter<'r, R: LinkResolver> {
+    resolver: &'r R,
+    output: ConvertedNote,
+    text: String,
+    lists: Vec<ListState>,
+    quote: Option<QuoteState>,
+    code: Option<String>,
+    table: Option<Vec<Vec<String>>>,
+    row: Vec<String>,
+    links: Vec<bool>,
+    image: Option<(String, String)>,
+}
+
+impl<'r, R: LinkResolver> Converter<'r, R> {
+    fn new(resolver: &'r R) -> Self {
+        Self {
+            resolver,
+            output: ConvertedNote::default(),
+            text: String::new(),
+            lists: Vec::new(),
+            quote: None,
+            code: None,
+            table: None,
+            row: Vec::new(),
+            links: Vec::new(),
+            image: None,
+        }
+    }
+
+    fn run(mut self, markdown: &str) -> ConvertedNote {
+        for event in Parser::new_ext(markdown, options()) {
+            self.handle(event);
+        }
+        self.end_text_block();
+        while let Some(list) = self.lists.pop() {
+            self.end_list(list);
+        }
+        if let Some(quote) = self.quote.take() {
+            self.end_quote(quote);
+        }
+        self.output
+    }
+
+    fn handle(&mut self, event: Event) {
+        match event {
+            Event::Start(Tag::Paragraph) | Event::Start(Tag::Heading { .. }) => {}
+            Event::End(TagEnd::Paragraph) | Event::End(TagEnd::HtmlBlock) => self.end_text_block(),
+            Event::End(TagEnd::Heading(level)) => {
+                if self.in_container() {
+                    self.end_text_block();
+                } else {
+                    let text = std::mem::take(&mut self.text);
+                    if !text.trim().is_empty() {
+                        self.push_block(block(
+                            "header",
+                            json!({ "text": text.trim(), "level": level as usize }),
+                        ));
+                    }
+                }
+            }
+            Event::Start(Tag::BlockQuote(kind)) => {
+                self.end_text_block();
+                match &mut self.quote {
+                    Some(quote) => quote.depth += 1,
+                    None => {
+                        self.quote = Some(QuoteState {
+                            depth: 1,
+                            lines: Vec::new(),
+                            caption: kind.map(quote_kind_caption).unwrap_or_default(),
+                        })
+                    }
+                }
+            }
+            Event::End(TagEnd::BlockQuote(_)) => {
+                self.end_text_block();
+                if let Some(quote) = &mut self.quote {
+                    quote.depth -= 1;
+                    if quote.depth == 0 {
+                        let quote = self.quote.take().unwrap();
+                        self.end_quote(quote);
+                    }
+                }
+            }
+            Event::Start(Tag::CodeBlock(_)) => {
+                self.end_text_block();
+                self.code = Some(String::new());
+            }
+            Event::End(TagEnd::CodeBlock) => {
+                let code = self.code.take().unwrap_or_default();
+                self.issue("Code blocks are kept as paragraphs.");
+                self.text = format!(
+                    "<code class=\"inline-code\">{}</code>",
+                    escape_html(code.trim_end_matches('\n')).replace('\n', "<br>")
+                );
+                self.end_text_block();
+            }
+            Event::Start(Tag::HtmlBlock) => self.end_text_block(),
+            Event::Html(html) | Event::InlineHtml(html) => {
+                self.issue("Raw HTML is kept as text.");
+                self.push_text(&html);
+            }
+            Event::Start(Tag::List(first)) => {
+                self.end_text_block();
+                self.lists.push(ListState {
+                    style: if first.is_some() {
+                        "ordered"
+                    } else {
+                        "unordered"
+                    },
+                    items: Vec::new(),
+                    item: None,
+                });
+            }
+            Event::End(TagEnd::List(_)) => {
+                if let Some(list) = self.lists.pop() {
+                    self.end_list(list);
+                }
+            }
+            Event::Start(Tag::Item) => {
+                if let Some(list) = self.lists.last_mut() {
+                    list.item = Some(ListItem::default());
+                }
+            }
+            Event::End(TagEnd::Item) => {
+                self.end_text_block();
+                if let Some(list) = self.lists.last_mut() {
+                    if let Some(item) = list.item.take() {
+                        list.items.push(item.into_value());
+                    }
+                }
+            }
+            Event::TaskListMarker(checked) => {
+                if let Some(list) = self.lists.last_mut() {
+                    list.style = "checklist";
+                    if let Some(item) = &mut list.item {
+                        item.checked = Some(checked);
+                    }
+                }
+            }
+            Event::Start(Tag::Table(_)) => {
+                self.end_text_block();
+                self.issue("Tables are kept as paragraphs.");
+                self.table = Some(Vec::new());
+            }
+            Event::Start(Tag::TableHead) | Event::Start(Tag::TableRow) => self.row.clear(),
+            Event::End(TagEnd::TableCell) => {
+                let cell = std::mem::take(&mut self.text);
+                self.row.push(cell.trim().to_string());
+            }
+            Event::End(TagEnd::TableHead) | Event::End(TagEnd::TableRow) => {
+                let row = std::mem::take(&mut self.row);
+                if let Some(table) = &mut self.table {
+                    table.push(row);
+                }
+            }
+            Event::End(TagEnd::Table) => {
+                for row in self.table.take().unwrap_or_default() {
+                    self.text = row.join(" | ");
+                    self.end_text_block();
+                }
+            }
+            Event::Start(Tag::Emphasis) => self.text.push_str("<i>"),
+            Event::End(TagEnd::Emphasis) => self.text.push_str("</i>"),
+            Event::Start(Tag::Strong) => self.text.push_str("<b>"),
+            Event::End(TagEnd::Strong) => self.text.push_str("</b>"),
+            Event::Start(Tag::Strikethrough) => self.text.push_str("<s>"),
+            Event::End(TagEnd::Strikethrough) => self.text.push_str("</s>"),
+            Event::Start(Tag::Link {
+                link_type,
+                dest_url,
+                ..
+            }) => match self.link_href(link_type, &dest_url) {
+                Some(href) => {
+                    self.text
+                        .push_str(&format!("<a href=\"{}\">", escape_html(&href)));
+                    self.links.push(true);
+                }
+                None => self.links.push(false),
+            },
+            // Popped even when no anchor was opened for the link.
+            Event::End(TagEnd::Link) if self.links.pop() == Some(true) => {
+                self.text.push_str("</a>");
+            }
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                self.image = Some((dest_url.to_string(), String::new()));
+            }
+            Event::End(TagEnd::Image) => {
+                if let Some((target, alt)) = self.image.take() {
+                    self.embed(&target, &alt);
+                }
+            }
+            Event::Text(text) => {
+                if let Some(code) = &mut self.code {
+                    code.push_str(&text);
+                } else if let Some((_, alt)) = &mut self.image {
+                    alt.push_str(&text);
+                } else {
+                    self.collect_tags(&text);
+                    self.push_text(&text);
+                }
+            }
+            Event::Code(code) => match &mut self.image {
+                Some((_, alt)) => alt.push_str(&code),
+                None => self.text.push_str(&format!(
+                    "<code class=\"inline-code\">{}</code>",
+                    escape_html(&code)
+                )),
+            },
+            // Quotes keep their lines, callouts need the first one apart.
+            Event::SoftBreak if self.quote.is_some() => self.text.push_str("<br>"),
+            Event::SoftBreak => self.text.push(' '),
+            Event::HardBreak => self.text.push_str("<br>"),
+            Event::Rule => {
+                self.end_text_block();
+                self.push_block(block("delimiter", json!({})));
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether a block can't be added at the top level right now.
+    fn in_container(&self) -> bool {
+        !self.lists.is_empty() || self.quote.is_some() || self.table.is_some()
+    }
+
+    fn push_text(&mut self, text: &str) {
+        self.text.push_str(&escape_html(text));
+    }
+
+    fn push_block(&mut self, block: Value) {
+        self.output.blocks.push(block);
+    }
+
+    fn issue(&mut self, message: &str) {
+        if !self.output.issues.iter().any(|i| i == message) {
+            self.output.issues.push(message.to_string());
+        }
+    }
+
+    fn collect_tags(&mut self, text: &str) {
+        for captures in HASHTAG.captures_iter(text) {
+            let tag = captures[1].trim_end_matches('/');
+            if !tag.is_empty() && !tag.chars().all(|c| c.is_ascii_digit()) {
+                self.output.tags.insert(normalize_tag(tag));
+            }
+        }
+    }
+
+    /// Ends the text being read: a paragraph, or a line of the open list
+    /// item or quote.
+    fn end_text_block(&mut self) {
+        let text = std::mem::take(&mut self.text);
+        let text = text.trim();
+        if text.is_empty() {
+            return;
+        }
+        if let Some(item) = self.lists.last_mut().and_then(|l| l.item.as_mut()) {
+            if !item.content.is_empty() {
+                item.content.push_str("<br>");
+            }
+            item.content.push_str(text);
+        } else if let Some(quote) = &mut self.quote {
+            quote.lines.push(text.to_string());
+        } else {
+            self.push_block(block("paragraph", json!({ "text": text })));
+        }
+    }
+
+    /// Adds a finished list to its parent item, its quote or the blocks.
+    fn end_list(&mut self, list: ListState) {
+        let items = match list.item {
+            Some(item) => [list.items, vec![item.into_value()]].concat(),
+            None => list.items,
+        };
+        if let Some(parent) = self.lists.last_mut().and_then(|l| l.item.as_mut()) {
+            parent.children.extend(items);
+        } else if let Some(quote) = &mut self.quote {
+            for item in items {
+                let content = item["content"].as_str().unwrap_or_default();
+                quote.lines.push(format!("• {}", content));
+            }
+        } else {
+            self.push_block(list_block(list.style, items));
+        }
+    }
+
+    /// Adds a finished quote, a callout title becomes its caption.
+    fn end_quote(&mut self, quote: QuoteState) {
+        let mut caption = quote.caption;
+        let mut text = quote.lines.join("<br>");
+        let (head, rest) = match text.split_once("<br>") {
+            Some((head, rest)) => (head.to_string(), rest.to_string()),
+            None => (text.clone(), String::new()),
+        };
+        if let Some(callout) = CALLOUT.captures(&head) {
+            caption = match callout[2].trim() {
+                "" => capitalize(&callout[1]),
+                title => title.to_string(),
+            };
+            text = rest;
+        }
+        if text.is_empty() && caption.is_empty() {
+            return;
+        }
+        if !self.lists.is_empty() {
+            self.text = text;
+            self.end_text_block();
+            return;
+        }
+        let data = json!({ "text": text, "caption": caption, "alignment": "left" });
+        self.push_block(block("quote", data));
+    }
+
+    /// The `href` of a link, `None` when its target is missing.
+    fn link_href(&mut self, link_type: LinkType, dest: &str) -> Option<String> {
+        match link_type {
+            LinkType::Email => return Some(format!("mailto:{}", dest)),
+            _ if is_external(dest) => return Some(dest.to_string()),
+            _ => {}
+        }
+        let decoded = percent_decode(dest);
+        let href = [dest, decoded.as_str()].into_iter().find_map(|target| {
+            self.resolver
+                .note_link(target)
+                .or_else(|| self.resolver.embed(target).map(|e| e.url().to_string()))
+        });
+        if href.is_none() {
+            self.issue(&format!("Link to the missing note or file '{}'.", decoded));
+        }
+        href
+    }
+
+    /// Adds an embedded file: an image or attaches block, or a link when
+    /// it can't be a block of its own.
+    fn embed(&mut self, target: &str, alt: &str) {
+        if is_external(target) {
+            self.issue("Remote images are kept as links.");
+            let label = if alt.is_empty() { target } else { alt };
+            self.push_anchor(target, label);
+            return;
+        }
+        let decoded = percent_decode(target);
+        let embed = self
+            .resolver
+            .embed(target)
+            .or_else(|| self.resolver.embed(&decoded));
+        let Some(embed) = embed else {
+            match self.resolver.note_link(target) {
+                Some(href) => {
+                    self.issue("Embedded notes are converted to links.");
+                    self.push_anchor(&href, target);
+                }
+                None => {
+                    self.issue(&format!("Missing embedded file '{}'.", decoded));
+                    self.push_text(target);
+                }
+            }
+            return;
+        };
+
+        if self.in_container() {
+            let name = decoded.rsplit('/').next().unwrap_or_default().to_string();
+            self.push_anchor(embed.url(), &name);
+            return;
+        }
+        self.end_text_block();
+        self.push_block(embed_block(embed, caption_of(alt, target)));
+    }
+
+    fn push_anchor(&mut self, href: &str, label: &str) {
+        self.text.push_str(&format!(
+            "<a href=\"{}\">{}</a>",
+            escape_html(href),
+            escape_html(label)
+        ));
+    }
+}
+
+/// A list block: `unordered`, `ordered` or `checklist`.
+pub(super) fn list_block(style: &str, items: Vec<Value>) -> Value {
+    block(
+        "list",
+        json!({ "style": style, "meta": {}, "items": items }),
+    )
+}
+
+/// The image or attaches block of an embedded file.
+pub(super) fn embed_block(embed: Embed, caption: String) -> Value {
+    match embed {
+        Embed::Image(file) => block(
+            "image",
+            json!({
+                "file": file,
+                "caption": caption,
+                "withBorder": false,
+                "withBackground": false,
+                "stretched": false,
+            }),
+        ),
+        Embed::File(file) => {
+            let title = file.title.clone();
+            block("attaches", json!({ "file": file, "title": title }))
+        }
+    }
+}
+
+/// The caption of a GFM alert.
+fn quote_kind_caption(kind: BlockQuoteKind) -> String {
+    match kind {
+        BlockQuoteKind::Note => "Note",
+        BlockQuoteKind::Tip => "Tip",
+        BlockQuoteKind::Important => "Important",
+        BlockQuoteKind::Warning => "Warning",
+        BlockQuoteKind::Caution => "Caution",
+    }
+    .to_string()
+}
+
+/// The caption of an embedded image: its alt text, unless it's the target
+/// or an Obsidian size (`300`, `300x200`).
+fn caption_of(alt: &str, target: &str) -> String {
+    let alt = alt.trim();
+    let is_size = !alt.is_empty() && alt.chars().all(|c| c.is_ascii_digit() || c == 'x');
+    if alt == target || is_size {
+        String::new()
+    } else {
+        alt.to_string()
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::features::{
+        attachments::domain::image::ImageFile,
+        import::domain::import::{SourceIndex, SourceResolver},
+        notes::domain::link::NoteLink,
+    };
+
+    fn index() -> SourceIndex {
+        let note = |folder: Option<&str>, name: &str| NoteLink {
+            folder: folder.map(str::to_string),
+            name: name.to_string(),
+            heading: None,
+        };
+        SourceIndex::new(
+            [
+                (
+                    "Projects/Plan.md".to_string(),
+                    note(Some("Projects"), "Plan"),
+                ),
+                ("Ideas.md".to_string(), note(None, "Ideas")),
+            ],
+            ["assets/pic.png".to_string()],
+        )
+    }
+
+    fn convert(markdown: &str, embeds: &HashMap<String, Embed>) -> ConvertedNote {
+        let index = index();
+        let resolver = SourceResolver {
+            index: &index,
+            folder: Some("Projects"),
+            embeds,
+        };
+        markdown_to_blocks(markdown, &resolver)
+    }
+
+    fn types(converted: &ConvertedNote) -> Vec<&str> {
+        converted
+            .blocks
+            .iter()
+            .map(|b| b["type"].as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn blocks_of_the_common_syntax() {
+        let converted = convert(
+            "# Title\n\nSome **bold** and *italic*.\n\n---\n\n1. one\n2. two\n\n- [x] done\n- [ ] todo\n",
+            &HashMap::new(),
+        );
+        assert_eq!(
+            types(&converted),
+            vec!["header", "paragraph", "delimiter", "list", "list"]
+        );
+        assert_eq!(converted.blocks[0]["data"]["level"], 1);
+        assert_eq!(
+            converted.blocks[1]["data"]["text"],
+            "Some <b>bold</b> and <i>italic</i>."
+        );
+        assert_eq!(converted.blocks[3]["data"]["style"], "ordered");
+
+        let checklist = &converted.blocks[4]["data"];
+        assert_eq!(checklist["style"], "checklist");
+        assert_eq!(checklist["items"][0]["meta"]["checked"], true);
+        assert_eq!(checklist["items"][1]["content"], "todo");
+        assert!(converted.issues.is_empty());
+    }
+
+    #[test]
+    fn links_point_to_the_imported_notes() {
+        let converted = convert(
+            "See [[Plan#Goals]], [ideas](../Ideas.md) and [[Missing]].",
+            &HashMap::new(),
+        );
+        let text = converted.blocks[0]["data"]["text"].as_str().unwrap();
+        assert!(text.contains("href=\"merino://note/Projects/Plan#Goals\""));
+        assert!(text.contains("href=\"merino://note/Ideas\""));
+        assert_eq!(
+            converted.issues,
+            vec!["Link to the missing note or file 'Missing'."]
+        );
+    }
+
+    #[test]
+    fn embedded_files_become_blocks() {
+        let image = ImageFile {
+            url: "attachments/pic.png".to_string(),
+            id: "pic".to_string(),
+            width: 1,
+            height: 1,
+            size: 1,
+            gps_removed: false,
+            thumbnails: Default::default(),
+        };
+        let embeds = HashMap::from([("assets/pic.png".to_string(), Embed::Image(image))]);
+        let converted = convert("Before\n\n![[pic.png]]\n\n![gone](nothing.png)", &embeds);
+
+        assert_eq!(types(&converted), vec!["paragraph", "image", "paragraph"]);
+        assert_eq!(
+            converted.blocks[1]["data"]["file"]["url"],
+            "attachments/pic.png"
+        );
+        assert_eq!(
+            converted.issues,
+            vec!["Missing embedded file 'nothing.png'."]
+        );
+    }
+
+    #[test]
+    fn quotes_callouts_and_tags() {
+        let converted = convert(
+            "> [!warning] Careful\n> it's #Hot and #2024\n\nCode:\n\n```\nlet a = 1;\n```\n",
+            &HashMap::new(),
+        );
+        assert_eq!(types(&converted), vec!["quote", "paragraph", "paragraph"]);
+        assert_eq!(converted.blocks[0]["data"]["caption"], "Careful");
+        assert_eq!(converted.blocks[0]["data"]["text"], "it's #Hot and #2024");
+        assert_eq!(converted.tags, BTreeSet::from(["hot".to_string()]));
+        assert_eq!(
+            converted.blocks[2]["data"]["text"],
+            "<code class=\"inline-code\">let a = 1;</code>"
+        );
+        assert_eq!(
+            converted.issues,
+            vec!["Code blocks are kept as paragraphs."]
+        );
+    }
+
+    #[test]
+    fn referenced_files_skip_external_targets() {
+        let files = referenced_files(
+            "![a](my%20pic.png) [site](https://example.com) [[Plan]] [mail](mailto:a@b.c)",
+        );
+        assert_eq!(files, vec!["my%20pic.png", "my pic.png", "Plan"]);
+    }
+
+    #[test]
+    fn documents_hold_the_blocks_and_properties() {
+        let properties = NoteProperties {
+            tags: vec!["a".to_string()],
+            ..Default::default()
+        };
+        let blocks = vec![block("paragraph", json!({ "text": "hi" }))];
+        let document: Value =
+            serde_json::from_str(&to_document(blocks, &properties).unwrap()).unwrap();
+        assert_eq!(document["blocks"][0]["data"]["text"], "hi");
+        assert_eq!(document["properties"]["tags"][0], "a");
+    }
+}
@@ -0,0 +1,8 @@
+pub mod errors;
+pub mod evernote;
+pub mod html;
+pub mod import;
+pub mod markdown;
+pub mod notion;
+pub mod obsidian;
+pub mod repository;
@@ -0,0 +1,189 @@
+//! # Notion Domain
+//! Defines how the "Markdown & CSV" exports of Notion are read: pages are
+//! Markdown files and databases CSV files, named after their title and the
+//! id of the page. The subpages of a page, and the rows of a database, are
+//! in a folder named like it.
+use std::sync::LazyLock;
+
+use chrono::NaiveDate;
+use regex::Regex;
+use serde_json::{json, Value};
+
+use super::markdown::block;
+use crate::{
+    features::notes::domain::properties::{NoteProperties, PropertyValue},
+    shared::utils::html::escape_html,
+};
+
+/// Extension of the exported databases.
+pub const CSV_EXTENSION: &str = "csv";
+
+/// Suffix of the database exports holding every row, not only the ones of
+/// the saved view.
+pub const ALL_ROWS_SUFFIX: &str = "_all";
+
+/// Matches the page id Notion adds to the names.
+static NOTION_ID: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s+[0-9a-f]{32}$").unwrap());
+
+/// # Strip Notion id
+/// The title of an exported page, file or folder name without its id.
+pub fn strip_notion_id(name: &str) -> &str {
+    match NOTION_ID.find(name) {
+        Some(id) if id.start() > 0 => &name[..id.start()],
+        _ => name,
+    }
+}
+
+/// # Notion folder
+/// The folder of a space matching an exported folder: its path without
+/// the page ids.
+pub fn notion_folder(folder: &str) -> String {
+    folder
+        .split('/')
+        .map(strip_notion_id)
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// # Strip title
+/// Removes the `# Title` heading every exported page starts with, the
+/// title is the name of the note.
+pub fn strip_title(body: &str) -> &str {
+    let body = body.trim_start_matches('\u{feff}').trim_start();
+    match body.strip_prefix("# ") {
+        Some(rest) => rest.split_once('\n').map_or("", |(_, rest)| rest),
+        None => body,
+    }
+}
+
+/// # Split properties
+/// Separates the `Column: value` lines Notion writes at the top of the
+/// rows of a database from the rest of the page. Only the columns of the
+/// database are recognized, so a page starting like a property is kept.
+/// ## Returns
+/// The properties by column, and the rest of the page.
+pub fn split_properties<'a>(body: &'a str, columns: &[String]) -> (Vec<(String, String)>, &'a str) {
+    let content = body.trim_start_matches(['\n', '\r']);
+    let mut properties = Vec::new();
+    let mut offset = 0;
+    for line in content.split_inclusive('\n') {
+        let trimmed = line.trim_end();
+        if trimmed.is_empty() {
+            break;
+        }
+        match trimmed.split_once(": ") {
+            Some((column, value)) if columns.iter().any(|c| c == column) => {
+                properties.push((column.to_string(), value.trim().to_string()));
+            }
+            _ => return (Vec::new(), body),
+        }
+        offset += line.len();
+    }
+    (properties, &content[offset..])
+}
+
+/// # Row properties
+/// Maps the properties of a database row to note properties: `Tags` and
+/// `Status` keep their meaning, dates, checkboxes and numbers are typed and
+/// the rest is kept as text.
+pub fn row_properties(properties: &[(String, String)]) -> NoteProperties {
+    let mut note_properties = NoteProperties::default();
+    for (column, value) in properties {
+        if value.is_empty() {
+            continue;
+        }
+        match column.to_lowercase().as_str() {
+            "tags" | "tag" => note_properties.tags.extend(
+                value
+                    .split(',')
+                    .map(str::trim)
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_string),
+            ),
+            "status" => note_properties.status = Some(value.clone()),
+            _ => {
+                if let Some(date) = parse_notion_date(value) {
+                    note_properties.dates.insert(column.clone(), date);
+                    continue;
+                }
+                let property = match value.as_str() {
+                    "Yes" => PropertyValue::Bool(true),
+                    "No" => PropertyValue::Bool(false),
+                    value => match value.parse::<f64>() {
+                        Ok(number) if number.is_finite() => PropertyValue::Number(number),
+                        _ => PropertyValue::Text(value.to_string()),
+                    },
+                };
+                note_properties.custom.insert(column.clone(), property);
+            }
+        }
+    }
+    note_properties
+}
+
+/// # Parse CSV
+/// Reads the rows of an exported database.
+pub fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let text = text.trim_start_matches('\u{feff}');
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match (c, quoted) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => quoted = false,
+            ('"', false) if field.is_empty() => quoted = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (c, _) => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+    rows.retain(|row| row.iter().any(|field| !field.is_empty()));
+    rows
+}
+
+/// # Database blocks
+/// The blocks of an exported database: a paragraph per row, the first one
+/// holding the column names.
+pub fn database_blocks(rows: &[Vec<String>]) -> Vec<Value> {
+    rows.iter()
+        .enumerate()
+        .map(|(i, row)| {
+            let text = row
+                .iter()
+                .map(|field| escape_html(field).replace('\n', "<br>"))
+                .collect::<Vec<_>>()
+                .join(" | ");
+            let text = if i == 0 {
+                format!("<b>{}</b>", text)
+            } else {
+                text
+            };
+            block("paragraph", json!({ "text": text }))
+        })
+        .collect()
+}
+
+/// A date as Notion writes it, `January 2, 2024`, optionally followed by
+/// a time or the end of a range, or as `2024-01-02`.
+fn parse_notion_date(text: &str) -> Option<NaiveDate> {
+    ["%B %d, %Y", "%Y-%m-%d", "%Y/%m/%d"]
+        .iter()
+        .find_map(|format| {
+            let (date, rest) = NaiveDate::parse_and_remainder(text, format).ok()?;
+            (rest.is_empty() || rest.starts_with(' ')).then_some(date)
+        })
+}
@@ -0,0 +1,192 @@
+//! # Obsidian Domain
+//! Defines how the notes of an Obsidian vault are read: their YAML
+//! frontmatter becomes the note properties.
+use chrono::NaiveDate;
+use serde_yaml::Value;
+
+use crate::features::notes::domain::properties::{NoteProperties, PropertyValue};
+
+/// # Split frontmatter
+/// Separates the YAML frontmatter, between `---` lines at the top of a note,
+/// from the Markdown body.
+pub fn split_frontmatter(content: &str) -> (Option<&str>, &str) {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let Some(after) = content.strip_prefix("---").and_then(|rest| {
+        rest.strip_prefix('\n')
+            .or_else(|| rest.strip_prefix("\r\n"))
+    }) else {
+        return (None, content);
+    };
+
+    let mut offset = 0;
+    for line in after.split_inclusive('\n') {
+        if line.trim_end() == "---" {
+            return (Some(&after[..offset]), &after[offset + line.len()..]);
+        }
+        offset += line.len();
+    }
+    (None, content)
+}
+
+/// # Frontmatter properties
+/// Maps a YAML frontmatter to note properties: `tags` and `status` keep
+/// their meaning, `YYYY-MM-DD` values become dates and the other scalars
+/// and lists become custom properties.
+/// ## Returns
+/// The properties and the keys that couldn't be converted, or why the
+/// frontmatter can't be read.
+pub fn frontmatter_properties(yaml: &str) -> Result<(NoteProperties, Vec<String>), String> {
+    let mapping = match serde_yaml::from_str::<Value>(yaml).map_err(|e| e.to_string())? {
+        Value::Null => return Ok((NoteProperties::default(), Vec::new())),
+        Value::Mapping(mapping) => mapping,
+        _ => return Err("the frontmatter isn't a list of keys".to_string()),
+    };
+
+    let mut properties = NoteProperties::default();
+    let mut skipped = Vec::new();
+    for (key, value) in mapping {
+        let Some(key) = scalar_text(&key) else {
+            skipped.push(format!("{:?}", key));
+            continue;
+        };
+        match key.to_lowercase().as_str() {
+            "tags" | "tag" => properties.tags.extend(
+                text_list(&value)
+                    .iter()
+                    .flat_map(|t| t.split([',', ' ']))
+                    .filter(|t| !t.is_empty())
+                    .map(str::to_string),
+            ),
+            "status" => properties.status = scalar_text(&value),
+            _ => match value {
+                Value::Null => {}
+                Value::Bool(flag) => {
+                    properties.custom.insert(key, PropertyValue::Bool(flag));
+                }
+                Value::Number(number) => match number.as_f64() {
+                    Some(number) => {
+                        properties.custom.insert(key, PropertyValue::Number(number));
+                    }
+                    None => skipped.push(key),
+                },
+                Value::String(text) => match parse_date(&text) {
+                    Some(date) => {
+                        properties.dates.insert(key, date);
+                    }
+                    None => {
+                        properties.custom.insert(key, PropertyValue::Text(text));
+                    }
+                },
+                Value::Sequence(items) if items.iter().all(|i| scalar_text(i).is_some()) => {
+                    let items = items.iter().filter_map(scalar_text).collect();
+                    properties.custom.insert(key, PropertyValue::List(items));
+                }
+                _ => skipped.push(key),
+            },
+        }
+    }
+    Ok((properties, skipped))
+}
+
+/// The text of a scalar value.
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Bool(flag) => Some(flag.to_string()),
+        Value::Number(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+/// The texts of a scalar or a list of scalars.
+fn text_list(value: &Value) -> Vec<String> {
+    match value {
+        Value::Sequence(items) => items.iter().filter_map(scalar_text).collect(),
+        value => scalar_text(value).into_iter().collect(),
+    }
+}
+
+/// A `YYYY-MM-DD` date, optionally followed by a time.
+fn parse_date(text: &str) -> Option<NaiveDate> {
+    let date = text.get(..10)?;
+    let rest = &text[10..];
+    if !rest.is_empty() && !rest.starts_with(['T', ' ']) {
+        return None;
+    }
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn frontmatter_is_split_from_the_body() {
+        let note = "\u{feff}---\r\ntags: [a]\r\n---\r\n# Body\n";
+        assert_eq!(split_frontmatter(note), (Some("tags: [a]\r\n"), "# Body\n"));
+
+        assert_eq!(
+            split_frontmatter("# No frontmatter"),
+            (None, "# No frontmatter")
+        );
+        assert_eq!(
+            split_frontmatter("---\nnever closed\n"),
+            (None, "---\nnever closed\n")
+        );
+    }
+
+    #[test]
+    fn frontmatter_becomes_properties() {
+        let yaml = "tags: [rust, 'two words']\nstatus: Draft\ndue: 2024-05-01T10:00\n\
+                    rating: 4\nshared: true\nauthors: [Ada, Grace]\nsource: web\n\
+                    nested: {a: 1}\nempty:\n";
+        let (properties, skipped) = frontmatter_properties(yaml).unwrap();
+
+        assert_eq!(properties.tags, vec!["rust", "two", "words"]);
+        assert_eq!(properties.status.as_deref(), Some("Draft"));
+        assert_eq!(
+            properties.dates.get("due"),
+            NaiveDate::from_ymd_opt(2024, 5, 1).as_ref()
+        );
+        assert_eq!(
+            properties.custom.get("rating"),
+            Some(&PropertyValue::Number(4.0))
+        );
+        assert_eq!(
+            properties.custom.get("shared"),
+            Some(&PropertyValue::Bool(true))
+        );
+        assert_eq!(
+            properties.custom.get("authors"),
+            Some(&PropertyValue::List(vec!["Ada".into(), "Grace".into()]))
+        );
+        assert_eq!(
+            properties.custom.get("source"),
+            Some(&PropertyValue::Text("web".into()))
+        );
+        assert!(!properties.custom.contains_key("empty"));
+        assert_eq!(skipped, vec!["nested"]);
+    }
+
+    #[test]
+    fn invalid_frontmatter_is_an_error() {
+        assert!(frontmatter_properties("- just\n- a list\n").is_err());
+        assert!(frontmatter_properties("key: [unclosed\n").is_err());
+        assert!(frontmatter_properties("").unwrap().0.is_empty());
+    }
+
+    #[test]
+    fn dates_need_a_day_and_an_optional_time() {
+        assert_eq!(
+            parse_date("2024-02-29"),
+            NaiveDate::from_ymd_opt(2024, 2, 29)
+        );
+        assert_eq!(
+            parse_date("2024-02-29 08:00"),
+            NaiveDate::from_ymd_opt(2024, 2, 29)
+        );
+        assert_eq!(parse_date("2024-02-30"), None);
+        assert_eq!(parse_date("2024-02-291"), None);
+        assert_eq!(parse_date("soon"), None);
+    }
+}
@@ -0,0 +1,35 @@
+//! # Repository
+//! Definition of the access to the files being imported.
+use std::path::Path;
+
+use async_trait::async_trait;
+
+use super::errors::ImportError;
+
+#[derive(Debug, Clone, Default)]
+/// # SourceTree
+/// The content of an imported directory, hidden entries excluded.
+/// ## Fields
+/// * `folders`: Every folder, `/` separated and relative to the directory,
+///   parents first.
+/// * `files`: Every file, `/` separated and relative to the directory.
+pub struct SourceTree {
+    pub folders: Vec<String>,
+    pub files: Vec<String>,
+}
+
+/// # SourceRepository
+/// Reads the files of another application, from a directory or a zip file.
+#[async_trait]
+pub trait SourceRepository {
+    /// # [GET] Source tree
+    /// Lists the folders and files of an imported directory or zip file.
+    async fn get_source_tree(&self, root: &Path) -> Result<SourceTree, ImportError>;
+
+    /// # [GET] Source file
+    /// Reads a file of an imported directory.
+    /// ## Fields
+    /// * `root`: The imported directory or zip file.
+    /// * `path`: The `/` separated path of the file, relative to `root`.
+    async fn read_source_file(&self, root: &Path, path: &str) -> Result<Vec<u8>, ImportError>;
+}
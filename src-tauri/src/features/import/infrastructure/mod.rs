@@ -0,0 +1,2 @@
+pub mod source_repository;
+pub mod tauri_commands;
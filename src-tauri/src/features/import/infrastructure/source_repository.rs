@@ -0,0 +1,146 @@
+//! # Source Repository
+//! External implementation of the access to the imported directories and
+//! zip files of the filesystem.
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io::Read,
+    path::{Component, Path, PathBuf},
+};
+
+use async_trait::async_trait;
+use tokio::fs;
+use zip::ZipArchive;
+
+use crate::features::import::domain::{
+    errors::ImportError,
+    repository::{SourceRepository, SourceTree},
+};
+
+/// # FileSystemSourceRepository
+/// Implementation of the SourceRepository trait.
+#[derive(Clone, Default)]
+pub struct FileSystemSourceRepository;
+
+impl FileSystemSourceRepository {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SourceRepository for FileSystemSourceRepository {
+    async fn get_source_tree(&self, root: &Path) -> Result<SourceTree, ImportError> {
+        if is_zip(root) {
+            let root = root.to_path_buf();
+            return tokio::task::spawn_blocking(move || zip_tree(&root))
+                .await
+                .map_err(|e| ImportError::Io(std::io::Error::other(e)))?;
+        }
+        if !root.is_dir() {
+            return Err(ImportError::InvalidSource(root.display().to_string()));
+        }
+
+        let mut tree = SourceTree::default();
+        let mut pending = vec![String::new()];
+        while let Some(relative) = pending.pop() {
+            let mut entries = fs::read_dir(root.join(&relative)).await?;
+            while let Some(entry) = entries.next_entry().await? {
+                let name = entry.file_name().to_string_lossy().to_string();
+                // Hidden entries, like `.obsidian` or `.trash`, belong to
+                // the application, not to the notes.
+                if name.starts_with('.') {
+                    continue;
+                }
+                let path = match relative.as_str() {
+                    "" => name,
+                    parent => format!("{}/{}", parent, name),
+                };
+                if entry.file_type().await?.is_dir() {
+                    tree.folders.push(path.clone());
+                    pending.push(path);
+                } else {
+                    tree.files.push(path);
+                }
+            }
+        }
+        tree.folders.sort();
+        tree.files.sort();
+        Ok(tree)
+    }
+
+    async fn read_source_file(&self, root: &Path, path: &str) -> Result<Vec<u8>, ImportError> {
+        if path.split('/').any(|segment| segment == "..") {
+            return Err(ImportError::InvalidSource(path.to_string()));
+        }
+        if is_zip(root) {
+            let (root, path) = (root.to_path_buf(), path.to_string());
+            return tokio::task::spawn_blocking(move || read_zip_entry(&root, &path))
+                .await
+                .map_err(|e| ImportError::Io(std::io::Error::other(e)))?;
+        }
+        Ok(fs::read(root.join(path)).await?)
+    }
+}
+
+/// Whether an imported root is a zip file rather than a directory.
+fn is_zip(root: &Path) -> bool {
+    root.is_file()
+        && root
+            .extension()
+            .is_some_and(|extension| extension.eq_ignore_ascii_case("zip"))
+}
+
+/// Lists the entries of a zip like the ones of a directory. The folders
+/// are taken from the paths, some zips have no entries for them.
+fn zip_tree(root: &Path) -> Result<SourceTree, ImportError> {
+    let zip = ZipArchive::new(File::open(root)?)?;
+    let mut folders = BTreeSet::new();
+    let mut files = BTreeSet::new();
+    for name in zip.file_names() {
+        let Some(path) = zip_entry_path(name) else {
+            continue;
+        };
+        let mut segments: Vec<&str> = path.split('/').collect();
+        if !name.ends_with('/') {
+            files.insert(path.clone());
+            segments.pop();
+        }
+        for end in 1..=segments.len() {
+            folders.insert(segments[..end].join("/"));
+        }
+    }
+    Ok(SourceTree {
+        folders: folders.into_iter().collect(),
+        files: files.into_iter().collect(),
+    })
+}
+
+/// Reads a file of a zip by its `/` separated path.
+fn read_zip_entry(root: &Path, path: &str) -> Result<Vec<u8>, ImportError> {
+    let mut zip = ZipArchive::new(File::open(root)?)?;
+    let name = zip
+        .file_names()
+        .find(|name| zip_entry_path(name).as_deref() == Some(path))
+        .map(str::to_string)
+        .ok_or_else(|| ImportError::InvalidSource(path.to_string()))?;
+    let mut content = Vec::new();
+    zip.by_name(&name)?.read_to_end(&mut content)?;
+    Ok(content)
+}
+
+/// The `/` separated path of a zip entry, `None` for the entries outside
+/// of the zip or hidden, like the `__MACOSX` metadata.
+fn zip_entry_path(name: &str) -> Option<String> {
+    let segments = PathBuf::from(name.replace('\\', "/"))
+        .components()
+        .map(|component| match component {
+            Component::Normal(segment) => Some(segment.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect::<Option<Vec<String>>>()?;
+    let hidden = segments
+        .iter()
+        .any(|s| s.starts_with('.') || s == "__MACOSX");
+    (!segments.is_empty() && !hidden).then(|| segments.join("/"))
+}
@@ -0,0 +1,166 @@
+//! # Tauri Commands [Import]
+//! Implementation of the commands importing notes from other applications
+//! to expose them on tauri for having them in the frontend application.
+use std::path::Path;
+
+use tauri::State;
+
+use crate::{
+    features::{
+        attachments::infrastructure::filesystem_repository::FileSystemAttachmentRepository,
+        import::{
+            application::{evernote, notion, obsidian},
+            domain::import::ImportReport,
+            infrastructure::source_repository::FileSystemSourceRepository,
+        },
+        search,
+    },
+    shared::state::state::AppState,
+};
+
+/// # [CREATE] Import Obsidian Vault
+/// Imports the notes, folders and attachments of an Obsidian vault into a
+/// space and indexes them.
+/// ## Params
+/// * `vault_path`: The directory of the vault.
+/// * `space_name`: The space receiving the notes, created when it doesn't
+///   exist. Named after the vault if not given.
+/// ## Result
+/// The report of the import, with everything that couldn't be converted.
+#[tauri::command]
+pub async fn import_obsidian_vault_cmd(
+    state: State<'_, AppState>,
+    attachments_repo: State<'_, FileSystemAttachmentRepository>,
+    source_repo: State<'_, FileSystemSourceRepository>,
+    vault_path: &str,
+    space_name: Option<&str>,
+) -> Result<ImportReport, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+    let spaces_repo_lock = state.spaces_repo.lock().await;
+    let folders_repo_lock = state.folders_repo.lock().await;
+
+    let report = obsidian::import_obsidian_vault_use_case(
+        &*spaces_repo_lock,
+        &*fs_repo_lock,
+        &*folders_repo_lock,
+        &*attachments_repo,
+        &*source_repo,
+        Path::new(vault_path),
+        space_name,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    search::application::index::reindex_space_use_case(
+        &*fs_repo_lock,
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &report.space,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(report)
+}
+
+/// # [CREATE] Import Notion Export
+/// Imports the pages, databases and files of a Notion "Markdown & CSV"
+/// export into a space and indexes them.
+/// ## Params
+/// * `export_path`: The zip of the export, or the directory it was extracted to.
+/// * `space_name`: The space receiving the notes, created when it doesn't
+///   exist. Named after the export if not given.
+/// ## Result
+/// The report of the import, with everything that couldn't be converted.
+#[tauri::command]
+pub async fn import_notion_export_cmd(
+    state: State<'_, AppState>,
+    attachments_repo: State<'_, FileSystemAttachmentRepository>,
+    source_repo: State<'_, FileSystemSourceRepository>,
+    export_path: &str,
+    space_name: Option<&str>,
+) -> Result<ImportReport, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+    let spaces_repo_lock = state.spaces_repo.lock().await;
+    let folders_repo_lock = state.folders_repo.lock().await;
+
+    let report = notion::import_notion_export_use_case(
+        &*spaces_repo_lock,
+        &*fs_repo_lock,
+        &*folders_repo_lock,
+        &*attachments_repo,
+        &*source_repo,
+        Path::new(export_path),
+        space_name,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    search::application::index::reindex_space_use_case(
+        &*fs_repo_lock,
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &report.space,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(report)
+}
+
+/// # [CREATE] Import Evernote Export
+/// Imports the notebooks of Evernote `.enex` exports into a space, as
+/// folders, and indexes them.
+/// ## Params
+/// * `export_path`: An `.enex` file, or a directory or zip holding several.
+/// * `space_name`: The space receiving the notes, created when it doesn't
+///   exist. Named after the export if not given.
+/// ## Result
+/// The report of the import, with everything that couldn't be converted.
+#[tauri::command]
+pub async fn import_evernote_export_cmd(
+    state: State<'_, AppState>,
+    attachments_repo: State<'_, FileSystemAttachmentRepository>,
+    source_repo: State<'_, FileSystemSourceRepository>,
+    export_path: &str,
+    space_name: Option<&str>,
+) -> Result<ImportReport, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+    let spaces_repo_lock = state.spaces_repo.lock().await;
+    let folders_repo_lock = state.folders_repo.lock().await;
+
+    let report = evernote::import_evernote_export_use_case(
+        &*spaces_repo_lock,
+        &*fs_repo_lock,
+        &*folders_repo_lock,
+        &*attachments_repo,
+        &*source_repo,
+        Path::new(export_path),
+        space_name,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    search::application::index::reindex_space_use_case(
+        &*fs_repo_lock,
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &report.space,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(report)
+}
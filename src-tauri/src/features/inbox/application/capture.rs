@@ -0,0 +1,134 @@
+//! # Capture **inbox** module
+//! Appends quick notes to the inbox.
+use chrono::Local;
+
+use crate::{
+    features::{
+        folders::{
+            application::create::ensure_folder_use_case, domain::repository::FolderRepository,
+        },
+        inbox::domain::{
+            errors::InboxError,
+            inbox::{append_blocks, capture_blocks, InboxSettings, QuickCapture},
+        },
+        notes::{
+            application::create::create_named_note_use_case,
+            domain::{errors::NoteError, repository::NoteRepository},
+        },
+        settings::{
+            application::get::get_settings_section, domain::repository::SettingsRepository,
+        },
+        space::domain::{errors::SpaceError, repository::SpaceRepository},
+    },
+    shared::utils::id_generator::generate_id,
+};
+
+/// # Get Inbox Settings
+/// Reads the inbox configuration from the settings, using the defaults when
+/// it isn't configured.
+pub async fn get_inbox_settings_use_case<S: SettingsRepository>(
+    settings_repo: &S,
+) -> Result<InboxSettings, InboxError> {
+    get_settings_section(settings_repo, InboxSettings::SETTINGS_KEY)
+        .await
+        .map_err(InboxError::Settings)
+}
+
+/// # Quick Capture Use Case
+/// Captures a text or a link into the inbox, creating the inbox space,
+/// folder and note when they don't exist yet. Captures are only appended,
+/// the existing content of the inbox is never rewritten.
+/// ## Fields
+/// * `text`: Plain text or a URL.
+/// ## Returns
+/// The `QuickCapture` with the note holding it.
+#[doc(alias = "quick_capture")]
+pub async fn quick_capture_use_case<N, P, F, S>(
+    notes_repo: &N,
+    spaces_repo: &P,
+    folders_repo: &F,
+    settings_repo: &S,
+    text: &str,
+) -> Result<QuickCapture, InboxError>
+where
+    N: NoteRepository,
+    P: SpaceRepository,
+    F: FolderRepository,
+    S: SettingsRepository,
+{
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(InboxError::EmptyCapture);
+    }
+
+    let settings = get_inbox_settings_use_case(settings_repo).await?;
+    let folder = settings.folder.as_deref().filter(|f| !f.is_empty());
+
+    // Creating a space rewrites the registry, only done the first time.
+    match spaces_repo.get_space(&settings.space).await {
+        Ok(_) => {}
+        Err(SpaceError::NotFound(_)) => {
+            spaces_repo.create_space(&settings.space).await?;
+        }
+        Err(e) => return Err(e.into()),
+    }
+    if let Some(folder) = folder {
+        ensure_folder_use_case(folders_repo, &settings.space, folder).await?;
+    }
+
+    let now = Local::now();
+    let capture_id = generate_id();
+    let blocks = capture_blocks(&capture_id, text, now);
+
+    if settings.note_per_capture {
+        let base_name = format!("Quick note {}", now.format("%Y-%m-%d %H.%M"));
+        let note =
+            create_named_note_use_case(notes_repo, &settings.space, &base_name, folder).await?;
+        let content = append_blocks("", blocks)?;
+        let note = notes_repo
+            .update_note_content(
+                &settings.space,
+                &note.name,
+                content.into_bytes(),
+                folder,
+                note.revision.as_deref().unwrap_or_default(),
+            )
+            .await?;
+
+        return Ok(QuickCapture {
+            id: note.name.clone(),
+            space: settings.space,
+            note,
+        });
+    }
+
+    let existing = match notes_repo
+        .get_note_content(&settings.space, &settings.note, folder)
+        .await
+    {
+        Ok(note) => note,
+        Err(NoteError::NotFound(_)) => {
+            notes_repo
+                .create_note(&settings.space, &settings.note, folder)
+                .await?
+        }
+        Err(e) => return Err(e.into()),
+    };
+
+    let content = append_blocks(existing.content.as_deref().unwrap_or_default(), blocks)?;
+    let note = notes_repo
+        .update_note_content(
+            &settings.space,
+            &settings.note,
+            content.into_bytes(),
+            folder,
+            existing.revision.as_deref().unwrap_or_default(),
+        )
+        .await?;
+
+    Ok(QuickCapture {
+        id: capture_id,
+        space: settings.space,
+        note,
+    })
+}
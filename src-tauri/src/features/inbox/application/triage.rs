@@ -0,0 +1,148 @@
+//! # Triage **inbox** module
+//! Moves the captured items out of the inbox.
+use crate::features::{
+    inbox::{
+        application::capture::get_inbox_settings_use_case,
+        domain::{
+            errors::InboxError,
+            inbox::{capture_title, take_capture_blocks, TriagedCapture},
+        },
+    },
+    notes::{
+        application::create::create_named_note_use_case,
+        domain::{errors::NoteError, note::Note, repository::NoteRepository},
+    },
+    settings::domain::repository::SettingsRepository,
+};
+
+/// # Triage Quick Note Use Case
+/// Moves a capture into a note of the chosen space and folder.
+///
+/// Captures appended to the inbox note are cut from it, captures stored as
+/// their own note are moved. The new note is named `note_name` or, when not
+/// given, after the captured text.
+/// ## Fields
+/// * `capture_id`: The id returned by the quick capture.
+/// * `target_space`: The space where the capture goes.
+/// * `target_folder`: The folder where the capture goes.
+/// * `note_name`: The preferred name of the new note.
+#[doc(alias = "triage_quick_note")]
+pub async fn triage_quick_note_use_case<N, S>(
+    notes_repo: &N,
+    settings_repo: &S,
+    capture_id: &str,
+    target_space: &str,
+    target_folder: Option<&str>,
+    note_name: Option<&str>,
+) -> Result<TriagedCapture, InboxError>
+where
+    N: NoteRepository,
+    S: SettingsRepository,
+{
+    let settings = get_inbox_settings_use_case(settings_repo).await?;
+    let folder = settings.folder.as_deref().filter(|f| !f.is_empty());
+
+    // Captures appended to the inbox note.
+    let inbox_note = match notes_repo
+        .get_note_content(&settings.space, &settings.note, folder)
+        .await
+    {
+        Ok(note) => Some(note),
+        Err(NoteError::NotFound(_)) => None,
+        Err(e) => return Err(e.into()),
+    };
+    if let Some(inbox_note) = inbox_note {
+        match take_capture_blocks(
+            inbox_note.content.as_deref().unwrap_or_default(),
+            capture_id,
+        ) {
+            Ok((remaining, captured)) => {
+                let note = write_triaged_note(
+                    notes_repo,
+                    &captured,
+                    target_space,
+                    target_folder,
+                    note_name,
+                )
+                .await?;
+                // The new note is written first, a failure here duplicates
+                // the capture instead of losing it.
+                let inbox = notes_repo
+                    .update_note_content(
+                        &settings.space,
+                        &settings.note,
+                        remaining.into_bytes(),
+                        folder,
+                        inbox_note.revision.as_deref().unwrap_or_default(),
+                    )
+                    .await?;
+                return Ok(TriagedCapture {
+                    note,
+                    inbox,
+                    inbox_removed: false,
+                });
+            }
+            Err(InboxError::CaptureNotFound(_)) => {}
+            Err(e) => return Err(e),
+        }
+    }
+
+    // Captures stored as their own note.
+    let captured = match notes_repo
+        .get_note_content(&settings.space, capture_id, folder)
+        .await
+    {
+        Ok(note) => note,
+        Err(NoteError::NotFound(_)) => {
+            return Err(InboxError::CaptureNotFound(capture_id.to_string()))
+        }
+        Err(e) => return Err(e.into()),
+    };
+    let note = write_triaged_note(
+        notes_repo,
+        captured.content.as_deref().unwrap_or_default(),
+        target_space,
+        target_folder,
+        note_name.or(Some(capture_id)),
+    )
+    .await?;
+    notes_repo
+        .delete_note(&settings.space, capture_id, folder)
+        .await?;
+
+    Ok(TriagedCapture {
+        note,
+        inbox: Note {
+            content: None,
+            ..captured
+        },
+        inbox_removed: true,
+    })
+}
+
+/// Creates the note receiving a capture, with a collision-safe name.
+async fn write_triaged_note<N: NoteRepository>(
+    notes_repo: &N,
+    content: &str,
+    target_space: &str,
+    target_folder: Option<&str>,
+    note_name: Option<&str>,
+) -> Result<Note, InboxError> {
+    let base_name = note_name
+        .map(str::to_string)
+        .filter(|n| !n.trim().is_empty())
+        .or_else(|| capture_title(content))
+        .unwrap_or_else(|| "Quick note".to_string());
+
+    let note =
+        create_named_note_use_case(notes_repo, target_space, &base_name, target_folder).await?;
+    Ok(notes_repo
+        .update_note_content(
+            target_space,
+            &note.name,
+            content.as_bytes().to_vec(),
+            target_folder,
+            note.revision.as_deref().unwrap_or_default(),
+        )
+        .await?)
+}
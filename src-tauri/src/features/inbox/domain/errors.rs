@@ -0,0 +1,40 @@
+//! # Errors
+//! Defines all error that could ocurr inside the inbox part of the application.
+use thiserror::Error;
+
+use crate::features::{
+    folders::domain::errors::FolderError, notes::domain::errors::NoteError,
+    space::domain::errors::SpaceError,
+};
+
+/// # InboxError Enum
+/// Defines the enum with all the possible error that could ocurr during a
+/// quick capture or its triage.
+/// ## Possible Errors
+/// * `EmptyCapture`: Nothing to capture.
+/// * `CaptureNotFound`: The captured item isn't in the inbox anymore.
+/// * `InvalidContent`: The inbox note isn't valid Editor.js JSON.
+/// * `Settings`: The inbox settings couldn't be read.
+#[derive(Debug, Error)]
+pub enum InboxError {
+    #[error("Nothing to capture.")]
+    EmptyCapture,
+
+    #[error("Captured item '{0}' not found in the inbox.")]
+    CaptureNotFound(String),
+
+    #[error("The inbox note is not valid: {0}")]
+    InvalidContent(String),
+
+    #[error("Failed to read the inbox settings: {0}")]
+    Settings(String),
+
+    #[error(transparent)]
+    Note(#[from] NoteError),
+
+    #[error(transparent)]
+    Folder(#[from] FolderError),
+
+    #[error(transparent)]
+    Space(#[from] SpaceError),
+}
@@ -0,0 +1,292 @@
+//! # Inbox Domain
+//! Defines the quick notes inbox and how the captures are written as
+//! Editor.js blocks.
+use std::sync::LazyLock;
+
+use chrono::{DateTime, Local};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use super::errors::InboxError;
+use crate::{
+    features::notes::domain::{note::Note, properties::empty_document},
+    shared::utils::html::escape_html,
+};
+
+/// Matches the HTML tags of a rich text.
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]+>").unwrap());
+
+/// Longest name derived from a capture.
+const MAX_TITLE_LEN: usize = 50;
+
+/// Matches the links inside a capture.
+static URL: LazyLock<Regex> = LazyLock::new(|| Regex::new(r#"https?://[^\s<>"]+"#).unwrap());
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+/// # InboxSettings
+/// Inbox configuration, stored under the `inbox` key of the settings.
+/// ## Fields
+/// * `space`: Space holding the inbox, created on the first capture.
+/// * `folder`: Folder of the space holding the inbox.
+/// * `note`: Name of the note the captures are appended to.
+/// * `note_per_capture`: Create a new note for every capture instead.
+pub struct InboxSettings {
+    pub space: String,
+    #[serde(default)]
+    pub folder: Option<String>,
+    pub note: String,
+    #[serde(default)]
+    pub note_per_capture: bool,
+}
+
+impl Default for InboxSettings {
+    fn default() -> Self {
+        Self {
+            space: "Inbox".to_string(),
+            folder: None,
+            note: "Inbox".to_string(),
+            note_per_capture: false,
+        }
+    }
+}
+
+impl InboxSettings {
+    /// Key of the inbox configuration inside the settings.
+    pub const SETTINGS_KEY: &str = "inbox";
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # TriagedCapture
+/// The result of moving a capture out of the inbox.
+/// ## Fields
+/// * `note`: The new note holding the capture.
+/// * `inbox`: The inbox note without the capture, or the capture note that
+///   has been removed from the inbox.
+/// * `inbox_removed`: Whether `inbox` has been deleted.
+pub struct TriagedCapture {
+    pub note: Note,
+    pub inbox: Note,
+    pub inbox_removed: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # QuickCapture
+/// A captured item.
+/// ## Fields
+/// * `id`: Identifies the capture to triage it later: the prefix of its
+///   block ids when appended to the inbox note, the note name otherwise.
+/// * `space`: The inbox space.
+/// * `note`: The note holding the capture, with its content.
+pub struct QuickCapture {
+    pub id: String,
+    pub space: String,
+    pub note: Note,
+}
+
+/// # Capture blocks
+/// Builds the blocks of a capture: a timestamp followed by one paragraph per
+/// paragraph of text. Links are turned into anchors. Every block id starts
+/// with `capture_id` so the capture can be found again.
+pub fn capture_blocks(capture_id: &str, text: &str, now: DateTime<Local>) -> Vec<Value> {
+    let mut blocks = vec![json!({
+        "id": format!("{}-0", capture_id),
+        "type": "paragraph",
+        "data": { "text": format!("<i>{}</i>", now.format("%Y-%m-%d %H:%M")) }
+    })];
+
+    let paragraphs = text.split("\n\n").map(str::trim).filter(|p| !p.is_empty());
+    for (i, paragraph) in paragraphs.enumerate() {
+        blocks.push(json!({
+            "id": format!("{}-{}", capture_id, i + 1),
+            "type": "paragraph",
+            "data": { "text": linkify(paragraph).replace('\n', "<br>") }
+        }));
+    }
+    blocks
+}
+
+/// # Append blocks
+/// Appends blocks to an Editor.js content, an empty content becomes a new
+/// document.
+pub fn append_blocks(content: &str, blocks: Vec<Value>) -> Result<String, InboxError> {
+    let mut document = parse_document(content)?;
+    if let Some(Value::Array(existing)) = document.get_mut("blocks") {
+        existing.extend(blocks);
+    }
+    document["time"] = Value::from(chrono::Utc::now().timestamp_millis());
+    serde_json::to_string(&document).map_err(|e| InboxError::InvalidContent(e.to_string()))
+}
+
+/// # Take capture blocks
+/// Removes the blocks of a capture from an Editor.js content.
+/// ## Returns
+/// The remaining content and a new document with the removed blocks.
+pub fn take_capture_blocks(
+    content: &str,
+    capture_id: &str,
+) -> Result<(String, String), InboxError> {
+    let mut document = parse_document(content)?;
+    let prefix = format!("{}-", capture_id);
+
+    let blocks = match document.get_mut("blocks") {
+        Some(Value::Array(blocks)) => std::mem::take(blocks),
+        _ => Vec::new(),
+    };
+    let (taken, kept): (Vec<Value>, Vec<Value>) = blocks.into_iter().partition(|block| {
+        block
+            .get("id")
+            .and_then(Value::as_str)
+            .is_some_and(|id| id.starts_with(&prefix))
+    });
+
+    if taken.is_empty() {
+        return Err(InboxError::CaptureNotFound(capture_id.to_string()));
+    }
+    document["blocks"] = Value::Array(kept);
+
+    let mut captured = empty_document();
+    captured.insert("blocks".to_string(), Value::Array(taken));
+
+    let to_string = |value: &Value| {
+        serde_json::to_string(value).map_err(|e| InboxError::InvalidContent(e.to_string()))
+    };
+    Ok((to_string(&document)?, to_string(&Value::Object(captured))?))
+}
+
+/// # Capture title
+/// Derives a note name from the first text block of a captured document
+/// (skipping the timestamp), without markup or path separators.
+pub fn capture_title(content: &str) -> Option<String> {
+    let document: Value = serde_json::from_str(content).ok()?;
+    let text = document
+        .get("blocks")?
+        .as_array()?
+        .iter()
+        .filter(|block| {
+            !block
+                .get("id")
+                .and_then(Value::as_str)
+                .is_some_and(|id| id.ends_with("-0"))
+        })
+        .find_map(|block| block.pointer("/data/text").and_then(Value::as_str))?;
+
+    let plain = TAG
+        .replace_all(text, " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&amp;", "&");
+    let title: String = plain
+        .chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '-',
+            c => c,
+        })
+        .take(MAX_TITLE_LEN)
+        .collect::<String>()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+
+    Some(title).filter(|t| !t.is_empty())
+}
+
+/// Parses an Editor.js document, an empty content is an empty document.
+fn parse_document(content: &str) -> Result<Value, InboxError> {
+    if content.trim().is_empty() {
+        return Ok(Value::Object(empty_document()));
+    }
+    let document: Value =
+        serde_json::from_str(content).map_err(|e| InboxError::InvalidContent(e.to_string()))?;
+    if !document.get("blocks").is_some_and(Value::is_array) {
+        return Err(InboxError::InvalidContent(
+            "missing the `blocks` list".to_string(),
+        ));
+    }
+    Ok(document)
+}
+
+/// Escapes a text turning its links into anchors.
+fn linkify(text: &str) -> String {
+    let mut html = String::new();
+    let mut last = 0;
+    for url in URL.find_iter(text) {
+        html.push_str(&escape_html(&text[last..url.start()]));
+        html.push_str(&format!(
+            "<a href=\"{0}\">{0}</a>",
+            escape_html(url.as_str())
+        ));
+        last = url.end();
+    }
+    html.push_str(&escape_html(&text[last..]));
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Local> {
+        Local::now()
+    }
+
+    #[test]
+    fn captures_are_split_in_paragraphs_and_linkified() {
+        let blocks = capture_blocks("abc", "See https://example.com\n\n<b>bold</b>", now());
+
+        assert_eq!(blocks.len(), 3);
+        assert_eq!(blocks[0]["id"], "abc-0");
+        assert_eq!(
+            blocks[1]["data"]["text"],
+            "See <a href=\"https://example.com\">https://example.com</a>"
+        );
+        assert_eq!(blocks[2]["data"]["text"], "&lt;b&gt;bold&lt;/b&gt;");
+    }
+
+    #[test]
+    fn captures_are_appended_then_taken_back() {
+        let inbox = append_blocks("", capture_blocks("one", "first", now())).unwrap();
+        let inbox = append_blocks(&inbox, capture_blocks("two", "second", now())).unwrap();
+
+        let (remaining, captured) = take_capture_blocks(&inbox, "one").unwrap();
+        let remaining: Value = serde_json::from_str(&remaining).unwrap();
+        let captured: Value = serde_json::from_str(&captured).unwrap();
+
+        let ids = |document: &Value| -> Vec<String> {
+            document["blocks"]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|b| b["id"].as_str().unwrap().to_string())
+                .collect()
+        };
+        assert_eq!(ids(&remaining), vec!["two-0", "two-1"]);
+        assert_eq!(ids(&captured), vec!["one-0", "one-1"]);
+    }
+
+    #[test]
+    fn unknown_captures_and_invalid_content_fail() {
+        let inbox = append_blocks("", capture_blocks("one", "first", now())).unwrap();
+        assert!(matches!(
+            take_capture_blocks(&inbox, "on"),
+            Err(InboxError::CaptureNotFound(_))
+        ));
+        assert!(append_blocks("{}", Vec::new()).is_err());
+        assert!(append_blocks("not json", Vec::new()).is_err());
+    }
+
+    #[test]
+    fn titles_skip_the_timestamp_and_markup() {
+        let document =
+            append_blocks("", capture_blocks("one", "Buy <milk> / eggs\nnow", now())).unwrap();
+        let (_, captured) = take_capture_blocks(&document, "one").unwrap();
+
+        assert_eq!(
+            capture_title(&captured).as_deref(),
+            Some("Buy -milk- - eggs now")
+        );
+        assert_eq!(capture_title(r#"{"blocks":[]}"#), None);
+    }
+}
@@ -0,0 +1,133 @@
+//! # Tauri Commands [Inbox]
+//! Implementation of the commands for the quick notes inbox to expose them on
+//! tauri for having them in the frontend application.
+use tauri::State;
+
+use crate::{
+    features::{
+        folders::infrastructure::filesystem_repository::FileSystemFolderRepository,
+        inbox::{
+            application::{capture, triage},
+            domain::inbox::{QuickCapture, TriagedCapture},
+        },
+        search::{self, domain::search::Searchable},
+        settings::infrastructure::settings_repository::FileSystemSettingsRepository,
+    },
+    shared::state::state::AppState,
+};
+
+/// # [CREATE] Quick capture
+/// Captures a text or a link into the inbox and indexes it right away.
+/// ## Params
+/// * `text`: The plain text or URL to capture.
+#[tauri::command]
+pub async fn quick_capture_cmd(
+    state: State<'_, AppState>,
+    folders_repo: State<'_, FileSystemFolderRepository>,
+    settings_repo: State<'_, FileSystemSettingsRepository>,
+    text: &str,
+) -> Result<QuickCapture, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+    let spaces_repo_lock = state.spaces_repo.lock().await;
+
+    let capture = capture::quick_capture_use_case(
+        &*fs_repo_lock,
+        &*spaces_repo_lock,
+        &*folders_repo,
+        &*settings_repo,
+        text,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    // Deletions only affect the documents added before them, so the inbox
+    // note can be replaced within a single commit.
+    search::application::delete::delete_document_use_case(
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &capture.note.get_unique_id(&capture.space),
+    )
+    .map_err(|e| e.to_string())?;
+
+    search::application::index::index_document_use_case(
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &capture.note,
+        &capture.space,
+    )
+    .map_err(|e| e.to_string())?;
+
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(capture)
+}
+
+/// # [UPDATE] Triage quick note
+/// Moves a captured item out of the inbox into a space and folder.
+/// ## Params
+/// * `capture_id`: The id returned by `quick_capture_cmd`.
+/// * `target_space`: The space where the capture goes.
+/// * `target_folder`: The folder where the capture goes.
+/// * `note_name`: The name of the new note, derived from the capture if empty.
+#[tauri::command]
+pub async fn triage_quick_note_cmd(
+    state: State<'_, AppState>,
+    settings_repo: State<'_, FileSystemSettingsRepository>,
+    capture_id: &str,
+    target_space: &str,
+    target_folder: Option<&str>,
+    note_name: Option<&str>,
+) -> Result<TriagedCapture, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    let triaged = triage::triage_quick_note_use_case(
+        &*fs_repo_lock,
+        &*settings_repo,
+        capture_id,
+        target_space,
+        target_folder,
+        note_name,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let inbox_space = capture::get_inbox_settings_use_case(&*settings_repo)
+        .await
+        .map_err(|e| e.to_string())?
+        .space;
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    search::application::delete::delete_document_use_case(
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &triaged.inbox.get_unique_id(&inbox_space),
+    )
+    .map_err(|e| e.to_string())?;
+
+    if !triaged.inbox_removed {
+        search::application::index::index_document_use_case(
+            &search_repo_lock,
+            &mut index_writer_lock,
+            &triaged.inbox,
+            &inbox_space,
+        )
+        .map_err(|e| e.to_string())?;
+    }
+
+    search::application::index::index_document_use_case(
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &triaged.note,
+        target_space,
+    )
+    .map_err(|e| e.to_string())?;
+
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(triaged)
+}
@@ -0,0 +1,177 @@
+//! # Get **journal** module
+//! Opens (creating them when needed) the periodic notes and navigates
+//! between the existing ones.
+use chrono::{Local, NaiveDate, TimeZone};
+
+use crate::features::{
+    folders::{application::create::ensure_folder_use_case, domain::repository::FolderRepository},
+    journal::domain::{
+        errors::JournalError,
+        journal::{JournalDirection, JournalPeriod, JournalSettings},
+    },
+    notes::domain::{errors::NoteError, note::Note, repository::NoteRepository},
+    settings::{application::get::get_settings_section, domain::repository::SettingsRepository},
+    templates::domain::{
+        errors::TemplateError,
+        repository::TemplateRepository,
+        template::{TemplateScope, TemplateVariables},
+    },
+};
+
+/// # Get Journal Settings
+/// Reads the journal configuration from the settings, using the defaults
+/// for everything that isn't configured.
+pub async fn get_journal_settings_use_case<S: SettingsRepository>(
+    settings_repo: &S,
+) -> Result<JournalSettings, JournalError> {
+    get_settings_section(settings_repo, JournalSettings::SETTINGS_KEY)
+        .await
+        .map_err(JournalError::Settings)
+}
+
+/// # Get Or Create Journal Note
+/// Opens the entry of the period containing `date`, creating it (with its
+/// folder and the configured template) if it doesn't exist yet.
+/// ## Fields
+/// * `space_name`: The space of the journal.
+/// * `period`: Daily, weekly or monthly.
+/// * `date`: Any day of the period.
+/// ## Returns
+/// The `Note` with its content and whether it has just been created.
+#[allow(clippy::too_many_arguments)]
+#[doc(alias = "get_or_create_daily_note")]
+pub async fn get_or_create_journal_note_use_case<N, F, T, S>(
+    notes_repo: &N,
+    folders_repo: &F,
+    templates_repo: &T,
+    settings_repo: &S,
+    space_name: &str,
+    period: JournalPeriod,
+    date: NaiveDate,
+) -> Result<(Note, bool), JournalError>
+where
+    N: NoteRepository,
+    F: FolderRepository,
+    T: TemplateRepository,
+    S: SettingsRepository,
+{
+    let settings = get_journal_settings_use_case(settings_repo).await?;
+    let period_settings = settings.period(period);
+    let note_name = period.note_name(date, &period_settings.format)?;
+    let folder = normalize_folder(&period_settings.folder);
+
+    match notes_repo
+        .get_note_content(space_name, &note_name, folder.as_deref())
+        .await
+    {
+        Ok(note) => return Ok((note, false)),
+        Err(NoteError::NotFound(_)) => {}
+        Err(e) => return Err(e.into()),
+    }
+
+    // The template is read first, a missing one leaves no empty entry.
+    let content = match period_settings.template.as_deref() {
+        Some(template_name) => {
+            let template = match templates_repo
+                .get_template(Some(space_name), template_name, TemplateScope::Space)
+                .await
+            {
+                Err(TemplateError::NotFound(_)) => {
+                    templates_repo
+                        .get_template(None, template_name, TemplateScope::Global)
+                        .await?
+                }
+                template => template?,
+            };
+            // Placeholders refer to the journal day, not to the creation time.
+            let variables = TemplateVariables {
+                now: Local
+                    .from_local_datetime(&period.start_of(date).and_hms_opt(0, 0, 0).unwrap())
+                    .earliest()
+                    .unwrap_or_else(Local::now),
+                title: note_name.clone(),
+                space: space_name.to_string(),
+            };
+            variables.expand(template.content.as_deref().unwrap_or_default())
+        }
+        None => String::new(),
+    };
+
+    if let Some(folder) = folder.as_deref() {
+        ensure_folder_use_case(folders_repo, space_name, folder).await?;
+    }
+    let created = notes_repo
+        .create_note(space_name, &note_name, folder.as_deref())
+        .await?;
+
+    let written = notes_repo
+        .update_note_content(
+            space_name,
+            &note_name,
+            content.into_bytes(),
+            folder.as_deref(),
+            created.revision.as_deref().unwrap_or_default(),
+        )
+        .await;
+    if written.is_err() {
+        let _ = notes_repo
+            .delete_note(space_name, &note_name, folder.as_deref())
+            .await;
+    }
+    Ok((written?, true))
+}
+
+/// # Get Adjacent Journal Note
+/// Finds the previous or next existing entry of a period, starting from the
+/// entry named `note_name`.
+/// ## Returns
+/// The adjacent `Note` without content, or `None` when there isn't one.
+#[doc(alias = "get_adjacent_journal_note")]
+pub async fn get_adjacent_journal_note_use_case<N, S>(
+    notes_repo: &N,
+    settings_repo: &S,
+    space_name: &str,
+    period: JournalPeriod,
+    note_name: &str,
+    direction: JournalDirection,
+) -> Result<Option<Note>, JournalError>
+where
+    N: NoteRepository,
+    S: SettingsRepository,
+{
+    let settings = get_journal_settings_use_case(settings_repo).await?;
+    let period_settings = settings.period(period);
+    let folder = normalize_folder(&period_settings.folder);
+
+    let current = period
+        .parse_date(note_name, &period_settings.format)
+        .ok_or_else(|| JournalError::InvalidName(note_name.to_string()))?;
+
+    let entries = notes_repo
+        .get_notes(space_name)
+        .await?
+        .into_iter()
+        .filter(|note| normalize_folder(note.folder.as_deref().unwrap_or_default()) == folder)
+        .filter_map(|note| {
+            period
+                .parse_date(&note.name, &period_settings.format)
+                .map(|date| (date, note))
+        });
+
+    let adjacent = match direction {
+        JournalDirection::Previous => entries
+            .filter(|(date, _)| *date < current)
+            .max_by_key(|(date, _)| *date),
+        JournalDirection::Next => entries
+            .filter(|(date, _)| *date > current)
+            .min_by_key(|(date, _)| *date),
+    };
+
+    Ok(adjacent.map(|(_, note)| note))
+}
+
+/// Normalizes a folder path to `/` separators, `None` for the space root.
+fn normalize_folder(folder: &str) -> Option<String> {
+    let folder = folder.replace('\\', "/").trim_matches('/').to_string();
+    Some(folder).filter(|f| !f.is_empty())
+}
@@ -0,0 +1,36 @@
+//! # Errors
+//! Defines all error that could ocurr inside the journal part of the application.
+use thiserror::Error;
+
+use crate::features::{
+    folders::domain::errors::FolderError, notes::domain::errors::NoteError,
+    templates::domain::errors::TemplateError,
+};
+
+/// # JournalError Enum
+/// Defines the enum with all the possible error that could ocurr during a
+/// Journal operation in the application.
+/// ## Possible Errors
+/// * `InvalidFormat`: The name format configured can't be used as a note name.
+/// * `InvalidName`: A note name doesn't match the journal name format.
+/// * `Settings`: The journal settings couldn't be read.
+#[derive(Debug, Error)]
+pub enum JournalError {
+    #[error("Invalid journal name format: {0}")]
+    InvalidFormat(String),
+
+    #[error("Note '{0}' is not a journal entry.")]
+    InvalidName(String),
+
+    #[error("Failed to read the journal settings: {0}")]
+    Settings(String),
+
+    #[error(transparent)]
+    Note(#[from] NoteError),
+
+    #[error(transparent)]
+    Folder(#[from] FolderError),
+
+    #[error(transparent)]
+    Template(#[from] TemplateError),
+}
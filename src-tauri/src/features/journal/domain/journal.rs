@@ -0,0 +1,224 @@
+//! # Journal Domain
+//! Defines the periodic notes (daily, weekly, monthly) and how they are named.
+use std::fmt::Write;
+
+use chrono::{Datelike, Duration, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use super::errors::JournalError;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// # JournalPeriod
+/// The period covered by a journal note.
+pub enum JournalPeriod {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// # JournalDirection
+/// Direction used to navigate between the existing journal entries.
+pub enum JournalDirection {
+    Previous,
+    Next,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # PeriodSettings
+/// Configuration of one journal period.
+/// ## Fields
+/// * `folder`: Folder of the space where the entries are created.
+/// * `format`: `chrono` format used to name the entries.
+/// * `template`: Template used to pre-fill new entries, looked up in the
+///   space templates first and then in the global ones.
+pub struct PeriodSettings {
+    pub folder: String,
+    pub format: String,
+    #[serde(default)]
+    pub template: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # JournalSettings
+/// Journal configuration, stored under the `journal` key of the settings:
+/// ```json
+/// "journal": {
+///     "daily": { "folder": "Journal", "format": "%Y-%m-%d", "template": "Daily" }
+/// }
+/// ```
+/// Missing periods use the defaults.
+pub struct JournalSettings {
+    #[serde(default = "JournalSettings::default_daily")]
+    pub daily: PeriodSettings,
+    #[serde(default = "JournalSettings::default_weekly")]
+    pub weekly: PeriodSettings,
+    #[serde(default = "JournalSettings::default_monthly")]
+    pub monthly: PeriodSettings,
+}
+
+impl Default for JournalSettings {
+    fn default() -> Self {
+        Self {
+            daily: Self::default_daily(),
+            weekly: Self::default_weekly(),
+            monthly: Self::default_monthly(),
+        }
+    }
+}
+
+impl JournalSettings {
+    /// Key of the journal configuration inside the settings.
+    pub const SETTINGS_KEY: &str = "journal";
+
+    fn default_daily() -> PeriodSettings {
+        PeriodSettings {
+            folder: "Journal".to_string(),
+            format: "%Y-%m-%d".to_string(),
+            template: None,
+        }
+    }
+
+    fn default_weekly() -> PeriodSettings {
+        PeriodSettings {
+            folder: "Journal".to_string(),
+            format: "%G-W%V".to_string(),
+            template: None,
+        }
+    }
+
+    fn default_monthly() -> PeriodSettings {
+        PeriodSettings {
+            folder: "Journal".to_string(),
+            format: "%Y-%m".to_string(),
+            template: None,
+        }
+    }
+
+    /// Returns the configuration of a period.
+    pub fn period(&self, period: JournalPeriod) -> &PeriodSettings {
+        match period {
+            JournalPeriod::Daily => &self.daily,
+            JournalPeriod::Weekly => &self.weekly,
+            JournalPeriod::Monthly => &self.monthly,
+        }
+    }
+}
+
+impl JournalPeriod {
+    /// # Start of period
+    /// The first day of the period containing `date`: the day itself, the
+    /// monday of its ISO week or the first day of its month.
+    pub fn start_of(&self, date: NaiveDate) -> NaiveDate {
+        match self {
+            JournalPeriod::Daily => date,
+            JournalPeriod::Weekly => {
+                date - Duration::days(date.weekday().num_days_from_monday() as i64)
+            }
+            JournalPeriod::Monthly => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    /// # Note name
+    /// Formats the name of the entry of the period containing `date`.
+    pub fn note_name(&self, date: NaiveDate, format: &str) -> Result<String, JournalError> {
+        let mut name = String::new();
+        write!(name, "{}", self.start_of(date).format(format))
+            .map_err(|_| JournalError::InvalidFormat(format.to_string()))?;
+
+        if name.trim().is_empty() || name.contains(['/', '\\']) {
+            return Err(JournalError::InvalidFormat(format.to_string()));
+        }
+        Ok(name)
+    }
+
+    /// # Parse date
+    /// Gets back the start of the period from an entry name. Formats that
+    /// don't carry a full date (`%G-W%V`, `%Y-%m`) are completed with the
+    /// first day of the week or the month.
+    pub fn parse_date(&self, name: &str, format: &str) -> Option<NaiveDate> {
+        let date = NaiveDate::parse_from_str(name, format)
+            .or_else(|_| {
+                NaiveDate::parse_from_str(&format!("{} 1", name), &format!("{} %u", format))
+            })
+            .or_else(|_| {
+                NaiveDate::parse_from_str(&format!("{} 01", name), &format!("{} %d", format))
+            })
+            .ok()?;
+
+        // Only names written by this format belong to the journal.
+        match self.note_name(date, format) {
+            Ok(formatted) if formatted == name => Some(self.start_of(date)),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn note_names_use_the_start_of_the_period() {
+        let thursday = date(2024, 5, 16);
+        assert_eq!(
+            JournalPeriod::Daily
+                .note_name(thursday, "%Y-%m-%d")
+                .unwrap(),
+            "2024-05-16"
+        );
+        assert_eq!(
+            JournalPeriod::Weekly.note_name(thursday, "%G-W%V").unwrap(),
+            "2024-W20"
+        );
+        assert_eq!(
+            JournalPeriod::Monthly.note_name(thursday, "%Y-%m").unwrap(),
+            "2024-05"
+        );
+    }
+
+    #[test]
+    fn formats_producing_paths_are_rejected() {
+        assert!(JournalPeriod::Daily
+            .note_name(date(2024, 5, 16), "%Y/%m/%d")
+            .is_err());
+        assert!(JournalPeriod::Daily
+            .note_name(date(2024, 5, 16), " ")
+            .is_err());
+    }
+
+    #[test]
+    fn dates_are_parsed_back() {
+        assert_eq!(
+            JournalPeriod::Daily.parse_date("2024-05-16", "%Y-%m-%d"),
+            Some(date(2024, 5, 16))
+        );
+        assert_eq!(
+            JournalPeriod::Weekly.parse_date("2024-W20", "%G-W%V"),
+            Some(date(2024, 5, 13))
+        );
+        assert_eq!(
+            JournalPeriod::Monthly.parse_date("2024-05", "%Y-%m"),
+            Some(date(2024, 5, 1))
+        );
+    }
+
+    #[test]
+    fn names_not_written_by_the_format_are_ignored() {
+        assert_eq!(
+            JournalPeriod::Daily.parse_date("Groceries", "%Y-%m-%d"),
+            None
+        );
+        assert_eq!(
+            JournalPeriod::Daily.parse_date("2024-5-16", "%Y-%m-%d"),
+            None
+        );
+        assert_eq!(JournalPeriod::Monthly.parse_date("2024-13", "%Y-%m"), None);
+    }
+}
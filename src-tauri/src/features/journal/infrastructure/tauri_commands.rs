@@ -0,0 +1,125 @@
+//! # Tauri Commands [Journal]
+//! Implementation of the commands for the daily, weekly and monthly notes to
+//! expose them on tauri for having them in the frontend application.
+use chrono::{Local, NaiveDate};
+use tauri::State;
+
+use crate::{
+    features::{
+        folders::infrastructure::filesystem_repository::FileSystemFolderRepository,
+        journal::{
+            application::get,
+            domain::journal::{JournalDirection, JournalPeriod},
+        },
+        notes::domain::note::Note,
+        search,
+        settings::infrastructure::settings_repository::FileSystemSettingsRepository,
+        templates::infrastructure::filesystem_repository::FileSystemTemplateRepository,
+    },
+    shared::state::state::AppState,
+};
+
+/// # [GET] Daily note
+/// Opens today's note (or the one of `date`), creating it if needed.
+/// ## Params
+/// * `space_name`: The space of the journal.
+/// * `date`: The day of the note, today if not given.
+#[tauri::command]
+pub async fn get_or_create_daily_note_cmd(
+    state: State<'_, AppState>,
+    folders_repo: State<'_, FileSystemFolderRepository>,
+    templates_repo: State<'_, FileSystemTemplateRepository>,
+    settings_repo: State<'_, FileSystemSettingsRepository>,
+    space_name: &str,
+    date: Option<NaiveDate>,
+) -> Result<Note, String> {
+    get_or_create_journal_note_cmd(
+        state,
+        folders_repo,
+        templates_repo,
+        settings_repo,
+        space_name,
+        JournalPeriod::Daily,
+        date,
+    )
+    .await
+}
+
+/// # [GET] Journal note
+/// Opens the daily, weekly or monthly note containing `date`, creating it if
+/// needed. New notes are indexed right away.
+/// ## Params
+/// * `space_name`: The space of the journal.
+/// * `period`: `daily`, `weekly` or `monthly`.
+/// * `date`: Any day of the period, today if not given.
+#[tauri::command]
+pub async fn get_or_create_journal_note_cmd(
+    state: State<'_, AppState>,
+    folders_repo: State<'_, FileSystemFolderRepository>,
+    templates_repo: State<'_, FileSystemTemplateRepository>,
+    settings_repo: State<'_, FileSystemSettingsRepository>,
+    space_name: &str,
+    period: JournalPeriod,
+    date: Option<NaiveDate>,
+) -> Result<Note, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    let (note, created) = get::get_or_create_journal_note_use_case(
+        &*fs_repo_lock,
+        &*folders_repo,
+        &*templates_repo,
+        &*settings_repo,
+        space_name,
+        period,
+        date.unwrap_or_else(|| Local::now().date_naive()),
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    if created {
+        let search_repo_lock = state.search_repo.lock().await;
+        let mut index_writer_lock = state.index_writer.lock().await;
+
+        search::application::index::index_document_use_case(
+            &search_repo_lock,
+            &mut index_writer_lock,
+            &note,
+            space_name,
+        )
+        .map_err(|e| e.to_string())?;
+
+        index_writer_lock.commit().map_err(|e| e.to_string())?;
+    }
+
+    Ok(note)
+}
+
+/// # [GET] Adjacent journal note
+/// Finds the previous or next existing entry of a journal.
+/// ## Params
+/// * `space_name`: The space of the journal.
+/// * `period`: `daily`, `weekly` or `monthly`.
+/// * `note_name`: The entry to start from.
+/// * `direction`: `previous` or `next`.
+#[tauri::command]
+pub async fn get_adjacent_journal_note_cmd(
+    state: State<'_, AppState>,
+    settings_repo: State<'_, FileSystemSettingsRepository>,
+    space_name: &str,
+    period: JournalPeriod,
+    note_name: &str,
+    direction: JournalDirection,
+) -> Result<Option<Note>, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    get::get_adjacent_journal_note_use_case(
+        &*fs_repo_lock,
+        &*settings_repo,
+        space_name,
+        period,
+        note_name,
+        direction,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
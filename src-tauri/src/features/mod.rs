@@ -1,7 +1,21 @@
 pub mod ai;
+pub mod archive;
+pub mod attachments;
+pub mod batch;
+pub mod duplicate;
+pub mod encryption;
 pub mod folders;
+pub mod health;
+pub mod import;
+pub mod inbox;
+pub mod journal;
 pub mod notes;
 pub mod search;
 pub mod settings;
+pub mod site;
 pub mod space;
+pub mod stats;
+pub mod templates;
 pub mod theming;
+pub mod tree;
+pub mod watcher;
@@ -1,4 +1,9 @@
-use crate::features::notes::domain::{errors::NoteError, note::Note, repository::NoteRepository};
+use std::io::ErrorKind;
+
+use crate::{
+    features::notes::domain::{errors::NoteError, note::Note, repository::NoteRepository},
+    shared::utils::file_name::is_valid_name,
+};
 
 /// # Create Note Use Case
 ///
@@ -10,8 +15,8 @@ use crate::features::notes::domain::{errors::NoteError, note::Note, repository::
 /// * `folder_path`: The path of the folder to create the note in.
 ///
 /// ## Returns
-/// A `Result` containing a `Vec` of `Note` if successful, or a `NoteError` if
-/// a problem occurred.
+/// A `Result` containing the created `Note` with its revision if successful,
+/// or a `NoteError` if a problem occurred.
 #[doc(alias = "create_note")]
 pub async fn create_note_use_case<T: NoteRepository>(
     repo: &T,
@@ -24,13 +29,8 @@ pub async fn create_note_use_case<T: NoteRepository>(
     loop {
         let note_name = format!("Untitled {}", note_number);
         match repo.create_note(space_name, &note_name, folder_path).await {
-            Ok(_) => {
-                return Ok(Note {
-                    name: note_name,
-                    content: None,
-                    folder: folder_path.map(|s| s.to_string()),
-                })
-            }
+            // The revision of the new note is needed for its first save.
+            Ok(note) => return Ok(note),
             Err(e) if matches!(e, NoteError::Io(_)) => {
                 note_number += 1;
                 continue;
@@ -39,3 +39,50 @@ pub async fn create_note_use_case<T: NoteRepository>(
         }
     }
 }
+
+/// # Create Named Note Use Case
+///
+/// Used to create a note named after `base_name`, when the name is already
+/// taken it tries `base_name 2`, `base_name 3`... in the same way
+/// `create_note_use_case` does with "Untitled n".
+/// ## Fields
+/// * `repo` (&T): The repository that is implemented on the function.
+/// * `space_name` (&str): The name of the space to create the note in.
+/// * `base_name` (&str): The preferred name of the note.
+/// * `folder_path`: The path of the folder to create the note in.
+///
+/// ## Returns
+/// A `Result` containing the created `Note`, or a `NoteError` if a problem
+/// occurred, `InvalidName` when `base_name` isn't a single path segment.
+#[doc(alias = "create_note")]
+pub async fn create_named_note_use_case<T: NoteRepository>(
+    repo: &T,
+    space_name: &str,
+    base_name: &str,
+    folder_path: Option<&str>,
+) -> Result<Note, NoteError> {
+    let base_name = base_name.trim();
+    if base_name.is_empty() {
+        return Err(NoteError::EmptyName);
+    }
+    if !is_valid_name(base_name) {
+        return Err(NoteError::InvalidName(base_name.to_string()));
+    }
+
+    let mut note_number = 1;
+    loop {
+        let note_name = if note_number == 1 {
+            base_name.to_string()
+        } else {
+            format!("{} {}", base_name, note_number)
+        };
+        match repo.create_note(space_name, &note_name, folder_path).await {
+            Ok(note) => return Ok(note),
+            Err(NoteError::Io(e)) if e.kind() == ErrorKind::AlreadyExists => {
+                note_number += 1;
+                continue;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
@@ -0,0 +1,35 @@
+//! # Integrity **notes** module
+//! Use cases checking that the note files survived an unclean shutdown.
+use crate::features::{
+    notes::domain::{errors::NoteError, integrity::IntegrityReport, repository::NoteRepository},
+    space::domain::repository::SpaceRepository,
+};
+
+/// # Check Notes Integrity
+/// Recovers the interrupted saves and finds the broken notes of every space.
+/// The locked encrypted spaces are skipped, they're checked when unlocked.
+/// ## Fields
+/// * `notes_repo` (&N): The notes repository.
+/// * `spaces_repo` (&S): The spaces repository, to list the spaces.
+/// ## Returns
+/// One `IntegrityReport` per space.
+#[doc(alias = "check_notes_integrity")]
+pub async fn check_notes_integrity_use_case<N: NoteRepository, S: SpaceRepository>(
+    notes_repo: &N,
+    spaces_repo: &S,
+) -> Result<Vec<IntegrityReport>, NoteError> {
+    let spaces = spaces_repo
+        .get_spaces()
+        .await
+        .map_err(|e| NoteError::NotFound(e.to_string()))?;
+
+    let mut reports = Vec::new();
+    for space in spaces {
+        match notes_repo.check_integrity(&space.name).await {
+            Ok(report) => reports.push(report),
+            Err(e) if e.is_space_locked() => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(reports)
+}
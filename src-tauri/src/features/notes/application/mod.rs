@@ -1,4 +1,6 @@
 pub mod create;
 pub mod delete;
 pub mod get;
+pub mod integrity;
+pub mod properties;
 pub mod update;
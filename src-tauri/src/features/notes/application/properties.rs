@@ -0,0 +1,53 @@
+//! # Properties **notes** module
+//! Use cases related to the typed properties of the notes (tags, status...).
+use crate::features::{
+    notes::domain::{
+        errors::NoteError,
+        note::Note,
+        properties::{NoteProperties, TagCount},
+        repository::NoteRepository,
+    },
+    search::{
+        domain::error::SearchError, infrastructure::search_repository::TantivySearchRepository,
+    },
+};
+
+/// # Set Note Properties
+/// Normalizes and replaces the properties of a note.
+/// ## Fields
+/// * `repo` (&T): The repo that is implemented on the function
+/// * `space_name`: The space of the note.
+/// * `note_name`: The name of the note.
+/// * `properties`: The new properties of the note.
+/// * `folder_path`: The folder path of the note.
+#[doc(alias = "set_note_properties")]
+pub async fn set_note_properties_use_case<T: NoteRepository>(
+    repo: &T,
+    space_name: &str,
+    note_name: &str,
+    properties: NoteProperties,
+    folder_path: Option<&str>,
+) -> Result<Note, NoteError> {
+    let properties = properties.normalize()?;
+    repo.update_note_properties(space_name, note_name, properties, folder_path)
+        .await
+}
+
+/// # Get Space Tags
+/// Lists every tag used in a space with the number of notes using it,
+/// sorted by name. The tags are counted in the search index, the private
+/// one of an unlocked encrypted space, rather than reading every note.
+/// ## Fields
+/// * `search_repo`: The search index.
+/// * `space_name`: The space to list the tags from.
+#[doc(alias = "get_tags")]
+pub fn get_space_tags_use_case(
+    search_repo: &TantivySearchRepository,
+    space_name: &str,
+) -> Result<Vec<TagCount>, SearchError> {
+    Ok(search_repo
+        .get_space_tags(space_name)?
+        .into_iter()
+        .map(|(name, count)| TagCount { name, count })
+        .collect())
+}
@@ -1,4 +1,9 @@
-use crate::features::notes::domain::{errors::NoteError, note::Note, repository::NoteRepository};
+use crate::features::notes::domain::{
+    conflict::{merge_blocks, NoteConflict},
+    errors::NoteError,
+    note::Note,
+    repository::NoteRepository,
+};
 
 /// # Update Note Content
 /// Saves and updates the content of a note.
@@ -8,6 +13,7 @@ use crate::features::notes::domain::{errors::NoteError, note::Note, repository::
 /// * `note_name`: The name of the note to be saved / udpated.
 /// * `content`: The content to be saved / updated on the note.
 /// * `folder_path`: The folder path of the note.
+/// * `expected_revision`: The revision the content was based on.
 #[doc(alias = "update_note_content")]
 pub async fn update_note_content_use_case<T: NoteRepository>(
     repo: &T,
@@ -15,9 +21,62 @@ pub async fn update_note_content_use_case<T: NoteRepository>(
     note_name: &str,
     content: Vec<u8>,
     folder_path: Option<&str>,
+    expected_revision: &str,
 ) -> Result<Note, NoteError> {
-    repo.update_note_content(space_name, note_name, content, folder_path)
-        .await
+    repo.update_note_content(
+        space_name,
+        note_name,
+        content,
+        folder_path,
+        expected_revision,
+    )
+    .await
+}
+
+/// # Merge Note Content
+/// Saves a content that conflicted with the stored one, merging the blocks
+/// edited on each side.
+/// ## Fields
+/// * `repo` (&T): The repo that is implemented on the function
+/// * `space_name`: The space of the note.
+/// * `note_name`: The name of the note.
+/// * `base_content`: The content the edits started from.
+/// * `content`: The content that couldn't be saved.
+/// * `folder_path`: The folder path of the note.
+/// ## Returns
+/// The saved note, or a `NoteError::Conflict` listing the blocks edited on
+/// both sides.
+#[doc(alias = "merge_note_content")]
+pub async fn merge_note_content_use_case<T: NoteRepository>(
+    repo: &T,
+    space_name: &str,
+    note_name: &str,
+    base_content: &str,
+    content: &str,
+    folder_path: Option<&str>,
+) -> Result<Note, NoteError> {
+    let current = repo
+        .get_note_content(space_name, note_name, folder_path)
+        .await?;
+    let stored = current.content.as_deref().unwrap_or_default();
+
+    match merge_blocks(base_content, content, stored) {
+        Ok(merged) => {
+            repo.update_note_content(
+                space_name,
+                note_name,
+                merged.into_bytes(),
+                folder_path,
+                current.revision.as_deref().unwrap_or_default(),
+            )
+            .await
+        }
+        Err(blocks) => Err(NoteError::Conflict(Box::new(NoteConflict {
+            current,
+            incoming: content.to_string(),
+            blocks,
+        }))),
+    }
 }
 
 /// # Update Note Name
@@ -58,3 +117,23 @@ pub async fn update_note_route_use_case<T: NoteRepository>(
     repo.update_note_route(space_name, note_name, old_folder, new_folder)
         .await
 }
+
+/// # Set Note Locked
+/// Locks (read-only) or unlocks a note.
+/// ## Fields
+/// * `repo` (&T): The repo that is implemented on the function
+/// * `space_name`: The space of the note.
+/// * `note_name`: The name of the note.
+/// * `folder_path`: The folder path of the note.
+/// * `locked`: The new lock state.
+#[doc(alias = "lock_note")]
+pub async fn set_note_locked_use_case<T: NoteRepository>(
+    repo: &T,
+    space_name: &str,
+    note_name: &str,
+    folder_path: Option<&str>,
+    locked: bool,
+) -> Result<Note, NoteError> {
+    repo.set_note_locked(space_name, note_name, folder_path, locked)
+        .await
+}
@@ -0,0 +1,238 @@
+//! # Conflicts Domain
+//! Defines the conflicts between two saves of the same note and how the
+//! Editor.js blocks of both versions are merged.
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use super::{note::Note, properties::empty_document};
+
+/// # Content revision
+/// The revision token of a note content: the SHA-256 of the stored file.
+pub fn content_revision(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # NoteConflict
+/// A save rejected because the note changed since it was read.
+/// ## Fields
+/// * `current`: The stored note, with its content and revision.
+/// * `incoming`: The content that couldn't be saved.
+/// * `blocks`: The ids of the blocks edited on both sides, empty when the
+///   versions haven't been merged.
+pub struct NoteConflict {
+    pub current: Note,
+    pub incoming: String,
+    #[serde(default)]
+    pub blocks: Vec<String>,
+}
+
+/// # Merge blocks
+/// Three-way merge of Editor.js documents. Blocks are matched by id: a block
+/// changed (or added, or removed) on one side only takes that side's version.
+/// The order of `theirs` is kept, unless only `ours` reordered the blocks,
+/// and the blocks added on the other side follow their previous block.
+/// ## Params
+/// * `base`: The content both sides started from.
+/// * `ours`: The content being saved.
+/// * `theirs`: The content stored meanwhile.
+/// ## Returns
+/// The merged content, or the ids of the blocks changed on both sides. No ids
+/// are returned when a version isn't a valid document.
+pub fn merge_blocks(base: &str, ours: &str, theirs: &str) -> Result<String, Vec<String>> {
+    let base_blocks = parse_blocks(base).ok_or_else(Vec::new)?;
+    let our_blocks = parse_blocks(ours).ok_or_else(Vec::new)?;
+    let their_blocks = parse_blocks(theirs).ok_or_else(Vec::new)?;
+    let mut document = match theirs.trim() {
+        "" => Value::Object(empty_document()),
+        _ => serde_json::from_str(theirs).map_err(|_| Vec::new())?,
+    };
+
+    let base_map = by_id(&base_blocks);
+    let our_map = by_id(&our_blocks);
+    let their_map = by_id(&their_blocks);
+
+    // Decide the version of every block.
+    let mut merged: HashMap<&str, &Value> = HashMap::new();
+    let mut conflicts = Vec::new();
+    let mut ids: Vec<&str> = their_blocks.iter().map(|(id, _)| id.as_str()).collect();
+    ids.extend(our_blocks.iter().map(|(id, _)| id.as_str()));
+    ids.extend(base_blocks.iter().map(|(id, _)| id.as_str()));
+    let mut seen = HashSet::new();
+    for id in ids.into_iter().filter(|id| seen.insert(*id)) {
+        let (b, o, t) = (base_map.get(id), our_map.get(id), their_map.get(id));
+        let chosen = if o == t || o == b {
+            t
+        } else if t == b {
+            o
+        } else {
+            conflicts.push(id.to_string());
+            continue;
+        };
+        if let Some(block) = chosen {
+            merged.insert(id, block);
+        }
+    }
+    if !conflicts.is_empty() {
+        return Err(conflicts);
+    }
+
+    // Pick the order of one side and slot in the blocks only the other has.
+    let base_order = order_of(&base_blocks, &base_map);
+    let (skeleton, other) = if order_of(&their_blocks, &base_map) == base_order
+        && order_of(&our_blocks, &base_map) != base_order
+    {
+        (&our_blocks, &their_blocks)
+    } else {
+        (&their_blocks, &our_blocks)
+    };
+
+    let mut order: Vec<&str> = skeleton.iter().map(|(id, _)| id.as_str()).collect();
+    for (position, (id, _)) in other.iter().enumerate() {
+        if order.contains(&id.as_str()) {
+            continue;
+        }
+        let after = other[..position]
+            .iter()
+            .rev()
+            .find_map(|(prev, _)| order.iter().position(|o| o == prev));
+        order.insert(after.map_or(0, |i| i + 1), id.as_str());
+    }
+
+    let blocks = order
+        .into_iter()
+        .filter_map(|id| merged.get(id).map(|block| (*block).clone()))
+        .collect();
+    document["blocks"] = Value::Array(blocks);
+    document["time"] = Value::from(chrono::Utc::now().timestamp_millis());
+
+    serde_json::to_string(&document).map_err(|_| Vec::new())
+}
+
+/// Reads the blocks of a document with their ids, an empty content has no
+/// blocks. Blocks without an id are identified by their position.
+fn parse_blocks(content: &str) -> Option<Vec<(String, Value)>> {
+    if content.trim().is_empty() {
+        return Some(Vec::new());
+    }
+    let document: Value = serde_json::from_str(content).ok()?;
+    let blocks = document.get("blocks")?.as_array()?;
+    Some(
+        blocks
+            .iter()
+            .enumerate()
+            .map(|(i, block)| {
+                let id = block
+                    .get("id")
+                    .and_then(Value::as_str)
+                    .map_or_else(|| format!("#{}", i), str::to_string);
+                (id, block.clone())
+            })
+            .collect(),
+    )
+}
+
+/// Indexes the blocks by id.
+fn by_id(blocks: &[(String, Value)]) -> HashMap<&str, &Value> {
+    blocks
+        .iter()
+        .map(|(id, block)| (id.as_str(), block))
+        .collect()
+}
+
+/// The order of the blocks that exist in the base document.
+fn order_of<'a>(blocks: &'a [(String, Value)], base: &HashMap<&str, &Value>) -> Vec<&'a str> {
+    blocks
+        .iter()
+        .map(|(id, _)| id.as_str())
+        .filter(|id| base.contains_key(id))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(blocks: &[(&str, &str)]) -> String {
+        let blocks: Vec<Value> = blocks
+            .iter()
+            .map(|(id, text)| serde_json::json!({"id": id, "type": "paragraph", "data": {"text": text}}))
+            .collect();
+        serde_json::json!({"time": 1, "blocks": blocks}).to_string()
+    }
+
+    fn texts(content: &str) -> Vec<String> {
+        let document: Value = serde_json::from_str(content).unwrap();
+        document["blocks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|b| b["data"]["text"].as_str().unwrap().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn revision_follows_the_content() {
+        assert_eq!(content_revision("a"), content_revision("a"));
+        assert_ne!(content_revision("a"), content_revision("b"));
+        assert_eq!(content_revision("").len(), 64);
+    }
+
+    #[test]
+    fn edits_of_different_blocks_are_merged() {
+        let base = doc(&[("a", "one"), ("b", "two")]);
+        let ours = doc(&[("a", "ONE"), ("b", "two")]);
+        let theirs = doc(&[("a", "one"), ("b", "TWO")]);
+        let merged = merge_blocks(&base, &ours, &theirs).unwrap();
+        assert_eq!(texts(&merged), vec!["ONE", "TWO"]);
+    }
+
+    #[test]
+    fn blocks_added_on_both_sides_follow_their_previous_block() {
+        let base = doc(&[("a", "one"), ("b", "two")]);
+        let ours = doc(&[("a", "one"), ("x", "ours"), ("b", "two")]);
+        let theirs = doc(&[("a", "one"), ("b", "two"), ("y", "theirs")]);
+        let merged = merge_blocks(&base, &ours, &theirs).unwrap();
+        assert_eq!(texts(&merged), vec!["one", "ours", "two", "theirs"]);
+    }
+
+    #[test]
+    fn our_reordering_is_kept_when_theirs_only_edited() {
+        let base = doc(&[("a", "one"), ("b", "two"), ("c", "three")]);
+        let ours = doc(&[("c", "three"), ("a", "one"), ("b", "two")]);
+        let theirs = doc(&[("a", "one"), ("b", "TWO"), ("c", "three")]);
+        let merged = merge_blocks(&base, &ours, &theirs).unwrap();
+        assert_eq!(texts(&merged), vec!["three", "one", "TWO"]);
+    }
+
+    #[test]
+    fn removed_blocks_stay_removed() {
+        let base = doc(&[("a", "one"), ("b", "two"), ("c", "three")]);
+        let ours = doc(&[("a", "ONE"), ("b", "two"), ("c", "three")]);
+        let theirs = doc(&[("a", "one"), ("c", "three")]);
+        let merged = merge_blocks(&base, &ours, &theirs).unwrap();
+        assert_eq!(texts(&merged), vec!["ONE", "three"]);
+    }
+
+    #[test]
+    fn blocks_edited_on_both_sides_conflict() {
+        let base = doc(&[("a", "one"), ("b", "two")]);
+        let ours = doc(&[("a", "mine"), ("b", "two")]);
+        let theirs = doc(&[("a", "yours"), ("b", "two")]);
+        assert_eq!(
+            merge_blocks(&base, &ours, &theirs),
+            Err(vec!["a".to_string()])
+        );
+    }
+
+    #[test]
+    fn invalid_documents_conflict_without_ids() {
+        let base = doc(&[("a", "one")]);
+        assert_eq!(merge_blocks(&base, "not json", &base), Err(Vec::new()));
+        let merged = merge_blocks("", &doc(&[("a", "one")]), "").unwrap();
+        assert_eq!(texts(&merged), vec!["one"]);
+    }
+}
@@ -4,6 +4,7 @@ use std::{io, string::FromUtf8Error};
 
 use thiserror::Error;
 
+use super::conflict::NoteConflict;
 use crate::shared::errors::app_errors::AppError;
 
 /// # NoteError Enum
@@ -14,6 +15,11 @@ use crate::shared::errors::app_errors::AppError;
 /// * `InavlidId`: A note has an invalid ID.
 /// * `FrontmatterParsing`: The frontmatter parser couldn't find the frontmatter.
 /// * `EmptyName`: The name of the note was empty.
+/// * `InvalidName`: The name of the note isn't a valid file name.
+/// * `InvalidProperty`: A note property couldn't be validated.
+/// * `Locked`: The note, or one of its folders, is locked.
+/// * `Conflict`: The note changed since it was read.
+/// * `MissingRevision`: A save didn't say which revision it was based on.
 #[derive(Debug, Error)]
 pub enum NoteError {
     #[error("Note not found in space: {0}")]
@@ -28,9 +34,31 @@ pub enum NoteError {
     #[error("Note name cannot be empty.")]
     EmptyName,
 
+    #[error("Invalid note name: {0}")]
+    InvalidName(String),
+
+    #[error("Invalid note property: {0}")]
+    InvalidProperty(String),
+
+    #[error("Note '{0}' is locked.")]
+    Locked(String),
+
+    #[error("Note '{}' was modified since it was read.", .0.current.name)]
+    Conflict(Box<NoteConflict>),
+
+    #[error("Note '{0}' can't be saved without the revision it was read at.")]
+    MissingRevision(String),
+
     #[error("Notes IO error: {0}")]
     Io(#[from] io::Error),
 
     #[error(transparent)]
     AppError(#[from] AppError),
 }
+
+impl NoteError {
+    /// Whether the note is in an encrypted space that is locked.
+    pub fn is_space_locked(&self) -> bool {
+        matches!(self, NoteError::AppError(AppError::SpaceLocked(_)))
+    }
+}
@@ -0,0 +1,62 @@
+//! # Integrity Domain
+//! Defines the report of the startup check of the note files.
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+/// # IntegrityReport
+/// The result of checking the note files of a space. Paths are relative to
+/// the space.
+/// ## Fields
+/// * `space`: The checked space.
+/// * `recovered`: Notes restored from the temporary file of an interrupted
+///   save.
+/// * `discarded`: Leftover temporary files removed, the note was intact.
+/// * `corrupted`: Notes whose content is no longer valid JSON.
+pub struct IntegrityReport {
+    pub space: String,
+    pub recovered: Vec<String>,
+    pub discarded: Vec<String>,
+    pub corrupted: Vec<String>,
+}
+
+impl IntegrityReport {
+    pub fn is_clean(&self) -> bool {
+        self.recovered.is_empty() && self.discarded.is_empty() && self.corrupted.is_empty()
+    }
+}
+
+/// # Is valid content
+/// Whether a stored note can be loaded: empty (a new note) or a JSON object.
+pub fn is_valid_content(content: &str) -> bool {
+    content.trim().is_empty()
+        || serde_json::from_str::<serde_json::Value>(content).is_ok_and(|v| v.is_object())
+}
+
+/// # Is complete content
+/// Whether a content can replace or keep a note after an interrupted save:
+/// a valid content that isn't empty, as a save truncating the note leaves
+/// it empty.
+pub fn is_complete_content(content: &str) -> bool {
+    !content.trim().is_empty() && is_valid_content(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_notes_are_valid_but_not_complete() {
+        assert!(is_valid_content(""));
+        assert!(is_valid_content("  \n"));
+        assert!(!is_complete_content(""));
+        assert!(!is_complete_content("  \n"));
+    }
+
+    #[test]
+    fn only_json_objects_are_valid() {
+        assert!(is_complete_content(r#"{"blocks":[]}"#));
+        assert!(!is_valid_content("[]"));
+        assert!(!is_valid_content(r#"{"blocks":"#));
+        assert!(!is_complete_content("not json"));
+    }
+}
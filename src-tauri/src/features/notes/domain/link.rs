@@ -0,0 +1,155 @@
+//! # Links Domain
+//! Defines the links between notes, written as anchors of the Editor.js
+//! rich text: `<a href="merino://note/folder/name#heading">`.
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// Prefix of the `href` of a link to a note.
+pub const NOTE_LINK_SCHEME: &str = "merino://note/";
+
+/// Matches the `href` of a link to a note, also inside the escaped rich
+/// text of a note file.
+static NOTE_HREF: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(r#"{}[^"\\\s<>]*"#, regex::escape(NOTE_LINK_SCHEME))).unwrap()
+});
+
+#[derive(Debug, Clone, PartialEq)]
+/// # NoteLink
+/// The target of a link to a note.
+/// ## Fields
+/// * `folder`: The folder of the note, `None` for the space root.
+/// * `name`: The name of the note.
+/// * `heading`: The heading of the note the link points to, if any.
+pub struct NoteLink {
+    pub folder: Option<String>,
+    pub name: String,
+    pub heading: Option<String>,
+}
+
+impl NoteLink {
+    /// # Href
+    /// The `href` of the link, every segment percent-encoded.
+    pub fn href(&self) -> String {
+        let mut href = NOTE_LINK_SCHEME.to_string();
+        for segment in self
+            .folder
+            .iter()
+            .flat_map(|f| f.split(['/', '\\']))
+            .filter(|s| !s.is_empty())
+        {
+            href.push_str(&percent_encode(segment));
+            href.push('/');
+        }
+        href.push_str(&percent_encode(&self.name));
+        if let Some(heading) = &self.heading {
+            href.push('#');
+            href.push_str(&percent_encode(heading));
+        }
+        href
+    }
+
+    /// # Parse
+    /// Reads the target of an `href`, `None` when it doesn't link a note.
+    pub fn parse(href: &str) -> Option<Self> {
+        let path = href.strip_prefix(NOTE_LINK_SCHEME)?;
+        let (path, heading) = match path.split_once('#') {
+            Some((path, heading)) => (
+                path,
+                Some(percent_decode(heading)).filter(|h| !h.is_empty()),
+            ),
+            None => (path, None),
+        };
+        let mut segments: Vec<String> = path.split('/').map(percent_decode).collect();
+        let name = segments.pop().filter(|n| !n.is_empty())?;
+        let folder = Some(segments.join("/")).filter(|f| !f.is_empty());
+        Some(Self {
+            folder,
+            name,
+            heading,
+        })
+    }
+}
+
+/// # Find links
+/// The links to notes found in a text, a note file included.
+pub fn find_links(text: &str) -> Vec<NoteLink> {
+    NOTE_HREF
+        .find_iter(text)
+        .filter_map(|m| NoteLink::parse(m.as_str()))
+        .collect()
+}
+
+/// Percent-encodes everything but the unreserved characters.
+pub fn percent_encode(text: &str) -> String {
+    text.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                (b as char).to_string()
+            }
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Decodes a percent-encoded text, invalid escapes are kept as they are.
+pub fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| text.get(i + 1..i + 3))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(b) => {
+                decoded.push(b);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8_lossy(&decoded).to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn links_are_found_in_rich_text_and_note_files() {
+        let text = r#"<a href="merino://note/projects/My%20plan#Next%20steps">plan</a> and <a href=\"merino://note/Inbox\">inbox</a>, <a href="https://merino.app">site</a>"#;
+
+        assert_eq!(
+            find_links(text),
+            vec![
+                NoteLink {
+                    folder: Some("projects".to_string()),
+                    name: "My plan".to_string(),
+                    heading: Some("Next steps".to_string()),
+                },
+                NoteLink {
+                    folder: None,
+                    name: "Inbox".to_string(),
+                    heading: None,
+                },
+            ]
+        );
+        assert!(find_links(r#"<a href="merino://note/">empty</a>"#).is_empty());
+    }
+
+    #[test]
+    fn hrefs_are_read_back() {
+        let link = NoteLink {
+            folder: Some("a/b c".to_string()),
+            name: "50% done".to_string(),
+            heading: None,
+        };
+        assert_eq!(link.href(), "merino://note/a/b%20c/50%25%20done");
+        assert_eq!(NoteLink::parse(&link.href()), Some(link));
+    }
+}
@@ -1,3 +1,7 @@
+pub mod conflict;
 pub mod errors;
+pub mod integrity;
+pub mod link;
 pub mod note;
+pub mod properties;
 pub mod repository;
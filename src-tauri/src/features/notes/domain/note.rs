@@ -1,15 +1,37 @@
 //! # Notes Domain
 //! Defines all structs needed for the notes.
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+use super::properties::NoteProperties;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
 /// # Note
 /// Struct that represents the note itself.
 /// ## Fields
 /// * `name`: The name of the note.
 /// * `content`: The note content itself
+/// * `folder`: The folder of the note relative to the space.
+/// * `properties`: The typed properties of the note, when they were read.
+/// * `locked`: Whether the note is read-only, by itself or by a locked folder.
+/// * `revision`: The revision of the content read, to be sent back on save.
+/// * `created`: When the note was created, if the platform knows it.
+/// * `modified`: When the note was last saved.
+/// * `pinned`: Whether the note is pinned in its folder.
 pub struct Note {
     pub name: String,
     pub content: Option<String>,
     pub folder: Option<String>,
+    #[serde(default)]
+    pub properties: Option<NoteProperties>,
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default)]
+    pub revision: Option<String>,
+    #[serde(default)]
+    pub created: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub modified: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub pinned: bool,
 }
@@ -0,0 +1,246 @@
+//! # Properties Domain
+//! Defines the typed properties (metadata) a note can carry next to its
+//! Editor.js data, and the helpers to read and write them inside the note file.
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use super::errors::NoteError;
+
+/// Key used inside the note JSON to store the properties, next to the
+/// `time`, `blocks` and `version` keys written by Editor.js.
+pub const PROPERTIES_KEY: &str = "properties";
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(untagged)]
+/// # PropertyValue
+/// A free value that can be stored under a custom key.
+/// ## Variants
+/// * `Bool`: A `true` / `false` flag.
+/// * `Number`: Any numeric value.
+/// * `Text`: A plain string.
+/// * `List`: A list of strings.
+pub enum PropertyValue {
+    Bool(bool),
+    Number(f64),
+    Text(String),
+    List(Vec<String>),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+/// # NoteProperties
+/// Typed metadata of a note.
+/// ## Fields
+/// * `tags`: Normalized tags (lowercase, without `#`).
+/// * `status`: The status of the note (`draft`, `done`...).
+/// * `dates`: Named dates such as `due` or `published`.
+/// * `custom`: Free key / value pairs.
+pub struct NoteProperties {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub dates: BTreeMap<String, NaiveDate>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub custom: BTreeMap<String, PropertyValue>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # TagCount
+/// A tag of a space and the number of notes using it.
+pub struct TagCount {
+    pub name: String,
+    pub count: usize,
+}
+
+impl NoteProperties {
+    /// Returns `true` when no property is set.
+    pub fn is_empty(&self) -> bool {
+        self.tags.is_empty()
+            && self.status.is_none()
+            && self.dates.is_empty()
+            && self.custom.is_empty()
+    }
+
+    /// # Normalize
+    /// Cleans the properties before persisting them: tags are trimmed,
+    /// stripped of `#`, lowercased, deduplicated and sorted; the status is
+    /// lowercased and keys can't be empty.
+    pub fn normalize(mut self) -> Result<Self, NoteError> {
+        let mut tags: Vec<String> = self
+            .tags
+            .iter()
+            .map(|t| normalize_tag(t))
+            .filter(|t| !t.is_empty())
+            .collect();
+        tags.sort();
+        tags.dedup();
+
+        if tags.iter().any(|t| t.chars().any(char::is_whitespace)) {
+            return Err(NoteError::InvalidProperty(
+                "Tags cannot contain whitespaces.".to_string(),
+            ));
+        }
+        self.tags = tags;
+
+        self.status = self
+            .status
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty());
+
+        if self
+            .dates
+            .keys()
+            .chain(self.custom.keys())
+            .any(|k| k.trim().is_empty())
+        {
+            return Err(NoteError::InvalidProperty(
+                "Property keys cannot be empty.".to_string(),
+            ));
+        }
+
+        Ok(self)
+    }
+}
+
+/// Normalizes a single tag, `#Rust ` becomes `rust`.
+pub fn normalize_tag(tag: &str) -> String {
+    tag.trim().trim_start_matches('#').to_lowercase()
+}
+
+/// # Extract properties
+/// Reads the properties stored inside a note file content. Empty files or
+/// contents that aren't a JSON object have no properties.
+pub fn extract_properties(content: &str) -> Option<NoteProperties> {
+    let value: Value = serde_json::from_str(content).ok()?;
+    let properties = value.get(PROPERTIES_KEY)?.clone();
+    serde_json::from_value(properties).ok()
+}
+
+/// # Embed properties
+/// Writes the properties inside the note file content, keeping the
+/// Editor.js data untouched. An empty or invalid content is replaced by an
+/// empty Editor.js document.
+pub fn embed_properties(content: &str, properties: &NoteProperties) -> Result<String, NoteError> {
+    let mut document = match serde_json::from_str::<Value>(content) {
+        Ok(Value::Object(map)) => map,
+        _ => empty_document(),
+    };
+
+    if properties.is_empty() {
+        document.remove(PROPERTIES_KEY);
+    } else {
+        let value = serde_json::to_value(properties)
+            .map_err(|e| NoteError::InvalidProperty(e.to_string()))?;
+        document.insert(PROPERTIES_KEY.to_string(), value);
+    }
+
+    serde_json::to_string(&Value::Object(document))
+        .map_err(|e| NoteError::InvalidProperty(e.to_string()))
+}
+
+/// # Empty document
+/// An Editor.js document without blocks.
+pub fn empty_document() -> Map<String, Value> {
+    let mut document = Map::new();
+    document.insert(
+        "time".to_string(),
+        Value::from(chrono::Utc::now().timestamp_millis()),
+    );
+    document.insert("blocks".to_string(), Value::Array(Vec::new()));
+    document
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_cleans_tags_and_status() {
+        let properties = NoteProperties {
+            tags: vec![" #Rust".into(), "rust".into(), "".into(), "#Notes ".into()],
+            status: Some(" Draft ".into()),
+            ..Default::default()
+        }
+        .normalize()
+        .unwrap();
+
+        assert_eq!(properties.tags, vec!["notes", "rust"]);
+        assert_eq!(properties.status.as_deref(), Some("draft"));
+    }
+
+    #[test]
+    fn normalize_rejects_invalid_tags_and_keys() {
+        let spaced = NoteProperties {
+            tags: vec!["two words".into()],
+            ..Default::default()
+        };
+        assert!(spaced.normalize().is_err());
+
+        let mut custom = BTreeMap::new();
+        custom.insert(" ".to_string(), PropertyValue::Bool(true));
+        let empty_key = NoteProperties {
+            custom,
+            ..Default::default()
+        };
+        assert!(empty_key.normalize().is_err());
+    }
+
+    #[test]
+    fn blank_status_is_removed() {
+        let properties = NoteProperties {
+            status: Some("  ".into()),
+            ..Default::default()
+        }
+        .normalize()
+        .unwrap();
+        assert!(properties.is_empty());
+    }
+
+    #[test]
+    fn embed_then_extract_round_trips() {
+        let content = r#"{"time":1,"blocks":[{"type":"paragraph","data":{"text":"hi"}}]}"#;
+        let mut dates = BTreeMap::new();
+        dates.insert(
+            "due".to_string(),
+            NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+        );
+        let properties = NoteProperties {
+            tags: vec!["rust".into()],
+            status: Some("done".into()),
+            dates,
+            custom: BTreeMap::from([("score".to_string(), PropertyValue::Number(4.0))]),
+        };
+
+        let embedded = embed_properties(content, &properties).unwrap();
+        assert_eq!(extract_properties(&embedded), Some(properties));
+
+        let value: Value = serde_json::from_str(&embedded).unwrap();
+        assert_eq!(value["blocks"][0]["data"]["text"], "hi");
+    }
+
+    #[test]
+    fn embedding_empty_properties_removes_them() {
+        let content = r#"{"blocks":[],"properties":{"tags":["a"]}}"#;
+        let embedded = embed_properties(content, &NoteProperties::default()).unwrap();
+        assert_eq!(extract_properties(&embedded), None);
+    }
+
+    #[test]
+    fn invalid_content_has_no_properties_and_is_replaced() {
+        assert_eq!(extract_properties(""), None);
+        assert_eq!(extract_properties("not json"), None);
+
+        let properties = NoteProperties {
+            tags: vec!["a".into()],
+            ..Default::default()
+        };
+        let embedded = embed_properties("not json", &properties).unwrap();
+        let value: Value = serde_json::from_str(&embedded).unwrap();
+        assert!(value["blocks"].as_array().unwrap().is_empty());
+        assert_eq!(extract_properties(&embedded), Some(properties));
+    }
+}
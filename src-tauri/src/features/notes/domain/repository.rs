@@ -1,9 +1,12 @@
 //! # Repository
 //! Implementation of the notes in this repository.
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 
 use super::errors::NoteError;
+use super::integrity::IntegrityReport;
 use super::note::Note;
+use super::properties::NoteProperties;
 
 /// # Notes Repository
 /// It implements the `list` of methods to interact with the notes in a space.
@@ -56,6 +59,8 @@ pub trait NoteRepository {
     /// * `note_name`: The name of the note to be saved / updated.
     /// * `content`: A `Vec` of `u8` (bytes) that will be saved / updated.
     /// * `folder_path`: The path of the folder to update the note in.
+    /// * `expected_revision`: The revision the content was based on, the save
+    ///   fails with `NoteError::Conflict` when the stored one differs.
     /// ## Result
     /// A `String` with a message saying successful creation, or a `NoteError`
     /// if not successful.
@@ -65,6 +70,7 @@ pub trait NoteRepository {
         note_name: &str,
         content: Vec<u8>,
         folder_path: Option<&str>,
+        expected_revision: &str,
     ) -> Result<Note, NoteError>;
 
     /// # Delete note
@@ -118,4 +124,71 @@ pub trait NoteRepository {
         old_folder: Option<&str>,
         new_folder: Option<&str>,
     ) -> Result<Note, NoteError>;
+
+    /// # Update note properties
+    /// Replaces the properties of a note, keeping its Editor.js data.
+    /// ## Fields
+    /// * `&self`
+    /// * `space_name`: The space of the note.
+    /// * `note_name`: The name of the note.
+    /// * `properties`: The new properties of the note.
+    /// * `folder_path`: The path of the folder of the note.
+    /// ## Result
+    /// The updated `Note` with its content, or a `NoteError` if not successful.
+    async fn update_note_properties(
+        &self,
+        space_name: &str,
+        note_name: &str,
+        properties: NoteProperties,
+        folder_path: Option<&str>,
+    ) -> Result<Note, NoteError>;
+
+    /// # Set note locked
+    /// Locks or unlocks a note. A locked note can't be updated, renamed,
+    /// moved or deleted.
+    /// ## Fields
+    /// * `&self`
+    /// * `space_name`: The space of the note.
+    /// * `note_name`: The name of the note.
+    /// * `folder_path`: The path of the folder of the note.
+    /// * `locked`: The new lock state.
+    /// ## Result
+    /// The updated `Note`, or a `NoteError` if not successful.
+    async fn set_note_locked(
+        &self,
+        space_name: &str,
+        note_name: &str,
+        folder_path: Option<&str>,
+        locked: bool,
+    ) -> Result<Note, NoteError>;
+
+    /// # Set note times
+    /// Restores the creation and modification times of a note file.
+    /// ## Fields
+    /// * `&self`
+    /// * `space_name`: The space of the note.
+    /// * `note_name`: The name of the note.
+    /// * `folder_path`: The path of the folder of the note.
+    /// * `created`: The creation time, only set where the platform allows it.
+    /// * `modified`: The modification time.
+    /// ## Result
+    /// `()`, or a `NoteError` if not successful.
+    async fn set_note_times(
+        &self,
+        space_name: &str,
+        note_name: &str,
+        folder_path: Option<&str>,
+        created: Option<DateTime<Utc>>,
+        modified: Option<DateTime<Utc>>,
+    ) -> Result<(), NoteError>;
+
+    /// # Check integrity
+    /// Restores the notes left behind by an interrupted save and reports the
+    /// notes that can't be parsed anymore.
+    /// ## Fields
+    /// * `&self`
+    /// * `space_name`: The space to check.
+    /// ## Result
+    /// The `IntegrityReport` of the space, or a `NoteError` if not successful.
+    async fn check_integrity(&self, space_name: &str) -> Result<IntegrityReport, NoteError>;
 }
@@ -3,17 +3,39 @@
 //! from the app _(Notes)_.
 use std::{
     collections::VecDeque,
+    fs::FileTimes,
     io::ErrorKind,
     path::{Path, PathBuf},
+    time::SystemTime,
 };
 
 use async_trait::async_trait;
-use log::{error};
-use tokio::fs::{self, read_dir, read_to_string, File};
+use chrono::{DateTime, Utc};
+use log::error;
+use tokio::fs::{self, read_dir, File, OpenOptions};
 
 use crate::{
-    features::notes::domain::{errors::NoteError, note::Note, repository::NoteRepository},
-    shared::repositories::filesystem_repository::FileSystemRepository,
+    features::{
+        folders::{
+            domain::ordering::EntryKind,
+            infrastructure::manifest::{
+                entry_times, find_locked_ancestor, is_content_locked, move_ordered_entry,
+                read_manifest, rename_ordered_entry, NOTE_LOCKED_KEY,
+            },
+        },
+        notes::domain::{
+            conflict::{content_revision, NoteConflict},
+            errors::NoteError,
+            integrity::{is_complete_content, is_valid_content, IntegrityReport},
+            note::Note,
+            properties::{self, NoteProperties},
+            repository::NoteRepository,
+        },
+    },
+    shared::{
+        errors::app_errors::AppError, repositories::filesystem_repository::FileSystemRepository,
+        utils::atomic_write::temp_target,
+    },
 };
 
 /// # FilesystemNoteRepository
@@ -28,6 +50,44 @@ impl FileSystemNoteRepository {
     pub fn new(filesystem_repo: FileSystemRepository) -> Self {
         Self { filesystem_repo }
     }
+
+    /// Whether a note is read-only, by its own flag or by a locked folder.
+    async fn is_locked(&self, space_path: &Path, note_path: &Path) -> Result<bool, NoteError> {
+        let flagged = self
+            .filesystem_repo
+            .read_file_to_string(note_path)
+            .await
+            .is_ok_and(|content| is_content_locked(&content));
+        if flagged {
+            return Ok(true);
+        }
+        match note_path.parent() {
+            Some(folder) => Ok(find_locked_ancestor(space_path, folder).await?.is_some()),
+            None => Ok(false),
+        }
+    }
+
+    /// Writes a note file without risking a truncated note on a crash,
+    /// sealed when the space is encrypted.
+    async fn write_note(&self, note_path: &Path, content: &str) -> Result<(), NoteError> {
+        self.filesystem_repo
+            .write_file(note_path, content.as_bytes())
+            .await?;
+        Ok(())
+    }
+
+    /// Fails with `NoteError::Locked` when the note can't be modified.
+    async fn ensure_unlocked(
+        &self,
+        space_path: &Path,
+        note_path: &Path,
+        note_name: &str,
+    ) -> Result<(), NoteError> {
+        if self.is_locked(space_path, note_path).await? {
+            return Err(NoteError::Locked(note_name.to_string()));
+        }
+        Ok(())
+    }
 }
 
 #[async_trait]
@@ -70,11 +130,18 @@ impl NoteRepository for FileSystemNoteRepository {
                 }
             };
 
+            let mut folder_notes: Vec<Note> = Vec::new();
+            let mut subfolders: Vec<PathBuf> = Vec::new();
+
             while let Some(entry) = entries.next_entry().await.unwrap_or(None) {
                 let path = entry.path();
 
                 if path.is_file() {
-                    if let Some(file_name) = path.file_name().and_then(|n| n.to_str()) {
+                    if let Some(file_name) = path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .filter(|n| !n.starts_with('.'))
+                    {
                         if let Some(note_name) = file_name.strip_suffix(".json") {
                             // Calculate the relative path of the note's folder from the space root.
                             // This is the key part that needs to be correct.
@@ -91,21 +158,37 @@ impl NoteRepository for FileSystemNoteRepository {
                             } else {
                                 Some(relative_path.to_str().unwrap().to_string())
                             };
-                            notes.push(Note {
+                            let (created, modified) = match entry.metadata().await {
+                                Ok(metadata) => entry_times(&metadata),
+                                Err(_) => (None, None),
+                            };
+                            folder_notes.push(Note {
                                 name: note_name.to_string(),
                                 content: None,
                                 folder,
+                                created,
+                                modified,
+                                ..Default::default()
                             });
                         }
                     }
                 } else if path.is_dir() {
                     if let Some(dir_name) = path.file_name().and_then(|n| n.to_str()) {
                         if !dir_name.starts_with('.') {
-                            directories_to_visit.push_back(path);
+                            subfolders.push(path);
                         }
                     }
                 }
             }
+
+            // `read_dir` yields the entries in no particular order.
+            read_manifest(&current_dir)
+                .await?
+                .ordering
+                .sort(&mut folder_notes);
+            notes.extend(folder_notes);
+            subfolders.sort();
+            directories_to_visit.extend(subfolders);
         }
         Ok(notes)
     }
@@ -126,19 +209,30 @@ impl NoteRepository for FileSystemNoteRepository {
         folder_path: Option<&str>,
     ) -> Result<Note, NoteError> {
         let space_path = self.filesystem_repo.get_space_path(space_name)?;
-        let mut note_path = space_path;
+        let mut note_path = space_path.clone();
         let folder_name = folder_path.map(|s| s.to_string());
         if let Some(folder) = folder_name.clone() {
             note_path.push(folder);
         }
+        if find_locked_ancestor(&space_path, &note_path)
+            .await?
+            .is_some()
+        {
+            return Err(NoteError::Locked(note_name.to_string()));
+        }
         note_path.push(format!("{}.json", note_name));
 
         match File::create_new(&note_path).await {
-            Ok(_) => Ok(Note {
-                name: note_name.to_string(),
-                content: None,
-                folder: folder_name,
-            }),
+            Ok(_) => {
+                self.filesystem_repo.mark_path_changed(&note_path);
+                Ok(Note {
+                    name: note_name.to_string(),
+                    content: None,
+                    folder: folder_name,
+                    revision: Some(content_revision("")),
+                    ..Default::default()
+                })
+            }
             Err(e) if e.kind() == ErrorKind::AlreadyExists => Err(NoteError::Io(e)),
             Err(e) => Err(NoteError::Io(e)),
         }
@@ -181,14 +275,31 @@ impl NoteRepository for FileSystemNoteRepository {
             return Err(NoteError::NotFound(error_message));
         }
 
-        let file_content = read_to_string(&note_path)
-            .await
-            .map_err(|e| NoteError::Io(e))?;
+        let file_content = self.filesystem_repo.read_file_to_string(&note_path).await?;
+        let (created, modified) = match fs::metadata(&note_path).await {
+            Ok(metadata) => entry_times(&metadata),
+            Err(_) => (None, None),
+        };
+        let pinned = match note_path.parent() {
+            Some(folder) => read_manifest(folder)
+                .await?
+                .ordering
+                .pinned(EntryKind::Note)
+                .iter()
+                .any(|n| n == note_name),
+            None => false,
+        };
 
         Ok(Note {
             name: note_name.to_string(),
+            properties: properties::extract_properties(&file_content),
+            revision: Some(content_revision(&file_content)),
             content: Some(file_content.to_string()),
             folder: folder_path.map(|s| s.to_string()),
+            locked: self.is_locked(&space_path, &note_path).await?,
+            created,
+            modified,
+            pinned,
         })
     }
 
@@ -199,6 +310,8 @@ impl NoteRepository for FileSystemNoteRepository {
     /// * `note_name`: The name of the note that will be updated / saved.
     /// * `content`: A `Vec` of `u8` (bytes) that is the content to be saved /
     /// updated.
+    /// * `expected_revision`: The revision the content was based on, a note
+    ///   that doesn't exist yet has the revision of an empty content.
     /// ## Result
     /// A `String` with a successful message, or a `NoteError` if not.
     async fn update_note_content(
@@ -207,7 +320,11 @@ impl NoteRepository for FileSystemNoteRepository {
         note_name: &str,
         content: Vec<u8>,
         folder_path: Option<&str>,
+        expected_revision: &str,
     ) -> Result<Note, NoteError> {
+        if expected_revision.trim().is_empty() {
+            return Err(NoteError::MissingRevision(note_name.to_string()));
+        }
         let space_path = self.filesystem_repo.get_space_path(space_name)?;
         let mut note_path = space_path.clone();
 
@@ -215,21 +332,58 @@ impl NoteRepository for FileSystemNoteRepository {
             note_path.push(PathBuf::from(folder));
         }
         note_path.push(format!("{}.json", note_name));
+        self.ensure_unlocked(&space_path, &note_path, note_name)
+            .await?;
 
         // Convert the incoming byte array into a Markdown String
-        let json_conversion =
+        let mut json_conversion =
             String::from_utf8(content).map_err(|e| NoteError::MarkdownConversion(e))?;
 
+        let stored = match self.filesystem_repo.read_file_to_string(&note_path).await {
+            Ok(stored) => Some(stored),
+            // A locked space can't be saved, the stored content isn't lost.
+            Err(e @ (AppError::SpaceLocked(_) | AppError::Crypto(_))) => return Err(e.into()),
+            Err(_) => None,
+        };
+
+        // Someone else saved the note since the content was read.
+        let current = stored.as_deref().unwrap_or_default();
+        let revision = content_revision(current);
+        if revision != expected_revision {
+            return Err(NoteError::Conflict(Box::new(NoteConflict {
+                current: Note {
+                    name: note_name.to_string(),
+                    properties: properties::extract_properties(current),
+                    content: Some(current.to_string()),
+                    folder: folder_path.map(|s| s.to_string()),
+                    revision: Some(revision),
+                    ..Default::default()
+                },
+                incoming: json_conversion,
+                blocks: Vec::new(),
+            })));
+        }
+
+        // Editor.js doesn't know about the note properties, keep the stored
+        // ones when the incoming content doesn't carry them.
+        if properties::extract_properties(&json_conversion).is_none() {
+            let stored_properties = stored.as_deref().and_then(properties::extract_properties);
+            if let Some(stored) = stored_properties {
+                json_conversion = properties::embed_properties(&json_conversion, &stored)?;
+            }
+        }
+
         let conversion = json_conversion.clone();
-        fs::write(&note_path, json_conversion)
-            .await
-            .map_err(|e| NoteError::Io(e))?;
+        self.write_note(&note_path, &json_conversion).await?;
 
         // CORRECTED: Return a Note with the correct folder and content
         Ok(Note {
             name: note_name.to_string(),
+            properties: properties::extract_properties(&conversion),
+            revision: Some(content_revision(&conversion)),
             content: Some(conversion),
             folder: folder_path.map(|s| s.to_string()),
+            ..Default::default()
         })
     }
 
@@ -256,9 +410,17 @@ impl NoteRepository for FileSystemNoteRepository {
         }
 
         note_path.push(format!("{}.json", note_name));
+        self.ensure_unlocked(&space_path, &note_path, note_name)
+            .await?;
 
         match fs::remove_file(&note_path).await {
-            Ok(_) => Ok(format!("Removed '{}' from '{}'.", note_name, space_name)),
+            Ok(_) => {
+                self.filesystem_repo.mark_path_changed(&note_path);
+                if let Some(folder) = note_path.parent() {
+                    move_ordered_entry(folder, None, EntryKind::Note, note_name).await;
+                }
+                Ok(format!("Removed '{}' from '{}'.", note_name, space_name))
+            }
             Err(e) if e.kind() == ErrorKind::NotFound => Err(NoteError::NotFound(e.to_string())),
             Err(e) => Err(NoteError::Io(e)),
         }
@@ -294,15 +456,26 @@ impl NoteRepository for FileSystemNoteRepository {
 
         old_path.push(format!("{}.json", note_name));
         new_path.push(format!("{}.json", new_note_name));
+        self.ensure_unlocked(&space_path, &old_path, note_name)
+            .await?;
 
         fs::rename(&old_path, &new_path)
             .await
             .map_err(|e| NoteError::Io(e))?;
+        self.filesystem_repo
+            .reseal_moved(&old_path, &new_path)
+            .await?;
+        self.filesystem_repo.mark_path_changed(&old_path);
+        self.filesystem_repo.mark_path_changed(&new_path);
+        if let Some(folder) = new_path.parent() {
+            rename_ordered_entry(folder, EntryKind::Note, note_name, new_note_name).await;
+        }
 
         Ok(Note {
             name: new_note_name.to_string(),
             content: None,
             folder: folder_path.map(|s| s.to_string()),
+            ..Default::default()
         })
     }
 
@@ -347,14 +520,287 @@ impl NoteRepository for FileSystemNoteRepository {
             ));
         }
 
+        self.ensure_unlocked(&space_path, &old_path, note_name)
+            .await?;
+        if let Some(destination) = new_path.parent() {
+            if find_locked_ancestor(&space_path, destination)
+                .await?
+                .is_some()
+            {
+                return Err(NoteError::Locked(note_name.to_string()));
+            }
+        }
+
         fs::rename(&old_path, &new_path)
             .await
             .map_err(|e| NoteError::Io(e))?;
+        self.filesystem_repo
+            .reseal_moved(&old_path, &new_path)
+            .await?;
+        self.filesystem_repo.mark_path_changed(&old_path);
+        self.filesystem_repo.mark_path_changed(&new_path);
+        if let (Some(from), Some(to)) = (old_path.parent(), new_path.parent()) {
+            move_ordered_entry(from, Some(to), EntryKind::Note, note_name).await;
+        }
 
         Ok(Note {
             name: note_name.to_string(),
             content: None,
             folder: new_folder_path_string,
+            ..Default::default()
         })
     }
+
+    /// # [UPDATE] Note properties
+    /// Replaces the properties stored inside the note file.
+    /// ## Params
+    /// * `space_name`: The space of the note.
+    /// * `note_name`: The name of the note.
+    /// * `note_properties`: The new properties, already normalized.
+    /// * `folder_path`: The folder of the note.
+    /// ## Result
+    /// The updated `Note` with its content, or a `NoteError` if not.
+    async fn update_note_properties(
+        &self,
+        space_name: &str,
+        note_name: &str,
+        note_properties: NoteProperties,
+        folder_path: Option<&str>,
+    ) -> Result<Note, NoteError> {
+        let note = self
+            .get_note_content(space_name, note_name, folder_path)
+            .await?;
+        if note.locked {
+            return Err(NoteError::Locked(note_name.to_string()));
+        }
+
+        let content = properties::embed_properties(
+            note.content.as_deref().unwrap_or_default(),
+            &note_properties,
+        )?;
+
+        let space_path = self.filesystem_repo.get_space_path(space_name)?;
+        let mut note_path = space_path.clone();
+        if let Some(folder) = folder_path {
+            note_path.push(PathBuf::from(folder));
+        }
+        note_path.push(format!("{}.json", note_name));
+
+        self.write_note(&note_path, &content).await?;
+
+        Ok(Note {
+            name: note_name.to_string(),
+            properties: Some(note_properties).filter(|p| !p.is_empty()),
+            revision: Some(content_revision(&content)),
+            content: Some(content),
+            folder: folder_path.map(|s| s.to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// # [UPDATE] Note lock
+    /// Writes or removes the lock flag of the note file. This is the only
+    /// write allowed on a locked note.
+    /// ## Params
+    /// * `space_name`: The space of the note.
+    /// * `note_name`: The name of the note.
+    /// * `folder_path`: The folder of the note.
+    /// * `locked`: The new lock state.
+    /// ## Result
+    /// The updated `Note`, still locked if one of its folders is, or a
+    /// `NoteError`.
+    async fn set_note_locked(
+        &self,
+        space_name: &str,
+        note_name: &str,
+        folder_path: Option<&str>,
+        locked: bool,
+    ) -> Result<Note, NoteError> {
+        let note = self
+            .get_note_content(space_name, note_name, folder_path)
+            .await?;
+
+        let mut document = match serde_json::from_str::<serde_json::Value>(
+            note.content.as_deref().unwrap_or_default(),
+        ) {
+            Ok(serde_json::Value::Object(map)) => map,
+            _ => properties::empty_document(),
+        };
+        if locked {
+            document.insert(NOTE_LOCKED_KEY.to_string(), serde_json::Value::Bool(true));
+        } else {
+            document.remove(NOTE_LOCKED_KEY);
+        }
+        let content = serde_json::Value::Object(document).to_string();
+
+        let space_path = self.filesystem_repo.get_space_path(space_name)?;
+        let mut note_path = space_path.clone();
+        if let Some(folder) = folder_path {
+            note_path.push(PathBuf::from(folder));
+        }
+        note_path.push(format!("{}.json", note_name));
+
+        self.write_note(&note_path, &content).await?;
+
+        Ok(Note {
+            locked: self.is_locked(&space_path, &note_path).await?,
+            revision: Some(content_revision(&content)),
+            content: Some(content),
+            ..note
+        })
+    }
+
+    /// # [UPDATE] Note times
+    /// Sets the timestamps of the note file. The creation time can only be
+    /// set on Windows and macOS, it's left as it is elsewhere.
+    /// ## Params
+    /// * `space_name`: The space of the note.
+    /// * `note_name`: The name of the note.
+    /// * `folder_path`: The folder of the note.
+    /// * `created`: The creation time to restore.
+    /// * `modified`: The modification time to restore.
+    async fn set_note_times(
+        &self,
+        space_name: &str,
+        note_name: &str,
+        folder_path: Option<&str>,
+        created: Option<DateTime<Utc>>,
+        modified: Option<DateTime<Utc>>,
+    ) -> Result<(), NoteError> {
+        let mut note_path = self.filesystem_repo.get_space_path(space_name)?;
+        if let Some(folder) = folder_path {
+            note_path.push(PathBuf::from(folder));
+        }
+        note_path.push(format!("{}.json", note_name));
+
+        let mut times = FileTimes::new();
+        if let Some(modified) = modified {
+            times = times.set_modified(modified.into());
+        }
+        if let Some(created) = created {
+            times = with_created(times, created.into());
+        }
+        let file = OpenOptions::new().write(true).open(&note_path).await?;
+        file.into_std().await.set_times(times)?;
+        Ok(())
+    }
+
+    /// # [CHECK] Integrity
+    /// Walks the space looking for the temporary files of interrupted saves
+    /// (`.name.json.<pid>-<n>.tmp`) and for notes that aren't valid JSON. A
+    /// temporary file replaces its note when the note is missing or broken
+    /// and the temporary content is valid, otherwise it is removed.
+    /// ## Params
+    /// * `space_name`: The space to check.
+    /// ## Result
+    /// The `IntegrityReport` of the space, or a `NoteError` if not.
+    async fn check_integrity(&self, space_name: &str) -> Result<IntegrityReport, NoteError> {
+        let space_path = self.filesystem_repo.get_space_path(space_name)?;
+        if !space_path.is_dir() {
+            return Err(NoteError::NotFound(format!(
+                "Space '{}' not found or is not a directory",
+                space_name
+            )));
+        }
+        // The notes of a locked space can't be told apart from broken ones.
+        self.filesystem_repo.space_key(space_name)?;
+
+        let relative = |path: &Path| {
+            path.strip_prefix(&space_path)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/")
+        };
+        let mut report = IntegrityReport {
+            space: space_name.to_string(),
+            ..Default::default()
+        };
+        let mut directories_to_visit: VecDeque<PathBuf> = VecDeque::new();
+        directories_to_visit.push_back(space_path.clone());
+
+        while let Some(current_dir) = directories_to_visit.pop_front() {
+            let mut entries = read_dir(&current_dir).await.map_err(NoteError::Io)?;
+            let mut temp_files = Vec::new();
+            let mut note_files = Vec::new();
+
+            while let Some(entry) = entries.next_entry().await.map_err(NoteError::Io)? {
+                let path = entry.path();
+                let Some(file_name) = path.file_name().and_then(|n| n.to_str()) else {
+                    continue;
+                };
+                if path.is_dir() {
+                    if !file_name.starts_with('.') {
+                        directories_to_visit.push_back(path);
+                    }
+                } else if let Some(target) = temp_target(file_name).filter(|n| n.ends_with(".json"))
+                {
+                    temp_files.push((path.clone(), current_dir.join(target)));
+                } else if !file_name.starts_with('.') && file_name.ends_with(".json") {
+                    note_files.push(path);
+                }
+            }
+
+            // Temporary files first, a recovered note is checked afterwards.
+            // An empty note is recovered too, a crash can leave it truncated.
+            for (temp_path, note_path) in temp_files {
+                let temp_valid = self
+                    .filesystem_repo
+                    .read_file_to_string(&temp_path)
+                    .await
+                    .is_ok_and(|c| is_complete_content(&c));
+                let note_valid = self
+                    .filesystem_repo
+                    .read_file_to_string(&note_path)
+                    .await
+                    .is_ok_and(|c| is_complete_content(&c));
+
+                if temp_valid && !note_valid {
+                    fs::rename(&temp_path, &note_path)
+                        .await
+                        .map_err(NoteError::Io)?;
+                    self.filesystem_repo.mark_space_changed(space_name);
+                    report.recovered.push(relative(&note_path));
+                    if !note_files.contains(&note_path) {
+                        note_files.push(note_path);
+                    }
+                } else {
+                    fs::remove_file(&temp_path).await.map_err(NoteError::Io)?;
+                    report.discarded.push(relative(&temp_path));
+                }
+            }
+
+            for note_path in note_files {
+                let valid = self
+                    .filesystem_repo
+                    .read_file_to_string(&note_path)
+                    .await
+                    .is_ok_and(|c| is_valid_content(&c));
+                if !valid {
+                    error!("Note '{}' is not valid JSON.", note_path.display());
+                    report.corrupted.push(relative(&note_path));
+                }
+            }
+        }
+        Ok(report)
+    }
+}
+
+/// Sets the creation time, on the platforms storing it.
+#[cfg(windows)]
+fn with_created(times: FileTimes, created: SystemTime) -> FileTimes {
+    use std::os::windows::fs::FileTimesExt;
+    times.set_created(created)
+}
+
+/// Sets the creation time, on the platforms storing it.
+#[cfg(target_os = "macos")]
+fn with_created(times: FileTimes, created: SystemTime) -> FileTimes {
+    use std::os::macos::fs::FileTimesExt;
+    times.set_created(created)
+}
+
+/// Sets the creation time, on the platforms storing it.
+#[cfg(not(any(windows, target_os = "macos")))]
+fn with_created(times: FileTimes, _created: SystemTime) -> FileTimes {
+    times
 }
@@ -5,14 +5,25 @@ use crate::features::search::domain::search::Searchable;
 use crate::{
     features::{
         notes::{
-            application::{create, delete, get, update},
-            domain::{errors::NoteError, note::Note},
+            application::{create, delete, get, integrity, properties, update},
+            domain::{
+                errors::NoteError,
+                integrity::IntegrityReport,
+                note::Note,
+                properties::{NoteProperties, TagCount},
+            },
             infrastructure::filesystem_repository::FileSystemNoteRepository,
         },
         search::{self},
+        space::application::get::get_space_use_case,
+        stats::{
+            application::writing::record_save_use_case,
+            infrastructure::filesystem_repository::FileSystemStatsRepository,
+        },
     },
     shared::state::state::AppState,
 };
+use log::error;
 use tauri::State;
 
 #[tauri::command]
@@ -38,7 +49,8 @@ pub async fn get_notes_in_space_cmd(
 /// ## Params
 /// * `repo`: A State of the FileSystemNoteRepository
 /// * `space_name`: The space of the note
-/// * `folder_path`: The path of the folder to create the note in.
+/// * `folder_path`: The path of the folder to create the note in, the
+///   default folder of the space if not given.
 /// ## Result
 /// Returns `Note` if succeded, if not a `String` which is a `NoteError`
 pub async fn create_note_in_space_cmd(
@@ -48,6 +60,22 @@ pub async fn create_note_in_space_cmd(
 ) -> Result<Note, String> {
     let fs_repo_lock = state.filesystem_repo.lock().await;
 
+    let default_folder = match folder_path {
+        Some(_) => None,
+        None => {
+            let spaces_repo_lock = state.spaces_repo.lock().await;
+            get_space_use_case(&*spaces_repo_lock, space_name)
+                .await
+                .ok()
+                .and_then(|space| {
+                    let folder = space.manifest.default_folder?;
+                    // A folder removed since it was chosen falls back to the root.
+                    space.route?.join(&folder).is_dir().then_some(folder)
+                })
+        }
+    };
+    let folder_path = folder_path.or(default_folder.as_deref());
+
     let new_note = create::create_note_use_case(&*fs_repo_lock, &space_name, folder_path)
         .await
         .map_err(|e| e.to_string())?;
@@ -96,13 +124,18 @@ pub async fn get_note_content_cmd(
 /// * `note_name`: The name of the note to be updated.
 /// * `content`: A `Vec` of `u8` (bytes) to update the content.
 /// * `folder_path`: The folder path of the note.
+/// * `expected_revision`: The revision of the note returned by its last read
+///   or save. When the stored note changed meanwhile, the error is the
+///   `NoteConflict` as JSON.
 #[tauri::command]
 pub async fn update_note_content_cmd(
     state: State<'_, AppState>,
+    stats_repo: State<'_, FileSystemStatsRepository>,
     space_name: &str,
     note_name: &str,
     content: Vec<u8>,
     folder_path: Option<&str>,
+    expected_revision: &str,
 ) -> Result<Note, String> {
     // 1. Acquire the filesystem lock ONCE.
     let fs_repo_lock = state.filesystem_repo.lock().await;
@@ -120,9 +153,12 @@ pub async fn update_note_content_cmd(
         note_name,
         content,
         folder_path,
+        expected_revision,
     )
     .await
-    .map_err(|e| e.to_string())?;
+    .map_err(save_error)?;
+
+    record_save(&stats_repo, space_name, &original_note, &updated_note).await;
 
     // Now that the filesystem lock is no longer needed for the next operations,
     // it will be dropped automatically when this block ends,
@@ -158,6 +194,102 @@ pub async fn update_note_content_cmd(
     Ok(updated_note)
 }
 
+/// # [UPDATE] Merge Note Content
+/// Saves a content rejected by a conflict, merging the blocks edited on each
+/// side.
+/// ## Params
+/// * `space_name`: The name of the space of the note.
+/// * `note_name`: The name of the note.
+/// * `base_content`: The content of the note when it was read.
+/// * `content`: The content that couldn't be saved.
+/// * `folder_path`: The folder path of the note.
+/// ## Result
+/// The merged note, or the `NoteConflict` as JSON listing the blocks edited
+/// on both sides.
+#[tauri::command]
+pub async fn merge_note_content_cmd(
+    state: State<'_, AppState>,
+    stats_repo: State<'_, FileSystemStatsRepository>,
+    space_name: &str,
+    note_name: &str,
+    base_content: &str,
+    content: &str,
+    folder_path: Option<&str>,
+) -> Result<Note, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    let original_note =
+        get::get_note_content_use_case(&*fs_repo_lock, space_name, note_name, folder_path)
+            .await
+            .map_err(|e| e.to_string())?;
+
+    let merged_note = update::merge_note_content_use_case(
+        &*fs_repo_lock,
+        space_name,
+        note_name,
+        base_content,
+        content,
+        folder_path,
+    )
+    .await
+    .map_err(save_error)?;
+
+    record_save(&stats_repo, space_name, &original_note, &merged_note).await;
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    search::application::delete::delete_document_use_case(
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &merged_note.get_unique_id(space_name),
+    )
+    .map_err(|e| e.to_string())?;
+
+    search::application::index::index_document_use_case(
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &merged_note,
+        space_name,
+    )
+    .map_err(|e| e.to_string())?;
+
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(merged_note)
+}
+
+/// Logs the words written by a save, the save succeeded even if the log
+/// can't be updated.
+async fn record_save(
+    stats_repo: &FileSystemStatsRepository,
+    space_name: &str,
+    before: &Note,
+    after: &Note,
+) {
+    if let Err(e) = record_save_use_case(
+        stats_repo,
+        space_name,
+        before.content.as_deref().unwrap_or_default(),
+        after.content.as_deref().unwrap_or_default(),
+    )
+    .await
+    {
+        error!("Failed to log the words written in '{}': {}", space_name, e);
+    }
+}
+
+/// Turns a save error into the command error, conflicts are sent as JSON so
+/// the frontend gets both versions.
+fn save_error(e: NoteError) -> String {
+    match e {
+        NoteError::Conflict(conflict) => {
+            serde_json::to_string(&conflict).unwrap_or_else(|e| e.to_string())
+        }
+        e => e.to_string(),
+    }
+}
+
 /// # [DELETE] Note
 /// Deletes a note from a space
 /// ## Params
@@ -178,6 +310,7 @@ pub async fn delete_note_cmd(
         name: note_name.to_string(),
         content: None,
         folder: folder_path.map(|s| s.to_string()),
+        ..Default::default()
     };
     let unique_id = note_to_delete.get_unique_id(space_name);
 
@@ -223,6 +356,7 @@ pub async fn update_note_name_cmd(
         name: note_name.to_string(),
         content: None,
         folder: folder_path.map(|s| s.to_string()),
+        ..Default::default()
     };
     let old_unique_id = old_note.get_unique_id(space_name);
 
@@ -287,6 +421,7 @@ pub async fn update_note_route_cmd(
         name: note_name.to_string(),
         content: None,
         folder: old_folder.map(|s| s.to_string()),
+        ..Default::default()
     };
     let old_unique_id = old_note.get_unique_id(space_name);
 
@@ -328,6 +463,115 @@ pub async fn update_note_route_cmd(
     Ok(updated_note)
 }
 
+/// # [UPDATE] Note Properties
+/// Replaces the properties (tags, status, dates...) of a note and reindexes it.
+/// ## Params
+/// * `space_name`: The name of the space of the note.
+/// * `note_name`: The name of the note to be updated.
+/// * `properties`: The new properties of the note.
+/// * `folder_path`: The folder path of the note.
+#[tauri::command]
+pub async fn set_note_properties_cmd(
+    state: State<'_, AppState>,
+    space_name: &str,
+    note_name: &str,
+    properties: NoteProperties,
+    folder_path: Option<&str>,
+) -> Result<Note, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    let updated_note = properties::set_note_properties_use_case(
+        &*fs_repo_lock,
+        space_name,
+        note_name,
+        properties,
+        folder_path,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    search::application::delete::delete_document_use_case(
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &updated_note.get_unique_id(space_name),
+    )
+    .map_err(|e| e.to_string())?;
+
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    search::application::index::index_document_use_case(
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &updated_note,
+        space_name,
+    )
+    .map_err(|e| e.to_string())?;
+
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(updated_note)
+}
+
+/// # [GET] Space Tags
+/// Lists the tags used in a space with the number of notes using each one.
+/// ## Params
+/// * `state`: The state of the app, holding the search index.
+/// * `space_name`: The name of the space.
+#[tauri::command]
+pub async fn get_space_tags_cmd(
+    state: State<'_, AppState>,
+    space_name: &str,
+) -> Result<Vec<TagCount>, String> {
+    let search_repo_lock = state.search_repo.lock().await;
+    properties::get_space_tags_use_case(&search_repo_lock, space_name).map_err(|e| e.to_string())
+}
+
+/// # [UPDATE] Lock Note
+/// Makes a note read-only.
+/// ## Params
+/// * `state`: The state of the app, holding the notes repository.
+/// * `space_name`: The name of the space of the note.
+/// * `note_name`: The name of the note to lock.
+/// * `folder_path`: The folder path of the note.
+#[tauri::command]
+pub async fn lock_note_cmd(
+    state: State<'_, AppState>,
+    space_name: &str,
+    note_name: &str,
+    folder_path: Option<&str>,
+) -> Result<Note, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    update::set_note_locked_use_case(&*fs_repo_lock, space_name, note_name, folder_path, true)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// # [UPDATE] Unlock Note
+/// Removes the lock of a note. The note stays read-only while one of its
+/// folders is locked.
+/// ## Params
+/// * `state`: The state of the app, holding the notes repository.
+/// * `space_name`: The name of the space of the note.
+/// * `note_name`: The name of the note to unlock.
+/// * `folder_path`: The folder path of the note.
+#[tauri::command]
+pub async fn unlock_note_cmd(
+    state: State<'_, AppState>,
+    space_name: &str,
+    note_name: &str,
+    folder_path: Option<&str>,
+) -> Result<Note, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    update::set_note_locked_use_case(&*fs_repo_lock, space_name, note_name, folder_path, false)
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn search_notes_cmd(
     state: State<'_, AppState>,
@@ -367,3 +611,20 @@ pub async fn search_notes_cmd(
     }
     Ok(notes)
 }
+
+/// # [CHECK] Notes Integrity
+/// Recovers the notes of interrupted saves and reports the notes that are no
+/// longer valid JSON, for every space. Also run at startup.
+/// ## Result
+/// One `IntegrityReport` per space.
+#[tauri::command]
+pub async fn check_notes_integrity_cmd(
+    state: State<'_, AppState>,
+) -> Result<Vec<IntegrityReport>, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+    let spaces_repo_lock = state.spaces_repo.lock().await;
+
+    integrity::check_notes_integrity_use_case(&*fs_repo_lock, &*spaces_repo_lock)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -10,3 +10,29 @@ pub fn delete_document_use_case(
 ) -> Result<(), SearchError> {
     repo.delete_document(index_writer, route.trim())
 }
+
+/// # Delete Folder Documents
+/// Removes the documents of the notes inside a folder of a space, its
+/// subfolders included. The caller is in charge of committing the writer.
+/// ## Result
+/// The number of documents removed.
+pub fn delete_folder_documents_use_case(
+    repo: &TantivySearchRepository,
+    index_writer: &mut IndexWriter,
+    space_name: &str,
+    folder_path: &str,
+) -> Result<usize, SearchError> {
+    let prefix = format!(
+        "{}/{}/",
+        space_name,
+        folder_path.replace('\\', "/").trim_matches('/')
+    );
+    let mut deleted = 0;
+    for route in repo.get_space_routes(space_name)? {
+        if route.starts_with(&prefix) {
+            repo.delete_document(index_writer, &route)?;
+            deleted += 1;
+        }
+    }
+    Ok(deleted)
+}
@@ -1,6 +1,10 @@
-use crate::features::search::{
-    domain::{error::SearchError, search::Searchable},
-    infrastructure::search_repository::TantivySearchRepository,
+use crate::features::{
+    notes::domain::repository::NoteRepository,
+    search::{
+        domain::{error::SearchError, search::Searchable},
+        infrastructure::search_repository::TantivySearchRepository,
+    },
+    space::domain::repository::SpaceRepository,
 };
 use tantivy::IndexWriter;
 
@@ -12,3 +16,58 @@ pub fn index_document_use_case<T: Searchable>(
 ) -> Result<(), SearchError> {
     repo.index_document(index_writer, document, space_name)
 }
+
+/// # Reindex Space
+/// Reads every note of a space and replaces its document in the index.
+/// A locked encrypted space isn't indexed until it's unlocked. The caller
+/// is in charge of committing the writer.
+/// ## Result
+/// The number of notes indexed.
+pub async fn reindex_space_use_case<N: NoteRepository>(
+    note_repo: &N,
+    repo: &TantivySearchRepository,
+    index_writer: &mut IndexWriter,
+    space_name: &str,
+) -> Result<usize, SearchError> {
+    let notes = note_repo
+        .get_notes(space_name)
+        .await
+        .map_err(|e| SearchError::Reindex(e.to_string()))?;
+
+    let mut indexed = 0;
+    for note in notes {
+        let note = match note_repo
+            .get_note_content(space_name, &note.name, note.folder.as_deref())
+            .await
+        {
+            Ok(note) => note,
+            Err(e) if e.is_space_locked() => return Ok(indexed),
+            Err(e) => return Err(SearchError::Reindex(e.to_string())),
+        };
+        repo.delete_document(index_writer, &note.get_unique_id(space_name))?;
+        repo.index_document(index_writer, &note, space_name)?;
+        indexed += 1;
+    }
+    Ok(indexed)
+}
+
+/// # Reindex All
+/// Reindexes the notes of every space and commits once at the end.
+pub async fn reindex_all_use_case<N: NoteRepository, S: SpaceRepository>(
+    note_repo: &N,
+    space_repo: &S,
+    repo: &TantivySearchRepository,
+    index_writer: &mut IndexWriter,
+) -> Result<usize, SearchError> {
+    let spaces = space_repo
+        .get_spaces()
+        .await
+        .map_err(|e| SearchError::Reindex(e.to_string()))?;
+
+    let mut indexed = 0;
+    for space in spaces {
+        indexed += reindex_space_use_case(note_repo, repo, index_writer, &space.name).await?;
+    }
+    index_writer.commit()?;
+    Ok(indexed)
+}
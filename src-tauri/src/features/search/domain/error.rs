@@ -10,6 +10,8 @@ pub enum SearchError {
     IoError(String),
     #[error("Query error: {0}")]
     Query(String),
+    #[error("Reindex error: {0}")]
+    Reindex(String),
 }
 
 impl From<tantivy::TantivyError> for SearchError {
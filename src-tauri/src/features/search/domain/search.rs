@@ -7,6 +7,12 @@ pub trait Searchable {
     fn get_search_name(&self) -> String;
     fn get_search_content(&self) -> Option<&str>;
     fn get_search_folder(&self) -> Option<&str>;
+    fn get_search_tags(&self) -> Vec<String> {
+        Vec::new()
+    }
+    fn get_search_status(&self) -> Option<&str> {
+        None
+    }
 }
 
 impl Searchable for Note {
@@ -35,4 +41,15 @@ impl Searchable for Note {
     fn get_search_folder(&self) -> Option<&str> {
         self.folder.as_deref()
     }
+
+    fn get_search_tags(&self) -> Vec<String> {
+        self.properties
+            .as_ref()
+            .map(|p| p.tags.clone())
+            .unwrap_or_default()
+    }
+
+    fn get_search_status(&self) -> Option<&str> {
+        self.properties.as_ref().and_then(|p| p.status.as_deref())
+    }
 }
@@ -1,19 +1,47 @@
-use std::{fs, path::Path};
+use std::{
+    borrow::Cow,
+    collections::{BTreeMap, HashMap, HashSet},
+    fs,
+    path::Path,
+    sync::{Arc, LazyLock, Mutex},
+};
+
+use regex::Regex;
 
 use tantivy::{
-    collector::TopDocs,
+    collector::{DocSetCollector, TopDocs},
     doc,
-    query::QueryParser,
-    schema::{Schema, Value, STORED, TEXT},
-    Index, IndexWriter, TantivyDocument,
+    query::{AllQuery, QueryParser},
+    schema::{Schema, Value, FAST, STORED, STRING, TEXT},
+    DocAddress, Index, IndexWriter, Score, TantivyDocument,
 };
 
 use crate::features::search::domain::{error::SearchError, search::Searchable};
 
+/// Matches the `tag:` and `status:` filters of a query.
+static PROPERTY_FILTER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\b(tag|status):#?([^\s()]+)").unwrap());
+
+/// Memory budget of the writers of the in-memory indexes, the least
+/// tantivy accepts.
+const PRIVATE_WRITER_MEMORY: usize = 15_000_000;
+
+/// The in-memory index of an unlocked encrypted space.
+struct PrivateIndex {
+    index: Index,
+    writer: IndexWriter,
+    dirty: bool,
+}
+
+/// The notes of the encrypted spaces are never written to the index on
+/// disk: while unlocked they're indexed in memory, one index per space,
+/// thrown away when the space is locked.
 #[derive(Clone)]
 pub struct TantivySearchRepository {
     index: Index,
     schema: Schema,
+    rebuilt: bool,
+    private: Arc<Mutex<HashMap<String, PrivateIndex>>>,
 }
 
 impl TantivySearchRepository {
@@ -26,22 +54,108 @@ impl TantivySearchRepository {
         schema_builder.add_text_field("name", TEXT | STORED);
         schema_builder.add_text_field("content", TEXT | STORED);
         schema_builder.add_text_field("folder", TEXT | STORED);
+        // Note properties are stored untokenized so `tag:rust` and
+        // `status:draft` match the exact (normalized) values.
+        schema_builder.add_text_field("tag", STRING | STORED | FAST);
+        schema_builder.add_text_field("status", STRING | STORED | FAST);
         let schema = schema_builder.build();
 
+        let mut rebuilt = false;
         let index = if !index_path.exists() {
-            fs::create_dir_all(&index_path)?;
+            fs::create_dir_all(index_path)?;
+            rebuilt = true;
             Index::create_in_dir(index_path, schema.clone())?
         } else {
-            Index::open_in_dir(index_path)?
+            let index = Index::open_in_dir(index_path)?;
+            if Self::same_fields(&index.schema(), &schema) {
+                index
+            } else {
+                // The index was created by an older schema, start from scratch
+                // and let the caller reindex the notes.
+                fs::remove_dir_all(index_path)?;
+                fs::create_dir_all(index_path)?;
+                rebuilt = true;
+                Index::create_in_dir(index_path, schema.clone())?
+            }
         };
 
-        Ok(Self { index, schema })
+        Ok(Self {
+            index,
+            schema,
+            rebuilt,
+            private: Arc::new(Mutex::new(HashMap::new())),
+        })
+    }
+
+    /// Whether the index was (re)created empty and needs the notes reindexed.
+    pub fn was_rebuilt(&self) -> bool {
+        self.rebuilt
+    }
+
+    fn same_fields(current: &Schema, expected: &Schema) -> bool {
+        let names = |schema: &Schema| {
+            schema
+                .fields()
+                .map(|(_, entry)| entry.name().to_string())
+                .collect::<Vec<_>>()
+        };
+        names(current) == names(expected)
     }
 
     pub fn get_index_writer(&self) -> Result<IndexWriter, SearchError> {
         Ok(self.index.writer(50_000_000)?)
     }
 
+    /// Indexes the notes of a space in memory from now on, until
+    /// `close_private_index`.
+    pub fn open_private_index(&self, space_name: &str) -> Result<(), SearchError> {
+        let mut private = self.private.lock().unwrap();
+        if private.contains_key(space_name) {
+            return Ok(());
+        }
+        let index = Index::create_in_ram(self.schema.clone());
+        let writer = index.writer_with_num_threads(1, PRIVATE_WRITER_MEMORY)?;
+        private.insert(
+            space_name.to_string(),
+            PrivateIndex {
+                index,
+                writer,
+                dirty: false,
+            },
+        );
+        Ok(())
+    }
+
+    /// Throws away the in-memory index of a space.
+    /// ## Result
+    /// Whether the space had one.
+    pub fn close_private_index(&self, space_name: &str) -> bool {
+        self.private.lock().unwrap().remove(space_name).is_some()
+    }
+
+    /// Moves the in-memory index of a renamed space. Its documents keep the
+    /// old routes until the space is reindexed.
+    pub fn rename_private_index(&self, space_name: &str, new_space_name: &str) {
+        let mut private = self.private.lock().unwrap();
+        if let Some(index) = private.remove(space_name) {
+            private.insert(new_space_name.to_string(), index);
+        }
+    }
+
+    /// # Compact
+    /// Commits the writer and merges every segment, so the deleted
+    /// documents are dropped from the files of the index instead of being
+    /// only marked as deleted.
+    pub fn compact(&self, index_writer: &mut IndexWriter) -> Result<(), SearchError> {
+        index_writer.commit()?;
+        let segments = self.index.searchable_segment_ids()?;
+        if !segments.is_empty() {
+            index_writer.merge(&segments).wait()?;
+        }
+        index_writer.garbage_collect_files().wait()?;
+        Ok(())
+    }
+
     pub fn index_document<T: Searchable>(
         &self,
         index_writer: &mut IndexWriter,
@@ -52,15 +166,32 @@ impl TantivySearchRepository {
         let name_field = self.schema.get_field("name").unwrap();
         let content_field = self.schema.get_field("content").unwrap();
         let folder_field = self.schema.get_field("folder").unwrap();
+        let tag_field = self.schema.get_field("tag").unwrap();
+        let status_field = self.schema.get_field("status").unwrap();
 
-        let new_doc = doc!(
+        let mut new_doc = doc!(
             route_field => document.get_unique_id(space_name),
             name_field => document.get_search_name(),
             content_field => document.get_search_content().unwrap_or_default(),
             folder_field => document.get_search_folder().unwrap_or_default(),
         );
+        for tag in document.get_search_tags() {
+            new_doc.add_text(tag_field, tag);
+        }
+        if let Some(status) = document.get_search_status() {
+            new_doc.add_text(status_field, status);
+        }
 
-        index_writer.add_document(new_doc)?;
+        let mut private = self.private.lock().unwrap();
+        match private.get_mut(space_name) {
+            Some(private) => {
+                private.writer.add_document(new_doc)?;
+                private.dirty = true;
+            }
+            None => {
+                index_writer.add_document(new_doc)?;
+            }
+        }
         Ok(())
     }
 
@@ -78,6 +209,13 @@ impl TantivySearchRepository {
         // Create a query that looks for the exact document route.
         let query = query_parser.parse_query(&format!("\"{}\"", document_route))?;
 
+        // The in-memory index of its space may hold it too.
+        let space_name = document_route.split('/').next().unwrap_or_default();
+        if let Some(private) = self.private.lock().unwrap().get_mut(space_name) {
+            private.writer.delete_query(query.box_clone())?;
+            private.dirty = true;
+        }
+
         // Use delete_query to delete all documents that match the query.
         // This is more reliable than delete_term because it uses the full query parser logic.
         index_writer.delete_query(Box::new(query))?;
@@ -86,7 +224,90 @@ impl TantivySearchRepository {
     }
 
     pub fn search_documents(&self, query: &str) -> Result<Vec<String>, SearchError> {
-        let reader = self.index.reader()?;
+        let query = normalize_filters(query);
+
+        let mut hits = self.search_index(&self.index, &query)?;
+        for private in self.private.lock().unwrap().values_mut() {
+            if private.dirty {
+                private.writer.commit()?;
+                private.dirty = false;
+            }
+            hits.extend(self.search_index(&private.index, &query)?);
+        }
+        hits.sort_by(|a, b| b.0.total_cmp(&a.0));
+        hits.truncate(10);
+
+        Ok(hits.into_iter().map(|(_, route)| route).collect())
+    }
+
+    /// # Space routes
+    /// The routes of the documents of a space, from the index of the space.
+    pub fn get_space_routes(&self, space_name: &str) -> Result<Vec<String>, SearchError> {
+        let route_field = self.schema.get_field("route").unwrap();
+        let mut routes: Vec<String> = self
+            .space_documents(space_name)?
+            .iter()
+            .filter_map(|doc| doc.get_first(route_field).and_then(|v| v.as_str()))
+            .map(str::to_string)
+            .collect();
+        routes.sort();
+        Ok(routes)
+    }
+
+    /// # Space tags
+    /// The tags of the documents of a space with the number of documents
+    /// using each one, from the index of the space.
+    pub fn get_space_tags(&self, space_name: &str) -> Result<BTreeMap<String, usize>, SearchError> {
+        let tag_field = self.schema.get_field("tag").unwrap();
+        let mut counts: BTreeMap<String, usize> = BTreeMap::new();
+        for doc in self.space_documents(space_name)? {
+            let tags: HashSet<&str> = doc.get_all(tag_field).filter_map(|v| v.as_str()).collect();
+            for tag in tags {
+                *counts.entry(tag.to_string()).or_default() += 1;
+            }
+        }
+        Ok(counts)
+    }
+
+    /// The documents of a space, read from its in-memory index when it's an
+    /// unlocked encrypted space.
+    fn space_documents(&self, space_name: &str) -> Result<Vec<TantivyDocument>, SearchError> {
+        let prefix = format!("{}/", space_name);
+        let mut private = self.private.lock().unwrap();
+        let index = match private.get_mut(space_name) {
+            Some(private) => {
+                if private.dirty {
+                    private.writer.commit()?;
+                    private.dirty = false;
+                }
+                &private.index
+            }
+            None => &self.index,
+        };
+
+        let searcher = index.reader()?.searcher();
+        let route_field = self.schema.get_field("route").unwrap();
+        let mut documents = Vec::new();
+        for doc_address in searcher.search(&AllQuery, &DocSetCollector)? {
+            let doc: TantivyDocument = searcher.doc(doc_address)?;
+            let in_space = doc
+                .get_first(route_field)
+                .and_then(|v| v.as_str())
+                .is_some_and(|route| route.starts_with(&prefix));
+            if in_space {
+                documents.push(doc);
+            }
+        }
+        Ok(documents)
+    }
+
+    /// The best 10 documents of an index for a query, with their score.
+    fn search_index(
+        &self,
+        index: &Index,
+        query: &str,
+    ) -> Result<Vec<(Score, String)>, SearchError> {
+        let reader = index.reader()?;
         let searcher = reader.searcher();
 
         let route_field = self.schema.get_field("route").unwrap();
@@ -95,12 +316,13 @@ impl TantivySearchRepository {
         let folder_field = self.schema.get_field("folder").unwrap();
 
         let query_parser =
-            QueryParser::for_index(&self.index, vec![name_field, content_field, folder_field]);
+            QueryParser::for_index(index, vec![name_field, content_field, folder_field]);
         let tantivy_query = query_parser.parse_query(query)?;
-        let top_docs = searcher.search(&tantivy_query, &TopDocs::with_limit(10))?;
+        let top_docs: Vec<(Score, DocAddress)> =
+            searcher.search(&tantivy_query, &TopDocs::with_limit(10))?;
 
         let mut results = Vec::new();
-        for (_score, doc_address) in top_docs {
+        for (score, doc_address) in top_docs {
             let retrieved_doc: TantivyDocument = searcher.doc(doc_address)?;
             let route = retrieved_doc
                 .get_first(route_field)
@@ -108,8 +330,79 @@ impl TantivySearchRepository {
                 .unwrap_or("")
                 .to_string();
 
-            results.push(route);
+            results.push((score, route));
         }
         Ok(results)
     }
 }
+
+/// Properties are indexed normalized, normalizes the `tag:` and `status:`
+/// filters of a query the same way.
+fn normalize_filters(query: &str) -> Cow<'_, str> {
+    PROPERTY_FILTER.replace_all(query, |caps: &regex::Captures| {
+        format!("{}:{}", &caps[1], caps[2].to_lowercase())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::notes::domain::{note::Note, properties::NoteProperties};
+
+    #[test]
+    fn filters_are_normalized() {
+        assert_eq!(
+            normalize_filters("rust tag:#Async status:Draft"),
+            "rust tag:async status:draft"
+        );
+        assert_eq!(normalize_filters("(tag:Web)"), "(tag:web)");
+    }
+
+    #[test]
+    fn other_terms_are_untouched() {
+        assert_eq!(normalize_filters("Hello World"), "Hello World");
+        assert_eq!(normalize_filters("hashtag:Foo"), "hashtag:Foo");
+    }
+
+    fn tagged_note(name: &str, tags: &[&str]) -> Note {
+        Note {
+            name: name.to_string(),
+            properties: Some(NoteProperties {
+                tags: tags.iter().map(|t| t.to_string()).collect(),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn tags_are_counted_per_space() {
+        let path = std::env::temp_dir().join(format!("merino-tags-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&path);
+        let repo = TantivySearchRepository::new(&path).unwrap();
+        let mut writer = repo.get_index_writer().unwrap();
+        repo.open_private_index("Secret").unwrap();
+
+        for (space, note) in [
+            ("Work", tagged_note("a", &["rust", "web"])),
+            ("Work", tagged_note("b", &["rust", "rust"])),
+            ("Work 2", tagged_note("c", &["rust"])),
+            ("Secret", tagged_note("d", &["diary"])),
+        ] {
+            repo.index_document(&mut writer, &note, space).unwrap();
+        }
+        writer.commit().unwrap();
+
+        assert_eq!(
+            repo.get_space_tags("Work").unwrap(),
+            BTreeMap::from([("rust".to_string(), 2), ("web".to_string(), 1)])
+        );
+        assert_eq!(
+            repo.get_space_tags("Secret").unwrap(),
+            BTreeMap::from([("diary".to_string(), 1)])
+        );
+        assert!(repo.get_space_tags("Empty").unwrap().is_empty());
+
+        fs::remove_dir_all(&path).unwrap();
+    }
+}
@@ -1,5 +1,22 @@
+use serde::de::DeserializeOwned;
+
 use crate::features::settings::domain::repository::SettingsRepository;
 
 pub async fn get_settings<T: SettingsRepository>(repo: &T) -> Result<String, String> {
     repo.get_settings().await
 }
+
+/// Reads the section `key` of the settings, falling back to its default when
+/// the section isn't there.
+pub async fn get_settings_section<T: SettingsRepository, S: DeserializeOwned + Default>(
+    repo: &T,
+    key: &str,
+) -> Result<S, String> {
+    let settings = repo.get_settings().await?;
+    let settings: serde_json::Value = serde_json::from_str(&settings).map_err(|e| e.to_string())?;
+
+    match settings.get(key) {
+        Some(section) => serde_json::from_value(section.clone()).map_err(|e| e.to_string()),
+        None => Ok(S::default()),
+    }
+}
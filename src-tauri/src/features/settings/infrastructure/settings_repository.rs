@@ -6,7 +6,10 @@ use tokio::fs;
 
 use crate::{
     features::settings::domain::repository::SettingsRepository,
-    shared::{repositories::filesystem_repository::FileSystemRepository, utils::{atomic_write::write_atomic, merge_values::merge_values}},
+    shared::{
+        repositories::filesystem_repository::FileSystemRepository,
+        utils::{atomic_write::write_synced, merge_values::merge_values},
+    },
 };
 
 #[derive(Clone)]
@@ -20,12 +23,11 @@ impl FileSystemSettingsRepository {
     }
 
     fn get_settings_path(&self) -> Result<PathBuf, String> {
-        let mut path = self
+        let path = self
             .filesystem_repo
-            .get_base_path()
+            .get_app_dir()
             .map_err(|e| e.to_string())?;
-        path.push("../.merino/settings.json");
-        Ok(path)
+        Ok(path.join("settings.json"))
     }
 }
 
@@ -39,9 +41,12 @@ impl SettingsRepository for FileSystemSettingsRepository {
                 "theme": "default",
                 "locale": "en"
             });
-            let serialized = serde_json::to_string_pretty(&default_settings).map_err(|e| e.to_string())?;
+            let serialized =
+                serde_json::to_string_pretty(&default_settings).map_err(|e| e.to_string())?;
 
-            write_atomic(&settings_path, &serialized).await?;
+            write_synced(&settings_path, serialized.as_bytes())
+                .await
+                .map_err(|e| format!("Failed to write settings: {}", e))?;
         }
         Ok(())
     }
@@ -85,10 +90,10 @@ impl SettingsRepository for FileSystemSettingsRepository {
         let merged_settings_str =
             serde_json::to_string_pretty(&current_settings).map_err(|e| e.to_string())?;
 
-        write_atomic(&settings_path, &merged_settings_str).await?;
+        write_synced(&settings_path, merged_settings_str.as_bytes())
+            .await
+            .map_err(|e| format!("Failed to write settings: {}", e))?;
 
         Ok(())
     }
-
-    
 }
@@ -0,0 +1,164 @@
+use std::{collections::BTreeSet, path::Path};
+
+use crate::features::{
+    attachments::domain::repository::AttachmentRepository,
+    notes::domain::{properties::extract_properties, repository::NoteRepository},
+    site::domain::{
+        errors::SiteError,
+        render::{render_note, SiteLayout, BASE_STYLESHEET, SEARCH_SCRIPT_CONTENT},
+        repository::SiteRepository,
+        site::{
+            SearchEntry, SiteExport, SiteFile, SiteMap, SiteOptions, ATTACHMENTS_DIR,
+            DEFAULT_THEME, INDEX_PAGE, SEARCH_INDEX, SEARCH_SCRIPT, STYLESHEET,
+        },
+    },
+    space::domain::repository::SpaceRepository,
+    theming::domain::repository::ThemingRepository,
+};
+
+/// # Export Space HTML use case
+/// Writes a space as a static website: a page per note rendered from its
+/// blocks, an index page, the navigation between the folders and the
+/// stylesheet of a theme. The links between notes point to their pages and
+/// the embedded attachments are copied. Optionally, the pages get a search
+/// box working without a server.
+/// ## Params
+/// * `space_name`: Name of the space to be exported.
+/// * `destination`: The directory the website is written in, named after
+///   the space. A previous export there is replaced.
+/// * `options`: The theme of the website and whether it can be searched.
+/// ## Result
+/// What was exported, with the notes that couldn't be rendered as they are.
+#[doc(alias = "export_space_html")]
+#[allow(clippy::too_many_arguments)]
+pub async fn export_space_html_use_case<S, N, A, T, R>(
+    space_repo: &S,
+    notes_repo: &N,
+    attachments_repo: &A,
+    theming_repo: &T,
+    site_repo: &R,
+    space_name: &str,
+    destination: &Path,
+    options: &SiteOptions,
+) -> Result<SiteExport, SiteError>
+where
+    S: SpaceRepository,
+    N: NoteRepository,
+    A: AttachmentRepository,
+    T: ThemingRepository,
+    R: SiteRepository,
+{
+    let space = space_repo.get_space(space_name).await?;
+    let theme = match options.theme.trim() {
+        "" => DEFAULT_THEME,
+        theme if theme.starts_with('.') || theme.contains(['/', '\\']) => {
+            return Err(SiteError::Theme(format!("Invalid theme '{}'.", theme)));
+        }
+        theme => theme,
+    };
+    let theme_css = theming_repo
+        .get_theme_content(theme.to_string())
+        .await
+        .map_err(SiteError::Theme)?;
+
+    let notes = notes_repo.get_notes(space_name).await?;
+    let map = SiteMap::new(notes.iter().map(|n| (n.folder.clone(), n.name.clone())));
+    let layout = SiteLayout {
+        title: space
+            .manifest
+            .display_name
+            .as_deref()
+            .filter(|n| !n.trim().is_empty())
+            .unwrap_or(&space.name),
+        description: space.manifest.description.as_deref(),
+        theme,
+        search: options.search,
+        map: &map,
+    };
+
+    let mut export = SiteExport::default();
+    let mut files = Vec::new();
+    let mut attachments = BTreeSet::new();
+    let mut search_index = Vec::new();
+    for page in map.pages() {
+        let note = notes_repo
+            .get_note_content(space_name, &page.title, page.folder.as_deref())
+            .await?;
+        let content = note.content.unwrap_or_default();
+        let tags = note
+            .properties
+            .or_else(|| extract_properties(&content))
+            .map(|p| p.tags)
+            .unwrap_or_default();
+
+        let rendered = render_note(&content, &map, &page.path);
+        let source = match &page.folder {
+            Some(folder) => format!("{}/{}", folder, page.title),
+            None => page.title.clone(),
+        };
+        export
+            .issues
+            .extend(rendered.issues.iter().map(|i| format!("{}: {}", source, i)));
+        attachments.extend(rendered.attachments);
+        files.push(SiteFile {
+            path: page.path.clone(),
+            content: layout.render_page(page, &tags, &rendered.html).into_bytes(),
+        });
+        if options.search {
+            search_index.push(SearchEntry {
+                title: page.title.clone(),
+                folder: page.folder.clone(),
+                path: page.path.clone(),
+                tags,
+                text: rendered.text,
+            });
+        }
+        export.pages += 1;
+    }
+
+    // Read through the repository, the attachments of an encrypted space
+    // are sealed on disk.
+    for id in attachments {
+        match attachments_repo
+            .get_attachment_content(space_name, &id)
+            .await
+        {
+            Ok(content) => {
+                files.push(SiteFile {
+                    path: format!("{}/{}", ATTACHMENTS_DIR, id),
+                    content,
+                });
+                export.attachments += 1;
+            }
+            Err(e) => export
+                .issues
+                .push(format!("The attachment '{}' can't be copied: {}", id, e)),
+        }
+    }
+
+    files.push(SiteFile {
+        path: INDEX_PAGE.to_string(),
+        content: layout.render_index().into_bytes(),
+    });
+    files.push(SiteFile {
+        path: STYLESHEET.to_string(),
+        content: format!("{}\n{}", BASE_STYLESHEET, theme_css).into_bytes(),
+    });
+    if options.search {
+        let index = serde_json::to_string(&search_index)
+            .map_err(|e| SiteError::Io(std::io::Error::other(e)))?;
+        files.push(SiteFile {
+            path: SEARCH_INDEX.to_string(),
+            content: format!("window.MERINO_SEARCH_INDEX = {};\n", index).into_bytes(),
+        });
+        files.push(SiteFile {
+            path: SEARCH_SCRIPT.to_string(),
+            content: SEARCH_SCRIPT_CONTENT.as_bytes().to_vec(),
+        });
+    }
+
+    export.path = site_repo
+        .write_site(destination, &space.name, files)
+        .await?;
+    Ok(export)
+}
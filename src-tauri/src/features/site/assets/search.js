@@ -0,0 +1,68 @@
+// Search box of the exported websites. The index is loaded by a script
+// setting `window.MERINO_SEARCH_INDEX`, pages opened from the disk can't
+// fetch files.
+(function () {
+    const input = document.getElementById("site-search");
+    const results = document.getElementById("site-search-results");
+    const index = window.MERINO_SEARCH_INDEX || [];
+    if (!input || !results) {
+        return;
+    }
+    const root = input.dataset.root || "";
+
+    function excerpt(text, term) {
+        const at = text.toLowerCase().indexOf(term);
+        if (at < 0) {
+            return text.slice(0, 120);
+        }
+        const start = Math.max(0, at - 40);
+        return (start > 0 ? "…" : "") + text.slice(start, at + term.length + 80);
+    }
+
+    input.addEventListener("input", function () {
+        const terms = input.value.toLowerCase().split(/\s+/).filter(Boolean);
+        results.replaceChildren();
+        if (terms.length === 0) {
+            return;
+        }
+        const matches = index
+            .map(function (entry) {
+                const title = entry.title.toLowerCase();
+                const tags = entry.tags.join(" ").toLowerCase();
+                const text = entry.text.toLowerCase();
+                let score = 0;
+                for (const term of terms) {
+                    if (title.includes(term)) {
+                        score += 3;
+                    } else if (tags.includes(term)) {
+                        score += 2;
+                    } else if (text.includes(term)) {
+                        score += 1;
+                    } else {
+                        return null;
+                    }
+                }
+                return { entry: entry, score: score };
+            })
+            .filter(Boolean)
+            .sort(function (a, b) {
+                return b.score - a.score || a.entry.title.localeCompare(b.entry.title);
+            })
+            .slice(0, 20);
+
+        for (const match of matches) {
+            const item = document.createElement("li");
+            const link = document.createElement("a");
+            link.href = root + match.entry.path
+                .split("/")
+                .map(encodeURIComponent)
+                .join("/");
+            link.textContent = match.entry.title;
+            const detail = document.createElement("small");
+            detail.textContent = excerpt(match.entry.text, terms[0]);
+            link.appendChild(detail);
+            item.appendChild(link);
+            results.appendChild(item);
+        }
+    });
+})();
@@ -0,0 +1,44 @@
+//! # Errors
+//! Defines all error that could ocurr while exporting a space as a static
+//! website.
+use std::io;
+
+use thiserror::Error;
+
+use crate::{
+    features::{
+        attachments::domain::errors::AttachmentError, notes::domain::errors::NoteError,
+        space::domain::errors::SpaceError,
+    },
+    shared::errors::app_errors::AppError,
+};
+
+/// # SiteError Enum
+/// Defines the enum with all the possible error that could ocurr while
+/// writing the website of a space.
+/// ## Possible Errors
+/// * `InvalidDestination`: The website can't be written where requested.
+/// * `Theme`: The theme of the website can't be read.
+#[derive(Debug, Error)]
+pub enum SiteError {
+    #[error("Invalid website destination: {0}")]
+    InvalidDestination(String),
+
+    #[error("Website theme error: {0}")]
+    Theme(String),
+
+    #[error("Website IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    Note(#[from] NoteError),
+
+    #[error(transparent)]
+    Space(#[from] SpaceError),
+
+    #[error(transparent)]
+    Attachment(#[from] AttachmentError),
+
+    #[error(transparent)]
+    AppError(#[from] AppError),
+}
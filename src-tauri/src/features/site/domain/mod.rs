@@ -0,0 +1,4 @@
+pub mod errors;
+pub mod render;
+pub mod repository;
+pub mod site;
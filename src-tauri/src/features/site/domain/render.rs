@@ -0,0 +1,736 @@
+//! # Render Domain
+//! Defines how the Editor.js blocks of the notes are rendered to the HTML
+//! pages of the website, with the navigation between them.
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    sync::LazyLock,
+};
+
+use regex::{Captures, Regex};
+use serde_json::Value;
+
+use super::site::{
+    relative_href, slug, SiteMap, SitePage, ATTACHMENTS_DIR, INDEX_PAGE, SEARCH_INDEX,
+    SEARCH_SCRIPT, STYLESHEET,
+};
+use crate::{
+    features::notes::domain::link::{NoteLink, NOTE_LINK_SCHEME},
+    shared::utils::html::{escape_html, plain_text},
+};
+
+/// Base stylesheet of the website, using the variables of the themes.
+pub const BASE_STYLESHEET: &str = include_str!("../assets/site.css");
+
+/// Script filtering the pages with the search index.
+pub const SEARCH_SCRIPT_CONTENT: &str = include_str!("../assets/search.js");
+
+/// Matches the `href` of the links between notes in a rich text.
+static NOTE_HREF: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(&format!(
+        r#"href="({}[^"]*)""#,
+        regex::escape(NOTE_LINK_SCHEME)
+    ))
+    .unwrap()
+});
+
+/// Matches what looks like an HTML tag in a rich text.
+static RICH_TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^<>]*>").unwrap());
+
+/// Reads a tag: whether it closes, its name and its attributes.
+static TAG_PARTS: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^<\s*(/?)\s*([a-zA-Z][a-zA-Z0-9]*)(\s[^>]*)?/?>$").unwrap());
+
+/// Matches the `href` attribute of a tag.
+static HREF_ATTRIBUTE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r#"(?i)(?:^|\s)href\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'=<>`]+))"#).unwrap()
+});
+
+/// Matches the character references of a rich text.
+static ENTITY: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"&(?:[a-zA-Z][a-zA-Z0-9]*|#[0-9]+|#[xX][0-9a-fA-F]+);").unwrap());
+
+/// The tags a rich text keeps in the pages, every other one is escaped.
+const ALLOWED_TAGS: [&str; 7] = ["b", "i", "u", "a", "code", "mark", "br"];
+
+#[derive(Debug, Default)]
+/// # RenderedNote
+/// The content of a note rendered to HTML.
+/// ## Fields
+/// * `html`: The rendered blocks.
+/// * `attachments`: The ids of the attachments the blocks embed.
+/// * `text`: The plain text of the note, for the search index.
+/// * `issues`: The blocks that couldn't be rendered as they are.
+pub struct RenderedNote {
+    pub html: String,
+    pub attachments: BTreeSet<String>,
+    pub text: String,
+    pub issues: Vec<String>,
+}
+
+/// # SiteLayout
+/// What every page of a website shares.
+/// ## Fields
+/// * `title`: The title of the website, the name of the space.
+/// * `description`: What the space is about.
+/// * `theme`: The theme set on the pages.
+/// * `search`: Whether the pages get the search box.
+/// * `map`: The pages of the website.
+pub struct SiteLayout<'a> {
+    pub title: &'a str,
+    pub description: Option<&'a str>,
+    pub theme: &'a str,
+    pub search: bool,
+    pub map: &'a SiteMap,
+}
+
+impl SiteLayout<'_> {
+    /// # Render page
+    /// The page of a note: the navigation and its rendered content.
+    pub fn render_page(&self, page: &SitePage, tags: &[String], content: &str) -> String {
+        let mut main = format!(
+            "<h1 class=\"note-title\">{}</h1>\n",
+            escape_html(&page.title)
+        );
+        if !tags.is_empty() {
+            main.push_str("<ul class=\"note-tags\">");
+            for tag in tags {
+                main.push_str(&format!("<li>#{}</li>", escape_html(tag)));
+            }
+            main.push_str("</ul>\n");
+        }
+        main.push_str(content);
+        self.document(&page.path, &page.title, &main)
+    }
+
+    /// # Render index
+    /// The home page: the description of the space and every note.
+    pub fn render_index(&self) -> String {
+        let mut main = format!(
+            "<h1 class=\"note-title\">{}</h1>\n",
+            escape_html(self.title)
+        );
+        if let Some(description) = self.description.filter(|d| !d.trim().is_empty()) {
+            main.push_str(&format!(
+                "<p class=\"site-description\">{}</p>\n",
+                escape_html(description)
+            ));
+        }
+        main.push_str("<div class=\"site-index\">\n");
+        main.push_str(&self.tree(INDEX_PAGE, None));
+        main.push_str("</div>\n");
+        self.document(INDEX_PAGE, self.title, &main)
+    }
+
+    /// The HTML document of a page of the website.
+    fn document(&self, path: &str, title: &str, main: &str) -> String {
+        let title = if path == INDEX_PAGE {
+            escape_html(title)
+        } else {
+            format!("{} · {}", escape_html(title), escape_html(self.title))
+        };
+        let mut nav = format!(
+            "<a class=\"site-title\" href=\"{}\">{}</a>\n",
+            relative_href(path, INDEX_PAGE),
+            escape_html(self.title)
+        );
+        let mut scripts = String::new();
+        if self.search {
+            nav.push_str(&format!(
+                "<input type=\"search\" id=\"site-search\" placeholder=\"Search\" data-root=\"{}\">\n\
+                 <ul id=\"site-search-results\"></ul>\n",
+                "../".repeat(path.matches('/').count())
+            ));
+            for script in [SEARCH_INDEX, SEARCH_SCRIPT] {
+                scripts.push_str(&format!(
+                    "<script src=\"{}\"></script>\n",
+                    relative_href(path, script)
+                ));
+            }
+        }
+        nav.push_str(&self.tree(path, Some(path)));
+
+        format!(
+            "<!DOCTYPE html>\n\
+             <html lang=\"en\" data-theme=\"{theme}\">\n\
+             <head>\n\
+             <meta charset=\"utf-8\">\n\
+             <meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n\
+             <title>{title}</title>\n\
+             <link rel=\"stylesheet\" href=\"{stylesheet}\">\n\
+             </head>\n\
+             <body>\n\
+             <nav class=\"site-nav\">\n{nav}</nav>\n\
+             <main class=\"note\">\n{main}</main>\n\
+             {scripts}\
+             </body>\n\
+             </html>\n",
+            theme = escape_html(self.theme),
+            stylesheet = relative_href(path, STYLESHEET),
+        )
+    }
+
+    /// The folders and notes of the website as nested lists, the folder of
+    /// the `current` page open.
+    fn tree(&self, from: &str, current: Option<&str>) -> String {
+        let mut root = TreeNode::default();
+        for page in self.map.pages() {
+            let mut node = &mut root;
+            for segment in page.folder.iter().flat_map(|f| f.split('/')) {
+                node = node.folders.entry(segment.to_string()).or_default();
+            }
+            node.pages.push(page);
+        }
+        let mut html = String::new();
+        root.render(from, current, &mut html);
+        html
+    }
+}
+
+/// A folder of the navigation.
+#[derive(Default)]
+struct TreeNode<'a> {
+    folders: BTreeMap<String, TreeNode<'a>>,
+    pages: Vec<&'a SitePage>,
+}
+
+impl TreeNode<'_> {
+    fn contains(&self, path: &str) -> bool {
+        self.pages.iter().any(|page| page.path == path)
+            || self.folders.values().any(|folder| folder.contains(path))
+    }
+
+    fn render(&self, from: &str, current: Option<&str>, html: &mut String) {
+        html.push_str("<ul class=\"site-tree\">\n");
+        for (name, folder) in &self.folders {
+            let open = match current {
+                Some(current) if folder.contains(current) => " open",
+                Some(_) => "",
+                None => " open",
+            };
+            html.push_str(&format!(
+                "<li class=\"site-folder\"><details{}><summary>{}</summary>\n",
+                open,
+                escape_html(name)
+            ));
+            folder.render(from, current, html);
+            html.push_str("</details></li>\n");
+        }
+        for page in &self.pages {
+            let class = if current == Some(page.path.as_str()) {
+                " class=\"current\""
+            } else {
+                ""
+            };
+            html.push_str(&format!(
+                "<li><a href=\"{}\"{}>{}</a></li>\n",
+                relative_href(from, &page.path),
+                class,
+                escape_html(&page.title)
+            ));
+        }
+        html.push_str("</ul>\n");
+    }
+}
+
+/// # Render note
+/// Renders the Editor.js content of a note for its page. The links between
+/// notes point to their pages, or are disabled when the linked note isn't
+/// part of the website, and the embedded files to the copied attachments.
+/// ## Fields
+/// * `content`: The Editor.js document of the note.
+/// * `map`: The pages of the website.
+/// * `path`: The path of the page of the note.
+pub fn render_note(content: &str, map: &SiteMap, path: &str) -> RenderedNote {
+    let mut renderer = Renderer {
+        map,
+        path,
+        output: RenderedNote::default(),
+        texts: Vec::new(),
+    };
+    match serde_json::from_str::<Value>(content) {
+        Ok(document) => {
+            if let Some(blocks) = document.get("blocks").and_then(Value::as_array) {
+                renderer.blocks(blocks);
+            }
+        }
+        Err(e) => renderer.issue(format!("Content can't be read: {}", e)),
+    }
+    renderer.output.text = renderer.texts.join("\n");
+    renderer.output
+}
+
+/// Renders the blocks of a note.
+struct Renderer<'a> {
+    map: &'a SiteMap,
+    path: &'a str,
+    output: RenderedNote,
+    texts: Vec<String>,
+}
+
+impl Renderer<'_> {
+    fn issue(&mut self, issue: String) {
+        if !self.output.issues.contains(&issue) {
+            self.output.issues.push(issue);
+        }
+    }
+
+    fn blocks(&mut self, blocks: &[Value]) {
+        for block in blocks {
+            let kind = block
+                .get("type")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            let data = block.get("data").cloned().unwrap_or(Value::Null);
+            let html = self.block(kind, &data);
+            self.output.html.push_str(&html);
+            self.output.html.push('\n');
+        }
+    }
+
+    fn block(&mut self, kind: &str, data: &Value) -> String {
+        match kind {
+            "paragraph" => format!("<p>{}</p>", self.rich_text(str_of(data, "text"))),
+            "header" => {
+                let level = data
+                    .get("level")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(2)
+                    .clamp(1, 6);
+                let text = str_of(data, "text");
+                format!(
+                    "<h{level} id=\"{}\">{}</h{level}>",
+                    escape_html(&slug(&plain_text(text))),
+                    self.rich_text(text)
+                )
+            }
+            "list" => {
+                let style = str_of(data, "style");
+                let items = data.get("items").and_then(Value::as_array);
+                self.list(style, items.map(Vec::as_slice).unwrap_or_default())
+            }
+            "checklist" => {
+                let items = data.get("items").and_then(Value::as_array);
+                let mut html = String::from("<ul class=\"checklist\">");
+                for item in items.map(Vec::as_slice).unwrap_or_default() {
+                    let checked = item.get("checked").and_then(Value::as_bool) == Some(true);
+                    html.push_str(&checklist_item(checked));
+                    html.push_str(&self.rich_text(str_of(item, "text")));
+                    html.push_str("</li>");
+                }
+                html.push_str("</ul>");
+                html
+            }
+            "quote" => {
+                let mut html = format!(
+                    "<blockquote><p>{}</p>",
+                    self.rich_text(str_of(data, "text"))
+                );
+                let caption = str_of(data, "caption");
+                if !caption.trim().is_empty() {
+                    html.push_str(&format!("<cite>{}</cite>", self.rich_text(caption)));
+                }
+                html.push_str("</blockquote>");
+                html
+            }
+            "delimiter" => "<hr>".to_string(),
+            "code" => {
+                let code = str_of(data, "code");
+                self.texts.push(code.to_string());
+                format!("<pre><code>{}</code></pre>", escape_html(code))
+            }
+            "table" => self.table(data),
+            "warning" => format!(
+                "<aside class=\"warning\"><strong>{}</strong><p>{}</p></aside>",
+                self.rich_text(str_of(data, "title")),
+                self.rich_text(str_of(data, "message"))
+            ),
+            "image" => self.image(data),
+            "attaches" => self.attaches(data),
+            "embed" => self.embed(data),
+            "columns" => {
+                let mut html = String::from("<div class=\"columns\">");
+                for column in data
+                    .get("cols")
+                    .and_then(Value::as_array)
+                    .map(Vec::as_slice)
+                    .unwrap_or_default()
+                {
+                    let blocks = column.get("blocks").and_then(Value::as_array);
+                    let previous = std::mem::take(&mut self.output.html);
+                    self.blocks(blocks.map(Vec::as_slice).unwrap_or_default());
+                    let inner = std::mem::replace(&mut self.output.html, previous);
+                    html.push_str(&format!("<div class=\"column\">\n{}</div>", inner));
+                }
+                html.push_str("</div>");
+                html
+            }
+            kind => {
+                self.issue(format!("'{}' blocks aren't rendered.", kind));
+                format!("<!-- {} block -->", escape_html(kind).replace("--", "-"))
+            }
+        }
+    }
+
+    /// A rich text, its links between notes pointing to their pages.
+    fn rich_text(&mut self, text: &str) -> String {
+        let text_plain = plain_text(text);
+        if !text_plain.trim().is_empty() {
+            self.texts.push(text_plain);
+        }
+        let text = sanitize(text);
+        NOTE_HREF
+            .replace_all(&text, |captures: &Captures| {
+                let link = NoteLink::parse(&captures[1]);
+                match link.as_ref().and_then(|l| self.map.link_href(self.path, l)) {
+                    Some(href) => format!("href=\"{}\"", escape_html(&href)),
+                    None => {
+                        let name = link.map_or_else(|| captures[1].to_string(), |l| l.name);
+                        self.issue(format!("The linked note '{}' isn't exported.", name));
+                        "class=\"missing-link\"".to_string()
+                    }
+                }
+            })
+            .to_string()
+    }
+
+    /// A list, its items holding nested lists. Both the current items with
+    /// `content` and the legacy string items are read.
+    fn list(&mut self, style: &str, items: &[Value]) -> String {
+        let (open, close) = match style {
+            "ordered" => ("<ol>", "</ol>"),
+            "checklist" => ("<ul class=\"checklist\">", "</ul>"),
+            _ => ("<ul>", "</ul>"),
+        };
+        let mut html = open.to_string();
+        for item in items {
+            let (content, children) = match item {
+                Value::String(content) => (content.as_str(), None),
+                item => (
+                    str_of(item, "content"),
+                    item.get("items").and_then(Value::as_array),
+                ),
+            };
+            if style == "checklist" {
+                let checked = item
+                    .pointer("/meta/checked")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                html.push_str(&checklist_item(checked));
+            } else {
+                html.push_str("<li>");
+            }
+            html.push_str(&self.rich_text(content));
+            if let Some(children) = children.filter(|c| !c.is_empty()) {
+                html.push_str(&self.list(style, children));
+            }
+            html.push_str("</li>");
+        }
+        html.push_str(close);
+        html
+    }
+
+    fn table(&mut self, data: &Value) -> String {
+        let headings = data.get("withHeadings").and_then(Value::as_bool) == Some(true);
+        let rows = data.get("content").and_then(Value::as_array);
+        let mut html = String::from("<table>");
+        for (i, row) in rows
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+            .iter()
+            .enumerate()
+        {
+            let cell = if headings && i == 0 { "th" } else { "td" };
+            html.push_str("<tr>");
+            for value in row.as_array().map(Vec::as_slice).unwrap_or_default() {
+                let text = value.as_str().unwrap_or_default();
+                html.push_str(&format!("<{cell}>{}</{cell}>", self.rich_text(text)));
+            }
+            html.push_str("</tr>");
+        }
+        html.push_str("</table>");
+        html
+    }
+
+    /// The `href` of an embedded file: the copied attachment, or its URL
+    /// when it's a remote one.
+    fn file_href(&mut self, data: &Value) -> Option<String> {
+        let file = data.get("file")?;
+        if let Some(id) = file
+            .get("id")
+            .and_then(Value::as_str)
+            .filter(|id| !id.is_empty() && !id.contains(['/', '\\']) && !id.starts_with('.'))
+        {
+            self.output.attachments.insert(id.to_string());
+            return Some(relative_href(
+                self.path,
+                &format!("{}/{}", ATTACHMENTS_DIR, id),
+            ));
+        }
+        let url = file.get("url").and_then(Value::as_str).unwrap_or_default();
+        if url.starts_with("http://") || url.starts_with("https://") {
+            return Some(url.to_string());
+        }
+        self.issue(format!("The embedded file '{}' isn't exported.", url));
+        None
+    }
+
+    fn image(&mut self, data: &Value) -> String {
+        let Some(href) = self.file_href(data) else {
+            return "<!-- missing image -->".to_string();
+        };
+        let caption = str_of(data, "caption");
+        let mut classes = Vec::new();
+        for (key, class) in [
+            ("withBorder", "with-border"),
+            ("withBackground", "with-background"),
+            ("stretched", "stretched"),
+        ] {
+            if data.get(key).and_then(Value::as_bool) == Some(true) {
+                classes.push(class);
+            }
+        }
+        let mut html = format!(
+            "<figure class=\"image {}\"><img src=\"{}\" alt=\"{}\">",
+            classes.join(" "),
+            escape_html(&href),
+            escape_html(&plain_text(caption))
+        );
+        if !caption.trim().is_empty() {
+            html.push_str(&format!(
+                "<figcaption>{}</figcaption>",
+                self.rich_text(caption)
+            ));
+        }
+        html.push_str("</figure>");
+        html
+    }
+
+    fn attaches(&mut self, data: &Value) -> String {
+        let Some(href) = self.file_href(data) else {
+            return "<!-- missing file -->".to_string();
+        };
+        let file = data.get("file").cloned().unwrap_or(Value::Null);
+        let title = [
+            str_of(data, "title"),
+            str_of(&file, "title"),
+            str_of(&file, "name"),
+        ]
+        .into_iter()
+        .find(|title| !title.trim().is_empty())
+        .unwrap_or("File");
+        self.texts.push(title.to_string());
+        let mut html = format!(
+            "<a class=\"attachment\" href=\"{}\" download>{}</a>",
+            escape_html(&href),
+            escape_html(title)
+        );
+        if let Some(size) = file.get("size").and_then(Value::as_u64) {
+            html.push_str(&format!(
+                " <span class=\"attachment-size\">{}</span>",
+                file_size(size)
+            ));
+        }
+        format!("<p>{}</p>", html)
+    }
+
+    fn embed(&mut self, data: &Value) -> String {
+        let embed = str_of(data, "embed");
+        let source = str_of(data, "source");
+        let caption = str_of(data, "caption");
+        let mut html = String::from("<figure class=\"embed\">");
+        if embed.starts_with("https://") {
+            let height = data.get("height").and_then(Value::as_u64).unwrap_or(320);
+            html.push_str(&format!(
+                "<iframe src=\"{}\" height=\"{}\" allowfullscreen loading=\"lazy\"></iframe>",
+                escape_html(embed),
+                height
+            ));
+        }
+        if !source.is_empty() {
+            html.push_str(&format!(
+                "<a href=\"{}\">{}</a>",
+                escape_html(source),
+                escape_html(source)
+            ));
+        }
+        if !caption.trim().is_empty() {
+            html.push_str(&format!(
+                "<figcaption>{}</figcaption>",
+                self.rich_text(caption)
+            ));
+        }
+        html.push_str("</figure>");
+        html
+    }
+}
+
+/// # Sanitize
+/// Keeps the allowed tags of a rich text, without their attributes but the
+/// safe `href` of the links, and escapes everything else.
+fn sanitize(text: &str) -> String {
+    let mut html = String::with_capacity(text.len());
+    let mut last = 0;
+    for tag in RICH_TAG.find_iter(text) {
+        html.push_str(&escape_text(&text[last..tag.start()]));
+        html.push_str(&allowed_tag(tag.as_str()).unwrap_or_else(|| escape_text(tag.as_str())));
+        last = tag.end();
+    }
+    html.push_str(&escape_text(&text[last..]));
+    html
+}
+
+/// A tag of the allowlist as it is rendered, `None` for any other tag.
+fn allowed_tag(tag: &str) -> Option<String> {
+    let parts = TAG_PARTS.captures(tag)?;
+    let name = parts[2].to_lowercase();
+    if !ALLOWED_TAGS.contains(&name.as_str()) {
+        return None;
+    }
+    if &parts[1] == "/" {
+        return Some(format!("</{}>", name));
+    }
+    let href = parts
+        .get(3)
+        .and_then(|attributes| HREF_ATTRIBUTE.captures(attributes.as_str()))
+        .and_then(|href| href.get(1).or(href.get(2)).or(href.get(3)))
+        .map(|href| href.as_str().replace("&amp;", "&"))
+        .filter(|href| name == "a" && is_safe_href(href));
+    Some(match href {
+        Some(href) => format!("<a href=\"{}\">", escape_html(href.trim())),
+        None => format!("<{}>", name),
+    })
+}
+
+/// Whether an `href` is kept: a relative one, or an http(s) or note link.
+/// The ones hiding their scheme behind references or control characters
+/// aren't.
+fn is_safe_href(href: &str) -> bool {
+    if ENTITY.is_match(href) || href.chars().any(char::is_control) {
+        return false;
+    }
+    let href = href.trim();
+    match href.find([':', '/', '?', '#']) {
+        Some(index) if href[index..].starts_with(':') => {
+            let scheme = href[..index].to_lowercase();
+            scheme == "http" || scheme == "https" || href.starts_with(NOTE_LINK_SCHEME)
+        }
+        _ => true,
+    }
+}
+
+/// Escapes a text of a rich text, its character references kept.
+fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    let mut last = 0;
+    for entity in ENTITY.find_iter(text) {
+        escaped.push_str(&escape_html(&text[last..entity.start()]));
+        escaped.push_str(entity.as_str());
+        last = entity.end();
+    }
+    escaped.push_str(&escape_html(&text[last..]));
+    escaped
+}
+
+/// A string field of block data, empty when missing.
+fn str_of<'v>(data: &'v Value, key: &str) -> &'v str {
+    data.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+/// The opening of a checklist item.
+fn checklist_item(checked: bool) -> String {
+    if checked {
+        "<li class=\"checked\"><input type=\"checkbox\" checked disabled> ".to_string()
+    } else {
+        "<li><input type=\"checkbox\" disabled> ".to_string()
+    }
+}
+
+/// A size in bytes as people read it.
+fn file_size(bytes: u64) -> String {
+    match bytes {
+        0..1024 => format!("{} B", bytes),
+        1024..1_048_576 => format!("{:.1} KB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::site::domain::site::page_path;
+
+    fn render(blocks: &str) -> RenderedNote {
+        let map = SiteMap::new([(None, "Index".to_string())]);
+        render_note(
+            &format!(r#"{{"blocks":{}}}"#, blocks),
+            &map,
+            &page_path(None, "Index"),
+        )
+    }
+
+    #[test]
+    fn blocks_are_rendered() {
+        let rendered = render(
+            r#"[
+                {"type":"header","data":{"text":"Main title","level":1}},
+                {"type":"paragraph","data":{"text":"Some <b>bold</b> text"}},
+                {"type":"list","data":{"style":"ordered","items":["one","two"]}},
+                {"type":"code","data":{"code":"a < b"}}
+            ]"#,
+        );
+        assert_eq!(
+            rendered.html,
+            "<h1 id=\"main-title\">Main title</h1>\n\
+             <p>Some <b>bold</b> text</p>\n\
+             <ol><li>one</li><li>two</li></ol>\n\
+             <pre><code>a &lt; b</code></pre>\n"
+        );
+        assert!(rendered.text.contains("Some bold text"));
+        assert!(rendered.issues.is_empty());
+    }
+
+    #[test]
+    fn unknown_markup_is_escaped() {
+        let rendered = render(
+            r#"[{"type":"paragraph","data":{"text":"<script>alert(1)</script> & <img src=x onerror=alert(1)>"}}]"#,
+        );
+        assert_eq!(
+            rendered.html,
+            "<p>&lt;script&gt;alert(1)&lt;/script&gt; &amp; &lt;img src=x onerror=alert(1)&gt;</p>\n"
+        );
+    }
+
+    #[test]
+    fn allowed_tags_lose_their_attributes() {
+        let rendered = render(
+            r#"[{"type":"paragraph","data":{"text":"<b onclick=\"x()\">a</b><mark style=\"x\">b</mark>&nbsp;<br/>"}}]"#,
+        );
+        assert_eq!(rendered.html, "<p><b>a</b><mark>b</mark>&nbsp;<br></p>\n");
+    }
+
+    #[test]
+    fn only_safe_links_are_kept() {
+        let rendered = render(
+            r#"[{"type":"paragraph","data":{"text":"<a href=\"https://merino.app/?a=1&amp;b=2\">web</a><a href=\"javascript:alert(1)\">js</a><a href=\"&#106;avascript:alert(1)\">ref</a><a href=\"docs/page.html\">relative</a>"}}]"#,
+        );
+        assert_eq!(
+            rendered.html,
+            "<p><a href=\"https://merino.app/?a=1&amp;b=2\">web</a><a>js</a><a>ref</a><a href=\"docs/page.html\">relative</a></p>\n"
+        );
+    }
+
+    #[test]
+    fn note_links_point_to_their_pages() {
+        let rendered = render(
+            r#"[{"type":"paragraph","data":{"text":"<a href=\"merino://note/Index\">here</a> <a href=\"merino://note/Missing\">gone</a>"}}]"#,
+        );
+        assert_eq!(
+            rendered.html,
+            "<p><a href=\"../notes/Index.html\">here</a> <a class=\"missing-link\">gone</a></p>\n"
+        );
+        assert_eq!(
+            rendered.issues,
+            vec!["The linked note 'Missing' isn't exported.".to_string()]
+        );
+    }
+}
@@ -0,0 +1,30 @@
+//! # Repository
+//! Defines where the websites of the spaces are written.
+use std::path::{Path, PathBuf};
+
+use async_trait::async_trait;
+
+use super::errors::SiteError;
+use super::site::SiteFile;
+
+/// # Site Repository
+/// It implements the methods to write the website of a space.
+#[async_trait]
+pub trait SiteRepository {
+    /// # [CREATE] Write site
+    /// Writes a website in a directory of `destination`, replacing a
+    /// previous export of it. The website is written aside and only moved
+    /// in place once complete.
+    /// ## Fields
+    /// * `destination`: The directory the website is written in.
+    /// * `site_name`: The name of the directory of the website.
+    /// * `files`: The files of the website.
+    /// ## Result
+    /// The directory of the website.
+    async fn write_site(
+        &self,
+        destination: &Path,
+        site_name: &str,
+        files: Vec<SiteFile>,
+    ) -> Result<PathBuf, SiteError>;
+}
@@ -0,0 +1,207 @@
+//! # Site Domain
+//! Defines the static website of a space: a page per note under `notes/`,
+//! an index page, the stylesheet, the copied attachments and, optionally,
+//! a search index read by the browser.
+use std::{collections::HashMap, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::features::notes::domain::link::{percent_encode, NoteLink};
+
+/// The home page of the website.
+pub const INDEX_PAGE: &str = "index.html";
+
+/// Directory of the website holding the pages of the notes.
+pub const PAGES_DIR: &str = "notes";
+
+/// Directory of the website holding the attachments.
+pub const ATTACHMENTS_DIR: &str = "attachments";
+
+/// Stylesheet of the website, the theme appended to the base one.
+pub const STYLESHEET: &str = "style.css";
+
+/// Search index of the website. A script rather than JSON, browsers don't
+/// let the pages opened from the disk fetch files.
+pub const SEARCH_INDEX: &str = "search-index.js";
+
+/// Script of the search box.
+pub const SEARCH_SCRIPT: &str = "search.js";
+
+/// File marking a directory as a website exported by the application, the
+/// only directories an export replaces.
+pub const SITE_MARKER: &str = ".merino-site";
+
+/// Theme of the application, it adds nothing to the base stylesheet.
+pub const DEFAULT_THEME: &str = "default";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # SiteOptions
+/// How the website of a space is written.
+/// ## Fields
+/// * `theme`: The theme whose stylesheet is used.
+/// * `search`: Whether the pages get a search box and its index.
+pub struct SiteOptions {
+    pub theme: String,
+    pub search: bool,
+}
+
+impl Default for SiteOptions {
+    fn default() -> Self {
+        Self {
+            theme: DEFAULT_THEME.to_string(),
+            search: false,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+/// # SiteExport
+/// What the export of a website did.
+/// ## Fields
+/// * `path`: The directory of the website.
+/// * `pages`: The number of note pages written.
+/// * `attachments`: The number of attachments copied.
+/// * `issues`: What couldn't be exported as it was, by note.
+pub struct SiteExport {
+    pub path: PathBuf,
+    pub pages: usize,
+    pub attachments: usize,
+    pub issues: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+/// # SiteFile
+/// A file generated for the website.
+/// ## Fields
+/// * `path`: Its `/` separated path in the website.
+/// * `content`: Its content.
+pub struct SiteFile {
+    pub path: String,
+    pub content: Vec<u8>,
+}
+
+#[derive(Debug, Serialize, Clone)]
+/// # SearchEntry
+/// A page in the search index of the website.
+/// ## Fields
+/// * `title`: The name of the note.
+/// * `folder`: The folder of the note.
+/// * `path`: The path of the page in the website.
+/// * `tags`: The tags of the note.
+/// * `text`: The plain text of the note.
+pub struct SearchEntry {
+    pub title: String,
+    pub folder: Option<String>,
+    pub path: String,
+    pub tags: Vec<String>,
+    pub text: String,
+}
+
+#[derive(Debug, Clone)]
+/// # SitePage
+/// The page of a note.
+/// ## Fields
+/// * `title`: The name of the note.
+/// * `folder`: The folder of the note, `None` for the space root.
+/// * `path`: The `/` separated path of the page in the website.
+pub struct SitePage {
+    pub title: String,
+    pub folder: Option<String>,
+    pub path: String,
+}
+
+/// # SiteMap
+/// The pages of a website, to resolve the links between notes.
+#[derive(Debug, Default)]
+pub struct SiteMap {
+    pages: Vec<SitePage>,
+    by_note: HashMap<(Option<String>, String), usize>,
+}
+
+impl SiteMap {
+    /// Lays out the pages of the notes, given by folder and name.
+    pub fn new(notes: impl IntoIterator<Item = (Option<String>, String)>) -> Self {
+        let mut pages: Vec<SitePage> = notes
+            .into_iter()
+            .map(|(folder, name)| {
+                let folder = folder.filter(|f| !f.is_empty());
+                SitePage {
+                    path: page_path(folder.as_deref(), &name),
+                    title: name,
+                    folder,
+                }
+            })
+            .collect();
+        pages.sort_by(|a, b| (&a.folder, &a.title).cmp(&(&b.folder, &b.title)));
+        let by_note = pages
+            .iter()
+            .enumerate()
+            .map(|(index, page)| ((page.folder.clone(), page.title.clone()), index))
+            .collect();
+        Self { pages, by_note }
+    }
+
+    /// Every page, sorted by folder and title.
+    pub fn pages(&self) -> &[SitePage] {
+        &self.pages
+    }
+
+    /// The page of a note.
+    pub fn page(&self, folder: Option<&str>, name: &str) -> Option<&SitePage> {
+        let key = (folder.map(str::to_string), name.to_string());
+        self.by_note.get(&key).map(|&index| &self.pages[index])
+    }
+
+    /// # Link href
+    /// The `href` of a link between notes, relative to the page `from`,
+    /// `None` when the linked note isn't part of the website.
+    pub fn link_href(&self, from: &str, link: &NoteLink) -> Option<String> {
+        let page = self.page(link.folder.as_deref(), &link.name)?;
+        let mut href = relative_href(from, &page.path);
+        if let Some(heading) = &link.heading {
+            href.push('#');
+            href.push_str(&slug(heading));
+        }
+        Some(href)
+    }
+}
+
+/// # Page path
+/// The path of the page of a note in the website.
+pub fn page_path(folder: Option<&str>, name: &str) -> String {
+    let mut path = PAGES_DIR.to_string();
+    for segment in folder
+        .into_iter()
+        .flat_map(|f| f.split(['/', '\\']))
+        .filter(|s| !s.is_empty())
+    {
+        path.push('/');
+        path.push_str(segment);
+    }
+    format!("{}/{}.html", path, name)
+}
+
+/// # Relative href
+/// The `href` of a file of the website from a page, every segment
+/// percent-encoded.
+pub fn relative_href(from: &str, to: &str) -> String {
+    let depth = from.matches('/').count();
+    let mut href = "../".repeat(depth);
+    href.push_str(
+        &to.split('/')
+            .map(percent_encode)
+            .collect::<Vec<_>>()
+            .join("/"),
+    );
+    href
+}
+
+/// # Slug
+/// The anchor of a heading: lowercase words joined by `-`.
+pub fn slug(text: &str) -> String {
+    text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
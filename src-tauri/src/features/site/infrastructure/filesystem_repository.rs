@@ -0,0 +1,151 @@
+//! # FileSystem Repository
+//! External implementation of the websites of the spaces as directories of
+//! the filesystem.
+use std::{
+    fs,
+    path::{Component, Path, PathBuf},
+};
+
+use async_trait::async_trait;
+
+use crate::features::site::domain::{
+    errors::SiteError,
+    repository::SiteRepository,
+    site::{SiteFile, SITE_MARKER},
+};
+
+/// # FileSystemSiteRepository
+/// Implementation of the SiteRepository trait.
+#[derive(Clone, Default)]
+pub struct FileSystemSiteRepository;
+
+impl FileSystemSiteRepository {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait]
+impl SiteRepository for FileSystemSiteRepository {
+    async fn write_site(
+        &self,
+        destination: &Path,
+        site_name: &str,
+        files: Vec<SiteFile>,
+    ) -> Result<PathBuf, SiteError> {
+        if !destination.is_dir() {
+            return Err(SiteError::InvalidDestination(format!(
+                "'{}' isn't a directory.",
+                destination.display()
+            )));
+        }
+        if site_name.is_empty() || site_name.starts_with('.') || site_name.contains(['/', '\\']) {
+            return Err(SiteError::InvalidDestination(format!(
+                "Invalid website name '{}'.",
+                site_name
+            )));
+        }
+        let site = destination.join(site_name);
+        // Only a previous export is replaced, never another directory.
+        if site.exists() && !site.join(SITE_MARKER).is_file() {
+            return Err(SiteError::InvalidDestination(format!(
+                "'{}' already exists and isn't an exported website.",
+                site.display()
+            )));
+        }
+
+        let staging = destination.join(format!(".{}.site", site_name));
+        let written = {
+            let staging = staging.clone();
+            tokio::task::spawn_blocking(move || write_files(&staging, files))
+                .await
+                .map_err(|e| SiteError::Io(std::io::Error::other(e)))?
+        };
+        if let Err(e) = written {
+            let _ = tokio::fs::remove_dir_all(&staging).await;
+            return Err(e);
+        }
+
+        if site.exists() {
+            tokio::fs::remove_dir_all(&site).await?;
+        }
+        tokio::fs::rename(&staging, &site).await?;
+        Ok(site)
+    }
+}
+
+/// Writes the files of a website to an empty directory.
+fn write_files(root: &Path, files: Vec<SiteFile>) -> Result<(), SiteError> {
+    if root.exists() {
+        fs::remove_dir_all(root)?;
+    }
+    fs::create_dir_all(root)?;
+    fs::write(root.join(SITE_MARKER), "")?;
+    for file in files {
+        let path = site_path(root, &file.path)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, file.content)?;
+    }
+    Ok(())
+}
+
+/// The file of a `/` separated path of the website, which can't leave it.
+fn site_path(root: &Path, path: &str) -> Result<PathBuf, SiteError> {
+    let relative = Path::new(path);
+    if path.is_empty()
+        || !relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_)))
+    {
+        return Err(SiteError::InvalidDestination(format!(
+            "Invalid website file '{}'.",
+            path
+        )));
+    }
+    Ok(root.join(relative))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(content: &str) -> Vec<SiteFile> {
+        vec![SiteFile {
+            path: "index.html".to_string(),
+            content: content.as_bytes().to_vec(),
+        }]
+    }
+
+    #[tokio::test]
+    async fn only_previous_exports_are_replaced() {
+        let destination = std::env::temp_dir().join(format!("merino-site-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&destination);
+        fs::create_dir_all(destination.join("Other")).unwrap();
+        fs::write(destination.join("Other").join("index.html"), "mine").unwrap();
+        let repo = FileSystemSiteRepository::new();
+
+        let refused = repo.write_site(&destination, "Other", page("site")).await;
+        assert!(matches!(refused, Err(SiteError::InvalidDestination(_))));
+        assert_eq!(
+            fs::read_to_string(destination.join("Other").join("index.html")).unwrap(),
+            "mine"
+        );
+
+        let site = repo
+            .write_site(&destination, "Site", page("first"))
+            .await
+            .unwrap();
+        assert!(site.join(SITE_MARKER).is_file());
+        repo.write_site(&destination, "Site", page("second"))
+            .await
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(site.join("index.html")).unwrap(),
+            "second"
+        );
+
+        fs::remove_dir_all(&destination).unwrap();
+    }
+}
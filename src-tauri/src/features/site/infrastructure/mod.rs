@@ -0,0 +1,2 @@
+pub mod filesystem_repository;
+pub mod tauri_commands;
@@ -0,0 +1,64 @@
+//! # Tauri Commands [Site]
+//! Implementation of the commands publishing spaces as static websites to
+//! expose them on tauri for having them in the frontend application.
+use std::path::Path;
+
+use tauri::State;
+
+use crate::{
+    features::{
+        attachments::infrastructure::filesystem_repository::FileSystemAttachmentRepository,
+        site::{
+            application::export,
+            domain::site::{SiteExport, SiteOptions},
+            infrastructure::filesystem_repository::FileSystemSiteRepository,
+        },
+        theming::infrastructre::theming_repository::FileSystemThemingRepository,
+    },
+    shared::state::state::AppState,
+};
+
+/// # [CREATE] Export Space HTML
+/// Publishes a space as a static website: a page per note, an index page,
+/// the navigation between folders, the copied attachments and a theme.
+/// ## Params
+/// * `space_name`: The space to be exported.
+/// * `destination`: The directory the website is written in, as
+///   `<space_name>/`. A previous export there is replaced.
+/// * `theme`: The theme of the website, the default one if not given.
+/// * `search`: Whether the pages get a search box, `false` if not given.
+/// ## Result
+/// What was exported, with the notes that couldn't be rendered as they are.
+#[tauri::command]
+#[allow(clippy::too_many_arguments)]
+pub async fn export_space_html_cmd(
+    state: State<'_, AppState>,
+    site_repo: State<'_, FileSystemSiteRepository>,
+    theming_repo: State<'_, FileSystemThemingRepository>,
+    attachments_repo: State<'_, FileSystemAttachmentRepository>,
+    space_name: &str,
+    destination: &str,
+    theme: Option<String>,
+    search: Option<bool>,
+) -> Result<SiteExport, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+    let spaces_repo_lock = state.spaces_repo.lock().await;
+
+    let defaults = SiteOptions::default();
+    let options = SiteOptions {
+        theme: theme.unwrap_or(defaults.theme),
+        search: search.unwrap_or(defaults.search),
+    };
+    export::export_space_html_use_case(
+        &*spaces_repo_lock,
+        &*fs_repo_lock,
+        &*attachments_repo,
+        &*theming_repo,
+        &*site_repo,
+        space_name,
+        Path::new(destination),
+        &options,
+    )
+    .await
+    .map_err(|e| e.to_string())
+}
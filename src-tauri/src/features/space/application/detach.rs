@@ -0,0 +1,14 @@
+use crate::features::space::domain::{
+    errors::SpaceError, repository::SpaceRepository, space::Space,
+};
+
+/// # Detach Space use case
+/// Removes a space from the application, keeping its folder on disk.
+/// ## Params
+/// * `space_name`: Name of the space to be detached.
+pub async fn detach_space_use_case<T: SpaceRepository>(
+    repo: &T,
+    space_name: &str,
+) -> Result<Space, SpaceError> {
+    repo.detach_space(space_name).await
+}
@@ -13,3 +13,14 @@ use crate::features::space::domain::{
 pub async fn get_spaces_use_case<T: SpaceRepository>(repo: &T) -> Result<Vec<Space>, SpaceError> {
     repo.get_spaces().await
 }
+
+/// # Get Space Use case
+/// Gets a space with its metadata.
+/// ## Params
+/// * `space_name`: Name of the space.
+pub async fn get_space_use_case<T: SpaceRepository>(
+    repo: &T,
+    space_name: &str,
+) -> Result<Space, SpaceError> {
+    repo.get_space(space_name).await
+}
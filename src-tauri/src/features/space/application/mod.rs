@@ -1,3 +1,7 @@
 pub mod create;
 pub mod delete;
+pub mod detach;
 pub mod get;
+pub mod open;
+pub mod relocate;
+pub mod update;
@@ -0,0 +1,18 @@
+use std::path::Path;
+
+use crate::features::space::domain::{
+    errors::SpaceError, repository::SpaceRepository, space::Space,
+};
+
+/// # Open Space use case
+/// Opens an existing folder, anywhere on disk, as a space.
+/// ## Params
+/// * `path`: Absolute path of the folder.
+/// * `space_name`: Name of the space, the folder name if `None`.
+pub async fn open_space_use_case<T: SpaceRepository>(
+    repo: &T,
+    path: &Path,
+    space_name: Option<&str>,
+) -> Result<Space, SpaceError> {
+    repo.open_space(path, space_name).await
+}
@@ -0,0 +1,18 @@
+use std::path::Path;
+
+use crate::features::space::domain::{
+    errors::SpaceError, repository::SpaceRepository, space::Space,
+};
+
+/// # Relocate Space use case
+/// Moves the folder of a space to another location.
+/// ## Params
+/// * `space_name`: Name of the space to be moved.
+/// * `path`: Absolute path the folder is moved to.
+pub async fn relocate_space_use_case<T: SpaceRepository>(
+    repo: &T,
+    space_name: &str,
+    path: &Path,
+) -> Result<Space, SpaceError> {
+    repo.relocate_space(space_name, path).await
+}
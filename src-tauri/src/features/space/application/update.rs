@@ -0,0 +1,32 @@
+use crate::features::space::domain::{
+    errors::SpaceError,
+    repository::SpaceRepository,
+    space::{Space, SpaceManifest},
+};
+
+/// # Rename Space use case
+/// Renames a space.
+/// ## Params
+/// * `space_name`: Name of the space to be renamed.
+/// * `new_space_name`: The new name of the space.
+pub async fn rename_space_use_case<T: SpaceRepository>(
+    repo: &T,
+    space_name: &str,
+    new_space_name: &str,
+) -> Result<Space, SpaceError> {
+    repo.rename_space(space_name, new_space_name).await
+}
+
+/// # Update Space Metadata use case
+/// Replaces the display name, icon, color, description and default folder
+/// of a space.
+/// ## Params
+/// * `space_name`: Name of the space.
+/// * `manifest`: The new metadata.
+pub async fn update_space_metadata_use_case<T: SpaceRepository>(
+    repo: &T,
+    space_name: &str,
+    manifest: SpaceManifest,
+) -> Result<Space, SpaceError> {
+    repo.update_space_metadata(space_name, manifest).await
+}
@@ -12,6 +12,8 @@ use crate::shared::errors::app_errors::AppError;
 /// ## Possible Errors
 /// * `NotFound`: A note hasn't been found.
 /// * `EmptyName`: The name of the note was empty.
+/// * `AlreadyExists`: A space with the name, or at the path, is registered.
+/// * `InvalidPath`: The path can't hold a space.
 #[derive(Debug, Error)]
 pub enum SpaceError {
     #[error("Note not found in space: {0}")]
@@ -20,6 +22,12 @@ pub enum SpaceError {
     #[error("Note name cannot be empty.")]
     EmptyName,
 
+    #[error("Space already exists: {0}")]
+    AlreadyExists(String),
+
+    #[error("Invalid space path: {0}")]
+    InvalidPath(String),
+
     #[error("Space IO error: {0}")]
     Io(#[from] io::Error),
 
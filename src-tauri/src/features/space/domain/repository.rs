@@ -1,9 +1,11 @@
 //! # Repository
 //! Implementation of the notes in this repository.
+use std::path::Path;
+
 use async_trait::async_trait;
 
 use super::errors::SpaceError;
-use super::space::Space;
+use super::space::{Space, SpaceManifest};
 
 /// # Notes Repository
 /// It implements the `list` of methods to interact with the notes in a space.
@@ -34,4 +36,70 @@ pub trait SpaceRepository {
     /// ## Result
     /// A `String` if successful with a message, if not `SpaceError`.
     async fn delete_space(&self, space_name: &str) -> Result<String, SpaceError>;
+
+    /// # [OPEN] Space (Method)
+    /// Registers an existing folder as a space, its files are left as they are.
+    /// ## Fields
+    /// * `&self`
+    /// * `path`: Absolute path of the folder.
+    /// * `space_name`: Name of the space, the folder name if `None`.
+    /// ## Result
+    /// The opened `Space` if successful, if not `SpaceError`.
+    async fn open_space(&self, path: &Path, space_name: Option<&str>) -> Result<Space, SpaceError>;
+
+    /// # [RELOCATE] Space (Method)
+    /// Moves the folder of a space to a new location.
+    /// ## Fields
+    /// * `&self`
+    /// * `space_name`: The name of the space to be moved.
+    /// * `path`: Absolute path the folder is moved to, it must not exist.
+    /// ## Result
+    /// The moved `Space` if successful, if not `SpaceError`.
+    async fn relocate_space(&self, space_name: &str, path: &Path) -> Result<Space, SpaceError>;
+
+    /// # [DETACH] Space (Method)
+    /// Forgets a space without deleting its folder.
+    /// ## Fields
+    /// * `&self`
+    /// * `space_name`: The name of the space to be detached.
+    /// ## Result
+    /// The detached `Space` if successful, if not `SpaceError`.
+    async fn detach_space(&self, space_name: &str) -> Result<Space, SpaceError>;
+
+    /// # [GET] Space (Method)
+    /// Gets a space with its metadata.
+    /// ## Fields
+    /// * `&self`
+    /// * `space_name`: The name of the space.
+    /// ## Result
+    /// The `Space` if successful, if not `SpaceError`.
+    async fn get_space(&self, space_name: &str) -> Result<Space, SpaceError>;
+
+    /// # [UPDATE] Space Name (Method)
+    /// Renames a space.
+    /// ## Fields
+    /// * `&self`
+    /// * `space_name`: The name of the space to be renamed.
+    /// * `new_space_name`: The new name of the space.
+    /// ## Result
+    /// The renamed `Space` if successful, if not `SpaceError`.
+    async fn rename_space(
+        &self,
+        space_name: &str,
+        new_space_name: &str,
+    ) -> Result<Space, SpaceError>;
+
+    /// # [UPDATE] Space Metadata (Method)
+    /// Replaces the metadata stored in the manifest of a space.
+    /// ## Fields
+    /// * `&self`
+    /// * `space_name`: The name of the space.
+    /// * `manifest`: The new metadata, the creation date is kept.
+    /// ## Result
+    /// The updated `Space` if successful, if not `SpaceError`.
+    async fn update_space_metadata(
+        &self,
+        space_name: &str,
+        manifest: SpaceManifest,
+    ) -> Result<Space, SpaceError>;
 }
@@ -1,9 +1,42 @@
 use std::path::PathBuf;
 
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+/// Hidden file of a space holding its metadata, the `space.json` manifest.
+/// Hidden files are never listed as notes.
+pub const SPACE_MANIFEST: &str = ".space.json";
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct Space {
     pub name: String,
     pub route: Option<PathBuf>,
+    #[serde(flatten)]
+    pub manifest: SpaceManifest,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+/// # SpaceManifest
+/// Metadata and configuration of a space, persisted in its `SPACE_MANIFEST`.
+/// ## Fields
+/// * `display_name`: Name shown instead of the space name.
+/// * `icon`: Icon of the space.
+/// * `color`: Color of the space.
+/// * `description`: What the space is about.
+/// * `created`: When the space was created.
+/// * `default_folder`: Folder the new notes are created in, the space root
+///   if `None`.
+pub struct SpaceManifest {
+    #[serde(default)]
+    pub display_name: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub color: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub created: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub default_folder: Option<String>,
 }
@@ -1,11 +1,23 @@
-use std::io::ErrorKind;
+use std::{
+    io::ErrorKind,
+    path::{Path, PathBuf},
+};
 
 use async_trait::async_trait;
+use chrono::Utc;
+use log::warn;
 use tokio::fs::{self, read_dir};
 
 use crate::{
-    features::space::domain::{errors::SpaceError, repository::SpaceRepository, space::Space},
-    shared::repositories::filesystem_repository::FileSystemRepository,
+    features::space::domain::{
+        errors::SpaceError,
+        repository::SpaceRepository,
+        space::{Space, SpaceManifest, SPACE_MANIFEST},
+    },
+    shared::{
+        repositories::filesystem_repository::FileSystemRepository,
+        utils::atomic_write::write_synced,
+    },
 };
 
 /// # FilesystemSpaceRepository
@@ -19,6 +31,94 @@ impl FileSystemSpaceRepository {
     pub fn new(filesystem_repo: FileSystemRepository) -> Self {
         Self { filesystem_repo }
     }
+
+    /// Checks the folder doesn't overlap the one of another space.
+    fn ensure_no_overlap(&self, path: &Path, except: Option<&str>) -> Result<(), SpaceError> {
+        for (name, space_path) in self.filesystem_repo.get_registered_spaces()? {
+            if Some(name.as_str()) == except {
+                continue;
+            }
+            if space_path.starts_with(path) || path.starts_with(&space_path) {
+                return Err(SpaceError::InvalidPath(format!(
+                    "'{}' overlaps the space '{}'",
+                    path.display(),
+                    name
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// The registered folder of a space.
+    fn registered_path(&self, space_name: &str) -> Result<PathBuf, SpaceError> {
+        self.filesystem_repo
+            .get_registered_spaces()?
+            .into_iter()
+            .find(|(name, _)| name == space_name)
+            .map(|(_, path)| path)
+            .ok_or_else(|| SpaceError::NotFound(space_name.to_string()))
+    }
+}
+
+/// Checks a name can be used for a space.
+fn validate_space_name(space_name: &str) -> Result<(), SpaceError> {
+    if space_name.trim().is_empty() {
+        return Err(SpaceError::EmptyName);
+    }
+    if space_name.starts_with('.') || space_name.contains(['/', '\\']) {
+        return Err(SpaceError::InvalidPath(space_name.to_string()));
+    }
+    Ok(())
+}
+
+/// Reads the manifest of a space, a missing or invalid one is empty.
+async fn read_manifest(space_path: &Path) -> SpaceManifest {
+    match fs::read_to_string(space_path.join(SPACE_MANIFEST)).await {
+        Ok(content) => serde_json::from_str(&content).unwrap_or_else(|e| {
+            warn!(
+                "Invalid space manifest in '{}': {}",
+                space_path.display(),
+                e
+            );
+            SpaceManifest::default()
+        }),
+        Err(_) => SpaceManifest::default(),
+    }
+}
+
+/// Writes the manifest of a space.
+async fn write_manifest(space_path: &Path, manifest: &SpaceManifest) -> Result<(), SpaceError> {
+    let content = serde_json::to_string_pretty(manifest)
+        .map_err(|e| SpaceError::Io(std::io::Error::new(ErrorKind::InvalidData, e)))?;
+    write_synced(&space_path.join(SPACE_MANIFEST), content.as_bytes()).await?;
+    Ok(())
+}
+
+/// Builds a space with the metadata of its manifest.
+async fn load_space(space_name: &str, space_path: PathBuf) -> Space {
+    Space {
+        name: space_name.to_string(),
+        manifest: read_manifest(&space_path).await,
+        route: Some(space_path),
+    }
+}
+
+/// Copies a folder with all its content, used when a rename can't move it.
+async fn copy_dir(from: &Path, to: &Path) -> Result<(), SpaceError> {
+    let mut pending = vec![(from.to_path_buf(), to.to_path_buf())];
+    while let Some((from, to)) = pending.pop() {
+        fs::create_dir_all(&to).await?;
+        let mut entries = read_dir(&from).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let target = to.join(entry.file_name());
+            if entry.file_type().await?.is_dir() {
+                pending.push((entry.path(), target));
+            } else {
+                fs::copy(entry.path(), target).await?;
+            }
+        }
+    }
+    Ok(())
 }
 
 #[async_trait]
@@ -28,7 +128,7 @@ impl FileSystemSpaceRepository {
 /// Implements all the methods for treating spaces.
 impl SpaceRepository for FileSystemSpaceRepository {
     /// # [GET] Spaces
-    /// Gets all the space in the default route (for now)
+    /// Gets all the registered spaces whose folder still exists.
     /// ## Fields
     /// * `&self`: Contains the repo.
     /// ## Result
@@ -40,18 +140,16 @@ impl SpaceRepository for FileSystemSpaceRepository {
             .await?;
 
         let mut spaces = Vec::new();
-        let mut entries = read_dir(&base_path).await.map_err(|e| SpaceError::Io(e))?;
-
-        while let Some(entry) = entries.next_entry().await.map_err(|e| SpaceError::Io(e))? {
-            let path = entry.path();
-            if path.is_dir() {
-                if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
-                    spaces.push(Space {
-                        name: name.to_string(),
-                        route: Some(path),
-                    })
-                }
+        for (name, path) in self.filesystem_repo.get_registered_spaces()? {
+            if !path.is_dir() {
+                warn!(
+                    "Space '{}' is missing its folder '{}'.",
+                    name,
+                    path.display()
+                );
+                continue;
             }
+            spaces.push(load_space(&name, path).await)
         }
 
         Ok(spaces)
@@ -69,11 +167,19 @@ impl SpaceRepository for FileSystemSpaceRepository {
             .ensure_directory_exists(&space_path)
             .await
             .map_err(|e| SpaceError::AppError(e))?;
+        self.filesystem_repo
+            .register_space(space_name, space_path.clone())
+            .await?;
+        if !space_path.join(SPACE_MANIFEST).exists() {
+            let manifest = SpaceManifest {
+                created: Some(Utc::now()),
+                ..Default::default()
+            };
+            write_manifest(&space_path, &manifest).await?;
+        }
+        self.filesystem_repo.mark_space_changed(space_name);
 
-        Ok(Space {
-            name: space_name.to_string(),
-            route: Some(space_path),
-        })
+        Ok(load_space(space_name, space_path).await)
     }
 
     /// # [DELETE] Space
@@ -86,9 +192,210 @@ impl SpaceRepository for FileSystemSpaceRepository {
         let space_path = self.filesystem_repo.get_space_path(&space_name)?;
 
         match fs::remove_dir_all(&space_path).await {
-            Ok(_) => Ok(format!("Removed '{}' space.", space_name)),
+            Ok(_) => {
+                self.filesystem_repo.unregister_space(space_name).await?;
+                self.filesystem_repo.keyring().lock(space_name);
+                self.filesystem_repo.mark_space_changed(space_name);
+                Ok(format!("Removed '{}' space.", space_name))
+            }
             Err(e) if e.kind() == ErrorKind::NotFound => Err(SpaceError::NotFound(e.to_string())),
             Err(e) => Err(SpaceError::Io(e)),
         }
     }
+
+    /// # [OPEN] Space
+    /// Registers an existing folder as a space.
+    /// ## Params
+    /// * `path`: Absolute path of the folder.
+    /// * `space_name`: Name of the space, the folder name if `None`.
+    /// ## Result
+    /// A `Space` if succeded, a `SpaceError` if not.
+    async fn open_space(&self, path: &Path, space_name: Option<&str>) -> Result<Space, SpaceError> {
+        if !path.is_absolute() || !path.is_dir() {
+            return Err(SpaceError::InvalidPath(path.display().to_string()));
+        }
+        let path = fs::canonicalize(path).await?;
+        let space_name = match space_name {
+            Some(name) => name.trim().to_string(),
+            None => path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        };
+        validate_space_name(&space_name)?;
+        if self.registered_path(&space_name).is_ok() {
+            return Err(SpaceError::AlreadyExists(space_name));
+        }
+        self.ensure_no_overlap(&path, None)?;
+
+        self.filesystem_repo
+            .register_space(&space_name, path.clone())
+            .await?;
+        self.filesystem_repo.mark_space_changed(&space_name);
+
+        Ok(load_space(&space_name, path).await)
+    }
+
+    /// # [RELOCATE] Space
+    /// Moves the folder of a space, copying it when the new location is on
+    /// another device.
+    /// ## Params
+    /// * `space_name`: Name of the space to be moved.
+    /// * `path`: Absolute path the folder is moved to.
+    /// ## Result
+    /// A `Space` if succeded, a `SpaceError` if not.
+    async fn relocate_space(&self, space_name: &str, path: &Path) -> Result<Space, SpaceError> {
+        let old_path = self.registered_path(space_name)?;
+        if !path.is_absolute() || path.exists() {
+            return Err(SpaceError::InvalidPath(path.display().to_string()));
+        }
+        let parent = path
+            .parent()
+            .ok_or_else(|| SpaceError::InvalidPath(path.display().to_string()))?;
+        fs::create_dir_all(parent).await?;
+        let path = fs::canonicalize(parent)
+            .await?
+            .join(path.file_name().unwrap_or_default());
+        if path.starts_with(&old_path) {
+            return Err(SpaceError::InvalidPath(path.display().to_string()));
+        }
+        self.ensure_no_overlap(&path, Some(space_name))?;
+
+        match fs::rename(&old_path, &path).await {
+            Ok(_) => {}
+            Err(e) if e.kind() == ErrorKind::CrossesDevices => {
+                if let Err(e) = copy_dir(&old_path, &path).await {
+                    let _ = fs::remove_dir_all(&path).await;
+                    return Err(e);
+                }
+                fs::remove_dir_all(&old_path).await?;
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                return Err(SpaceError::NotFound(space_name.to_string()))
+            }
+            Err(e) => return Err(SpaceError::Io(e)),
+        }
+
+        self.filesystem_repo
+            .register_space(space_name, path.clone())
+            .await?;
+        self.filesystem_repo.mark_space_changed(space_name);
+
+        Ok(load_space(space_name, path).await)
+    }
+
+    /// # [DETACH] Space
+    /// Unregisters a space, its folder is kept on disk.
+    /// ## Params
+    /// * `space_name`: Name of the space to be detached.
+    /// ## Result
+    /// A `Space` if succeded, a `SpaceError` if not.
+    async fn detach_space(&self, space_name: &str) -> Result<Space, SpaceError> {
+        let path = self.registered_path(space_name)?;
+        self.filesystem_repo.unregister_space(space_name).await?;
+        self.filesystem_repo.keyring().lock(space_name);
+        self.filesystem_repo.mark_space_changed(space_name);
+
+        Ok(load_space(space_name, path).await)
+    }
+
+    /// # [GET] Space
+    /// Gets a registered space with its metadata.
+    /// ## Params
+    /// * `space_name`: Name of the space.
+    /// ## Result
+    /// A `Space` if succeded, a `SpaceError` if not.
+    async fn get_space(&self, space_name: &str) -> Result<Space, SpaceError> {
+        let path = self.registered_path(space_name)?;
+        if !path.is_dir() {
+            return Err(SpaceError::NotFound(space_name.to_string()));
+        }
+        Ok(load_space(space_name, path).await)
+    }
+
+    /// # [UPDATE] Space Name
+    /// Renames a space. A space kept in the base directory has its folder
+    /// renamed too, one opened from elsewhere keeps its folder.
+    /// ## Params
+    /// * `space_name`: Name of the space to be renamed.
+    /// * `new_space_name`: The new name.
+    /// ## Result
+    /// A `Space` if succeded, a `SpaceError` if not.
+    async fn rename_space(
+        &self,
+        space_name: &str,
+        new_space_name: &str,
+    ) -> Result<Space, SpaceError> {
+        let new_space_name = new_space_name.trim();
+        validate_space_name(new_space_name)?;
+        let path = self.registered_path(space_name)?;
+        if new_space_name == space_name {
+            return Ok(load_space(space_name, path).await);
+        }
+        if self.registered_path(new_space_name).is_ok() {
+            return Err(SpaceError::AlreadyExists(new_space_name.to_string()));
+        }
+
+        let base_path = self.filesystem_repo.get_base_path()?;
+        let new_path = if path == base_path.join(space_name) {
+            let new_path = base_path.join(new_space_name);
+            if new_path.exists() {
+                return Err(SpaceError::AlreadyExists(new_space_name.to_string()));
+            }
+            fs::rename(&path, &new_path).await?;
+            new_path
+        } else {
+            path
+        };
+
+        self.filesystem_repo.unregister_space(space_name).await?;
+        self.filesystem_repo
+            .register_space(new_space_name, new_path.clone())
+            .await?;
+        self.filesystem_repo
+            .keyring()
+            .rename(space_name, new_space_name);
+        self.filesystem_repo.mark_space_changed(space_name);
+        self.filesystem_repo.mark_space_changed(new_space_name);
+
+        Ok(load_space(new_space_name, new_path).await)
+    }
+
+    /// # [UPDATE] Space Metadata
+    /// Replaces the metadata of a space, its creation date is kept.
+    /// ## Params
+    /// * `space_name`: Name of the space.
+    /// * `manifest`: The new metadata.
+    /// ## Result
+    /// A `Space` if succeded, a `SpaceError` if not.
+    async fn update_space_metadata(
+        &self,
+        space_name: &str,
+        manifest: SpaceManifest,
+    ) -> Result<Space, SpaceError> {
+        let path = self.registered_path(space_name)?;
+        if !path.is_dir() {
+            return Err(SpaceError::NotFound(space_name.to_string()));
+        }
+        let default_folder = manifest
+            .default_folder
+            .map(|f| f.trim_matches(['/', '\\']).to_string())
+            .filter(|f| !f.is_empty());
+        if let Some(folder) = &default_folder {
+            if !path.join(folder).is_dir() {
+                return Err(SpaceError::InvalidPath(folder.clone()));
+            }
+        }
+
+        let stored = read_manifest(&path).await;
+        let manifest = SpaceManifest {
+            created: stored.created,
+            default_folder,
+            ..manifest
+        };
+        write_manifest(&path, &manifest).await?;
+        self.filesystem_repo.mark_space_changed(space_name);
+
+        Ok(load_space(space_name, path).await)
+    }
 }
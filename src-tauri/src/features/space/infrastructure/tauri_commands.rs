@@ -1,9 +1,20 @@
+use std::path::Path;
+
+use log::error;
 use tauri::State;
 
-use crate::features::space::{
-    application::{create, delete, get},
-    domain::space::Space,
-    infrastructure::filesystem_repo::FileSystemSpaceRepository,
+use crate::{
+    features::{
+        notes::domain::repository::NoteRepository,
+        search::{self, domain::search::Searchable},
+        space::{
+            application::{create, delete, detach, get, open, relocate, update},
+            domain::space::{Space, SpaceManifest},
+            infrastructure::filesystem_repo::FileSystemSpaceRepository,
+        },
+        watcher::infrastructure::space_watcher::SpaceWatcher,
+    },
+    shared::state::state::AppState,
 };
 
 #[tauri::command]
@@ -34,3 +45,163 @@ pub async fn delete_space_cmd(
         .await
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn open_space_cmd(
+    state: State<'_, AppState>,
+    repo: State<'_, FileSystemSpaceRepository>,
+    path: &str,
+    space_name: Option<&str>,
+) -> Result<Space, String> {
+    let space = open::open_space_use_case(&*repo, Path::new(path), space_name)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    search::application::index::reindex_space_use_case(
+        &*fs_repo_lock,
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &space.name,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(space)
+}
+
+#[tauri::command]
+pub async fn relocate_space_cmd(
+    repo: State<'_, FileSystemSpaceRepository>,
+    watcher: State<'_, SpaceWatcher>,
+    space_name: &str,
+    path: &str,
+) -> Result<Space, String> {
+    // The watch holds the old folder, it's restarted on the new one.
+    let watched = watcher.active_space().as_deref() == Some(space_name);
+    if watched {
+        watcher.unwatch();
+    }
+
+    let result = relocate::relocate_space_use_case(&*repo, space_name, Path::new(path)).await;
+
+    if watched {
+        if let Err(e) = watcher.watch(space_name) {
+            error!("Failed to watch space '{}' again: {}", space_name, e);
+        }
+    }
+    result.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn detach_space_cmd(
+    state: State<'_, AppState>,
+    repo: State<'_, FileSystemSpaceRepository>,
+    watcher: State<'_, SpaceWatcher>,
+    space_name: &str,
+) -> Result<Space, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+    let notes = fs_repo_lock
+        .get_notes(space_name)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let space = detach::detach_space_use_case(&*repo, space_name)
+        .await
+        .map_err(|e| e.to_string())?;
+    if watcher.active_space().as_deref() == Some(space_name) {
+        watcher.unwatch();
+    }
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    for note in &notes {
+        search::application::delete::delete_document_use_case(
+            &search_repo_lock,
+            &mut index_writer_lock,
+            &note.get_unique_id(space_name),
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(space)
+}
+
+#[tauri::command]
+pub async fn rename_space_cmd(
+    state: State<'_, AppState>,
+    repo: State<'_, FileSystemSpaceRepository>,
+    watcher: State<'_, SpaceWatcher>,
+    space_name: &str,
+    new_space_name: &str,
+) -> Result<Space, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+    let notes = fs_repo_lock
+        .get_notes(space_name)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let watched = watcher.active_space().as_deref() == Some(space_name);
+    if watched {
+        watcher.unwatch();
+    }
+    let result = update::rename_space_use_case(&*repo, space_name, new_space_name).await;
+    let space = match result {
+        Ok(space) => space,
+        Err(e) => {
+            if watched {
+                if let Err(e) = watcher.watch(space_name) {
+                    error!("Failed to watch space '{}' again: {}", space_name, e);
+                }
+            }
+            return Err(e.to_string());
+        }
+    };
+    if watched {
+        if let Err(e) = watcher.watch(&space.name) {
+            error!("Failed to watch space '{}': {}", space.name, e);
+        }
+    }
+
+    // The documents of the index are identified by the space name.
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    for note in &notes {
+        search::application::delete::delete_document_use_case(
+            &search_repo_lock,
+            &mut index_writer_lock,
+            &note.get_unique_id(space_name),
+        )
+        .map_err(|e| e.to_string())?;
+    }
+    search_repo_lock.rename_private_index(space_name, &space.name);
+    search::application::index::reindex_space_use_case(
+        &*fs_repo_lock,
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &space.name,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(space)
+}
+
+#[tauri::command]
+pub async fn update_space_metadata_cmd(
+    repo: State<'_, FileSystemSpaceRepository>,
+    space_name: &str,
+    metadata: SpaceManifest,
+) -> Result<Space, String> {
+    update::update_space_metadata_use_case(&*repo, space_name, metadata)
+        .await
+        .map_err(|e| e.to_string())
+}
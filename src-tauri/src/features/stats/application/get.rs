@@ -0,0 +1,58 @@
+//! # Get **stats** module
+//! Use cases computing the statistics of the notes and spaces.
+use crate::features::{
+    notes::domain::repository::NoteRepository,
+    stats::domain::{
+        errors::StatsError,
+        stats::{NoteStats, SpaceStats, TextStats},
+    },
+};
+
+/// # Get Note Stats
+/// Counts the words, characters and blocks of a note.
+/// ## Fields
+/// * `repo` (&T): The repo that is implemented on the function
+/// * `space_name`: The space of the note.
+/// * `note_name`: The name of the note.
+/// * `folder_path`: The folder of the note.
+#[doc(alias = "note_stats")]
+pub async fn get_note_stats_use_case<T: NoteRepository>(
+    repo: &T,
+    space_name: &str,
+    note_name: &str,
+    folder_path: Option<&str>,
+) -> Result<NoteStats, StatsError> {
+    let note = repo
+        .get_note_content(space_name, note_name, folder_path)
+        .await?;
+    Ok(NoteStats::of_note(&note))
+}
+
+/// # Get Space Stats
+/// Adds up the statistics of every note of a space.
+/// ## Fields
+/// * `repo` (&T): The repo that is implemented on the function
+/// * `space_name`: The space to count.
+#[doc(alias = "space_stats")]
+pub async fn get_space_stats_use_case<T: NoteRepository>(
+    repo: &T,
+    space_name: &str,
+) -> Result<SpaceStats, StatsError> {
+    let mut space_stats = SpaceStats {
+        space: space_name.to_string(),
+        notes: 0,
+        stats: TextStats::default(),
+        last_modified: None,
+    };
+
+    for note in repo.get_notes(space_name).await? {
+        let note = repo
+            .get_note_content(space_name, &note.name, note.folder.as_deref())
+            .await?;
+        let note_stats = NoteStats::of_note(&note);
+        space_stats.notes += 1;
+        space_stats.stats.add(&note_stats.stats);
+        space_stats.last_modified = space_stats.last_modified.max(note_stats.modified);
+    }
+    Ok(space_stats)
+}
@@ -0,0 +1,74 @@
+//! # Writing **stats** module
+//! Use cases keeping the daily writing log of the spaces and the writing
+//! goals.
+use chrono::Local;
+
+use crate::features::stats::domain::{
+    errors::StatsError, repository::StatsRepository, stats::TextStats, writing::WritingProgress,
+};
+
+/// Days of the log returned when none are asked for.
+pub const DEFAULT_PROGRESS_DAYS: u64 = 30;
+
+/// # Record Save
+/// Logs the words added or removed by a save of a note for today.
+/// ## Fields
+/// * `repo` (&T): The repo that is implemented on the function
+/// * `space_name`: The space of the note.
+/// * `before`: The content of the note before the save.
+/// * `after`: The saved content.
+#[doc(alias = "record_words")]
+pub async fn record_save_use_case<T: StatsRepository>(
+    repo: &T,
+    space_name: &str,
+    before: &str,
+    after: &str,
+) -> Result<(), StatsError> {
+    let before = TextStats::of_content(before).words;
+    let after = TextStats::of_content(after).words;
+    if before == after {
+        return Ok(());
+    }
+
+    let mut log = repo.get_writing_log(space_name).await?;
+    log.record(Local::now().date_naive(), before, after);
+    repo.save_writing_log(space_name, &log).await
+}
+
+/// # Get Writing Progress
+/// The words written in a space over the last days and the streaks.
+/// ## Fields
+/// * `repo` (&T): The repo that is implemented on the function
+/// * `space_name`: The space of the log.
+/// * `days`: How many days to return, today included.
+#[doc(alias = "writing_progress")]
+pub async fn get_writing_progress_use_case<T: StatsRepository>(
+    repo: &T,
+    space_name: &str,
+    days: Option<u64>,
+) -> Result<WritingProgress, StatsError> {
+    let log = repo.get_writing_log(space_name).await?;
+    Ok(log.progress(
+        Local::now().date_naive(),
+        days.unwrap_or(DEFAULT_PROGRESS_DAYS),
+    ))
+}
+
+/// # Set Writing Goal
+/// Sets the words to write every day in a space, `None` or `0` removes the
+/// goal.
+/// ## Fields
+/// * `repo` (&T): The repo that is implemented on the function
+/// * `space_name`: The space of the goal.
+/// * `goal`: The daily words.
+#[doc(alias = "writing_goal")]
+pub async fn set_writing_goal_use_case<T: StatsRepository>(
+    repo: &T,
+    space_name: &str,
+    goal: Option<u64>,
+) -> Result<WritingProgress, StatsError> {
+    let mut log = repo.get_writing_log(space_name).await?;
+    log.goal = goal.filter(|g| *g > 0);
+    repo.save_writing_log(space_name, &log).await?;
+    Ok(log.progress(Local::now().date_naive(), DEFAULT_PROGRESS_DAYS))
+}
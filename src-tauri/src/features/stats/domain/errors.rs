@@ -0,0 +1,27 @@
+//! # Errors
+//! Defines all error that could ocurr while computing the statistics.
+use std::io;
+
+use thiserror::Error;
+
+use crate::{features::notes::domain::errors::NoteError, shared::errors::app_errors::AppError};
+
+/// # StatsError Enum
+/// Defines the enum with all the possible error that could ocurr while
+/// computing the statistics or keeping the writing log.
+/// ## Possible Errors
+/// * `InvalidLog`: The writing log of the space can't be read.
+#[derive(Debug, Error)]
+pub enum StatsError {
+    #[error("Invalid writing log: {0}")]
+    InvalidLog(String),
+
+    #[error("Stats IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    Note(#[from] NoteError),
+
+    #[error(transparent)]
+    AppError(#[from] AppError),
+}
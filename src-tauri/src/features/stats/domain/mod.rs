@@ -0,0 +1,4 @@
+pub mod errors;
+pub mod repository;
+pub mod stats;
+pub mod writing;
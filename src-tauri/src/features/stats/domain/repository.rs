@@ -0,0 +1,19 @@
+//! # Repository
+//! Defines where the writing log of a space is stored.
+use async_trait::async_trait;
+
+use super::errors::StatsError;
+use super::writing::WritingLog;
+
+/// # Stats Repository
+/// It implements the methods to keep the writing log of the spaces.
+#[async_trait]
+pub trait StatsRepository {
+    /// # [GET] Writing log
+    /// Reads the writing log of a space, an empty log when there's none.
+    async fn get_writing_log(&self, space_name: &str) -> Result<WritingLog, StatsError>;
+
+    /// # [UPDATE] Writing log
+    /// Replaces the writing log of a space.
+    async fn save_writing_log(&self, space_name: &str, log: &WritingLog) -> Result<(), StatsError>;
+}
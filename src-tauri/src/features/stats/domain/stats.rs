@@ -0,0 +1,145 @@
+//! # Stats Domain
+//! Defines the statistics of the notes and how they are computed from the
+//! Editor.js blocks.
+use std::collections::BTreeMap;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{features::notes::domain::note::Note, shared::utils::html::plain_text};
+
+/// Average reading speed used for the reading time.
+pub const WORDS_PER_MINUTE: u64 = 200;
+
+/// Keys of the block data that aren't text written by the user.
+const NON_TEXT_KEYS: [&str; 10] = [
+    "url",
+    "link",
+    "file",
+    "style",
+    "service",
+    "source",
+    "embed",
+    "meta",
+    "id",
+    "alignment",
+];
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+/// # TextStats
+/// The counts of a text.
+/// ## Fields
+/// * `words`: The number of words.
+/// * `characters`: The number of characters, spaces included.
+/// * `reading_minutes`: The reading time, rounded up.
+/// * `blocks`: The number of blocks of every type.
+pub struct TextStats {
+    pub words: u64,
+    pub characters: u64,
+    pub reading_minutes: u64,
+    pub blocks: BTreeMap<String, u64>,
+}
+
+impl TextStats {
+    /// # Of content
+    /// Counts the blocks of an Editor.js content. An invalid content has no
+    /// blocks.
+    pub fn of_content(content: &str) -> Self {
+        let mut stats = TextStats::default();
+        let Ok(document) = serde_json::from_str::<Value>(content) else {
+            return stats;
+        };
+        let blocks = document.get("blocks").and_then(Value::as_array);
+
+        for block in blocks.into_iter().flatten() {
+            let block_type = block
+                .get("type")
+                .and_then(Value::as_str)
+                .unwrap_or("unknown");
+            *stats.blocks.entry(block_type.to_string()).or_default() += 1;
+
+            let mut texts = Vec::new();
+            if let Some(data) = block.get("data") {
+                collect_text(data, &mut texts);
+            }
+            for text in texts {
+                let text = plain_text(&text);
+                stats.words += text.split_whitespace().count() as u64;
+                stats.characters += text.chars().count() as u64;
+            }
+        }
+        stats.reading_minutes = stats.words.div_ceil(WORDS_PER_MINUTE);
+        stats
+    }
+
+    /// Adds the counts of another text.
+    pub fn add(&mut self, other: &TextStats) {
+        self.words += other.words;
+        self.characters += other.characters;
+        for (block_type, count) in &other.blocks {
+            *self.blocks.entry(block_type.clone()).or_default() += count;
+        }
+        self.reading_minutes = self.words.div_ceil(WORDS_PER_MINUTE);
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # NoteStats
+/// The statistics of a note.
+/// ## Fields
+/// * `name`: The name of the note.
+/// * `folder`: The folder of the note.
+/// * `stats`: The counts of the note.
+/// * `created`: When the note was created, if the platform knows it.
+/// * `modified`: When the note was last saved.
+pub struct NoteStats {
+    pub name: String,
+    pub folder: Option<String>,
+    #[serde(flatten)]
+    pub stats: TextStats,
+    pub created: Option<DateTime<Utc>>,
+    pub modified: Option<DateTime<Utc>>,
+}
+
+impl NoteStats {
+    /// The statistics of a note read with its content.
+    pub fn of_note(note: &Note) -> Self {
+        Self {
+            name: note.name.clone(),
+            folder: note.folder.clone(),
+            stats: TextStats::of_content(note.content.as_deref().unwrap_or_default()),
+            created: note.created,
+            modified: note.modified,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # SpaceStats
+/// The statistics of every note of a space.
+/// ## Fields
+/// * `space`: The name of the space.
+/// * `notes`: The number of notes.
+/// * `stats`: The counts of all the notes together.
+/// * `last_modified`: When a note of the space was last saved.
+pub struct SpaceStats {
+    pub space: String,
+    pub notes: u64,
+    #[serde(flatten)]
+    pub stats: TextStats,
+    pub last_modified: Option<DateTime<Utc>>,
+}
+
+/// Gathers the strings of a block data, skipping the non text keys.
+fn collect_text(value: &Value, texts: &mut Vec<String>) {
+    match value {
+        Value::String(text) => texts.push(text.clone()),
+        Value::Array(values) => values.iter().for_each(|v| collect_text(v, texts)),
+        Value::Object(map) => map
+            .iter()
+            .filter(|(key, _)| !NON_TEXT_KEYS.contains(&key.as_str()))
+            .for_each(|(_, v)| collect_text(v, texts)),
+        _ => {}
+    }
+}
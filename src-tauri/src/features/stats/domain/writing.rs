@@ -0,0 +1,139 @@
+//! # Writing Domain
+//! Defines the daily log of the words written in a space, the writing goal
+//! and the streaks of days reaching it.
+use std::collections::BTreeMap;
+
+use chrono::{Days, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// Hidden file of a space holding its writing log.
+pub const STATS_FILE: &str = ".stats.json";
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+/// # DayLog
+/// The words written on a day.
+/// ## Fields
+/// * `added`: The words added by the saves of the day.
+/// * `removed`: The words removed by the saves of the day.
+pub struct DayLog {
+    #[serde(default)]
+    pub added: u64,
+    #[serde(default)]
+    pub removed: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+/// # WritingLog
+/// The writing log of a space, persisted in its `STATS_FILE`.
+/// ## Fields
+/// * `goal`: The words to add every day, any word counts when not set.
+/// * `days`: The log of every day something was written.
+pub struct WritingLog {
+    #[serde(default)]
+    pub goal: Option<u64>,
+    #[serde(default)]
+    pub days: BTreeMap<NaiveDate, DayLog>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # DayProgress
+/// A day of the writing log.
+pub struct DayProgress {
+    pub date: NaiveDate,
+    #[serde(flatten)]
+    pub log: DayLog,
+    pub goal_met: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # WritingProgress
+/// The progress towards the writing goal.
+/// ## Fields
+/// * `goal`: The daily goal, if any.
+/// * `today`: The words written today.
+/// * `days`: The last days, oldest first, today included.
+/// * `current_streak`: The consecutive days reaching the goal up to today,
+///   or up to yesterday while today's goal isn't reached yet.
+/// * `longest_streak`: The most consecutive days ever reaching the goal.
+pub struct WritingProgress {
+    pub goal: Option<u64>,
+    pub today: DayProgress,
+    pub days: Vec<DayProgress>,
+    pub current_streak: u64,
+    pub longest_streak: u64,
+}
+
+impl WritingLog {
+    /// # Record
+    /// Logs a save that took a note from `before` to `after` words.
+    pub fn record(&mut self, date: NaiveDate, before: u64, after: u64) {
+        if before == after {
+            return;
+        }
+        let day = self.days.entry(date).or_default();
+        if after > before {
+            day.added += after - before;
+        } else {
+            day.removed += before - after;
+        }
+    }
+
+    /// Whether the words of a day reach the goal.
+    pub fn goal_met(&self, day: &DayLog) -> bool {
+        match self.goal {
+            Some(goal) => day.added >= goal,
+            None => day.added > 0,
+        }
+    }
+
+    /// The progress of a day, an empty one when nothing was written.
+    pub fn day(&self, date: NaiveDate) -> DayProgress {
+        let log = self.days.get(&date).copied().unwrap_or_default();
+        DayProgress {
+            date,
+            goal_met: self.goal_met(&log),
+            log,
+        }
+    }
+
+    /// # Progress
+    /// The progress of the last `days` days up to `today`.
+    pub fn progress(&self, today: NaiveDate, days: u64) -> WritingProgress {
+        let days = (0..days.max(1))
+            .rev()
+            .filter_map(|back| today.checked_sub_days(Days::new(back)))
+            .map(|date| self.day(date))
+            .collect();
+
+        // Today's goal can still be reached, the streak isn't broken yet.
+        let mut date = match self.day(today).goal_met {
+            true => Some(today),
+            false => today.pred_opt(),
+        };
+        let mut current_streak = 0;
+        while let Some(day) = date.filter(|d| self.day(*d).goal_met) {
+            current_streak += 1;
+            date = day.pred_opt();
+        }
+
+        let mut longest_streak = 0;
+        let mut streak = 0;
+        let mut previous: Option<NaiveDate> = None;
+        for (date, _) in self.days.iter().filter(|(_, log)| self.goal_met(log)) {
+            streak = match previous.and_then(|p| p.succ_opt()) {
+                Some(next) if next == *date => streak + 1,
+                _ => 1,
+            };
+            longest_streak = longest_streak.max(streak);
+            previous = Some(*date);
+        }
+
+        WritingProgress {
+            goal: self.goal,
+            today: self.day(today),
+            days,
+            current_streak,
+            longest_streak,
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! # FileSystem Repository
+//! External implementation of the storage of the writing logs, kept in a
+//! hidden file of every space, sealed when the space is encrypted.
+use std::io::ErrorKind;
+
+use async_trait::async_trait;
+
+use crate::{
+    features::stats::domain::{
+        errors::StatsError,
+        repository::StatsRepository,
+        writing::{WritingLog, STATS_FILE},
+    },
+    shared::{
+        errors::app_errors::AppError, repositories::filesystem_repository::FileSystemRepository,
+    },
+};
+
+/// # FileSystemStatsRepository
+/// Implementation of the StatsRepository trait.
+#[derive(Clone)]
+pub struct FileSystemStatsRepository {
+    filesystem_repo: FileSystemRepository,
+}
+
+impl FileSystemStatsRepository {
+    pub fn new(filesystem_repo: FileSystemRepository) -> Self {
+        Self { filesystem_repo }
+    }
+}
+
+#[async_trait]
+impl StatsRepository for FileSystemStatsRepository {
+    async fn get_writing_log(&self, space_name: &str) -> Result<WritingLog, StatsError> {
+        let path = self
+            .filesystem_repo
+            .get_space_path(space_name)?
+            .join(STATS_FILE);
+
+        match self.filesystem_repo.read_file_to_string(&path).await {
+            Ok(content) => {
+                serde_json::from_str(&content).map_err(|e| StatsError::InvalidLog(e.to_string()))
+            }
+            Err(AppError::Io(e)) if e.kind() == ErrorKind::NotFound => Ok(WritingLog::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn save_writing_log(&self, space_name: &str, log: &WritingLog) -> Result<(), StatsError> {
+        let space_path = self.filesystem_repo.get_space_path(space_name)?;
+        if !space_path.is_dir() {
+            return Err(StatsError::Io(ErrorKind::NotFound.into()));
+        }
+        let content =
+            serde_json::to_string_pretty(log).map_err(|e| StatsError::InvalidLog(e.to_string()))?;
+        self.filesystem_repo
+            .write_file(&space_path.join(STATS_FILE), content.as_bytes())
+            .await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+//! # Tauri Commands [Stats]
+//! Implementation of the commands for the statistics and writing goals to
+//! expose them on tauri for having them in the frontend application.
+use tauri::State;
+
+use crate::features::{
+    notes::infrastructure::filesystem_repository::FileSystemNoteRepository,
+    stats::{
+        application::{get, writing},
+        domain::{
+            stats::{NoteStats, SpaceStats},
+            writing::WritingProgress,
+        },
+        infrastructure::filesystem_repository::FileSystemStatsRepository,
+    },
+};
+
+/// # [GET] Note Stats
+/// Gets the word, character and block counts of a note, its reading time
+/// and its dates.
+/// ## Params
+/// * `repo`: A State of the FileSystemNoteRepository
+/// * `space_name`: The name of the space of the note.
+/// * `note_name`: The name of the note.
+/// * `folder_path`: The folder path of the note.
+#[tauri::command]
+pub async fn get_note_stats_cmd(
+    repo: State<'_, FileSystemNoteRepository>,
+    space_name: &str,
+    note_name: &str,
+    folder_path: Option<&str>,
+) -> Result<NoteStats, String> {
+    get::get_note_stats_use_case(&*repo, space_name, note_name, folder_path)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// # [GET] Space Stats
+/// Gets the counts of all the notes of a space.
+/// ## Params
+/// * `repo`: A State of the FileSystemNoteRepository
+/// * `space_name`: The name of the space.
+#[tauri::command]
+pub async fn get_space_stats_cmd(
+    repo: State<'_, FileSystemNoteRepository>,
+    space_name: &str,
+) -> Result<SpaceStats, String> {
+    get::get_space_stats_use_case(&*repo, space_name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// # [GET] Writing Progress
+/// Gets the words written every day in a space, the goal and the streaks.
+/// ## Params
+/// * `stats_repo`: A State of the FileSystemStatsRepository
+/// * `space_name`: The name of the space.
+/// * `days`: How many days to return, 30 when not given.
+#[tauri::command]
+pub async fn get_writing_progress_cmd(
+    stats_repo: State<'_, FileSystemStatsRepository>,
+    space_name: &str,
+    days: Option<u64>,
+) -> Result<WritingProgress, String> {
+    writing::get_writing_progress_use_case(&*stats_repo, space_name, days)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// # [UPDATE] Writing Goal
+/// Sets the words to write every day in a space.
+/// ## Params
+/// * `stats_repo`: A State of the FileSystemStatsRepository
+/// * `space_name`: The name of the space.
+/// * `goal`: The daily words, no goal when not given.
+#[tauri::command]
+pub async fn set_writing_goal_cmd(
+    stats_repo: State<'_, FileSystemStatsRepository>,
+    space_name: &str,
+    goal: Option<u64>,
+) -> Result<WritingProgress, String> {
+    writing::set_writing_goal_use_case(&*stats_repo, space_name, goal)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,87 @@
+use crate::features::{
+    notes::{
+        application::create::create_named_note_use_case,
+        domain::{note::Note, repository::NoteRepository},
+    },
+    templates::domain::{
+        errors::TemplateError,
+        repository::TemplateRepository,
+        template::{Template, TemplateScope, TemplateVariables},
+    },
+};
+
+/// # Save Template Use Case
+/// Creates or overwrites a template with the given Editor.js content.
+/// ## Fields
+/// * `repo` (&T): The repository that is implemented on the function.
+/// * `space_name`: The space of the template, required for `Space` scope.
+/// * `template_name`: The name of the template.
+/// * `scope`: Where the template is stored.
+/// * `content`: The content of the template, placeholders included.
+#[doc(alias = "save_template")]
+pub async fn save_template_use_case<T: TemplateRepository>(
+    repo: &T,
+    space_name: Option<&str>,
+    template_name: &str,
+    scope: TemplateScope,
+    content: &str,
+) -> Result<Template, TemplateError> {
+    repo.save_template(space_name, template_name.trim(), scope, content)
+        .await
+}
+
+/// # Create Note From Template Use Case
+/// Creates a note pre-filled with the content of a template.
+///
+/// The note is named `note_name` or, when not given, after the template. If
+/// the name is taken a number is appended (`Meeting 2`). The placeholders are
+/// expanded once the final name is known so `{{title}}` matches it.
+/// ## Fields
+/// * `templates_repo` (&T): The templates repository.
+/// * `notes_repo` (&N): The notes repository.
+/// * `space_name`: The space where the note is created.
+/// * `template_name`: The name of the template.
+/// * `scope`: Where the template is stored.
+/// * `note_name`: The preferred name of the note.
+/// * `folder_path`: The folder where the note is created.
+/// ## Returns
+/// The created `Note` with its content, or a `TemplateError`.
+#[doc(alias = "create_note_from_template")]
+pub async fn create_note_from_template_use_case<T: TemplateRepository, N: NoteRepository>(
+    templates_repo: &T,
+    notes_repo: &N,
+    space_name: &str,
+    template_name: &str,
+    scope: TemplateScope,
+    note_name: Option<&str>,
+    folder_path: Option<&str>,
+) -> Result<Note, TemplateError> {
+    let template = templates_repo
+        .get_template(Some(space_name), template_name, scope)
+        .await?;
+
+    let base_name = note_name
+        .filter(|n| !n.trim().is_empty())
+        .unwrap_or(&template.name);
+    let note = create_named_note_use_case(notes_repo, space_name, base_name, folder_path).await?;
+
+    let content = TemplateVariables::new(&note.name, space_name)
+        .expand(template.content.as_deref().unwrap_or_default());
+
+    let written = notes_repo
+        .update_note_content(
+            space_name,
+            &note.name,
+            content.into_bytes(),
+            folder_path,
+            note.revision.as_deref().unwrap_or_default(),
+        )
+        .await;
+    // An empty note isn't left behind when the template can't be written.
+    if written.is_err() {
+        let _ = notes_repo
+            .delete_note(space_name, &note.name, folder_path)
+            .await;
+    }
+    Ok(written?)
+}
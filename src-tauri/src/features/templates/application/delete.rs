@@ -0,0 +1,20 @@
+use crate::features::templates::domain::{
+    errors::TemplateError, repository::TemplateRepository, template::TemplateScope,
+};
+
+/// # Delete Template Use Case
+/// Removes a template.
+/// ## Fields
+/// * `repo` (&T): The repository that is implemented on the function.
+/// * `space_name`: The space of the template, required for `Space` scope.
+/// * `template_name`: The name of the template.
+/// * `scope`: Where the template is stored.
+#[doc(alias = "delete_template")]
+pub async fn delete_template_use_case<T: TemplateRepository>(
+    repo: &T,
+    space_name: Option<&str>,
+    template_name: &str,
+    scope: TemplateScope,
+) -> Result<String, TemplateError> {
+    repo.delete_template(space_name, template_name, scope).await
+}
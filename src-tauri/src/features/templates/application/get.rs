@@ -0,0 +1,35 @@
+use crate::features::templates::domain::{
+    errors::TemplateError,
+    repository::TemplateRepository,
+    template::{Template, TemplateScope},
+};
+
+/// # Get Templates Use Case
+/// Lists the templates of a space and the global ones.
+/// ## Fields
+/// * `repo` (&T): The repository that is implemented on the function.
+/// * `space_name`: The space to list the templates from, if any.
+#[doc(alias = "get_templates")]
+pub async fn get_templates_use_case<T: TemplateRepository>(
+    repo: &T,
+    space_name: Option<&str>,
+) -> Result<Vec<Template>, TemplateError> {
+    repo.get_templates(space_name).await
+}
+
+/// # Get Template Use Case
+/// Reads a template with its raw content (placeholders are not expanded).
+/// ## Fields
+/// * `repo` (&T): The repository that is implemented on the function.
+/// * `space_name`: The space of the template, required for `Space` scope.
+/// * `template_name`: The name of the template.
+/// * `scope`: Where the template is stored.
+#[doc(alias = "get_template")]
+pub async fn get_template_use_case<T: TemplateRepository>(
+    repo: &T,
+    space_name: Option<&str>,
+    template_name: &str,
+    scope: TemplateScope,
+) -> Result<Template, TemplateError> {
+    repo.get_template(space_name, template_name, scope).await
+}
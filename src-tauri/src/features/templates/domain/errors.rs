@@ -0,0 +1,40 @@
+//! # Errors
+//! Defines all error that could ocurr inside the templates part of the application.
+use std::io;
+
+use thiserror::Error;
+
+use crate::{features::notes::domain::errors::NoteError, shared::errors::app_errors::AppError};
+
+/// # TemplateError Enum
+/// Defines the enum with all the possible error that could ocurr during a
+/// Template operation in the application.
+/// ## Possible Errors
+/// * `NotFound`: A template hasn't been found.
+/// * `EmptyName`: The name of the template was empty.
+/// * `InvalidName`: The name can't be used as a file name.
+/// * `MissingSpace`: A space template was requested without a space.
+/// * `Note`: The note created from the template failed.
+#[derive(Debug, Error)]
+pub enum TemplateError {
+    #[error("Template not found: {0}")]
+    NotFound(String),
+
+    #[error("Template name cannot be empty.")]
+    EmptyName,
+
+    #[error("Invalid template name: '{0}'.")]
+    InvalidName(String),
+
+    #[error("A space is required for space templates.")]
+    MissingSpace,
+
+    #[error("Templates IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    Note(#[from] NoteError),
+
+    #[error(transparent)]
+    AppError(#[from] AppError),
+}
@@ -0,0 +1,63 @@
+//! # Repository
+//! Defines how the templates are stored.
+use async_trait::async_trait;
+
+use super::errors::TemplateError;
+use super::template::{Template, TemplateScope};
+
+/// # Template Repository
+/// It implements the methods to interact with the templates of a space and
+/// the global ones.
+#[async_trait]
+pub trait TemplateRepository {
+    /// # [GET] Templates
+    /// Lists the global templates and, if a space is given, the templates of
+    /// that space.
+    /// ## Fields
+    /// * `space_name`: The space to list the templates from.
+    /// ## Result
+    /// A `Vec` of `Template` without content, or a `TemplateError`.
+    async fn get_templates(&self, space_name: Option<&str>)
+        -> Result<Vec<Template>, TemplateError>;
+
+    /// # [GET] Template content
+    /// Reads a template with its content.
+    /// ## Fields
+    /// * `space_name`: The space of the template, required for `Space` scope.
+    /// * `template_name`: The name of the template.
+    /// * `scope`: Where the template is stored.
+    async fn get_template(
+        &self,
+        space_name: Option<&str>,
+        template_name: &str,
+        scope: TemplateScope,
+    ) -> Result<Template, TemplateError>;
+
+    /// # [CREATE] Save template
+    /// Creates or overwrites a template.
+    /// ## Fields
+    /// * `space_name`: The space of the template, required for `Space` scope.
+    /// * `template_name`: The name of the template.
+    /// * `scope`: Where the template is stored.
+    /// * `content`: The Editor.js content of the template.
+    async fn save_template(
+        &self,
+        space_name: Option<&str>,
+        template_name: &str,
+        scope: TemplateScope,
+        content: &str,
+    ) -> Result<Template, TemplateError>;
+
+    /// # [DELETE] Template
+    /// Deletes a template.
+    /// ## Fields
+    /// * `space_name`: The space of the template, required for `Space` scope.
+    /// * `template_name`: The name of the template.
+    /// * `scope`: Where the template is stored.
+    async fn delete_template(
+        &self,
+        space_name: Option<&str>,
+        template_name: &str,
+        scope: TemplateScope,
+    ) -> Result<String, TemplateError>;
+}
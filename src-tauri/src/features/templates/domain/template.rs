@@ -0,0 +1,93 @@
+//! # Templates Domain
+//! Defines the templates and the variables that are expanded when a note is
+//! created from them.
+use std::{fmt::Write, sync::LazyLock};
+
+use chrono::{DateTime, Local};
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+
+/// Matches `{{name}}` and `{{name:format}}` placeholders.
+static PLACEHOLDER: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*(\w+)(?::([^}]*))?\s*\}\}").unwrap());
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+/// # TemplateScope
+/// Where a template is stored.
+/// * `Space`: Inside the reserved `.templates` folder of a space.
+/// * `Global`: Under `.merino/templates`, available for every space.
+pub enum TemplateScope {
+    Space,
+    Global,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # Template
+/// A note used as a model to create other notes.
+/// ## Fields
+/// * `name`: The name of the template.
+/// * `scope`: Where the template is stored.
+/// * `content`: The Editor.js content of the template, when it was read.
+pub struct Template {
+    pub name: String,
+    pub scope: TemplateScope,
+    pub content: Option<String>,
+}
+
+/// # TemplateVariables
+/// Values available to the placeholders of a template.
+/// * `{{date}}` / `{{date:%d/%m/%Y}}`: The creation date, optionally formatted.
+/// * `{{time}}` / `{{time:%H:%M:%S}}`: The creation time, optionally formatted.
+/// * `{{title}}`: The name of the created note.
+/// * `{{space}}`: The space where the note is created.
+pub struct TemplateVariables {
+    pub now: DateTime<Local>,
+    pub title: String,
+    pub space: String,
+}
+
+impl TemplateVariables {
+    pub fn new(title: &str, space: &str) -> Self {
+        Self {
+            now: Local::now(),
+            title: title.to_string(),
+            space: space.to_string(),
+        }
+    }
+
+    /// # Expand
+    /// Replaces the placeholders of a template content. As the content is
+    /// Editor.js JSON the values are escaped to be valid inside JSON strings.
+    /// Unknown placeholders are kept untouched.
+    pub fn expand(&self, content: &str) -> String {
+        PLACEHOLDER
+            .replace_all(content, |caps: &Captures| {
+                let format = caps.get(2).map(|m| m.as_str().trim());
+                let value = match &caps[1] {
+                    "date" => self.format_now(format.unwrap_or("%Y-%m-%d")),
+                    "time" => self.format_now(format.unwrap_or("%H:%M")),
+                    "title" => Some(self.title.clone()),
+                    "space" => Some(self.space.clone()),
+                    _ => None,
+                };
+                value
+                    .map(|v| escape_json(&v))
+                    .unwrap_or_else(|| caps[0].to_string())
+            })
+            .into_owned()
+    }
+
+    /// Formats the creation time, `None` if the format is invalid.
+    fn format_now(&self, format: &str) -> Option<String> {
+        let mut formatted = String::new();
+        write!(formatted, "{}", self.now.format(format)).ok()?;
+        Some(formatted)
+    }
+}
+
+/// Escapes a value to be placed inside a JSON string.
+fn escape_json(value: &str) -> String {
+    let quoted = serde_json::to_string(value).unwrap_or_default();
+    quoted[1..quoted.len() - 1].to_string()
+}
@@ -0,0 +1,189 @@
+//! # FileSystem Repository
+//! Stores the templates as Editor.js JSON files, inside the reserved
+//! `.templates` folder of a space or under `.merino/templates`.
+use std::{io::ErrorKind, path::PathBuf};
+
+use async_trait::async_trait;
+use tokio::fs::{self, read_dir};
+
+use crate::{
+    features::templates::domain::{
+        errors::TemplateError,
+        repository::TemplateRepository,
+        template::{Template, TemplateScope},
+    },
+    shared::{
+        errors::app_errors::AppError, repositories::filesystem_repository::FileSystemRepository,
+        utils::file_name::is_valid_name,
+    },
+};
+
+/// Reserved folder of a space holding its templates. Hidden folders are
+/// skipped when listing notes, so templates never show up as notes.
+pub const SPACE_TEMPLATES_DIR: &str = ".templates";
+
+/// # FileSystemTemplateRepository
+/// Implementation of the TemplateRepository trait.
+#[derive(Clone)]
+pub struct FileSystemTemplateRepository {
+    filesystem_repo: FileSystemRepository,
+}
+
+impl FileSystemTemplateRepository {
+    pub fn new(filesystem_repo: FileSystemRepository) -> Self {
+        Self { filesystem_repo }
+    }
+
+    fn get_templates_path(
+        &self,
+        space_name: Option<&str>,
+        scope: TemplateScope,
+    ) -> Result<PathBuf, TemplateError> {
+        match scope {
+            TemplateScope::Global => Ok(self.filesystem_repo.get_app_dir()?.join("templates")),
+            TemplateScope::Space => {
+                let space_name = space_name.ok_or(TemplateError::MissingSpace)?;
+                Ok(self
+                    .filesystem_repo
+                    .get_space_path(space_name)?
+                    .join(SPACE_TEMPLATES_DIR))
+            }
+        }
+    }
+
+    fn get_template_path(
+        &self,
+        space_name: Option<&str>,
+        template_name: &str,
+        scope: TemplateScope,
+    ) -> Result<PathBuf, TemplateError> {
+        if template_name.trim().is_empty() {
+            return Err(TemplateError::EmptyName);
+        }
+        if !is_valid_name(template_name) {
+            return Err(TemplateError::InvalidName(template_name.to_string()));
+        }
+        Ok(self
+            .get_templates_path(space_name, scope)?
+            .join(format!("{}.json", template_name)))
+    }
+
+    async fn list_templates(
+        &self,
+        space_name: Option<&str>,
+        scope: TemplateScope,
+    ) -> Result<Vec<Template>, TemplateError> {
+        let templates_path = self.get_templates_path(space_name, scope)?;
+        let mut templates = Vec::new();
+
+        if !templates_path.is_dir() {
+            return Ok(templates);
+        }
+
+        let mut entries = read_dir(&templates_path).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            if let Some(name) = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.strip_suffix(".json"))
+            {
+                templates.push(Template {
+                    name: name.to_string(),
+                    scope,
+                    content: None,
+                });
+            }
+        }
+        templates.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(templates)
+    }
+}
+
+#[async_trait]
+impl TemplateRepository for FileSystemTemplateRepository {
+    async fn get_templates(
+        &self,
+        space_name: Option<&str>,
+    ) -> Result<Vec<Template>, TemplateError> {
+        let mut templates = Vec::new();
+        if space_name.is_some() {
+            templates.extend(
+                self.list_templates(space_name, TemplateScope::Space)
+                    .await?,
+            );
+        }
+        templates.extend(self.list_templates(None, TemplateScope::Global).await?);
+        Ok(templates)
+    }
+
+    async fn get_template(
+        &self,
+        space_name: Option<&str>,
+        template_name: &str,
+        scope: TemplateScope,
+    ) -> Result<Template, TemplateError> {
+        let template_path = self.get_template_path(space_name, template_name, scope)?;
+
+        let content = match self
+            .filesystem_repo
+            .read_file_to_string(&template_path)
+            .await
+        {
+            Ok(content) => content,
+            Err(AppError::Io(e)) if e.kind() == ErrorKind::NotFound => {
+                return Err(TemplateError::NotFound(template_name.to_string()))
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Template {
+            name: template_name.to_string(),
+            scope,
+            content: Some(content),
+        })
+    }
+
+    async fn save_template(
+        &self,
+        space_name: Option<&str>,
+        template_name: &str,
+        scope: TemplateScope,
+        content: &str,
+    ) -> Result<Template, TemplateError> {
+        let template_path = self.get_template_path(space_name, template_name, scope)?;
+        self.filesystem_repo
+            .ensure_directory_exists(&self.get_templates_path(space_name, scope)?)
+            .await?;
+
+        self.filesystem_repo
+            .write_file(&template_path, content.as_bytes())
+            .await?;
+
+        Ok(Template {
+            name: template_name.to_string(),
+            scope,
+            content: Some(content.to_string()),
+        })
+    }
+
+    async fn delete_template(
+        &self,
+        space_name: Option<&str>,
+        template_name: &str,
+        scope: TemplateScope,
+    ) -> Result<String, TemplateError> {
+        let template_path = self.get_template_path(space_name, template_name, scope)?;
+
+        match fs::remove_file(&template_path).await {
+            Ok(_) => Ok(format!("Removed template '{}'.", template_name)),
+            Err(e) if e.kind() == ErrorKind::NotFound => {
+                Err(TemplateError::NotFound(template_name.to_string()))
+            }
+            Err(e) => Err(TemplateError::Io(e)),
+        }
+    }
+}
@@ -0,0 +1,136 @@
+//! # Tauri Commands [Templates]
+//! Implementation of the commands for the templates to expose them on tauri
+//! for having them in the frontend application.
+use tauri::State;
+
+use crate::{
+    features::{
+        notes::domain::note::Note,
+        search,
+        templates::{
+            application::{create, delete, get},
+            domain::template::{Template, TemplateScope},
+            infrastructure::filesystem_repository::FileSystemTemplateRepository,
+        },
+    },
+    shared::state::state::AppState,
+};
+
+/// # [GET] Templates
+/// Lists the templates of a space and the global ones.
+/// ## Params
+/// * `repo`: A State of the FileSystemTemplateRepository
+/// * `space_name`: The space to list the templates from.
+#[tauri::command]
+pub async fn get_templates_cmd(
+    repo: State<'_, FileSystemTemplateRepository>,
+    space_name: Option<&str>,
+) -> Result<Vec<Template>, String> {
+    get::get_templates_use_case(&*repo, space_name)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// # [GET] Template
+/// Reads a template with its content.
+/// ## Params
+/// * `space_name`: The space of the template, required for `space` scope.
+/// * `template_name`: The name of the template.
+/// * `scope`: `space` or `global`.
+#[tauri::command]
+pub async fn get_template_cmd(
+    repo: State<'_, FileSystemTemplateRepository>,
+    space_name: Option<&str>,
+    template_name: &str,
+    scope: TemplateScope,
+) -> Result<Template, String> {
+    get::get_template_use_case(&*repo, space_name, template_name, scope)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// # [CREATE] Template
+/// Creates or overwrites a template.
+/// ## Params
+/// * `space_name`: The space of the template, required for `space` scope.
+/// * `template_name`: The name of the template.
+/// * `scope`: `space` or `global`.
+/// * `content`: The Editor.js content of the template.
+#[tauri::command]
+pub async fn save_template_cmd(
+    repo: State<'_, FileSystemTemplateRepository>,
+    space_name: Option<&str>,
+    template_name: &str,
+    scope: TemplateScope,
+    content: String,
+) -> Result<Template, String> {
+    create::save_template_use_case(&*repo, space_name, template_name, scope, &content)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// # [DELETE] Template
+/// Deletes a template.
+/// ## Params
+/// * `space_name`: The space of the template, required for `space` scope.
+/// * `template_name`: The name of the template.
+/// * `scope`: `space` or `global`.
+#[tauri::command]
+pub async fn delete_template_cmd(
+    repo: State<'_, FileSystemTemplateRepository>,
+    space_name: Option<&str>,
+    template_name: &str,
+    scope: TemplateScope,
+) -> Result<String, String> {
+    delete::delete_template_use_case(&*repo, space_name, template_name, scope)
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// # [CREATE] Note from template
+/// Creates a note pre-filled with a template and indexes it.
+/// ## Params
+/// * `space_name`: The space where the note is created.
+/// * `template_name`: The name of the template.
+/// * `scope`: `space` or `global`.
+/// * `note_name`: The preferred name of the note, the template name if empty.
+/// * `folder_path`: The folder where the note is created.
+#[tauri::command]
+pub async fn create_note_from_template_cmd(
+    repo: State<'_, FileSystemTemplateRepository>,
+    state: State<'_, AppState>,
+    space_name: &str,
+    template_name: &str,
+    scope: TemplateScope,
+    note_name: Option<&str>,
+    folder_path: Option<&str>,
+) -> Result<Note, String> {
+    let fs_repo_lock = state.filesystem_repo.lock().await;
+
+    let new_note = create::create_note_from_template_use_case(
+        &*repo,
+        &*fs_repo_lock,
+        space_name,
+        template_name,
+        scope,
+        note_name,
+        folder_path,
+    )
+    .await
+    .map_err(|e| e.to_string())?;
+
+    let search_repo_lock = state.search_repo.lock().await;
+    let mut index_writer_lock = state.index_writer.lock().await;
+
+    search::application::index::index_document_use_case(
+        &search_repo_lock,
+        &mut index_writer_lock,
+        &new_note,
+        space_name,
+    )
+    .map_err(|e| e.to_string())?;
+
+    index_writer_lock.commit().map_err(|e| e.to_string())?;
+
+    Ok(new_note)
+}
@@ -19,13 +19,11 @@ impl FileSystemThemingRepository {
     }
 
     fn get_themes_path(&self) -> Result<PathBuf, String> {
-        let mut path = self
+        let path = self
             .filesystem_repo
-            .get_base_path()
+            .get_app_dir()
             .map_err(|e| e.to_string())?;
-
-        path.push("../.merino/themes");
-        Ok(path)
+        Ok(path.join("themes"))
     }
 
     fn get_theme_content_path(&self, theme_name: &str) -> Result<PathBuf, String> {
@@ -0,0 +1,81 @@
+//! # Get **tree** module
+//! Builds the nested tree of a space from the listings of its folders.
+use std::collections::HashMap;
+
+use crate::features::tree::domain::{
+    errors::TreeError,
+    repository::TreeRepository,
+    tree::{FolderListing, TreeFolder},
+};
+
+/// # Get Space Tree
+/// Lists a folder and its subfolders, down to `depth` levels.
+/// ## Fields
+/// * `repo` (&T): The repo that is implemented on the function
+/// * `space_name`: The space to list.
+/// * `folder_path`: The root of the tree, the space root when `None`.
+/// * `depth`: How many levels of subfolders to list, all of them when
+///   `None`. With `0` only the folder itself is listed.
+#[doc(alias = "get_tree")]
+pub async fn get_space_tree_use_case<T: TreeRepository>(
+    repo: &T,
+    space_name: &str,
+    folder_path: Option<&str>,
+    depth: Option<usize>,
+) -> Result<TreeFolder, TreeError> {
+    let folder_path = folder_path
+        .map(|p| p.trim_matches(['/', '\\']))
+        .filter(|p| !p.is_empty());
+    let root = repo.get_listing(space_name, folder_path).await?;
+
+    // Read the listings level by level, then nest them.
+    let mut listings: HashMap<String, FolderListing> = HashMap::new();
+    let mut level: Vec<String> = subfolder_paths(&root);
+    let mut remaining = depth;
+    while !level.is_empty() && remaining != Some(0) {
+        let mut next = Vec::new();
+        for path in level {
+            let listing = repo.get_listing(space_name, Some(&path)).await?;
+            next.extend(subfolder_paths(&listing));
+            listings.insert(path, listing);
+        }
+        level = next;
+        remaining = remaining.map(|d| d - 1);
+    }
+
+    Ok(assemble(root, &mut listings))
+}
+
+/// The paths of the subfolders of a listing.
+fn subfolder_paths(listing: &FolderListing) -> Vec<String> {
+    listing
+        .folders
+        .iter()
+        .filter_map(|f| f.path.clone())
+        .collect()
+}
+
+/// Nests the listed subfolders inside their parent.
+fn assemble(listing: FolderListing, listings: &mut HashMap<String, FolderListing>) -> TreeFolder {
+    let locked = listing.locked;
+    let folders = listing
+        .folders
+        .into_iter()
+        .map(|folder| {
+            let key = folder.path.clone().unwrap_or_default();
+            match listings.remove(&key) {
+                Some(sublisting) => assemble(sublisting, listings),
+                None => TreeFolder::unloaded(folder, locked),
+            }
+        })
+        .collect();
+
+    TreeFolder {
+        name: listing.folder.name().to_string(),
+        folder: listing.folder,
+        locked,
+        loaded: true,
+        folders,
+        notes: listing.notes,
+    }
+}
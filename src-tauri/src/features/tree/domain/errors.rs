@@ -0,0 +1,24 @@
+//! # Errors
+//! Defines all error that could ocurr while reading the tree of a space.
+use std::io;
+
+use thiserror::Error;
+
+use crate::shared::errors::app_errors::AppError;
+
+/// # TreeError Enum
+/// Defines the enum with all the possible error that could ocurr while
+/// listing the folders and notes of a space.
+/// ## Possible Errors
+/// * `NotFound`: The space or the folder doesn't exist.
+#[derive(Debug, Error)]
+pub enum TreeError {
+    #[error("Folder '{0}' not found.")]
+    NotFound(String),
+
+    #[error("Tree IO error: {0}")]
+    Io(#[from] io::Error),
+
+    #[error(transparent)]
+    AppError(#[from] AppError),
+}
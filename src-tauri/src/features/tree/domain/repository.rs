@@ -0,0 +1,22 @@
+//! # Repository
+//! Defines how the tree of a space is read.
+use async_trait::async_trait;
+
+use super::errors::TreeError;
+use super::tree::FolderListing;
+
+/// # Tree Repository
+/// It implements the methods to list the folders of a space one at a time.
+#[async_trait]
+pub trait TreeRepository {
+    /// # [GET] Listing
+    /// Lists the direct subfolders and notes of a folder.
+    /// ## Fields
+    /// * `space_name`: The space of the folder.
+    /// * `folder_path`: The folder, the space root when `None`.
+    async fn get_listing(
+        &self,
+        space_name: &str,
+        folder_path: Option<&str>,
+    ) -> Result<FolderListing, TreeError>;
+}
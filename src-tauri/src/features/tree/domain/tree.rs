@@ -0,0 +1,57 @@
+//! # Tree Domain
+//! Defines the nested tree of the folders and notes of a space.
+use serde::{Deserialize, Serialize};
+
+use crate::features::{folders::domain::folder::Folder, notes::domain::note::Note};
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # FolderListing
+/// The direct entries of a folder, already sorted by its ordering.
+/// ## Fields
+/// * `folder`: The folder itself.
+/// * `locked`: Whether the folder, or one of its parents, is locked.
+/// * `folders`: The subfolders, without their content.
+/// * `notes`: The notes, without their content.
+pub struct FolderListing {
+    pub folder: Folder,
+    pub locked: bool,
+    pub folders: Vec<Folder>,
+    pub notes: Vec<Note>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+/// # TreeFolder
+/// A folder of the tree with its subfolders and notes.
+/// ## Fields
+/// * `folder`: The folder, its path is empty for the space root.
+/// * `name`: The name of the folder, empty for the space root.
+/// * `locked`: Whether the folder, or one of its parents, is locked.
+/// * `loaded`: Whether `folders` and `notes` were listed. Folders deeper
+///   than the requested depth aren't, load them by their path.
+/// * `folders`: The subfolders, sorted.
+/// * `notes`: The notes, sorted.
+pub struct TreeFolder {
+    #[serde(flatten)]
+    pub folder: Folder,
+    pub name: String,
+    pub locked: bool,
+    pub loaded: bool,
+    pub folders: Vec<TreeFolder>,
+    pub notes: Vec<Note>,
+}
+
+impl TreeFolder {
+    /// # Unloaded
+    /// A subfolder whose entries haven't been listed, it inherits the lock of
+    /// its parent.
+    pub fn unloaded(folder: Folder, parent_locked: bool) -> Self {
+        Self {
+            name: folder.name().to_string(),
+            folder,
+            locked: parent_locked,
+            loaded: false,
+            folders: Vec::new(),
+            notes: Vec::new(),
+        }
+    }
+}
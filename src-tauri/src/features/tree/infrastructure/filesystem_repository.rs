@@ -0,0 +1,173 @@
+//! # FileSystem Repository
+//! External implementation of the listing of the folders of a space, served
+//! from memory until the space changes.
+use std::{
+    collections::HashMap,
+    path::Path,
+    sync::{Arc, Mutex},
+};
+
+use async_trait::async_trait;
+use tokio::fs::{self, read_dir};
+
+use crate::{
+    features::{
+        folders::{
+            domain::{folder::Folder, ordering::EntryKind},
+            infrastructure::manifest::{entry_times, find_locked_ancestor, read_manifest},
+        },
+        notes::domain::note::Note,
+        tree::domain::{errors::TreeError, repository::TreeRepository, tree::FolderListing},
+    },
+    shared::repositories::filesystem_repository::FileSystemRepository,
+};
+
+/// The listings read from a space, valid while its generation is the same.
+struct CachedSpace {
+    generation: u64,
+    listings: HashMap<String, FolderListing>,
+}
+
+/// # FileSystemTreeRepository
+/// Implementation of the TreeRepository trait. Listings are cached per
+/// space and dropped whenever a note or folder of the space changes.
+#[derive(Clone)]
+pub struct FileSystemTreeRepository {
+    filesystem_repo: FileSystemRepository,
+    cache: Arc<Mutex<HashMap<String, CachedSpace>>>,
+}
+
+impl FileSystemTreeRepository {
+    pub fn new(filesystem_repo: FileSystemRepository) -> Self {
+        Self {
+            filesystem_repo,
+            cache: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Reads the entries of a folder in one pass over the directory.
+    async fn read_listing(
+        &self,
+        space_path: &Path,
+        folder_path: Option<&str>,
+    ) -> Result<FolderListing, TreeError> {
+        let folder_dir = match folder_path {
+            Some(folder) => space_path.join(folder),
+            None => space_path.to_path_buf(),
+        };
+        let metadata = match fs::metadata(&folder_dir).await {
+            Ok(metadata) if metadata.is_dir() => metadata,
+            _ => {
+                return Err(TreeError::NotFound(
+                    folder_path.unwrap_or_default().to_string(),
+                ))
+            }
+        };
+
+        let mut folders: Vec<Folder> = Vec::new();
+        let mut notes: Vec<Note> = Vec::new();
+        let mut entries = read_dir(&folder_dir).await?;
+        while let Some(entry) = entries.next_entry().await? {
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if file_name.starts_with('.') {
+                continue;
+            }
+            let entry_metadata = entry.metadata().await?;
+            let (created, modified) = entry_times(&entry_metadata);
+
+            if entry_metadata.is_dir() {
+                let path = match folder_path {
+                    Some(folder) => Path::new(folder).join(&file_name),
+                    None => file_name.into(),
+                };
+                folders.push(Folder {
+                    path: Some(path.to_string_lossy().to_string()),
+                    created,
+                    modified,
+                    ..Default::default()
+                });
+            } else if let Some(note_name) = file_name.strip_suffix(".json") {
+                notes.push(Note {
+                    name: note_name.to_string(),
+                    folder: folder_path.map(|f| f.to_string()),
+                    created,
+                    modified,
+                    ..Default::default()
+                });
+            }
+        }
+
+        let ordering = read_manifest(&folder_dir).await?.ordering;
+        ordering.sort(&mut folders);
+        ordering.sort(&mut notes);
+
+        let (created, modified) = entry_times(&metadata);
+        let mut folder = Folder {
+            path: Some(folder_path.unwrap_or_default().to_string()),
+            created,
+            modified,
+            ..Default::default()
+        };
+        if let (Some(parent), Some(_)) = (folder_dir.parent(), folder_path) {
+            let parent_ordering = read_manifest(parent).await?.ordering;
+            let pinned = parent_ordering.pinned(EntryKind::Folder);
+            folder.pinned = pinned.iter().any(|n| n == folder.name());
+        }
+
+        Ok(FolderListing {
+            folder,
+            locked: find_locked_ancestor(space_path, &folder_dir)
+                .await?
+                .is_some(),
+            folders,
+            notes,
+        })
+    }
+}
+
+#[async_trait]
+impl TreeRepository for FileSystemTreeRepository {
+    async fn get_listing(
+        &self,
+        space_name: &str,
+        folder_path: Option<&str>,
+    ) -> Result<FolderListing, TreeError> {
+        let key = folder_path.unwrap_or_default().to_string();
+        // Read before listing: a change made meanwhile makes this listing
+        // outdated right away.
+        let generation = self.filesystem_repo.space_generation(space_name);
+        {
+            let cache = self.cache.lock().unwrap();
+            let cached = cache
+                .get(space_name)
+                .filter(|c| c.generation == generation)
+                .and_then(|c| c.listings.get(&key));
+            if let Some(listing) = cached {
+                return Ok(listing.clone());
+            }
+        }
+
+        let space_path = self.filesystem_repo.get_space_path(space_name)?;
+        if !space_path.is_dir() {
+            return Err(TreeError::NotFound(space_name.to_string()));
+        }
+        let listing = self.read_listing(&space_path, folder_path).await?;
+
+        let mut cache = self.cache.lock().unwrap();
+        let cached = cache
+            .entry(space_name.to_string())
+            .or_insert_with(|| CachedSpace {
+                generation,
+                listings: HashMap::new(),
+            });
+        if cached.generation != generation {
+            *cached = CachedSpace {
+                generation,
+                listings: HashMap::new(),
+            };
+        }
+        cached.listings.insert(key, listing.clone());
+
+        Ok(listing)
+    }
+}
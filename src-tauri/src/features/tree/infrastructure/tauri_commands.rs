@@ -0,0 +1,29 @@
+//! # Tauri Commands [Tree]
+//! Implementation of the commands for the tree of a space to expose them on
+//! tauri for having them in the frontend application.
+use tauri::State;
+
+use crate::features::tree::{
+    application::get, domain::tree::TreeFolder,
+    infrastructure::filesystem_repository::FileSystemTreeRepository,
+};
+
+/// # [GET] Space Tree
+/// Gets the nested folders and notes of a space, with their metadata.
+/// ## Params
+/// * `repo`: A State of the FileSystemTreeRepository
+/// * `space_name`: The name of the space.
+/// * `folder_path`: Loads only the subtree of this folder.
+/// * `depth`: How many levels of subfolders to load, all of them when not
+///   given. The folders below are returned with `loaded` set to `false`.
+#[tauri::command]
+pub async fn get_space_tree_cmd(
+    repo: State<'_, FileSystemTreeRepository>,
+    space_name: &str,
+    folder_path: Option<&str>,
+    depth: Option<usize>,
+) -> Result<TreeFolder, String> {
+    get::get_space_tree_use_case(&*repo, space_name, folder_path, depth)
+        .await
+        .map_err(|e| e.to_string())
+}
@@ -0,0 +1,68 @@
+use tantivy::IndexWriter;
+
+use crate::features::{
+    notes::domain::{errors::NoteError, repository::NoteRepository},
+    search::{
+        application::{delete::delete_folder_documents_use_case, index::reindex_space_use_case},
+        domain::{error::SearchError, search::Searchable},
+        infrastructure::search_repository::TantivySearchRepository,
+    },
+    watcher::domain::event::{SpaceChange, SpaceChangeKind},
+};
+
+/// # Sync Index
+/// Applies the external changes of a space to the search index and commits
+/// them. Changed notes are read again; folder changes reindex their space, as
+/// the notes moved with a folder aren't reported one by one.
+/// ## Params
+/// * `note_repo`: Repository to read the changed notes.
+/// * `repo`: The search repository.
+/// * `index_writer`: The index writer.
+/// * `changes`: The changes to apply.
+pub async fn sync_index_use_case<N: NoteRepository>(
+    note_repo: &N,
+    repo: &TantivySearchRepository,
+    index_writer: &mut IndexWriter,
+    changes: &[SpaceChange],
+) -> Result<(), SearchError> {
+    let mut spaces_to_reindex: Vec<&str> = Vec::new();
+
+    for change in changes {
+        let unique_id = change.note().get_unique_id(&change.space);
+        match change.kind {
+            SpaceChangeKind::NoteCreated | SpaceChangeKind::NoteChanged => {
+                repo.delete_document(index_writer, &unique_id)?;
+                match note_repo
+                    .get_note_content(&change.space, &change.name, change.folder.as_deref())
+                    .await
+                {
+                    Ok(note) => repo.index_document(index_writer, &note, &change.space)?,
+                    // Deleted again before the change was handled.
+                    Err(NoteError::NotFound(_)) => {}
+                    Err(e) => return Err(SearchError::Reindex(e.to_string())),
+                }
+            }
+            SpaceChangeKind::NoteDeleted => repo.delete_document(index_writer, &unique_id)?,
+            SpaceChangeKind::FolderCreated | SpaceChangeKind::FolderDeleted => {
+                if change.kind == SpaceChangeKind::FolderDeleted {
+                    delete_folder_documents_use_case(
+                        repo,
+                        index_writer,
+                        &change.space,
+                        &change.path(),
+                    )?;
+                }
+                if !spaces_to_reindex.contains(&change.space.as_str()) {
+                    spaces_to_reindex.push(&change.space);
+                }
+            }
+        }
+    }
+
+    for space_name in spaces_to_reindex {
+        reindex_space_use_case(note_repo, repo, index_writer, space_name).await?;
+    }
+
+    index_writer.commit()?;
+    Ok(())
+}
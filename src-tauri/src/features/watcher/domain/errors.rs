@@ -0,0 +1,23 @@
+//! # Errors
+//! Defines all error that could ocurr inside the watcher part of the application.
+use thiserror::Error;
+
+use crate::shared::errors::app_errors::AppError;
+
+/// # WatcherError Enum
+/// Defines the enum with all the possible error that could ocurr while
+/// watching a space.
+/// ## Possible Errors
+/// * `NotFound`: The space to watch doesn't exist.
+/// * `Watch`: The filesystem watcher couldn't be started.
+#[derive(Debug, Error)]
+pub enum WatcherError {
+    #[error("Space '{0}' not found.")]
+    NotFound(String),
+
+    #[error("Failed to watch the space: {0}")]
+    Watch(String),
+
+    #[error(transparent)]
+    AppError(#[from] AppError),
+}
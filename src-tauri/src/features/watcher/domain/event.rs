@@ -0,0 +1,213 @@
+//! # Watcher Domain
+//! Defines the changes made to a space outside of the application and the
+//! events sent to the frontend for them.
+use std::path::{Component, Path};
+
+use serde::{Deserialize, Serialize};
+
+use crate::features::notes::domain::note::Note;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "kebab-case")]
+/// # SpaceChangeKind
+/// What happened to a note or folder of the watched space.
+pub enum SpaceChangeKind {
+    NoteCreated,
+    NoteChanged,
+    NoteDeleted,
+    FolderCreated,
+    FolderDeleted,
+}
+
+impl SpaceChangeKind {
+    /// Name of the event emitted to the frontend.
+    pub fn event_name(self) -> &'static str {
+        match self {
+            Self::NoteCreated => "note-created",
+            Self::NoteChanged => "note-changed",
+            Self::NoteDeleted => "note-deleted",
+            Self::FolderCreated => "folder-created",
+            Self::FolderDeleted => "folder-deleted",
+        }
+    }
+
+    pub fn is_folder(self) -> bool {
+        matches!(self, Self::FolderCreated | Self::FolderDeleted)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+/// # SpaceChange
+/// A change of the watched space, the payload of the emitted events.
+/// ## Fields
+/// * `kind`: What happened.
+/// * `space`: The space of the note or folder.
+/// * `name`: The name of the note or folder.
+/// * `folder`: The folder containing it, relative to the space.
+pub struct SpaceChange {
+    pub kind: SpaceChangeKind,
+    pub space: String,
+    pub name: String,
+    pub folder: Option<String>,
+}
+
+impl SpaceChange {
+    /// # From relative path
+    /// Builds the change of a path relative to the space root. Hidden entries
+    /// (and everything inside hidden folders) and files that aren't notes are
+    /// ignored.
+    pub fn from_relative_path(
+        kind: SpaceChangeKind,
+        space_name: &str,
+        relative_path: &Path,
+    ) -> Option<Self> {
+        let mut parts = Vec::new();
+        for component in relative_path.components() {
+            match component {
+                Component::Normal(part) => {
+                    let part = part.to_str()?;
+                    if part.starts_with('.') {
+                        return None;
+                    }
+                    parts.push(part);
+                }
+                _ => return None,
+            }
+        }
+        let file_name = parts.pop()?;
+
+        let name = if kind.is_folder() {
+            file_name
+        } else {
+            file_name.strip_suffix(".json")?
+        };
+
+        Some(Self {
+            kind,
+            space: space_name.to_string(),
+            name: name.to_string(),
+            folder: Some(parts.join("/")).filter(|f| !f.is_empty()),
+        })
+    }
+
+    /// Path of the note or folder relative to the space.
+    pub fn path(&self) -> String {
+        match &self.folder {
+            Some(folder) => format!("{}/{}", folder, self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// The changed note, without content.
+    pub fn note(&self) -> Note {
+        Note {
+            name: self.name.clone(),
+            folder: self.folder.clone(),
+            ..Default::default()
+        }
+    }
+}
+
+/// # Coalesce
+/// Removes the repeated changes of a batch, keeping the first one, and the
+/// changes of notes created in the same batch.
+pub fn coalesce(changes: Vec<SpaceChange>) -> Vec<SpaceChange> {
+    let mut result: Vec<SpaceChange> = Vec::new();
+    for change in changes {
+        let duplicated = result.iter().any(|other| {
+            other.space == change.space
+                && other.name == change.name
+                && other.folder == change.folder
+                && (other.kind == change.kind
+                    || (other.kind == SpaceChangeKind::NoteCreated
+                        && change.kind == SpaceChangeKind::NoteChanged))
+        });
+        if !duplicated {
+            result.push(change);
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(kind: SpaceChangeKind, name: &str) -> SpaceChange {
+        SpaceChange {
+            kind,
+            space: "Work".to_string(),
+            name: name.to_string(),
+            folder: None,
+        }
+    }
+
+    #[test]
+    fn paths_are_read_as_notes_and_folders() {
+        let note = SpaceChange::from_relative_path(
+            SpaceChangeKind::NoteChanged,
+            "Work",
+            Path::new("projects/2024/plan.json"),
+        )
+        .unwrap();
+        assert_eq!(note.name, "plan");
+        assert_eq!(note.folder.as_deref(), Some("projects/2024"));
+        assert_eq!(note.path(), "projects/2024/plan");
+
+        let folder = SpaceChange::from_relative_path(
+            SpaceChangeKind::FolderCreated,
+            "Work",
+            Path::new("projects"),
+        )
+        .unwrap();
+        assert_eq!(folder.name, "projects");
+        assert_eq!(folder.folder, None);
+    }
+
+    #[test]
+    fn hidden_and_foreign_paths_are_ignored() {
+        for path in [
+            ".attachments/image.png",
+            "projects/.name.json.12-3.tmp",
+            ".templates/daily.json",
+            "projects/readme.md",
+            "../outside.json",
+            "",
+        ] {
+            assert_eq!(
+                SpaceChange::from_relative_path(
+                    SpaceChangeKind::NoteChanged,
+                    "Work",
+                    Path::new(path)
+                ),
+                None,
+                "{}",
+                path
+            );
+        }
+    }
+
+    #[test]
+    fn repeated_changes_are_coalesced() {
+        let changes = coalesce(vec![
+            change(SpaceChangeKind::NoteCreated, "a"),
+            change(SpaceChangeKind::NoteChanged, "a"),
+            change(SpaceChangeKind::NoteChanged, "b"),
+            change(SpaceChangeKind::NoteChanged, "b"),
+            change(SpaceChangeKind::NoteDeleted, "b"),
+            change(SpaceChangeKind::NoteChanged, "c"),
+            change(SpaceChangeKind::NoteCreated, "c"),
+        ]);
+
+        assert_eq!(
+            changes,
+            vec![
+                change(SpaceChangeKind::NoteCreated, "a"),
+                change(SpaceChangeKind::NoteChanged, "b"),
+                change(SpaceChangeKind::NoteDeleted, "b"),
+                change(SpaceChangeKind::NoteChanged, "c"),
+                change(SpaceChangeKind::NoteCreated, "c"),
+            ]
+        );
+    }
+}
@@ -0,0 +1,2 @@
+pub mod space_watcher;
+pub mod tauri_commands;
@@ -0,0 +1,211 @@
+//! # Space Watcher
+//! Watches the active space on disk so the changes made by other tools reach
+//! the frontend and the search index.
+use std::{
+    path::Path,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use log::{error, info};
+use notify_debouncer_full::{
+    new_debouncer,
+    notify::{
+        event::{ModifyKind, RemoveKind, RenameMode},
+        EventKind, RecommendedWatcher, RecursiveMode,
+    },
+    DebounceEventResult, DebouncedEvent, Debouncer, RecommendedCache,
+};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::{
+    features::watcher::{
+        application::sync::sync_index_use_case,
+        domain::{
+            errors::WatcherError,
+            event::{coalesce, SpaceChange, SpaceChangeKind},
+        },
+    },
+    shared::{repositories::filesystem_repository::FileSystemRepository, state::state::AppState},
+};
+
+/// Time the events of a path are collected before being handled.
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// The watcher of the active space, dropping it stops the watch.
+struct ActiveWatch {
+    space_name: String,
+    _debouncer: Debouncer<RecommendedWatcher, RecommendedCache>,
+}
+
+/// # SpaceWatcher
+/// Service watching one space at a time: the one open in the frontend.
+#[derive(Clone)]
+pub struct SpaceWatcher {
+    app_handle: AppHandle,
+    filesystem_repo: FileSystemRepository,
+    active: Arc<Mutex<Option<ActiveWatch>>>,
+}
+
+impl SpaceWatcher {
+    pub fn new(app_handle: AppHandle, filesystem_repo: FileSystemRepository) -> Self {
+        Self {
+            app_handle,
+            filesystem_repo,
+            active: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// The name of the watched space, if any.
+    pub fn active_space(&self) -> Option<String> {
+        self.active
+            .lock()
+            .unwrap()
+            .as_ref()
+            .map(|watch| watch.space_name.clone())
+    }
+
+    /// # Watch
+    /// Starts watching a space, replacing the previous one.
+    pub fn watch(&self, space_name: &str) -> Result<(), WatcherError> {
+        if self.active_space().as_deref() == Some(space_name) {
+            return Ok(());
+        }
+
+        let space_path = self.filesystem_repo.get_space_path(space_name)?;
+        if !space_path.is_dir() {
+            return Err(WatcherError::NotFound(space_name.to_string()));
+        }
+
+        let handler = {
+            let app_handle = self.app_handle.clone();
+            let filesystem_repo = self.filesystem_repo.clone();
+            let space_name = space_name.to_string();
+            let space_path = space_path.clone();
+            move |result: DebounceEventResult| match result {
+                Ok(events) => {
+                    // The app already handled the changes it made itself.
+                    let changes: Vec<SpaceChange> = events
+                        .iter()
+                        .filter(|event| {
+                            !event.paths.iter().all(|p| filesystem_repo.is_own_change(p))
+                        })
+                        .flat_map(|event| classify(&space_path, &space_name, event))
+                        .collect();
+                    if !changes.is_empty() {
+                        filesystem_repo.mark_space_changed(&space_name);
+                    }
+                    dispatch(&app_handle, coalesce(changes));
+                }
+                Err(errors) => {
+                    for e in errors {
+                        error!("Failed to watch space '{}': {}", space_name, e);
+                    }
+                }
+            }
+        };
+
+        let mut debouncer = new_debouncer(DEBOUNCE_TIMEOUT, None, handler)
+            .map_err(|e| WatcherError::Watch(e.to_string()))?;
+        debouncer
+            .watch(&space_path, RecursiveMode::Recursive)
+            .map_err(|e| WatcherError::Watch(e.to_string()))?;
+
+        *self.active.lock().unwrap() = Some(ActiveWatch {
+            space_name: space_name.to_string(),
+            _debouncer: debouncer,
+        });
+        info!("Watching space '{}'.", space_name);
+        Ok(())
+    }
+
+    /// # Unwatch
+    /// Stops watching the active space.
+    pub fn unwatch(&self) {
+        if let Some(watch) = self.active.lock().unwrap().take() {
+            info!("Stopped watching space '{}'.", watch.space_name);
+        }
+    }
+}
+
+/// Turns a filesystem event into the changes of the space.
+fn classify(space_path: &Path, space_name: &str, event: &DebouncedEvent) -> Vec<SpaceChange> {
+    let change = |kind: SpaceChangeKind, path: &Path| {
+        path.strip_prefix(space_path)
+            .ok()
+            .and_then(|relative| SpaceChange::from_relative_path(kind, space_name, relative))
+    };
+    let created = |path: &Path| {
+        if path.is_dir() {
+            change(SpaceChangeKind::FolderCreated, path)
+        } else {
+            change(SpaceChangeKind::NoteCreated, path)
+        }
+    };
+    // A removed path can't be inspected anymore, folders have no extension.
+    let removed = |path: &Path, is_folder: bool| {
+        if is_folder || path.extension().is_none() {
+            change(SpaceChangeKind::FolderDeleted, path)
+        } else {
+            change(SpaceChangeKind::NoteDeleted, path)
+        }
+    };
+
+    let paths = &event.paths;
+    match event.kind {
+        EventKind::Create(_) => paths.iter().filter_map(|p| created(p)).collect(),
+        EventKind::Modify(ModifyKind::Name(RenameMode::Both)) if paths.len() == 2 => {
+            [removed(&paths[0], paths[1].is_dir()), created(&paths[1])]
+                .into_iter()
+                .flatten()
+                .collect()
+        }
+        EventKind::Modify(ModifyKind::Name(_)) => paths
+            .iter()
+            .filter_map(|p| {
+                if p.exists() {
+                    created(p)
+                } else {
+                    removed(p, false)
+                }
+            })
+            .collect(),
+        EventKind::Modify(ModifyKind::Metadata(_)) => Vec::new(),
+        EventKind::Modify(_) => paths
+            .iter()
+            .filter(|p| p.is_file())
+            .filter_map(|p| change(SpaceChangeKind::NoteChanged, p))
+            .collect(),
+        EventKind::Remove(kind) => paths
+            .iter()
+            .filter_map(|p| removed(p, kind == RemoveKind::Folder))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Emits the changes to the frontend and applies them to the search index.
+fn dispatch(app_handle: &AppHandle, changes: Vec<SpaceChange>) {
+    if changes.is_empty() {
+        return;
+    }
+    for change in &changes {
+        if let Err(e) = app_handle.emit(change.kind.event_name(), change) {
+            error!("Failed to emit '{}': {}", change.kind.event_name(), e);
+        }
+    }
+
+    let app_handle = app_handle.clone();
+    tauri::async_runtime::spawn(async move {
+        let state = app_handle.state::<AppState>();
+        let notes_repo = state.filesystem_repo.lock().await;
+        let search_repo = state.search_repo.lock().await;
+        let mut index_writer = state.index_writer.lock().await;
+
+        if let Err(e) =
+            sync_index_use_case(&*notes_repo, &search_repo, &mut index_writer, &changes).await
+        {
+            error!("Failed to index the external changes: {}", e);
+        }
+    });
+}
@@ -0,0 +1,29 @@
+//! # Tauri Commands [Watcher]
+//! Implementation of the commands for watching the spaces to expose them on
+//! tauri for having them in the frontend application.
+use tauri::State;
+
+use crate::features::watcher::infrastructure::space_watcher::SpaceWatcher;
+
+/// # [UPDATE] Watch Space
+/// Watches the space open in the frontend for external changes, emitting
+/// `note-created`, `note-changed`, `note-deleted`, `folder-created` and
+/// `folder-deleted` events. Replaces the previously watched space.
+/// ## Params
+/// * `watcher`: A State of the SpaceWatcher.
+/// * `space_name`: The space to watch.
+#[tauri::command]
+pub async fn watch_space_cmd(
+    watcher: State<'_, SpaceWatcher>,
+    space_name: &str,
+) -> Result<(), String> {
+    watcher.watch(space_name).map_err(|e| e.to_string())
+}
+
+/// # [UPDATE] Unwatch Space
+/// Stops watching the space for external changes.
+#[tauri::command]
+pub async fn unwatch_space_cmd(watcher: State<'_, SpaceWatcher>) -> Result<(), String> {
+    watcher.unwatch();
+    Ok(())
+}
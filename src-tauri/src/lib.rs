@@ -2,25 +2,79 @@ use crate::features::ai::infrastructure::genai_repository::GenAIRepository;
 use crate::features::ai::infrastructure::tauri_commands::{
     create_ollama_model_cmd, delete_ollama_model_cmd,
 };
+use crate::features::archive::infrastructure::filesystem_repository::FileSystemArchiveRepository;
+use crate::features::archive::infrastructure::tauri_commands::{
+    export_space_cmd, import_space_cmd,
+};
+use crate::features::attachments::infrastructure::filesystem_repository::FileSystemAttachmentRepository;
+use crate::features::attachments::infrastructure::tauri_commands::{
+    collect_attachments_cmd, get_attachment_content_cmd, get_attachment_path_cmd,
+    get_attachments_usage_cmd, get_image_thumbnails_cmd, rebuild_thumbnails_cmd,
+    upload_attachment_cmd, upload_image_cmd,
+};
+use crate::features::batch::infrastructure::export_repository::FileSystemExportRepository;
+use crate::features::batch::infrastructure::tauri_commands::apply_batch_cmd;
+use crate::features::duplicate::infrastructure::tauri_commands::{
+    duplicate_folder_cmd, duplicate_note_cmd,
+};
+use crate::features::encryption::domain::encryption::SPACE_LOCKED_EVENT;
+use crate::features::encryption::infrastructure::filesystem_repository::FileSystemEncryptionRepository;
+use crate::features::encryption::infrastructure::tauri_commands::{
+    disable_space_encryption_cmd, enable_space_encryption_cmd, get_space_encryption_cmd,
+    lock_space_cmd, unlock_space_cmd,
+};
 use crate::features::folders::infrastructure::filesystem_repository::FileSystemFolderRepository;
+use crate::features::health::infrastructure::filesystem_repository::FileSystemHealthRepository;
+use crate::features::health::infrastructure::tauri_commands::check_space_cmd;
+use crate::features::import::infrastructure::source_repository::FileSystemSourceRepository;
+use crate::features::import::infrastructure::tauri_commands::{
+    import_evernote_export_cmd, import_notion_export_cmd, import_obsidian_vault_cmd,
+};
+use crate::features::inbox::infrastructure::tauri_commands::{
+    quick_capture_cmd, triage_quick_note_cmd,
+};
+use crate::features::journal::infrastructure::tauri_commands::{
+    get_adjacent_journal_note_cmd, get_or_create_daily_note_cmd, get_or_create_journal_note_cmd,
+};
+use crate::features::notes::application::integrity::check_notes_integrity_use_case;
+use crate::features::search::application::index::reindex_all_use_case;
 use crate::features::search::infrastructure::search_repository::TantivySearchRepository;
 use crate::features::settings::infrastructure::settings_repository::FileSystemSettingsRepository;
 use crate::features::settings::infrastructure::tauri_commands::{
     create_settings_cmd, get_settings_cmd, update_settings_cmd,
 };
+use crate::features::site::infrastructure::filesystem_repository::FileSystemSiteRepository;
+use crate::features::site::infrastructure::tauri_commands::export_space_html_cmd;
 use crate::features::space::infrastructure::filesystem_repo::FileSystemSpaceRepository;
+use crate::features::stats::infrastructure::filesystem_repository::FileSystemStatsRepository;
+use crate::features::stats::infrastructure::tauri_commands::{
+    get_note_stats_cmd, get_space_stats_cmd, get_writing_progress_cmd, set_writing_goal_cmd,
+};
+use crate::features::templates::infrastructure::filesystem_repository::FileSystemTemplateRepository;
+use crate::features::templates::infrastructure::tauri_commands::{
+    create_note_from_template_cmd, delete_template_cmd, get_template_cmd, get_templates_cmd,
+    save_template_cmd,
+};
 use crate::features::theming::infrastructre::tauri_commands::{
     create_themes_path_cmd, get_theme_content_cmd, get_themes_cmd,
 };
 use crate::features::theming::infrastructre::theming_repository::FileSystemThemingRepository;
+use crate::features::tree::infrastructure::filesystem_repository::FileSystemTreeRepository;
+use crate::features::tree::infrastructure::tauri_commands::get_space_tree_cmd;
+use crate::features::watcher::infrastructure::space_watcher::SpaceWatcher;
+use crate::features::watcher::infrastructure::tauri_commands::{
+    unwatch_space_cmd, watch_space_cmd,
+};
 use crate::shared::repositories::filesystem_repository::FileSystemRepository;
 use crate::shared::state::state::AppState;
 use crate::{
     features::notes::infrastructure::filesystem_repository::FileSystemNoteRepository,
     shared::logger::logger::MerinoLogger,
 };
-use log::LevelFilter;
-use tauri::Manager;
+use log::{error, info, LevelFilter};
+use std::time::{Duration, Instant};
+use tauri::{Emitter, Manager};
+use tauri_plugin_store::StoreExt;
 
 // Declare modules
 pub mod features;
@@ -32,19 +86,37 @@ use features::ai::infrastructure::tauri_commands::{
     get_web_models_cmd,
 };
 use features::folders::infrastructure::tauri_commands::{
-    create_folder_cmd, delete_folder_cmd, get_folders_in_space_cmd, update_folder_name_cmd,
-    update_folder_route_cmd,
+    create_folder_cmd, delete_folder_cmd, get_folder_ordering_cmd, get_folders_in_space_cmd,
+    lock_folder_cmd, pin_folder_cmd, pin_note_cmd, reorder_folders_cmd, reorder_notes_cmd,
+    set_folder_sort_cmd, unlock_folder_cmd, unpin_folder_cmd, unpin_note_cmd,
+    update_folder_metadata_cmd, update_folder_name_cmd, update_folder_route_cmd,
 };
 use features::notes::infrastructure::tauri_commands::{
-    create_note_in_space_cmd, delete_note_cmd, get_note_content_cmd, get_notes_in_space_cmd,
-    search_notes_cmd, update_note_content_cmd, update_note_name_cmd, update_note_route_cmd,
+    check_notes_integrity_cmd, create_note_in_space_cmd, delete_note_cmd, get_note_content_cmd,
+    get_notes_in_space_cmd, get_space_tags_cmd, lock_note_cmd, merge_note_content_cmd,
+    search_notes_cmd, set_note_properties_cmd, unlock_note_cmd, update_note_content_cmd,
+    update_note_name_cmd, update_note_route_cmd,
 };
 use features::space::infrastructure::tauri_commands::{
-    create_space_cmd, delete_space_cmd, get_spaces_cmd,
+    create_space_cmd, delete_space_cmd, detach_space_cmd, get_spaces_cmd, open_space_cmd,
+    relocate_space_cmd, rename_space_cmd, update_space_metadata_cmd,
 };
 /// Static setup for the logger
 static LOGGER: MerinoLogger = MerinoLogger;
 
+/// How often the unlocked spaces are checked for their auto-lock.
+const AUTO_LOCK_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Store where the frontend persists the space it has open, and its key.
+const PERSISTENT_STATE_STORE: &str = "merino-persistent-state.json";
+const ACTIVE_SPACE_KEY: &str = "activeNameSpace";
+
+/// The space open in the frontend when the app was last closed.
+fn last_active_space(app: &tauri::App) -> Option<String> {
+    let store = app.store(PERSISTENT_STATE_STORE).ok()?;
+    store.get(ACTIVE_SPACE_KEY)?.as_str().map(str::to_string)
+}
+
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
     // Logger setup
@@ -77,9 +149,39 @@ pub fn run() {
 
             let theming_repo = FileSystemThemingRepository::new(filesystem_repo.clone());
 
+            let templates_repo = FileSystemTemplateRepository::new(filesystem_repo.clone());
+
+            let attachments_repo = FileSystemAttachmentRepository::new(filesystem_repo.clone());
+
+            let export_repo = FileSystemExportRepository::new();
+
+            let tree_repo = FileSystemTreeRepository::new(filesystem_repo.clone());
+
+            let stats_repo = FileSystemStatsRepository::new(filesystem_repo.clone());
+
+            let archive_repo = FileSystemArchiveRepository::new(filesystem_repo.clone());
+
+            let source_repo = FileSystemSourceRepository::new();
+
+            let site_repo = FileSystemSiteRepository::new();
+
+            let encryption_repo = FileSystemEncryptionRepository::new(filesystem_repo.clone());
+
+            let health_repo = FileSystemHealthRepository::new(filesystem_repo.clone());
+
+            // Watches the space open in the frontend for external changes,
+            // starting with the one left open when the app was closed.
+            let space_watcher = SpaceWatcher::new(app_handle.clone(), filesystem_repo.clone());
+            if let Some(space_name) = last_active_space(app) {
+                if let Err(e) = space_watcher.watch(&space_name) {
+                    error!("Failed to watch space '{}': {}", space_name, e);
+                }
+            }
+
             let index_writer = search_repo
                 .get_index_writer()
                 .expect("Failed to get IndexWriter");
+            let needs_reindex = search_repo.was_rebuilt();
 
             let app_state = AppState::new(
                 notes_repo.clone(),
@@ -99,6 +201,87 @@ pub fn run() {
             app.manage(search_repo);
             app.manage(settings_repo);
             app.manage(theming_repo);
+            app.manage(templates_repo);
+            app.manage(attachments_repo);
+            app.manage(export_repo);
+            app.manage(tree_repo);
+            app.manage(stats_repo);
+            app.manage(archive_repo);
+            app.manage(source_repo);
+            app.manage(site_repo);
+            app.manage(encryption_repo);
+            app.manage(health_repo);
+            app.manage(space_watcher);
+
+            // Locks the encrypted spaces left unused longer than their
+            // auto-lock timeout.
+            let keyring = filesystem_repo.keyring().clone();
+            let auto_lock_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let mut interval = tokio::time::interval(AUTO_LOCK_CHECK_INTERVAL);
+                loop {
+                    interval.tick().await;
+                    let expired = keyring.lock_expired(Instant::now());
+                    if expired.is_empty() {
+                        continue;
+                    }
+                    let state = auto_lock_handle.state::<AppState>();
+                    let search_repo = state.search_repo.lock().await;
+                    for space_name in expired {
+                        search_repo.close_private_index(&space_name);
+                        info!("Space '{}' locked after its auto-lock timeout.", space_name);
+                        let _ = auto_lock_handle.emit(SPACE_LOCKED_EVENT, space_name);
+                    }
+                }
+            });
+
+            let app_handle = app_handle.clone();
+            tauri::async_runtime::spawn(async move {
+                let state = app_handle.state::<AppState>();
+                let notes_repo = state.filesystem_repo.lock().await;
+                let spaces_repo = state.spaces_repo.lock().await;
+
+                // Recover the saves interrupted by a crash before anything
+                // reads the notes.
+                match check_notes_integrity_use_case(&*notes_repo, &*spaces_repo).await {
+                    Ok(reports) => {
+                        for report in reports.iter().filter(|r| !r.is_clean()) {
+                            info!(
+                                "Space '{}': {} notes recovered, {} temporary files removed.",
+                                report.space,
+                                report.recovered.len(),
+                                report.discarded.len()
+                            );
+                            for note in &report.corrupted {
+                                error!(
+                                    "Space '{}': note '{}' can't be parsed.",
+                                    report.space, note
+                                );
+                            }
+                        }
+                    }
+                    Err(e) => error!("Failed to check the notes integrity: {}", e),
+                }
+
+                // A fresh index (first run or schema change) has to be filled
+                // with the notes already on disk.
+                if needs_reindex {
+                    let search_repo = state.search_repo.lock().await;
+                    let mut index_writer = state.index_writer.lock().await;
+
+                    match reindex_all_use_case(
+                        &*notes_repo,
+                        &*spaces_repo,
+                        &search_repo,
+                        &mut index_writer,
+                    )
+                    .await
+                    {
+                        Ok(count) => info!("Search index rebuilt with {} notes.", count),
+                        Err(e) => error!("Failed to rebuild the search index: {}", e),
+                    }
+                }
+            });
 
             Ok(())
         })
@@ -111,6 +294,8 @@ pub fn run() {
             create_note_in_space_cmd,
             get_note_content_cmd,
             update_note_content_cmd,
+            merge_note_content_cmd,
+            check_notes_integrity_cmd,
             create_space_cmd,
             delete_space_cmd,
             delete_note_cmd,
@@ -134,7 +319,67 @@ pub fn run() {
             check_ollama_status_cmd,
             get_web_models_cmd,
             create_ollama_model_cmd,
-            delete_ollama_model_cmd
+            delete_ollama_model_cmd,
+            set_note_properties_cmd,
+            get_space_tags_cmd,
+            get_templates_cmd,
+            get_template_cmd,
+            save_template_cmd,
+            delete_template_cmd,
+            create_note_from_template_cmd,
+            get_or_create_daily_note_cmd,
+            get_or_create_journal_note_cmd,
+            get_adjacent_journal_note_cmd,
+            quick_capture_cmd,
+            triage_quick_note_cmd,
+            lock_note_cmd,
+            unlock_note_cmd,
+            lock_folder_cmd,
+            unlock_folder_cmd,
+            watch_space_cmd,
+            unwatch_space_cmd,
+            upload_attachment_cmd,
+            get_attachment_path_cmd,
+            get_attachment_content_cmd,
+            get_attachments_usage_cmd,
+            collect_attachments_cmd,
+            upload_image_cmd,
+            get_image_thumbnails_cmd,
+            rebuild_thumbnails_cmd,
+            duplicate_note_cmd,
+            duplicate_folder_cmd,
+            apply_batch_cmd,
+            get_folder_ordering_cmd,
+            set_folder_sort_cmd,
+            reorder_notes_cmd,
+            reorder_folders_cmd,
+            pin_note_cmd,
+            unpin_note_cmd,
+            pin_folder_cmd,
+            unpin_folder_cmd,
+            update_folder_metadata_cmd,
+            get_space_tree_cmd,
+            get_note_stats_cmd,
+            get_space_stats_cmd,
+            get_writing_progress_cmd,
+            set_writing_goal_cmd,
+            open_space_cmd,
+            relocate_space_cmd,
+            detach_space_cmd,
+            rename_space_cmd,
+            update_space_metadata_cmd,
+            export_space_cmd,
+            import_space_cmd,
+            import_obsidian_vault_cmd,
+            import_notion_export_cmd,
+            import_evernote_export_cmd,
+            export_space_html_cmd,
+            enable_space_encryption_cmd,
+            disable_space_encryption_cmd,
+            unlock_space_cmd,
+            lock_space_cmd,
+            get_space_encryption_cmd,
+            check_space_cmd
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
@@ -6,6 +6,8 @@ use thiserror::Error;
 /// The error types that can be used are:
 /// * `Io`: Io related errors
 /// * `Note`: Note Specific errors
+/// * `SpaceLocked`: The space is encrypted and must be unlocked first
+/// * `Crypto`: An encrypted file can't be opened
 /// * `Internal`: Something unexpected but app related happened
 /// * `Unknown`: We don't know what happened or where it comes from
 #[derive(Debug, Error)]
@@ -20,6 +22,17 @@ pub enum AppError {
     #[error("IO Error: {0}")]
     Io(#[from] std::io::Error),
 
+    /// # Space Locked
+    /// The files of an encrypted space can't be read or written while it's
+    /// locked.
+    #[error("Space '{0}' is encrypted and locked.")]
+    SpaceLocked(String),
+
+    /// # Crypto
+    /// An encrypted file can't be opened: wrong key or tampered content.
+    #[error("Encrypted file can't be opened: {0}")]
+    Crypto(String),
+
     /// # Internal
     /// App Error related, but unknown of why it happend.
     #[error("An unexpected error ocurred: {0}")]
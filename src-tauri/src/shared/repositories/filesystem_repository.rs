@@ -1,35 +1,69 @@
-use std::{io::ErrorKind, path::PathBuf};
+use std::{
+    collections::HashMap,
+    io::ErrorKind,
+    path::{Component, Path, PathBuf},
+    sync::{Arc, Mutex, RwLock},
+    time::{Duration, Instant},
+};
 
 use log::{debug, error};
 use tauri::{AppHandle, Manager};
 use tokio::fs::{self};
 
-use crate::shared::errors::app_errors::AppError;
+use super::{
+    keyring::{SpaceKeyring, ENCRYPTION_MANIFEST},
+    space_registry::SpaceRegistry,
+};
+use crate::shared::{
+    errors::app_errors::AppError,
+    utils::{
+        atomic_write::write_synced,
+        crypto::{self, SpaceKey},
+    },
+};
 /// Constant for defining the BASE DIR of the app
 
 const BASE_DIR_NAME: &str = "merino";
 
+/// Directory of the app data holding the files of the app itself: the space
+/// registry, settings, global templates, themes and search index.
+const APP_DIR_NAME: &str = ".merino";
+
+/// Time a change made by the app is remembered, long enough for the watcher
+/// to receive its events.
+const OWN_CHANGE_EXPIRY: Duration = Duration::from_secs(3);
+
 /// # Filesystem Repository
-/// A generic implementation of all filesystem interactions.
+/// A generic implementation of all filesystem interactions. The clones share
+/// the generation of every space, bumped on each change of its files, so
+/// caches know when to be thrown away, the space registry, read once, the
+/// keys of the unlocked encrypted spaces and the paths the app just changed.
 #[derive(Clone, Debug)]
 pub struct FileSystemRepository {
     app_handle: AppHandle,
+    generations: Arc<Mutex<HashMap<String, u64>>>,
+    own_changes: Arc<Mutex<HashMap<PathBuf, Instant>>>,
+    registry: Arc<RwLock<Option<SpaceRegistry>>>,
+    registry_update: Arc<tokio::sync::Mutex<()>>,
+    keyring: SpaceKeyring,
 }
 
 /// Implementation for the generic repository.
 impl FileSystemRepository {
     pub fn new(app_handle: AppHandle) -> Self {
-        Self { app_handle }
+        Self {
+            app_handle,
+            generations: Arc::new(Mutex::new(HashMap::new())),
+            own_changes: Arc::new(Mutex::new(HashMap::new())),
+            registry: Arc::new(RwLock::new(None)),
+            registry_update: Arc::new(tokio::sync::Mutex::new(())),
+            keyring: SpaceKeyring::default(),
+        }
     }
     /// _[PUBLIC]_ Get the base path of the application.
     pub fn get_base_path(&self) -> Result<PathBuf, AppError> {
         debug!("Retrieving the app base path.");
-        let app_data_dir = self.app_handle.path().app_data_dir().map_err(|e| {
-            error!("Failed to get the app data directory, reason: {}", e);
-            AppError::TauriIo(e)
-        })?;
-
-        let base_path = app_data_dir.join(BASE_DIR_NAME);
+        let base_path = self.get_app_data_dir()?.join(BASE_DIR_NAME);
         debug!(
             "Base path successfully resolved to: {}",
             base_path.display()
@@ -37,9 +71,84 @@ impl FileSystemRepository {
         Ok(base_path)
     }
 
-    /// _[PUBLIC]_ Get the path for a specific space.
+    /// _[PUBLIC]_ Get the directory of the files of the app itself,
+    /// `.merino` next to the base path.
+    pub fn get_app_dir(&self) -> Result<PathBuf, AppError> {
+        Ok(self.get_app_data_dir()?.join(APP_DIR_NAME))
+    }
+
+    fn get_app_data_dir(&self) -> Result<PathBuf, AppError> {
+        self.app_handle.path().app_data_dir().map_err(|e| {
+            error!("Failed to get the app data directory, reason: {}", e);
+            AppError::TauriIo(e)
+        })
+    }
+
+    /// _[PUBLIC]_ Get the path for a specific space: its registered
+    /// directory, or the base directory for a space not registered yet.
     pub fn get_space_path(&self, space_name: &str) -> Result<PathBuf, AppError> {
-        Ok(self.get_base_path()?.join(space_name))
+        let registered = self.with_registry(|r| r.spaces.get(space_name).cloned())?;
+        match registered {
+            Some(path) => Ok(path),
+            None => Ok(self.get_base_path()?.join(space_name)),
+        }
+    }
+
+    /// _[PUBLIC]_ The registered spaces with their directory, by name.
+    pub fn get_registered_spaces(&self) -> Result<Vec<(String, PathBuf)>, AppError> {
+        self.with_registry(|r| r.spaces.clone().into_iter().collect())
+    }
+
+    /// _[PUBLIC]_ Registers (or moves) a space to a directory.
+    pub async fn register_space(&self, space_name: &str, path: PathBuf) -> Result<(), AppError> {
+        self.update_registry(|r| {
+            r.spaces.insert(space_name.to_string(), path);
+        })
+        .await
+    }
+
+    /// _[PUBLIC]_ Forgets a space, its directory is left untouched.
+    pub async fn unregister_space(&self, space_name: &str) -> Result<(), AppError> {
+        self.update_registry(|r| {
+            r.spaces.remove(space_name);
+        })
+        .await
+    }
+
+    /// Path of the space registry file.
+    fn get_registry_path(&self) -> Result<PathBuf, AppError> {
+        Ok(self.get_app_dir()?.join("spaces.json"))
+    }
+
+    /// Reads the registry, loading it on first use.
+    fn with_registry<T>(&self, read: impl FnOnce(&SpaceRegistry) -> T) -> Result<T, AppError> {
+        if let Some(registry) = self.registry.read().unwrap().as_ref() {
+            return Ok(read(registry));
+        }
+        let mut registry = self.registry.write().unwrap();
+        if registry.is_none() {
+            let loaded = SpaceRegistry::load(&self.get_registry_path()?, &self.get_base_path()?)
+                .map_err(|e| {
+                    error!("Failed to read the space registry, reason: {}", e);
+                    AppError::Io(e)
+                })?;
+            *registry = Some(loaded);
+        }
+        Ok(read(registry.as_ref().unwrap()))
+    }
+
+    /// Changes the registry and persists it. The updates are serialized, so
+    /// none of them starts from a registry another one is still saving.
+    async fn update_registry(
+        &self,
+        update: impl FnOnce(&mut SpaceRegistry),
+    ) -> Result<(), AppError> {
+        let _updating = self.registry_update.lock().await;
+        let mut registry = self.with_registry(SpaceRegistry::clone)?;
+        update(&mut registry);
+        registry.save(&self.get_registry_path()?).await?;
+        *self.registry.write().unwrap() = Some(registry);
+        Ok(())
     }
 
     /// _[PUBLIC]_ Ensure a directory exists, creating it if necessary.
@@ -50,4 +159,182 @@ impl FileSystemRepository {
             Err(e) => Err(AppError::Io(e)),
         }
     }
+
+    /// _[PUBLIC]_ The generation of a space, it changes whenever the notes or
+    /// folders of the space change.
+    pub fn space_generation(&self, space_name: &str) -> u64 {
+        let generations = self.generations.lock().unwrap();
+        generations.get(space_name).copied().unwrap_or_default()
+    }
+
+    /// _[PUBLIC]_ Records a change of the files of a space.
+    pub fn mark_space_changed(&self, space_name: &str) {
+        let mut generations = self.generations.lock().unwrap();
+        *generations.entry(space_name.to_string()).or_default() += 1;
+    }
+
+    /// _[PUBLIC]_ Records a change made by the app to a file or folder
+    /// inside a space, its watcher events are ignored for a short while.
+    pub fn mark_path_changed(&self, path: &Path) {
+        self.own_changes
+            .lock()
+            .unwrap()
+            .insert(path.to_path_buf(), Instant::now() + OWN_CHANGE_EXPIRY);
+        if let Some((space_name, _)) = self.space_of_path(path) {
+            self.mark_space_changed(&space_name);
+        }
+    }
+
+    /// _[PUBLIC]_ Whether a path, or one of its folders, was just changed by
+    /// the app itself.
+    pub fn is_own_change(&self, path: &Path) -> bool {
+        let mut own_changes = self.own_changes.lock().unwrap();
+        let now = Instant::now();
+        own_changes.retain(|_, expiry| *expiry > now);
+        path.ancestors().any(|p| own_changes.contains_key(p))
+    }
+
+    /// The space holding a path, with its directory.
+    fn space_of_path(&self, path: &Path) -> Option<(String, PathBuf)> {
+        let registered = self
+            .with_registry(|r| {
+                r.space_of(path)
+                    .and_then(|name| Some((name.to_string(), r.spaces.get(name)?.clone())))
+            })
+            .ok()
+            .flatten();
+        if registered.is_some() {
+            return registered;
+        }
+        let base_path = self.get_base_path().ok()?;
+        let relative = path.strip_prefix(&base_path).ok()?;
+        match relative.components().next() {
+            Some(Component::Normal(space_name)) => Some((
+                space_name.to_string_lossy().to_string(),
+                base_path.join(space_name),
+            )),
+            _ => None,
+        }
+    }
+
+    /// _[PUBLIC]_ The keys of the unlocked encrypted spaces.
+    pub fn keyring(&self) -> &SpaceKeyring {
+        &self.keyring
+    }
+
+    /// _[PUBLIC]_ Whether a space is encrypted.
+    pub fn is_space_encrypted(&self, space_name: &str) -> Result<bool, AppError> {
+        Ok(self
+            .get_space_path(space_name)?
+            .join(ENCRYPTION_MANIFEST)
+            .is_file())
+    }
+
+    /// _[PUBLIC]_ The key of a space: `None` when it isn't encrypted,
+    /// `AppError::SpaceLocked` when it's encrypted and locked.
+    pub fn space_key(&self, space_name: &str) -> Result<Option<SpaceKey>, AppError> {
+        if !self.is_space_encrypted(space_name)? {
+            return Ok(None);
+        }
+        match self.keyring.key(space_name) {
+            Some(key) => Ok(Some(key)),
+            None => Err(AppError::SpaceLocked(space_name.to_string())),
+        }
+    }
+
+    /// The key of the space holding a path, as `space_key`, with the name
+    /// the file is sealed under.
+    fn path_key(&self, path: &Path) -> Result<Option<(SpaceKey, String)>, AppError> {
+        let Some((space_name, space_path)) = self.space_of_path(path) else {
+            return Ok(None);
+        };
+        if !space_path.join(ENCRYPTION_MANIFEST).is_file() {
+            return Ok(None);
+        }
+        match self.keyring.key(&space_name) {
+            Some(key) => Ok(Some((key, crypto::sealed_name(&space_path, path)))),
+            None => Err(AppError::SpaceLocked(space_name)),
+        }
+    }
+
+    /// _[PUBLIC]_ Reads a file of a space, decrypting it when it's sealed.
+    pub async fn read_file(&self, path: &Path) -> Result<Vec<u8>, AppError> {
+        let content = fs::read(path).await?;
+        if !crypto::is_sealed(&content) {
+            return Ok(content);
+        }
+        let Some((key, name)) = self.path_key(path)? else {
+            return Err(AppError::Crypto(format!(
+                "'{}' is sealed outside an encrypted space",
+                path.display()
+            )));
+        };
+        crypto::open(&key, &name, &content)
+            .map_err(|e| AppError::Crypto(format!("'{}': {}", path.display(), e)))
+    }
+
+    /// _[PUBLIC]_ Reads a text file of a space, decrypting it when it's
+    /// sealed.
+    pub async fn read_file_to_string(&self, path: &Path) -> Result<String, AppError> {
+        String::from_utf8(self.read_file(path).await?)
+            .map_err(|e| AppError::Io(std::io::Error::new(ErrorKind::InvalidData, e.utf8_error())))
+    }
+
+    /// _[PUBLIC]_ Writes a file of a space without risking a truncated file
+    /// on a crash, sealed when the space is encrypted.
+    pub async fn write_file(&self, path: &Path, content: &[u8]) -> Result<(), AppError> {
+        match self.path_key(path)? {
+            Some((key, name)) => {
+                let sealed = crypto::seal(&key, &name, content).map_err(AppError::Crypto)?;
+                write_synced(path, &sealed).await?;
+            }
+            None => write_synced(path, content).await?,
+        }
+        self.mark_path_changed(path);
+        Ok(())
+    }
+
+    /// _[PUBLIC]_ Seals again the files just moved from `from` to `to`, a
+    /// file or a folder: a sealed file is bound to its path and doesn't open
+    /// anywhere else. Their modification time is kept.
+    pub async fn reseal_moved(&self, from: &Path, to: &Path) -> Result<(), AppError> {
+        let Some((space_name, space_path)) = self.space_of_path(to) else {
+            return Ok(());
+        };
+        let Some(key) = self.space_key(&space_name)? else {
+            return Ok(());
+        };
+
+        let mut pending = vec![to.to_path_buf()];
+        while let Some(path) = pending.pop() {
+            if fs::metadata(&path).await?.is_dir() {
+                let mut entries = fs::read_dir(&path).await?;
+                while let Some(entry) = entries.next_entry().await? {
+                    pending.push(entry.path());
+                }
+                continue;
+            }
+            let content = fs::read(&path).await?;
+            if !crypto::is_sealed(&content) {
+                continue;
+            }
+            let old_path = match path.strip_prefix(to) {
+                Ok(rest) if !rest.as_os_str().is_empty() => from.join(rest),
+                _ => from.to_path_buf(),
+            };
+            let old_name = crypto::sealed_name(&space_path, &old_path);
+            let new_name = crypto::sealed_name(&space_path, &path);
+            let content = crypto::open(&key, &old_name, &content)
+                .and_then(|c| crypto::seal(&key, &new_name, &c))
+                .map_err(|e| AppError::Crypto(format!("'{}': {}", path.display(), e)))?;
+
+            let modified = fs::metadata(&path).await?.modified()?;
+            write_synced(&path, &content).await?;
+            std::fs::File::options()
+                .write(true)
+                .open(&path)?
+                .set_modified(modified)?;
+        }
+        Ok(())
+    }
 }
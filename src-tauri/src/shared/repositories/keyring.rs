@@ -0,0 +1,110 @@
+//! # Space Keyring
+//! Keeps in memory the keys of the encrypted spaces while they're unlocked.
+//! A space left unused longer than its auto-lock timeout is locked again.
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use crate::shared::utils::crypto::SpaceKey;
+
+/// Hidden file of a space holding how its key is derived. A space with one
+/// is encrypted.
+pub const ENCRYPTION_MANIFEST: &str = ".encryption.json";
+
+/// An unlocked space.
+struct UnlockedSpace {
+    key: SpaceKey,
+    auto_lock: Option<Duration>,
+    last_used: Instant,
+}
+
+/// # SpaceKeyring
+/// The keys of the unlocked spaces, by space name. The clones share them.
+#[derive(Clone, Default)]
+pub struct SpaceKeyring {
+    spaces: Arc<Mutex<HashMap<String, UnlockedSpace>>>,
+}
+
+impl fmt::Debug for SpaceKeyring {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let spaces = self.spaces.lock().unwrap();
+        f.debug_struct("SpaceKeyring")
+            .field("unlocked", &spaces.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl SpaceKeyring {
+    /// Keeps the key of a space until it's locked.
+    /// ## Params
+    /// * `auto_lock`: How long the space stays unlocked without being used,
+    ///   forever if `None`.
+    pub fn unlock(&self, space_name: &str, key: SpaceKey, auto_lock: Option<Duration>) {
+        self.spaces.lock().unwrap().insert(
+            space_name.to_string(),
+            UnlockedSpace {
+                key,
+                auto_lock,
+                last_used: Instant::now(),
+            },
+        );
+    }
+
+    /// Forgets the key of a space.
+    /// ## Result
+    /// Whether the space was unlocked.
+    pub fn lock(&self, space_name: &str) -> bool {
+        self.spaces.lock().unwrap().remove(space_name).is_some()
+    }
+
+    /// Whether a space is unlocked.
+    pub fn is_unlocked(&self, space_name: &str) -> bool {
+        self.spaces.lock().unwrap().contains_key(space_name)
+    }
+
+    /// The auto-lock timeout of an unlocked space.
+    pub fn auto_lock(&self, space_name: &str) -> Option<Duration> {
+        let spaces = self.spaces.lock().unwrap();
+        spaces.get(space_name).and_then(|s| s.auto_lock)
+    }
+
+    /// The key of an unlocked space, using it postpones its auto-lock.
+    pub fn key(&self, space_name: &str) -> Option<SpaceKey> {
+        let mut spaces = self.spaces.lock().unwrap();
+        let space = spaces.get_mut(space_name)?;
+        space.last_used = Instant::now();
+        Some(space.key.clone())
+    }
+
+    /// Moves the key of a renamed space.
+    pub fn rename(&self, space_name: &str, new_space_name: &str) {
+        let mut spaces = self.spaces.lock().unwrap();
+        if let Some(space) = spaces.remove(space_name) {
+            spaces.insert(new_space_name.to_string(), space);
+        }
+    }
+
+    /// # Lock expired
+    /// Locks the spaces unused for longer than their auto-lock timeout.
+    /// ## Result
+    /// The names of the spaces locked.
+    pub fn lock_expired(&self, now: Instant) -> Vec<String> {
+        let mut spaces = self.spaces.lock().unwrap();
+        let expired: Vec<String> = spaces
+            .iter()
+            .filter(|(_, space)| {
+                space.auto_lock.is_some_and(|timeout| {
+                    now.saturating_duration_since(space.last_used) >= timeout
+                })
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+        for name in &expired {
+            spaces.remove(name);
+        }
+        expired
+    }
+}
@@ -1 +1,3 @@
 pub mod filesystem_repository;
+pub mod keyring;
+pub mod space_registry;
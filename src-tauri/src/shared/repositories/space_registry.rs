@@ -0,0 +1,68 @@
+//! # Space Registry
+//! Maps the name of every space to the directory holding it, so spaces can
+//! live anywhere on the filesystem.
+use std::{
+    collections::BTreeMap,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::shared::utils::atomic_write::write_synced;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+/// # SpaceRegistry
+/// The registered spaces, persisted in `.merino/spaces.json`.
+/// ## Fields
+/// * `spaces`: The absolute path of every space, by name.
+pub struct SpaceRegistry {
+    #[serde(default)]
+    pub spaces: BTreeMap<String, PathBuf>,
+}
+
+impl SpaceRegistry {
+    /// # Load
+    /// Reads the registry. When there's none yet, the spaces found in the
+    /// base directory, where every space used to live, are registered.
+    pub fn load(registry_path: &Path, base_path: &Path) -> io::Result<Self> {
+        match fs::read_to_string(registry_path) {
+            Ok(content) => serde_json::from_str(&content)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                let mut registry = Self::default();
+                let entries = match fs::read_dir(base_path) {
+                    Ok(entries) => entries,
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(registry),
+                    Err(e) => return Err(e),
+                };
+                for entry in entries.flatten() {
+                    let name = entry.file_name().to_string_lossy().to_string();
+                    if entry.path().is_dir() && !name.starts_with('.') {
+                        registry.spaces.insert(name, entry.path());
+                    }
+                }
+                Ok(registry)
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Writes the registry.
+    pub async fn save(&self, registry_path: &Path) -> io::Result<()> {
+        if let Some(parent) = registry_path.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        write_synced(registry_path, content.as_bytes()).await
+    }
+
+    /// The space holding a path, if any.
+    pub fn space_of(&self, path: &Path) -> Option<&str> {
+        self.spaces
+            .iter()
+            .find(|(_, space_path)| path.starts_with(space_path))
+            .map(|(name, _)| name.as_str())
+    }
+}
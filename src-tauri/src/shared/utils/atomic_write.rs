@@ -1,13 +1,113 @@
-use std::path::Path;
+use std::{
+    io,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
 
-use tokio::fs;
+use tokio::{fs, io::AsyncWriteExt};
 
-pub async fn write_atomic(path: &Path, data: &str) -> Result<(), String> {
-    let tmp_path = path.with_extension("tmp");
+/// Suffix of the temporary files written by [`write_synced`].
+pub const TMP_SUFFIX: &str = ".tmp";
 
-    fs::write(&tmp_path, data).await.map_err(|e| format!("Failed to write tmp settings file: {}", e))?;
+/// Number of the temporary files written by the process, so concurrent
+/// writes of a file never share one.
+static TEMP_FILES: AtomicU64 = AtomicU64::new(0);
 
-    fs::rename(&tmp_path, path).await.map_err(|e| format!("Failed to rename tmp settings file: {}", e))?;
+/// # Temp path
+/// The hidden sibling a file is written to before replacing it, unique to
+/// the write: `notes/.name.json.1234-5.tmp` for `notes/name.json`, with the
+/// process id and a counter.
+pub fn temp_path(path: &Path) -> PathBuf {
+    let file_name = path
+        .file_name()
+        .map(|n| n.to_string_lossy())
+        .unwrap_or_default();
+    let number = TEMP_FILES.fetch_add(1, Ordering::Relaxed);
+    path.with_file_name(format!(
+        ".{}.{}-{}{}",
+        file_name,
+        std::process::id(),
+        number,
+        TMP_SUFFIX
+    ))
+}
 
+/// # Temp target
+/// The name of the file a temporary file replaces, `None` when the name
+/// isn't one of a temporary file. The ones written without the unique part,
+/// `.name.json.tmp`, are read too.
+pub fn temp_target(file_name: &str) -> Option<&str> {
+    let name = file_name.strip_prefix('.')?.strip_suffix(TMP_SUFFIX)?;
+    let target = match name.rsplit_once('.') {
+        Some((target, unique)) if is_unique_part(unique) => target,
+        _ => name,
+    };
+    Some(target).filter(|t| !t.is_empty())
+}
+
+/// Whether a part of a temporary file name is the `pid-counter` of a write.
+fn is_unique_part(part: &str) -> bool {
+    part.split_once('-').is_some_and(|(pid, number)| {
+        [pid, number]
+            .iter()
+            .all(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit()))
+    })
+}
+
+/// # Write synced
+/// Crash-safe write: the data goes to a temporary sibling flushed to disk,
+/// which then replaces the file. A crash leaves either the old content or
+/// the new one, plus at most a leftover temporary file.
+pub async fn write_synced(path: &Path, data: &[u8]) -> io::Result<()> {
+    let tmp_path = temp_path(path);
+
+    let written = async {
+        let mut file = fs::File::create(&tmp_path).await?;
+        file.write_all(data).await?;
+        file.sync_all().await
+    }
+    .await;
+    if let Err(e) = written {
+        let _ = fs::remove_file(&tmp_path).await;
+        return Err(e);
+    }
+
+    fs::rename(&tmp_path, path).await?;
+
+    // Persist the rename itself.
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() {
+        fs::File::open(parent).await?.sync_all().await?;
+    }
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn temp_paths_are_unique_and_lead_back_to_their_file() {
+        let path = Path::new("notes").join("name.json");
+        let first = temp_path(&path);
+        let second = temp_path(&path);
+        assert_ne!(first, second);
+
+        for temp in [first, second] {
+            assert_eq!(temp.parent(), path.parent());
+            let file_name = temp.file_name().unwrap().to_str().unwrap();
+            assert!(file_name.starts_with('.') && file_name.ends_with(TMP_SUFFIX));
+            assert_eq!(temp_target(file_name), Some("name.json"));
+        }
+    }
+
+    #[test]
+    fn temp_targets_are_read_from_file_names() {
+        assert_eq!(temp_target(".name.json.tmp"), Some("name.json"));
+        assert_eq!(temp_target(".v1.2.json.12-3.tmp"), Some("v1.2.json"));
+        assert_eq!(temp_target(".a-b.tmp"), Some("a-b"));
+        assert_eq!(temp_target("name.json.tmp"), None);
+        assert_eq!(temp_target(".name.json"), None);
+        assert_eq!(temp_target("..tmp"), None);
+    }
+}
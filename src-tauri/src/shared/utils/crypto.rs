@@ -0,0 +1,229 @@
+//! # Crypto
+//! Encryption at rest of the files of the encrypted spaces. Keys are derived
+//! from a passphrase with Argon2id and files are sealed with
+//! XChaCha20-Poly1305, so a file changed on disk fails to open instead of
+//! being read as garbage.
+//!
+//! A sealed file is `MAGIC | nonce (24 bytes) | ciphertext and tag`. The
+//! path of the file inside its space is authenticated with it, so a sealed
+//! file can't be swapped with another one of the space.
+//!
+//! Only the content of the notes, attachments, templates and writing logs is
+//! sealed. The names of the notes and folders, the folder manifests (order,
+//! pins, icons) and `space.json` stay in plaintext, a locked space is still
+//! listed from them.
+use std::path::{Component, Path};
+
+use argon2::{Algorithm, Argon2, Params, Version};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    XChaCha20Poly1305, XNonce,
+};
+use zeroize::Zeroizing;
+
+use super::atomic_write::temp_target;
+
+/// Header of the sealed files, also authenticated with them.
+pub const SEALED_MAGIC: &[u8] = b"MERINO-SEALED-1\n";
+
+/// Length of the keys, in bytes.
+pub const KEY_LEN: usize = 32;
+
+/// Length of the salts of the key derivation, in bytes.
+pub const SALT_LEN: usize = 16;
+
+/// Length of the nonces, in bytes.
+const NONCE_LEN: usize = 24;
+
+/// A key, wiped from memory when dropped.
+pub type SpaceKey = Zeroizing<[u8; KEY_LEN]>;
+
+/// # Derive key
+/// Derives the key of a space from its passphrase with Argon2id.
+/// ## Params
+/// * `memory_kib`: The memory cost, in KiB.
+/// * `iterations`: The time cost.
+/// * `parallelism`: The number of lanes.
+pub fn derive_key(
+    passphrase: &str,
+    salt: &[u8],
+    memory_kib: u32,
+    iterations: u32,
+    parallelism: u32,
+) -> Result<SpaceKey, String> {
+    let params = Params::new(memory_kib, iterations, parallelism, Some(KEY_LEN))
+        .map_err(|e| e.to_string())?;
+    let mut key = Zeroizing::new([0u8; KEY_LEN]);
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(passphrase.as_bytes(), salt, key.as_mut())
+        .map_err(|e| e.to_string())?;
+    Ok(key)
+}
+
+/// # Sealed name
+/// The name a file of a space is sealed under: its path inside the space,
+/// with `/` separators. The temporary file of a write is sealed under the
+/// name of the file it replaces.
+pub fn sealed_name(space_path: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(space_path).unwrap_or(path);
+    let mut parts: Vec<String> = relative
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(part) => Some(part.to_string_lossy().to_string()),
+            _ => None,
+        })
+        .collect();
+    if let Some(last) = parts.last_mut() {
+        if let Some(target) = temp_target(last) {
+            *last = target.to_string();
+        }
+    }
+    parts.join("/")
+}
+
+/// The data authenticated with a content: the header and its name.
+fn associated_data(name: &str) -> Vec<u8> {
+    [SEALED_MAGIC, name.as_bytes()].concat()
+}
+
+/// Whether a content is sealed.
+pub fn is_sealed(data: &[u8]) -> bool {
+    data.starts_with(SEALED_MAGIC)
+}
+
+/// # Seal
+/// Encrypts a content with a random nonce.
+/// ## Params
+/// * `name`: The name the content is sealed under, see [`sealed_name`].
+pub fn seal(key: &SpaceKey, name: &str, data: &[u8]) -> Result<Vec<u8>, String> {
+    let nonce: [u8; NONCE_LEN] = rand::random();
+    let cipher = XChaCha20Poly1305::new(key.as_ref().into());
+    let ciphertext = cipher
+        .encrypt(
+            XNonce::from_slice(&nonce),
+            Payload {
+                msg: data,
+                aad: &associated_data(name),
+            },
+        )
+        .map_err(|e| e.to_string())?;
+
+    let mut sealed = Vec::with_capacity(SEALED_MAGIC.len() + NONCE_LEN + ciphertext.len());
+    sealed.extend_from_slice(SEALED_MAGIC);
+    sealed.extend_from_slice(&nonce);
+    sealed.extend_from_slice(&ciphertext);
+    Ok(sealed)
+}
+
+/// # Open
+/// Decrypts a sealed content.
+/// ## Params
+/// * `name`: The name the content was sealed under.
+/// ## Result
+/// The content, or an error when the key or the name is wrong or the
+/// content was tampered with.
+pub fn open(key: &SpaceKey, name: &str, sealed: &[u8]) -> Result<Vec<u8>, String> {
+    let body = sealed
+        .strip_prefix(SEALED_MAGIC)
+        .filter(|body| body.len() >= NONCE_LEN)
+        .ok_or_else(|| "not a sealed content".to_string())?;
+    let (nonce, ciphertext) = body.split_at(NONCE_LEN);
+    XChaCha20Poly1305::new(key.as_ref().into())
+        .decrypt(
+            XNonce::from_slice(nonce),
+            Payload {
+                msg: ciphertext,
+                aad: &associated_data(name),
+            },
+        )
+        .map_err(|_| "the content can't be authenticated".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::shared::utils::atomic_write::temp_path;
+
+    fn key(byte: u8) -> SpaceKey {
+        Zeroizing::new([byte; KEY_LEN])
+    }
+
+    #[test]
+    fn sealed_content_opens_under_its_name() {
+        let sealed = seal(&key(1), "notes/a.json", b"{\"blocks\":[]}").unwrap();
+
+        assert!(is_sealed(&sealed));
+        assert_eq!(
+            open(&key(1), "notes/a.json", &sealed).unwrap(),
+            b"{\"blocks\":[]}"
+        );
+    }
+
+    #[test]
+    fn sealing_twice_gives_different_contents() {
+        assert_ne!(
+            seal(&key(1), "a.json", b"same").unwrap(),
+            seal(&key(1), "a.json", b"same").unwrap()
+        );
+    }
+
+    #[test]
+    fn wrong_key_fails_to_open() {
+        let sealed = seal(&key(1), "a.json", b"secret").unwrap();
+
+        assert!(open(&key(2), "a.json", &sealed).is_err());
+    }
+
+    #[test]
+    fn other_name_fails_to_open() {
+        let sealed = seal(&key(1), "a.json", b"secret").unwrap();
+
+        assert!(open(&key(1), "b.json", &sealed).is_err());
+        assert!(open(&key(1), "folder/a.json", &sealed).is_err());
+    }
+
+    #[test]
+    fn tampered_content_fails_to_open() {
+        let mut sealed = seal(&key(1), "a.json", b"secret").unwrap();
+        let last = sealed.len() - 1;
+        sealed[last] ^= 1;
+
+        assert!(open(&key(1), "a.json", &sealed).is_err());
+    }
+
+    #[test]
+    fn plain_content_isnt_opened() {
+        assert!(!is_sealed(b"{}"));
+        assert!(open(&key(1), "a.json", b"{}").is_err());
+        assert!(open(&key(1), "a.json", SEALED_MAGIC).is_err());
+    }
+
+    #[test]
+    fn sealed_name_is_relative_to_the_space() {
+        let space = Path::new("/spaces/work");
+
+        assert_eq!(
+            sealed_name(space, &space.join("projects").join("a.json")),
+            "projects/a.json"
+        );
+        assert_eq!(sealed_name(space, &space.join("a.json")), "a.json");
+    }
+
+    #[test]
+    fn temporary_files_are_sealed_under_their_target() {
+        let space = Path::new("/spaces/work");
+
+        assert_eq!(
+            sealed_name(space, &space.join("projects").join(".a.json.tmp")),
+            "projects/a.json"
+        );
+        assert_eq!(
+            sealed_name(space, &temp_path(&space.join("projects").join("a.json"))),
+            "projects/a.json"
+        );
+        assert_eq!(
+            sealed_name(space, &space.join(".stats.json")),
+            ".stats.json"
+        );
+    }
+}
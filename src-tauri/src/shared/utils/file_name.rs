@@ -0,0 +1,28 @@
+//! # File Name
+//! Checks the names given by the user that become file names.
+
+/// Characters no platform accepts in a file name.
+const RESERVED_CHARS: &[char] = &['/', '\\', '<', '>', ':', '"', '|', '?', '*'];
+
+/// # Valid name
+/// Whether a name can be used as a file name: a single visible path segment,
+/// without reserved or control characters.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.starts_with('.') && !name.contains(RESERVED_CHARS) && !name.chars().any(char::is_control)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn names_are_single_visible_segments() {
+        assert!(is_valid_name("Meeting notes"));
+        assert!(is_valid_name("daily-2024"));
+        for name in [
+            "../../x", "a/b", "a\\b", "..", ".hidden", "a:b", "a*", "a\u{0}",
+        ] {
+            assert!(!is_valid_name(name), "{}", name);
+        }
+    }
+}
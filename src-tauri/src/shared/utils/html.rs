@@ -0,0 +1,52 @@
+//! # HTML
+//! Helpers for the rich texts of Editor.js, stored as HTML.
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+/// Matches the line breaks of a rich text.
+static BREAK: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"(?i)<br\s*/?>").unwrap());
+
+/// Matches the HTML tags of a rich text.
+static TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<[^>]+>").unwrap());
+
+/// Escapes the HTML special characters, Editor.js stores rich text as HTML.
+pub fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Removes the markup of a rich text, line breaks still separate words.
+pub fn plain_text(text: &str) -> String {
+    let text = BREAK.replace_all(text, "\n");
+    TAG.replace_all(&text, "")
+        .replace("&nbsp;", " ")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn special_characters_are_escaped() {
+        assert_eq!(
+            escape_html(r#"<a href="x">&</a>"#),
+            "&lt;a href=&quot;x&quot;&gt;&amp;&lt;/a&gt;"
+        );
+    }
+
+    #[test]
+    fn plain_text_drops_the_markup() {
+        assert_eq!(
+            plain_text("<b>bold</b>&nbsp;and<br>it&#39;s &lt;kept&gt;"),
+            "bold and\nit's <kept>"
+        );
+    }
+}
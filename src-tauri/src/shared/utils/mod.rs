@@ -1,3 +1,6 @@
 pub mod id_generator;
 pub mod atomic_write;
+pub mod crypto;
+pub mod file_name;
+pub mod html;
 pub mod merge_values;
@@ -3,7 +3,7 @@ import CustomParagraphTool from "./plugins/pharagraph-conversion";
 import Header from "@editorjs/header";
 import DragDrop from "editorjs-drag-drop";
 import { debounce } from "./debounce";
-import { updateNoteContent } from "../../api/tauri/update/notes-api-update";
+import { rememberNote, saveNoteContent } from "./save-note";
 import EditorjsList from '@editorjs/list';
 import EmbedControl from 'editorjs-embed-control';
 import Quote from "@cychann/editorjs-quote";
@@ -78,20 +78,22 @@ const editorConfig: EditorJSConfig = {
     }
 }
 
-export function initializeEditor(holderId: string, activeSpaceName: string, noteName: string, initialContent: string | null, noteFolder: string | null = null) {
+export function initializeEditor(holderId: string, activeSpaceName: string, noteName: string, initialContent: string | null, noteFolder: string | null = null, initialRevision: string | null = null) {
+    rememberNote(activeSpaceName, { name: noteName, folder: noteFolder, content: initialContent, revision: initialRevision });
+
     const debounceSave = debounce(async (contentData: OutputData | undefined) => {
         if (!activeSpaceName || !noteName) {
             return;
         }
 
-        try {
-            const jsonString = JSON.stringify(contentData);
-            const encoder = new TextEncoder();
-            const contentBytes = Array.from(encoder.encode(jsonString));
-
-            await updateNoteContent(activeSpaceName, noteName, contentBytes, noteFolder)
-        } catch (e) {
-            console.error("Failed to save note: ", e)
+        // After a merge or a conflict the editor shows the saved version.
+        const replaced = await saveNoteContent(activeSpaceName, noteName, noteFolder, JSON.stringify(contentData));
+        if (replaced) {
+            try {
+                await editor.blocks.render(JSON.parse(replaced));
+            } catch (e) {
+                console.error("Failed to show the saved content:", e);
+            }
         }
     }, 500)
 
@@ -0,0 +1,73 @@
+import type { NoteContentResponse } from "../../api/tauri/interfaces/notes-interface";
+import { mergeNoteContent, updateNoteContent } from "../../api/tauri/update/notes-api-update";
+import { toasts } from "../../stores/notifications/toast-store";
+
+// A save rejected because the note changed since it was read, as sent by the
+// backend.
+interface NoteConflict {
+    current: NoteContentResponse;
+    incoming: string;
+    blocks: string[];
+}
+
+// The revision and content every note was last read or saved at, saves are
+// checked against them.
+const savedVersions = new Map<string, { revision: string; content: string }>();
+
+function noteKey(spaceName: string, noteName: string, folder: string | null) {
+    return `${spaceName}/${folder ?? ""}/${noteName}`;
+}
+
+function parseConflict(error: unknown): NoteConflict | null {
+    if (typeof error !== "string") return null;
+    try {
+        const conflict = JSON.parse(error);
+        return conflict?.current && typeof conflict.incoming === "string" ? conflict : null;
+    } catch {
+        return null;
+    }
+}
+
+// Remembers the version of a note just read, or returned by a command that
+// changed it (properties, lock...), so the next save is based on it.
+export function rememberNote(spaceName: string, note: { name: string; folder: string | null; content: string | null; revision: string | null }) {
+    if (!note.revision) return;
+    savedVersions.set(noteKey(spaceName, note.name, note.folder), {
+        revision: note.revision,
+        content: note.content ?? "",
+    });
+}
+
+// Saves the content of a note. When the note changed since it was read, the
+// blocks edited on each side are merged; when both sides edited the same
+// blocks, the stored version wins and the conflict is shown.
+// Returns the content the editor must show instead of its own, null when the
+// save went through as is.
+export async function saveNoteContent(spaceName: string, noteName: string, folder: string | null, content: string): Promise<string | null> {
+    const key = noteKey(spaceName, noteName, folder);
+    const known = savedVersions.get(key);
+
+    try {
+        const saved = await updateNoteContent(spaceName, noteName, Array.from(new TextEncoder().encode(content)), folder, known?.revision ?? "");
+        savedVersions.set(key, { revision: saved.revision, content });
+        return null;
+    } catch (e) {
+        const conflict = parseConflict(e);
+        if (!conflict) {
+            console.error("Failed to save note: ", e);
+            toasts.add(`Failed to save '${noteName}'.`, "error");
+            return null;
+        }
+
+        try {
+            const merged = await mergeNoteContent(spaceName, noteName, known?.content ?? "", content, folder);
+            rememberNote(spaceName, merged);
+            return merged.content;
+        } catch (e) {
+            const unresolved = parseConflict(e) ?? conflict;
+            rememberNote(spaceName, unresolved.current);
+            toasts.add(`'${noteName}' was changed meanwhile and your edits conflict with it, the saved version was reloaded.`, "error");
+            return unresolved.current.content;
+        }
+    }
+}
@@ -1,7 +1,7 @@
 import { invoke } from "@tauri-apps/api/core";
 import { listFolders } from "../../api/tauri/get/folders-api-get";
 import { currentRightClickedFolder, folders, inputRenameFolderElement, renamedFolder } from "../../stores/workspace/folders-store";
-import type { Folder } from "../../api/tauri/interfaces/folders-interface";
+import type { Folder, FolderEntry } from "../../api/tauri/interfaces/folders-interface";
 import { contextMenuVisible, contextMenuX, contextMenuY, folderContextMenuVisible, folderContextMenuX, folderContextMenuY, folderRightClicked } from "../../stores/contextmenu/contextmenu-store";
 import { get } from "svelte/store";
 import { activeSpace } from "../../stores/workspace/spaces-store";
@@ -73,10 +73,13 @@ export async function handleFolderActionContextMenu(actionType: string): Promise
             await createNote(get(activeSpace) ?? '', get(folderRightClicked)?.path ?? '')
             toasts.add(`Created note in folder "${get(folderRightClicked)?.path}" in space.`, "success")
             break;
-        case "create_folder":
-            await createFolder(get(activeSpace) ?? '', get(folderRightClicked)?.path ?? '')
-            toasts.add(`Created folder in folder "${get(folderRightClicked)?.path}" in space.`, "success")
+        case "create_folder": {
+            const created = await createFolder(get(activeSpace) ?? '', get(folderRightClicked)?.path ?? '')
+            if (created) {
+                toasts.add(`Created folder "${created.path}" in space.`, "success")
+            }
             break;
+        }
     }
 }
 
@@ -98,16 +101,20 @@ export function closeFolderContextMenu() {
     folderRightClicked.set(null);
 }
 
-export async function createFolder(spaceName: string, folderPath: string | null) {
+// Creates an "Untitled n" folder, returns it with its path relative to the
+// space, or null when it couldn't be created.
+export async function createFolder(spaceName: string, folderPath: string | null): Promise<FolderEntry | null> {
     if (!spaceName) {
-        return;
+        return null;
     }
 
     try {
-        await createFolderInSpace(spaceName, folderPath);
+        const created = await createFolderInSpace(spaceName, folderPath);
         loadNotesInSpace(spaceName);
+        return created;
     } catch (e) {
         console.error("Failed to create folder: ", e);
+        return null;
     }
 }
 
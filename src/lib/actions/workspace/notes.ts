@@ -6,7 +6,8 @@ import { invoke } from "@tauri-apps/api/core";
 import type { Note } from "../../types/notes";
 import { contextMenuVisible, contextMenuX, contextMenuY, folderContextMenuVisible } from "../../stores/contextmenu/contextmenu-store";
 import { createNoteInSpace } from "../../api/tauri/create/notes-api-create";
-import { renameNoteInSpace } from "../../api/tauri/update/notes-api-update";
+import { lockNote, renameNoteInSpace, setNoteProperties, unlockNote } from "../../api/tauri/update/notes-api-update";
+import { rememberNote } from "../editor/save-note";
 import { tick } from "svelte";
 import { showTooltip, tooltipText, tooltipX, tooltipY } from "../../stores/tooltip/tooltip-store";
 import { closeNote, openNote } from "../editor/notes-buffer";
@@ -236,4 +237,21 @@ export function buildNoteTree(notes: any[], folders: { path: string }[]) {
     });
 
     return tree;
-}
\ No newline at end of file
+}
+
+// Locks or unlocks a note, the editor saves against the revision it returns.
+export async function setNoteLocked(spaceName: string, noteName: string, folderPath: string | null, locked: boolean) {
+    const note = locked
+        ? await lockNote(spaceName, noteName, folderPath)
+        : await unlockNote(spaceName, noteName, folderPath);
+    rememberNote(spaceName, note);
+    return note;
+}
+
+// Replaces the properties of a note, the editor saves against the revision it
+// returns.
+export async function updateNoteProperties(spaceName: string, noteName: string, folderPath: string | null, properties: Record<string, unknown>) {
+    const note = await setNoteProperties(spaceName, noteName, properties, folderPath);
+    rememberNote(spaceName, note);
+    return note;
+}
@@ -1,5 +1,6 @@
 import { invoke } from "@tauri-apps/api/core";
+import type { FolderEntry } from "../interfaces/folders-interface";
 
-export async function createFolderInSpace(spaceName: string | null, folderPath: string | null) {
-    return await invoke("create_folder_cmd", { spaceName, folderPath });
-}
\ No newline at end of file
+export async function createFolderInSpace(spaceName: string | null, folderPath: string | null): Promise<FolderEntry> {
+    return await invoke<FolderEntry>("create_folder_cmd", { spaceName, folderPath });
+}
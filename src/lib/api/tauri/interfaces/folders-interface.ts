@@ -1,3 +1,16 @@
 export interface Folder {
     name: string;
-}
\ No newline at end of file
+}
+
+// A folder as returned by the backend, its path relative to the space.
+export interface FolderEntry {
+    path: string;
+    created: string | null;
+    modified: string | null;
+    pinned: boolean;
+    icon: string | null;
+    color: string | null;
+    description: string | null;
+    default_template: string | null;
+    collapsed: boolean;
+}
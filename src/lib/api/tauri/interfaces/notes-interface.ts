@@ -2,4 +2,5 @@ export interface NoteContentResponse {
     name: string;
     content: string;
     folder: string
+    revision: string
 }
\ No newline at end of file
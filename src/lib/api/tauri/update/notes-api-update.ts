@@ -1,9 +1,31 @@
 import { invoke } from "@tauri-apps/api/core";
+import type { NoteContentResponse } from "../interfaces/notes-interface";
 
 export async function renameNoteInSpace(spaceName: string | null, noteName: string, newNoteName: string, folderPath: string) {
     return await invoke<any>('update_note_name_cmd', { spaceName, noteName, newNoteName, folderPath })
 }
 
-export async function updateNoteContent(spaceName: string, noteName: string, content: number[], folderPath: string | null): Promise<void> {
-    return await invoke("update_note_content_cmd", { spaceName, noteName, content, folderPath });
+// `expectedRevision` is the revision of the last read or save of the note, the
+// save is rejected when the note changed since.
+export async function updateNoteContent(spaceName: string, noteName: string, content: number[], folderPath: string | null, expectedRevision: string): Promise<NoteContentResponse> {
+    return await invoke<NoteContentResponse>("update_note_content_cmd", { spaceName, noteName, content, folderPath, expectedRevision });
+}
+
+// Saves a content rejected by a conflict, merging the blocks edited on each
+// side since `baseContent` was read. Rejected again with the conflict as JSON
+// when both sides edited the same blocks.
+export async function mergeNoteContent(spaceName: string, noteName: string, baseContent: string, content: string, folderPath: string | null): Promise<NoteContentResponse> {
+    return await invoke<NoteContentResponse>("merge_note_content_cmd", { spaceName, noteName, baseContent, content, folderPath });
+}
+
+export async function setNoteProperties(spaceName: string, noteName: string, properties: Record<string, unknown>, folderPath: string | null): Promise<NoteContentResponse> {
+    return await invoke<NoteContentResponse>("set_note_properties_cmd", { spaceName, noteName, properties, folderPath });
+}
+
+export async function lockNote(spaceName: string, noteName: string, folderPath: string | null): Promise<NoteContentResponse> {
+    return await invoke<NoteContentResponse>("lock_note_cmd", { spaceName, noteName, folderPath });
+}
+
+export async function unlockNote(spaceName: string, noteName: string, folderPath: string | null): Promise<NoteContentResponse> {
+    return await invoke<NoteContentResponse>("unlock_note_cmd", { spaceName, noteName, folderPath });
 }
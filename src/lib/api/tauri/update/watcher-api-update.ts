@@ -0,0 +1,9 @@
+import { invoke } from "@tauri-apps/api/core";
+
+export async function watchSpace(spaceName: string) {
+    return await invoke<void>('watch_space_cmd', { spaceName })
+}
+
+export async function unwatchSpace() {
+    return await invoke<void>('unwatch_space_cmd')
+}
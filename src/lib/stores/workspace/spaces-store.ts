@@ -1,6 +1,7 @@
 import { writable } from "svelte/store";
 import type { Space } from "../../api/tauri/interfaces/spaces-interface";
 import { load } from '@tauri-apps/plugin-store';
+import { unwatchSpace, watchSpace } from "../../api/tauri/update/watcher-api-update";
 
 export const spacesStore = writable<{ spaces?: Space[]}>({
     spaces: []
@@ -46,5 +47,11 @@ export async function saveActiveNameSpace(name: string | null) {
 activeSpace.subscribe(async (name) => {
     if (isInitialLoadComplete) {
         await saveActiveNameSpace(name);
+        // The space restored on start is already watched by the backend.
+        try {
+            await (name ? watchSpace(name) : unwatchSpace());
+        } catch(e) {
+            console.error("Failed to watch the space.", e);
+        }
     }
 })
\ No newline at end of file